@@ -14,7 +14,7 @@ pub use sherpax_runtime::{
 
 use crate::chain_spec::get_account_id_from_seed;
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct BtcGenesisParams {
     pub network: BtcNetwork,
     pub confirmation_number: u32,
@@ -50,11 +50,22 @@ impl BtcGenesisParams {
             nonce: self.nonce,
         }
     }
+
+    /// Check that the hash of the genesis header matches the declared hash.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.header().hash() != self.hash() {
+            return Err(format!(
+                "The hash of genesis header at height {} does not match {}",
+                self.height, self.hash
+            ));
+        }
+        Ok(())
+    }
 }
 
 pub fn btc_genesis_params(res: &str) -> BtcGenesisParams {
     let params: BtcGenesisParams = serde_json::from_str(res).expect("JSON was not well-formatted");
-    assert_eq!(params.header().hash(), params.hash());
+    params.validate().expect("Genesis header is invalid");
     params
 }
 
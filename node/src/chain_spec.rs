@@ -1,12 +1,9 @@
 use crate::bitcoin::{
     btc_genesis_params, BtcGenesisParams, BtcParams, BtcTrusteeParams, Chain, TrusteeInfoConfig,
 };
-use frame_benchmarking::frame_support::PalletId;
 use hex_literal::hex;
 use sc_chain_spec::ChainSpecExtension;
-use sc_service::config::TelemetryEndpoints;
 use sc_service::{ChainType, Properties};
-use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 pub use sherpax_runtime::{
     constants::currency::UNITS, opaque::SessionKeys, AccountId, AssetsBridgeConfig, AssetsConfig,
@@ -16,25 +13,13 @@ pub use sherpax_runtime::{
     WASM_BINARY,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::crypto::UncheckedInto;
 use sp_core::{sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
-use sp_runtime::traits::{AccountIdConversion, IdentifyAccount, Verify};
-use std::{collections::BTreeMap, convert::TryInto};
+use sp_runtime::traits::{IdentifyAccount, Verify};
 
-// Note this is the URL for the telemetry server
-const POLKADOT_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
-const CHAINX_TELEMETRY_URL: &str = "wss://telemetry.chainx.org/submit/";
+/// The EVM chain id of SherpaX.
+pub const SHERPAX_CHAIN_ID: u64 = 1506;
 
-const DEFAULT_PROTOCOL_ID: &str = "sherpax";
-
-macro_rules! bootnodes {
-    ( $( $bootnode:expr, )* ) => {
-        vec![
-            $($bootnode.to_string().try_into().expect("The bootnode is invalid"),)*
-        ]
-    }
-}
 /// Node `ChainSpec` extensions.
 ///
 /// Additional parameters for some Substrate core modules,
@@ -76,110 +61,79 @@ pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
         get_from_seed::<GrandpaId>(s),
     )
 }
-type AssetId = u32;
-type AssetName = Vec<u8>;
-type AssetSymbol = Vec<u8>;
-type AssetDecimals = u8;
-type AssetSufficient = bool;
-type AssetMinBalance = Balance;
 
-/// Asset registration
-fn sbtc() -> (Chain, AssetId) {
-    (Chain::Bitcoin, 1)
+pub type AssetId = u32;
+
+/// The registration of a genesis asset.
+#[derive(Clone, Debug)]
+pub struct AssetParams {
+    pub id: AssetId,
+    pub owner: AccountId,
+    /// The chain of a gateway asset, registered to the gateway records.
+    pub chain: Option<Chain>,
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    pub decimals: u8,
+    pub sufficient: bool,
+    pub min_balance: Balance,
 }
 
-/// Asset registration
-fn doge() -> (Chain, AssetId) {
-    (Chain::Dogecoin, 9)
+/// The parameters of the genesis, shared by the compiled-in chain specs and `build-genesis`.
+#[derive(Clone)]
+pub struct GenesisParams {
+    pub initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+    pub root_key: AccountId,
+    pub technical_committee: Vec<AccountId>,
+    pub balances: Vec<(AccountId, Balance)>,
+    pub vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
+    pub assets: Vec<AssetParams>,
+    pub bitcoin: BtcGenesisParams,
+    pub dogecoin: BtcGenesisParams,
+    pub trustees: Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>,
+    pub ethereum_chain_id: u64,
 }
 
-#[allow(clippy::type_complexity)]
-fn reserved_assets(
-    root_key: &AccountId,
-) -> (
-    Vec<(AssetId, AccountId, AssetSufficient, AssetMinBalance)>,
-    Vec<(AssetId, AssetName, AssetSymbol, AssetDecimals)>,
-) {
-    (
-        vec![
-            (0, root_key.clone(), true, 10_000_000_000u128),
-            (1, root_key.clone(), true, 1u128),
-            (2, root_key.clone(), true, 10_000_000_000u128),
-            (3, root_key.clone(), true, 10_000_000_000u128),
-            (4, root_key.clone(), true, 10_000_000_000u128),
-            (5, root_key.clone(), true, 10_000_000_000u128),
-            (6, root_key.clone(), true, 10_000_000_000u128),
-            (7, root_key.clone(), true, 10_000_000_000u128),
-            (8, root_key.clone(), true, 10_000_000_000u128),
-            (9, root_key.clone(), true, 1u128),
-        ],
-        vec![
-            (
-                0,
-                "Reserved0".to_string().into_bytes(),
-                "RSV0".to_string().into_bytes(),
-                18,
-            ),
-            (
-                1,
-                "SBTC".to_string().into_bytes(),
-                "SBTC".to_string().into_bytes(),
-                8,
-            ),
-            (
-                2,
-                "Reserved2".to_string().into_bytes(),
-                "RSV2".to_string().into_bytes(),
-                18,
-            ),
-            (
-                3,
-                "Reserved3".to_string().into_bytes(),
-                "RSV3".to_string().into_bytes(),
-                18,
-            ),
-            (
-                4,
-                "Reserved4".to_string().into_bytes(),
-                "RSV4".to_string().into_bytes(),
-                18,
-            ),
-            (
-                5,
-                "Reserved5".to_string().into_bytes(),
-                "RSV5".to_string().into_bytes(),
-                18,
-            ),
-            (
-                6,
-                "Reserved6".to_string().into_bytes(),
-                "RSV6".to_string().into_bytes(),
-                18,
-            ),
-            (
-                7,
-                "Reserved7".to_string().into_bytes(),
-                "RSV7".to_string().into_bytes(),
-                18,
-            ),
-            (
-                8,
-                "Reserved8".to_string().into_bytes(),
-                "RSV8".to_string().into_bytes(),
-                18,
-            ),
-            (
-                9,
-                "Dogecoin".to_string().into_bytes(),
-                "Doge".to_string().into_bytes(),
-                8,
-            ),
-        ],
-    )
+/// The reserved assets, SBTC of Bitcoin and Doge of Dogecoin.
+fn reserved_assets(root_key: &AccountId) -> Vec<AssetParams> {
+    let asset = |id, chain, name: &str, symbol: &str, decimals, min_balance| AssetParams {
+        id,
+        owner: root_key.clone(),
+        chain,
+        name: name.as_bytes().to_vec(),
+        symbol: symbol.as_bytes().to_vec(),
+        decimals,
+        sufficient: true,
+        min_balance,
+    };
+    let mut assets = vec![
+        asset(0, None, "Reserved0", "RSV0", 18, 10_000_000_000u128),
+        asset(1, Some(Chain::Bitcoin), "SBTC", "SBTC", 8, 1u128),
+    ];
+    for id in 2..9 {
+        let (name, symbol) = (format!("Reserved{}", id), format!("RSV{}", id));
+        assets.push(asset(id, None, &name, &symbol, 18, 10_000_000_000u128));
+    }
+    assets.push(asset(
+        9,
+        Some(Chain::Dogecoin),
+        "Dogecoin",
+        "Doge",
+        8,
+        1u128,
+    ));
+    assets
 }
 
-#[cfg(feature = "runtime-benchmarks")]
-pub fn benchmarks_config() -> Result<ChainSpec, String> {
+/// Endow each account with 4096 KSX.
+fn endowed_balances(endowed_accounts: Vec<AccountId>) -> Vec<(AccountId, Balance)> {
+    endowed_accounts
+        .into_iter()
+        .map(|k| (k, UNITS * 4096))
+        .collect()
+}
+
+/// The properties of the KSX token.
+pub fn sherpax_properties() -> Properties {
     let mut properties = Properties::new();
     properties.insert("tokenSymbol".into(), "KSX".into());
     properties.insert("tokenDecimals".into(), 18i32.into());
@@ -187,33 +141,40 @@ pub fn benchmarks_config() -> Result<ChainSpec, String> {
         "ss58Format".into(),
         sherpax_runtime::SS58Prefix::get().into(),
     );
+    properties
+}
 
+#[cfg(feature = "runtime-benchmarks")]
+pub fn benchmarks_config() -> Result<ChainSpec, String> {
     Ok(ChainSpec::from_genesis(
         "Benchmarks",
         "benchmarks",
         ChainType::Development,
         move || {
             let caller: AccountId = frame_benchmarking::whitelisted_caller();
-            sherpax_genesis(
+            sherpax_genesis(GenesisParams {
                 // Initial PoA authorities
-                vec![authority_keys_from_seed("Alice")],
+                initial_authorities: vec![authority_keys_from_seed("Alice")],
                 // Sudo account
-                caller.clone(),
+                root_key: caller.clone(),
+                technical_committee: technical_committee_membership(),
                 // Pre-funded accounts
-                vec![
+                balances: endowed_balances(vec![
                     get_account_id_from_seed::<sr25519::Public>("Alice"),
                     get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
                     caller.clone(),
-                ],
-                false,
-                btc_genesis_params(include_str!(
+                ]),
+                vesting: vec![],
+                assets: reserved_assets(&caller),
+                bitcoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/btc_genesis_params_benchmarks.json"
                 )),
-                btc_genesis_params(include_str!(
+                dogecoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/dogecoin_genesis_params_testnet.json"
                 )),
-                crate::bitcoin::benchmarks_trustees(),
-            )
+                trustees: crate::bitcoin::benchmarks_trustees(),
+                ethereum_chain_id: SHERPAX_CHAIN_ID,
+            })
         },
         // Bootnodes
         vec![],
@@ -223,21 +184,13 @@ pub fn benchmarks_config() -> Result<ChainSpec, String> {
         None,
         // Properties
         None,
-        Some(properties),
+        Some(sherpax_properties()),
         // Extensions
         Default::default(),
     ))
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
-    let mut properties = Properties::new();
-    properties.insert("tokenSymbol".into(), "KSX".into());
-    properties.insert("tokenDecimals".into(), 18i32.into());
-    properties.insert(
-        "ss58Format".into(),
-        sherpax_runtime::SS58Prefix::get().into(),
-    );
-
     Ok(ChainSpec::from_genesis(
         // Name
         "Development",
@@ -245,29 +198,33 @@ pub fn development_config() -> Result<ChainSpec, String> {
         "dev",
         ChainType::Development,
         move || {
-            sherpax_genesis(
+            let root_key = get_account_id_from_seed::<sr25519::Public>("Alice");
+            sherpax_genesis(GenesisParams {
                 // Initial PoA authorities
-                vec![authority_keys_from_seed("Alice")],
+                initial_authorities: vec![authority_keys_from_seed("Alice")],
                 // Sudo account
-                get_account_id_from_seed::<sr25519::Public>("Alice"),
+                root_key: root_key.clone(),
+                technical_committee: technical_committee_membership(),
                 // Pre-funded accounts
-                vec![
+                balances: endowed_balances(vec![
                     get_account_id_from_seed::<sr25519::Public>("Alice"),
                     get_account_id_from_seed::<sr25519::Public>("Bob"),
                     get_account_id_from_seed::<sr25519::Public>("Charlie"),
                     get_account_id_from_seed::<sr25519::Public>("Dave"),
                     get_account_id_from_seed::<sr25519::Public>("Eve"),
                     get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-                ],
-                false,
-                btc_genesis_params(include_str!(
+                ]),
+                vesting: vec![],
+                assets: reserved_assets(&root_key),
+                bitcoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/btc_genesis_params_testnet.json"
                 )),
-                btc_genesis_params(include_str!(
+                dogecoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/dogecoin_genesis_params_testnet.json"
                 )),
-                crate::bitcoin::dev_trustees(),
-            )
+                trustees: crate::bitcoin::dev_trustees(),
+                ethereum_chain_id: SHERPAX_CHAIN_ID,
+            })
         },
         // Bootnodes
         vec![],
@@ -277,21 +234,13 @@ pub fn development_config() -> Result<ChainSpec, String> {
         None,
         None,
         // Properties
-        Some(properties),
+        Some(sherpax_properties()),
         // Extensions
         Default::default(),
     ))
 }
 
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
-    let mut properties = Properties::new();
-    properties.insert("tokenSymbol".into(), "KSX".into());
-    properties.insert("tokenDecimals".into(), 18i32.into());
-    properties.insert(
-        "ss58Format".into(),
-        sherpax_runtime::SS58Prefix::get().into(),
-    );
-
     Ok(ChainSpec::from_genesis(
         // Name
         "Local Testnet",
@@ -299,32 +248,36 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
         "local_testnet",
         ChainType::Local,
         move || {
-            sherpax_genesis(
+            let root_key = get_account_id_from_seed::<sr25519::Public>("Alice");
+            sherpax_genesis(GenesisParams {
                 // Initial PoA authorities
-                vec![
+                initial_authorities: vec![
                     authority_keys_from_seed("Alice"),
                     authority_keys_from_seed("Bob"),
                 ],
                 // Sudo account
-                get_account_id_from_seed::<sr25519::Public>("Alice"),
+                root_key: root_key.clone(),
+                technical_committee: technical_committee_membership(),
                 // Pre-funded accounts
-                vec![
+                balances: endowed_balances(vec![
                     get_account_id_from_seed::<sr25519::Public>("Alice"),
                     get_account_id_from_seed::<sr25519::Public>("Bob"),
                     get_account_id_from_seed::<sr25519::Public>("Charlie"),
                     get_account_id_from_seed::<sr25519::Public>("Dave"),
                     get_account_id_from_seed::<sr25519::Public>("Eve"),
                     get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-                ],
-                true,
-                btc_genesis_params(include_str!(
+                ]),
+                vesting: vec![],
+                assets: reserved_assets(&root_key),
+                bitcoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/btc_genesis_params_testnet.json"
                 )),
-                btc_genesis_params(include_str!(
+                dogecoin: btc_genesis_params(include_str!(
                     "../res/genesis_config/gateway/dogecoin_genesis_params_testnet.json"
                 )),
-                crate::bitcoin::mainnet_trustees(),
-            )
+                trustees: crate::bitcoin::mainnet_trustees(),
+                ethereum_chain_id: SHERPAX_CHAIN_ID,
+            })
         },
         // Bootnodes
         vec![],
@@ -334,89 +287,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
         None,
         None,
         // Properties
-        Some(properties),
-        // Extensions
-        Default::default(),
-    ))
-}
-
-#[allow(unused)]
-pub fn mainnet_config() -> Result<ChainSpec, String> {
-    let bootnodes = bootnodes![
-        "/ip4/52.77.243.26/tcp/10025/ws/p2p/12D3KooWK6zL4BFCFgcfCLn8xMmZcAp1wX6nTGfJx3gRzbq6qE3Y",
-        "/ip4/47.114.74.52/tcp/40041/ws/p2p/12D3KooWJws7aM9euRhEM2CAvNTvKboiVi9wFRdHeWjtLUEiAJWo",
-        "/ip4/116.62.46.8/tcp/40042/ws/p2p/12D3KooWSAeap3NaSihLuz85tX8uKn8f8Wfgo8iY9WFM1MRAvQiX",
-    ];
-
-    let mut properties = Properties::new();
-    properties.insert("tokenSymbol".into(), "KSX".into());
-    properties.insert("tokenDecimals".into(), 18i32.into());
-    properties.insert(
-        "ss58Format".into(),
-        sherpax_runtime::SS58Prefix::get().into(),
-    );
-
-    Ok(ChainSpec::from_genesis(
-        // Name
-        "SherpaX",
-        // ID
-        "sherpax_singleton",
-        ChainType::Live,
-        move || {
-            sherpax_genesis(
-                // Initial PoA authorities
-                vec![
-                    (
-                        // Ff3b3gdWT2nwC9BSjcys1S8Tth2XBayEgHZkX8pbCrErqgf
-                        hex!("884f4d6638c1f70ed80537be200df124efc384e8177f74377a2be919513dcc3a")
-                            .into(),
-                        hex!("e07d42d9b6a3403be406efaaaf952981c2e124cabc305b49b179546d5cfe7f0e")
-                            .unchecked_into(),
-                        hex!("67b4639b336f7fcefc2b7696be57dbf5059208d01ad67e08ff9688d97efdb519")
-                            .unchecked_into(),
-                    ),
-                    (
-                        // J1SDJ7KvkESXfT8RjSP9Sy8TfUh2UMstRTh7CN7be9NipQB
-                        hex!("f054d6fd1444f2e78f2839dc4ec5e4f35f0fc003cf006f3f712f659cdc2ecb63")
-                            .into(),
-                        hex!("86a185b97c75744c614355991d5faac5ea8a57eb6b24a4baf352246f5eb58221")
-                            .unchecked_into(),
-                        hex!("c17b592b9ccf92127726607881c51304df8b8bb002caff9cd864a046cc85d4d0")
-                            .unchecked_into(),
-                    ),
-                ],
-                // Sudo account
-                // FCcnKcbTe5EYDZXDCKwbhkPAoYyakG1iRBxS9Ai5m2uFTfn
-                hex!("74276b30236e3ffc822c0e5ec0ac8b02933dac11fcefc88733c8a61cdaa45a59").into(),
-                // Pre-funded accounts
-                vec![
-                    hex!("74276b30236e3ffc822c0e5ec0ac8b02933dac11fcefc88733c8a61cdaa45a59").into(),
-                ],
-                true,
-                btc_genesis_params(include_str!(
-                    "../res/genesis_config/gateway/btc_genesis_params_testnet.json"
-                )),
-                btc_genesis_params(include_str!(
-                    "../res/genesis_config/gateway/dogecoin_genesis_params_mainnet.json"
-                )),
-                crate::bitcoin::mainnet_trustees(),
-            )
-        },
-        // Bootnodes
-        bootnodes,
-        // Telemetry
-        Some(
-            TelemetryEndpoints::new(vec![
-                (CHAINX_TELEMETRY_URL.to_string(), 0),
-                (POLKADOT_TELEMETRY_URL.to_string(), 0),
-            ])
-            .expect("SherpaX telemetry url is valid; qed"),
-        ),
-        // Protocol ID
-        Some(DEFAULT_PROTOCOL_ID),
-        None,
-        // Properties
-        Some(properties),
+        Some(sherpax_properties()),
         // Extensions
         Default::default(),
     ))
@@ -438,60 +309,38 @@ fn technical_committee_membership() -> Vec<AccountId> {
 }
 
 /// Configure initial storage state for FRAME modules.
-pub fn sherpax_genesis(
-    initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
-    root_key: AccountId,
-    endowed_accounts: Vec<AccountId>,
-    load_genesis: bool,
-    bitcoin: BtcGenesisParams,
-    dogecoin: BtcGenesisParams,
-    trustees: Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>,
-) -> GenesisConfig {
-    let (balances, vesting) = if load_genesis {
-        load_genesis_config(&root_key)
-    } else {
-        let balances = endowed_accounts
+pub fn sherpax_genesis(params: GenesisParams) -> GenesisConfig {
+    let GenesisParams {
+        initial_authorities,
+        root_key,
+        technical_committee,
+        balances,
+        vesting,
+        assets,
+        bitcoin,
+        dogecoin,
+        trustees,
+        ethereum_chain_id,
+    } = params;
+
+    let genesis_trustees = |chain: Chain| {
+        trustees
             .iter()
-            .cloned()
-            .map(|k| (k, UNITS * 4096))
-            .collect();
-
-        (balances, Default::default())
+            .find(|(c, _, _)| *c == chain)
+            .map(|(_, _, trustee_params)| {
+                trustee_params
+                    .iter()
+                    .map(|i| (i.0).clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
     };
-
-    let btc_genesis_trustees = trustees
-        .iter()
-        .find_map(|(chain, _, trustee_params)| {
-            if *chain == Chain::Bitcoin {
-                Some(
-                    trustee_params
-                        .iter()
-                        .map(|i| (i.0).clone())
-                        .collect::<Vec<_>>(),
-                )
-            } else {
-                None
-            }
-        })
-        .expect("bitcoin trustees generation can not fail; qed");
-    let doge_genesis_trustees = trustees
+    let btc_genesis_trustees = genesis_trustees(Chain::Bitcoin);
+    let doge_genesis_trustees = genesis_trustees(Chain::Dogecoin);
+    let initial_asset_chain = assets
         .iter()
-        .find_map(|(chain, _, trustee_params)| {
-            if *chain == Chain::Dogecoin {
-                Some(
-                    trustee_params
-                        .iter()
-                        .map(|i| (i.0).clone())
-                        .collect::<Vec<_>>(),
-                )
-            } else {
-                None
-            }
-        })
-        .expect("dogecoin trustees generation can not fail; qed");
-    let sbtc_info = sbtc();
-    let doge_info = doge();
-    let assets_info = reserved_assets(&root_key);
+        .filter_map(|asset| asset.chain.map(|chain| (asset.id, chain)))
+        .collect();
     let wasm_binary = WASM_BINARY.unwrap();
     GenesisConfig {
         system: SystemConfig {
@@ -518,7 +367,9 @@ pub fn sherpax_genesis(
             key: Some(root_key),
         },
         vesting: VestingConfig { vesting },
-        ethereum_chain_id: EthereumChainIdConfig { chain_id: 1506u64 },
+        ethereum_chain_id: EthereumChainIdConfig {
+            chain_id: ethereum_chain_id,
+        },
         evm: Default::default(),
         ethereum: Default::default(),
         base_fee: BaseFeeConfig::new(
@@ -527,8 +378,14 @@ pub fn sherpax_genesis(
             sp_runtime::Permill::from_parts(125_000),
         ),
         assets: sherpax_runtime::AssetsConfig {
-            assets: assets_info.0,
-            metadata: assets_info.1,
+            assets: assets
+                .iter()
+                .map(|a| (a.id, a.owner.clone(), a.sufficient, a.min_balance))
+                .collect(),
+            metadata: assets
+                .into_iter()
+                .map(|a| (a.id, a.name, a.symbol, a.decimals))
+                .collect(),
             accounts: vec![],
         },
         assets_bridge: AssetsBridgeConfig { admin_key: None },
@@ -537,7 +394,7 @@ pub fn sherpax_genesis(
         democracy: Default::default(),
         technical_committee: Default::default(),
         technical_membership: TechnicalMembershipConfig {
-            members: technical_committee_membership(),
+            members: technical_committee,
             phantom: Default::default(),
         },
         treasury: Default::default(),
@@ -580,135 +437,7 @@ pub fn sherpax_genesis(
             max_withdrawal_count: 100,
         },
         x_gateway_records: sherpax_runtime::XGatewayRecordsConfig {
-            initial_asset_chain: vec![(sbtc_info.1, sbtc_info.0), (doge_info.1, doge_info.0)],
+            initial_asset_chain,
         },
     }
 }
-
-#[allow(clippy::type_complexity)]
-fn load_genesis_config(
-    root_key: &AccountId,
-) -> (
-    Vec<(AccountId, Balance)>,
-    Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
-) {
-    let chainx_snapshot = include_bytes!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/res/genesis_config/balances/genesis_balances_chainx_snapshot_7418_7868415220855310000000000.json"
-    ))
-        .to_vec();
-
-    let comingchat_miners = include_bytes!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/res/genesis_config/balances/genesis_balances_comingchat_miners_334721_2140742819000000000000000.json"
-    ))
-        .to_vec();
-
-    let sherpax_contributors = include_bytes!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/res/genesis_config/balances/genesis_balances_sherpax_contributors_1873_94046984872650000000000.json"
-    ))
-        .to_vec();
-
-    let vestings = include_bytes!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/res/genesis_config/vesting/genesis_vesting_342133_894769078020746000000000.json"
-    ))
-    .to_vec();
-
-    let balances_configs: Vec<sherpax_runtime::BalancesConfig> = config_from_json_bytes(vec![
-        chainx_snapshot,
-        comingchat_miners,
-        sherpax_contributors,
-    ])
-    .unwrap();
-
-    let mut mutated_balances: Vec<(AccountId, u128)> = balances_configs
-        .into_iter()
-        .flat_map(|bc| bc.balances)
-        .collect();
-
-    // total transfer vesting balances
-    let transfer_balances = 2631584779144690000000000u128;
-    // 30000 ksx + transfer vesting balances
-    let root_balance = 30000000000000000000000u128.saturating_add(transfer_balances);
-
-    let back_to_treasury = 21000000000000000000000000u128
-        .saturating_sub(root_balance)
-        .saturating_sub(10103205024727960000000000u128);
-
-    // 5S7WgdAXVK7mh8REvXfk9LdHs3Xqu9B2E9zzY8e4LE8Gg2ZX
-    let treasury_account: AccountId = PalletId(*b"pcx/trsy").into_account();
-
-    mutated_balances.push((root_key.clone(), root_balance));
-    mutated_balances.push((treasury_account, back_to_treasury));
-
-    let vesting_configs: Vec<sherpax_runtime::VestingConfig> =
-        config_from_json_bytes(vec![vestings]).unwrap();
-
-    let mut total_issuance: Balance = 0u128;
-    let balances: Vec<(AccountId, u128)> = mutated_balances
-        .into_iter()
-        .fold(
-            BTreeMap::<AccountId, Balance>::new(),
-            |mut acc, (account_id, amount)| {
-                if let Some(balance) = acc.get_mut(&account_id) {
-                    *balance = balance
-                        .checked_add(amount)
-                        .expect("balance cannot overflow when building genesis");
-                } else {
-                    acc.insert(account_id.clone(), amount);
-                }
-
-                total_issuance = total_issuance
-                    .checked_add(amount)
-                    .expect("total insurance cannot overflow when building genesis");
-                acc
-            },
-        )
-        .into_iter()
-        .collect();
-
-    assert_eq!(
-        balances.len(),
-        342133 + 1 + 1 + 1873 - 35,
-        "total accounts must be equal to 344013"
-    );
-
-    assert_eq!(
-        total_issuance,
-        21000000 * UNITS,
-        "total issuance must be equal to 21000000000000000000000000"
-    );
-
-    let vestings: Vec<(AccountId, BlockNumber, BlockNumber, Balance)> = vesting_configs
-        .into_iter()
-        .flat_map(|vc| vc.vesting)
-        .collect();
-    let vesting_liquid = vestings.iter().map(|(_, _, _, free)| free).sum::<u128>();
-
-    assert_eq!(
-        vestings.len(),
-        342133,
-        "total vesting accounts must be equal 342138."
-    );
-    assert_eq!(
-        vesting_liquid, 894769078020746000000000u128,
-        "total vesting liquid must be equal 894769078020746000000000"
-    );
-
-    (balances, vestings)
-}
-
-fn config_from_json_bytes<T: DeserializeOwned>(bytes: Vec<Vec<u8>>) -> Result<Vec<T>, String> {
-    let mut configs = vec![];
-
-    for raw in bytes {
-        let config = serde_json::from_slice(&raw)
-            .map_err(|e| format!("Error parsing config file: {}", e))?;
-
-        configs.push(config)
-    }
-
-    Ok(configs)
-}
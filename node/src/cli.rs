@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use sc_cli::RunCmd;
 
 #[derive(Debug, clap::Parser)]
@@ -7,6 +9,157 @@ pub struct Cli {
 
     #[clap(flatten)]
     pub run: RunCmd,
+
+    /// Seal the blocks by manual-seal instead of Aura and GRANDPA, for development only.
+    ///
+    /// `instant` seals a block once a transaction is imported, `manual` seals a block by the
+    /// `engine_createBlock` RPC, and a number seals a block every that many milliseconds.
+    #[clap(long)]
+    pub sealing: Option<Sealing>,
+
+    #[clap(flatten)]
+    pub evm_tracing: EvmTracingParams,
+
+    #[clap(flatten)]
+    pub btc_relayer: BtcRelayerParams,
+}
+
+/// The block sealing of the development node.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+    /// Seal a block once a transaction is imported into the pool.
+    Instant,
+    /// Seal a block by the `engine_createBlock` RPC.
+    Manual,
+    /// Seal a block every given milliseconds.
+    Interval(u64),
+}
+
+impl std::str::FromStr for Sealing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instant" => Ok(Self::Instant),
+            "manual" => Ok(Self::Manual),
+            interval => match interval.parse() {
+                Ok(0) | Err(_) => Err(format!(
+                    "Invalid sealing `{}`, expected `instant`, `manual` or milliseconds",
+                    interval
+                )),
+                Ok(millis) => Ok(Self::Interval(millis)),
+            },
+        }
+    }
+}
+
+/// The EVM tracing RPCs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ArgEnum)]
+pub enum EthApi {
+    /// `debug_traceTransaction` and `debug_traceBlockByNumber`.
+    Debug,
+    /// `trace_filter`.
+    Trace,
+}
+
+/// Parameters of the EVM tracing RPCs.
+#[derive(Debug, Clone, clap::Args)]
+pub struct EvmTracingParams {
+    /// Enable the EVM tracing RPCs, e.g. `--ethapi=debug,trace`.
+    ///
+    /// The tracing runtime, i.e. the runtime compiled with the `evm-tracing` feature, must be
+    /// loaded by `--wasm-runtime-overrides` and executed by `--execution=wasm`.
+    #[clap(long, arg_enum, use_value_delimiter = true)]
+    pub ethapi: Vec<EthApi>,
+
+    /// The maximum number of traces returned by a `trace_filter` request.
+    #[clap(long, default_value = "500")]
+    pub ethapi_trace_max_count: u32,
+
+    /// The maximum number of blocks replayed by a `trace_filter` request.
+    #[clap(long, default_value = "1000")]
+    pub ethapi_trace_max_block_range: u32,
+
+    /// The maximum number of traced blocks cached for `trace_filter`.
+    #[clap(long, default_value = "1024")]
+    pub ethapi_trace_cache_size: usize,
+}
+
+impl EvmTracingParams {
+    /// Return the config of the EVM tracing RPCs.
+    pub fn config(&self) -> crate::evm_tracing::EvmTracingConfig {
+        crate::evm_tracing::EvmTracingConfig {
+            debug: self.ethapi.contains(&EthApi::Debug),
+            trace: self.ethapi.contains(&EthApi::Trace),
+            trace_max_count: self.ethapi_trace_max_count,
+            trace_max_block_range: self.ethapi_trace_max_block_range,
+            trace_cache: std::sync::Arc::new(std::sync::Mutex::new(
+                crate::evm_tracing::TraceCache::new(self.ethapi_trace_cache_size),
+            )),
+        }
+    }
+}
+
+/// Parameters of the built-in bitcoin relayer.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BtcRelayerParams {
+    /// Enable the built-in bitcoin relayer, which relays headers and transactions from a bitcoind.
+    #[clap(long = "btc-relayer")]
+    pub enabled: bool,
+
+    /// The JSON-RPC endpoint of the bitcoind to relay from.
+    #[clap(long = "btc-relayer-url", default_value = "http://127.0.0.1:8332")]
+    pub url: String,
+
+    /// The JSON-RPC username of the bitcoind.
+    #[clap(long = "btc-relayer-user")]
+    pub user: Option<String>,
+
+    /// The file containing the JSON-RPC password of the bitcoind, so that the password is not
+    /// exposed on the command line.
+    #[clap(long = "btc-relayer-password-file", parse(from_os_str))]
+    pub password_file: Option<PathBuf>,
+
+    /// The file containing the secret URI of the key signing `push_header`/`push_transaction`,
+    /// e.g. `//Alice`. The account must have registered as a bitcoin relayer by
+    /// `register_relayer`.
+    #[clap(long = "btc-relayer-key-file", parse(from_os_str))]
+    pub key_file: Option<PathBuf>,
+
+    /// The interval in seconds between two polls of the bitcoind.
+    #[clap(long = "btc-relayer-interval", default_value = "30")]
+    pub interval: u64,
+}
+
+impl BtcRelayerParams {
+    /// Return the relayer config if the relayer is enabled.
+    pub fn config(&self) -> Result<Option<crate::relayer::BtcRelayerConfig>, String> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        let key_file = self
+            .key_file
+            .as_deref()
+            .ok_or("`--btc-relayer-key-file` is required by `--btc-relayer`")?;
+        let suri = read_secret(key_file)?;
+        let signer = <sp_core::sr25519::Pair as sp_core::Pair>::from_string(&suri, None)
+            .map_err(|e| format!("Invalid key in `--btc-relayer-key-file`: {:?}", e))?;
+        let password = self.password_file.as_deref().map(read_secret).transpose()?;
+        Ok(Some(crate::relayer::BtcRelayerConfig {
+            url: self.url.clone(),
+            user: self.user.clone(),
+            password,
+            signer,
+            interval: std::time::Duration::from_secs(self.interval),
+        }))
+    }
+}
+
+/// Read a secret from the first line of the file.
+fn read_secret(path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read `{}`: {}", path.display(), e))?;
+    Ok(content.lines().next().unwrap_or_default().to_string())
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -36,6 +189,13 @@ pub enum Subcommand {
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
 
+    /// Build a chain specification from a genesis config file.
+    BuildGenesis(BuildGenesisCmd),
+
+    /// Trustee utilities for signing the taproot withdrawal transactions.
+    #[clap(subcommand)]
+    Trustee(TrusteeSubcommand),
+
     /// The custom benchmark subcommmand benchmarking runtime pallets.
     #[clap(name = "benchmark", about = "Benchmark runtime pallets.")]
     Benchmark(frame_benchmarking_cli::BenchmarkCmd),
@@ -48,3 +208,119 @@ pub enum Subcommand {
     #[cfg(not(feature = "try-runtime"))]
     TryRuntime,
 }
+
+/// Build the chain spec from the balances, vesting, trustees, genesis headers and assets of
+/// a genesis config, see `node/res/genesis/mainnet.toml`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BuildGenesisCmd {
+    /// The TOML or JSON genesis config file.
+    #[clap(long, parse(from_os_str))]
+    pub config: std::path::PathBuf,
+
+    /// Force raw genesis storage output.
+    #[clap(long)]
+    pub raw: bool,
+
+    /// The output file of the chain spec, stdout by default.
+    #[clap(long, parse(from_os_str))]
+    pub output: Option<std::path::PathBuf>,
+}
+
+/// The trustee utilities, a withdrawal is signed in the following steps:
+///
+/// 1. `create-tx`: build the unsigned withdrawal tx into a signing session file.
+/// 2. `nonce`: each signer adds its public nonces to the session file.
+/// 3. `sign`: each signer adds its partial signatures to the session file.
+/// 4. `combine`: combine the partial signatures into the final witness.
+/// 5. `verify`: check the signed tx with `xgatewaybitcoin_verifyTxValid`.
+#[derive(Debug, clap::Subcommand)]
+pub enum TrusteeSubcommand {
+    /// Generate a trustee keypair, the public key is accepted by `setup_trustee`.
+    GenerateKey(TrusteeKeyParams),
+
+    /// List the pending withdrawals with the fee info.
+    Withdrawals(TrusteeNodeParams),
+
+    /// Build the unsigned withdrawal tx into a signing session file.
+    CreateTx(TrusteeCreateTxCmd),
+
+    /// Add the public nonces of the local key to the signing session.
+    Nonce(TrusteeSignCmd),
+
+    /// Add the partial signatures of the local key to the signing session.
+    Sign(TrusteeSignCmd),
+
+    /// Combine the partial signatures into the final witness and print the signed tx.
+    Combine(TrusteeSessionParams),
+
+    /// Check the signed tx of the signing session against the node.
+    Verify(TrusteeVerifyCmd),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeKeyParams {
+    /// The file storing the hex encoded secret key of the trustee.
+    #[clap(long, parse(from_os_str))]
+    pub key_file: std::path::PathBuf,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeNodeParams {
+    /// The HTTP JSON-RPC endpoint of a sherpax node.
+    #[clap(long, default_value = "http://127.0.0.1:8546")]
+    pub url: String,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeSessionParams {
+    /// The signing session file.
+    #[clap(long, parse(from_os_str))]
+    pub session: std::path::PathBuf,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeCreateTxCmd {
+    #[clap(flatten)]
+    pub node: TrusteeNodeParams,
+
+    #[clap(flatten)]
+    pub session: TrusteeSessionParams,
+
+    /// The pending withdrawals to be processed, all of them by default.
+    #[clap(long, use_value_delimiter = true)]
+    pub withdrawal_ids: Vec<u32>,
+
+    /// The hot address UTXOs to be spent, in the format of `txid:vout:value`.
+    #[clap(long, required = true, use_value_delimiter = true)]
+    pub utxos: Vec<String>,
+
+    /// The miner fee in satoshi, the rest of the inputs is changed to the hot address.
+    #[clap(long)]
+    pub miner_fee: u64,
+
+    /// The hex encoded hot public keys of the current trustees, in the session order.
+    #[clap(long, required = true, use_value_delimiter = true)]
+    pub trustees: Vec<String>,
+
+    /// The hex encoded hot public keys of the trustees signing the withdrawal.
+    #[clap(long, required = true, use_value_delimiter = true)]
+    pub signers: Vec<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeSignCmd {
+    #[clap(flatten)]
+    pub key: TrusteeKeyParams,
+
+    #[clap(flatten)]
+    pub session: TrusteeSessionParams,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TrusteeVerifyCmd {
+    #[clap(flatten)]
+    pub node: TrusteeNodeParams,
+
+    #[clap(flatten)]
+    pub session: TrusteeSessionParams,
+}
@@ -15,10 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::{Cli, Subcommand};
+use crate::cli::{Cli, Sealing, Subcommand};
 use crate::{chain_spec, service};
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
-use sc_service::PartialComponents;
+use sc_service::{ChainType, Configuration, PartialComponents};
 use sherpax_runtime::Block;
 
 impl SubstrateCli for Cli {
@@ -91,6 +91,23 @@ impl SubstrateCli for Cli {
     }
 }
 
+/// Reject the manual-seal `--sealing` except for the development and local chains, whose blocks
+/// are not imported by the Aura and GRANDPA nodes.
+fn check_sealing(
+    config: &Configuration,
+    sealing: Option<Sealing>,
+) -> sc_cli::Result<Option<Sealing>> {
+    match config.chain_spec.chain_type() {
+        ChainType::Development | ChainType::Local => Ok(sealing),
+        chain_type if sealing.is_some() => Err(sc_cli::Error::Input(format!(
+            "`--sealing` is only for the development and local chains, not the {:?} chain `{}`",
+            chain_type,
+            config.chain_spec.id()
+        ))),
+        _ => Ok(None),
+    }
+}
+
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
     let cli = Cli::from_args();
@@ -104,46 +121,50 @@ pub fn run() -> sc_cli::Result<()> {
         Some(Subcommand::CheckBlock(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|mut config| {
+                let sealing = check_sealing(&config, cli.sealing)?;
                 let PartialComponents {
                     client,
                     task_manager,
                     import_queue,
                     ..
-                } = service::new_partial(&mut config)?;
+                } = service::new_partial(&mut config, sealing)?;
                 Ok((cmd.run(client, import_queue), task_manager))
             })
         }
         Some(Subcommand::ExportBlocks(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|mut config| {
+                let sealing = check_sealing(&config, cli.sealing)?;
                 let PartialComponents {
                     client,
                     task_manager,
                     ..
-                } = service::new_partial(&mut config)?;
+                } = service::new_partial(&mut config, sealing)?;
                 Ok((cmd.run(client, config.database), task_manager))
             })
         }
         Some(Subcommand::ExportState(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|mut config| {
+                let sealing = check_sealing(&config, cli.sealing)?;
                 let PartialComponents {
                     client,
                     task_manager,
                     ..
-                } = service::new_partial(&mut config)?;
+                } = service::new_partial(&mut config, sealing)?;
                 Ok((cmd.run(client, config.chain_spec), task_manager))
             })
         }
         Some(Subcommand::ImportBlocks(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|mut config| {
+                let sealing = check_sealing(&config, cli.sealing)?;
                 let PartialComponents {
                     client,
                     task_manager,
                     import_queue,
                     ..
-                } = service::new_partial(&mut config)?;
+                } = service::new_partial(&mut config, sealing)?;
                 Ok((cmd.run(client, import_queue), task_manager))
             })
         }
@@ -162,15 +183,18 @@ pub fn run() -> sc_cli::Result<()> {
         Some(Subcommand::Revert(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|mut config| {
+                let sealing = check_sealing(&config, cli.sealing)?;
                 let PartialComponents {
                     client,
                     task_manager,
                     backend,
                     ..
-                } = service::new_partial(&mut config)?;
+                } = service::new_partial(&mut config, sealing)?;
                 Ok((cmd.run(client, backend), task_manager))
             })
         }
+        Some(Subcommand::BuildGenesis(cmd)) => cmd.run(),
+        Some(Subcommand::Trustee(cmd)) => cmd.run(),
         Some(Subcommand::Benchmark(cmd)) => {
             if cfg!(feature = "runtime-benchmarks") {
                 let runner = cli.create_runner(cmd)?;
@@ -203,9 +227,14 @@ pub fn run() -> sc_cli::Result<()> {
                 You can enable it with `--features try-runtime`."
             .into()),
         None => {
+            let btc_relayer = cli.btc_relayer.config()?;
+            let sealing = cli.sealing;
+            let evm_tracing = cli.evm_tracing.config();
             let runner = cli.create_runner(&cli.run)?;
             runner.run_node_until_exit(|config| async move {
-                service::new_full(config).map_err(sc_cli::Error::Service)
+                let sealing = check_sealing(&config, sealing)?;
+                service::new_full(config, sealing, evm_tracing, btc_relayer)
+                    .map_err(sc_cli::Error::Service)
             })
         }
     }
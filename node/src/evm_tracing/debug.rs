@@ -0,0 +1,192 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The `debug_*` tracing RPCs in the format of geth.
+
+use std::sync::Arc;
+
+use fc_rpc::{frontier_backend_client, internal_err};
+use fc_rpc_core::types::BlockNumber;
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sc_client_api::BlockBackend;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+
+use sherpax_runtime::opaque::Block;
+use xp_evm_tracing::DebugRuntimeApi;
+
+use super::{
+    listener::{CallFrame, CallListener, StepListener, StepTrace},
+    replay_block,
+};
+
+/// The tracer of `callTracer`, the struct logs are returned without a tracer.
+const CALL_TRACER: &str = "callTracer";
+
+/// The options of the tracing.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+    /// `callTracer` for the call frames, or the struct logs if `None`.
+    pub tracer: Option<String>,
+    /// Exclude the storage from the struct logs.
+    #[serde(default)]
+    pub disable_storage: bool,
+    /// Exclude the memory from the struct logs.
+    #[serde(default)]
+    pub disable_memory: bool,
+    /// Exclude the stack from the struct logs.
+    #[serde(default)]
+    pub disable_stack: bool,
+}
+
+/// The trace of a transaction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TransactionTrace {
+    CallTracer(CallFrame),
+    StructLogs(StepTrace),
+}
+
+/// The trace of a transaction in a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionTrace {
+    pub tx_hash: H256,
+    pub result: TransactionTrace,
+}
+
+/// The `debug_*` tracing RPCs.
+#[rpc(server)]
+pub trait EvmDebugApi {
+    /// Trace the ethereum transaction `transaction_hash`.
+    #[rpc(name = "debug_traceTransaction")]
+    fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        params: Option<TraceParams>,
+    ) -> Result<TransactionTrace>;
+
+    /// Trace all the ethereum transactions of the block `number`.
+    #[rpc(name = "debug_traceBlockByNumber")]
+    fn trace_block_by_number(
+        &self,
+        number: BlockNumber,
+        params: Option<TraceParams>,
+    ) -> Result<Vec<BlockTransactionTrace>>;
+}
+
+pub struct EvmDebug<C> {
+    client: Arc<C>,
+    backend: Arc<fc_db::Backend<Block>>,
+}
+
+impl<C> EvmDebug<C> {
+    pub fn new(client: Arc<C>, backend: Arc<fc_db::Backend<Block>>) -> Self {
+        Self { client, backend }
+    }
+}
+
+impl<C> EvmDebug<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C: Send + Sync + 'static,
+    C::Api: DebugRuntimeApi<Block>,
+{
+    /// Replay the block `hash` with the listener chosen by `params`.
+    fn trace(
+        &self,
+        hash: H256,
+        transaction_hash: Option<H256>,
+        params: Option<TraceParams>,
+    ) -> Result<Vec<(H256, TransactionTrace)>> {
+        let params = params.unwrap_or_default();
+        match params.tracer.as_deref() {
+            None => {
+                let mut listener = StepListener::new(
+                    !params.disable_stack,
+                    !params.disable_memory,
+                    !params.disable_storage,
+                );
+                replay_block(&*self.client, hash, transaction_hash, &mut listener)?;
+                Ok(listener
+                    .transactions
+                    .into_iter()
+                    .map(|trace| (trace.transaction_hash, TransactionTrace::StructLogs(trace)))
+                    .collect())
+            }
+            Some(CALL_TRACER) => {
+                let mut listener = CallListener::default();
+                replay_block(&*self.client, hash, transaction_hash, &mut listener)?;
+                listener
+                    .transactions
+                    .into_iter()
+                    .map(|(transaction_hash, frame)| {
+                        let frame = frame.ok_or_else(|| {
+                            internal_err(format!("No call frame of {:?}", transaction_hash))
+                        })?;
+                        Ok((transaction_hash, TransactionTrace::CallTracer(frame)))
+                    })
+                    .collect()
+            }
+            Some(tracer) => Err(internal_err(format!(
+                "Unsupported tracer `{}`, expected `{}` or none for the struct logs",
+                tracer, CALL_TRACER
+            ))),
+        }
+    }
+}
+
+impl<C> EvmDebugApi for EvmDebug<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C: Send + Sync + 'static,
+    C::Api: DebugRuntimeApi<Block>,
+{
+    fn trace_transaction(
+        &self,
+        transaction_hash: H256,
+        params: Option<TraceParams>,
+    ) -> Result<TransactionTrace> {
+        let (ethereum_block_hash, _) = frontier_backend_client::load_transactions(
+            &*self.client,
+            &self.backend,
+            transaction_hash,
+            true,
+        )?
+        .ok_or_else(|| internal_err(format!("Transaction {:?} not found", transaction_hash)))?;
+        let id = frontier_backend_client::load_hash(&self.backend, ethereum_block_hash)?
+            .ok_or_else(|| internal_err(format!("Block {:?} not found", ethereum_block_hash)))?;
+        let hash = self
+            .client
+            .expect_block_hash_from_id(&id)
+            .map_err(|e| internal_err(format!("{:?}", e)))?;
+
+        self.trace(hash, Some(transaction_hash), params)?
+            .into_iter()
+            .find_map(|(hash, trace)| (hash == transaction_hash).then(|| trace))
+            .ok_or_else(|| internal_err(format!("Transaction {:?} not traced", transaction_hash)))
+    }
+
+    fn trace_block_by_number(
+        &self,
+        number: BlockNumber,
+        params: Option<TraceParams>,
+    ) -> Result<Vec<BlockTransactionTrace>> {
+        let id =
+            frontier_backend_client::native_block_id(&*self.client, &self.backend, Some(number))?
+                .ok_or_else(|| internal_err("Block not found"))?;
+        let hash = self
+            .client
+            .expect_block_hash_from_id(&id)
+            .map_err(|e| internal_err(format!("{:?}", e)))?;
+
+        Ok(self
+            .trace(hash, None, params)?
+            .into_iter()
+            .map(|(tx_hash, result)| BlockTransactionTrace { tx_hash, result })
+            .collect())
+    }
+}
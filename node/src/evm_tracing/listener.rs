@@ -0,0 +1,263 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The listeners collecting the EVM tracing events of the replayed transactions.
+
+use std::collections::BTreeMap;
+
+use fc_rpc_core::types::Bytes;
+use serde::{Serialize, Serializer};
+use sp_core::{H160, H256, U256};
+
+use xp_evm_tracing::{CallType, Event, Listener, StepFilter};
+
+use super::opcodes::opcode_name;
+
+/// The type of a [`CallFrame`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameType {
+    Call(CallType),
+    SelfDestruct,
+}
+
+impl FrameType {
+    /// The name of the frame type used by the `callTracer`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Call(CallType::Call) => "CALL",
+            Self::Call(CallType::CallCode) => "CALLCODE",
+            Self::Call(CallType::DelegateCall) => "DELEGATECALL",
+            Self::Call(CallType::StaticCall) => "STATICCALL",
+            Self::Call(CallType::Create) => "CREATE",
+            Self::Call(CallType::Create2) => "CREATE2",
+            Self::SelfDestruct => "SELFDESTRUCT",
+        }
+    }
+}
+
+impl Serialize for FrameType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// A call frame of the `callTracer`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub frame_type: FrameType,
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    pub output: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// Collects the top call frame of each traced transaction.
+#[derive(Default)]
+pub struct CallListener {
+    /// The traced transactions with their top call frames.
+    pub transactions: Vec<(H256, Option<CallFrame>)>,
+    /// The call frames being executed.
+    stack: Vec<CallFrame>,
+}
+
+impl CallListener {
+    /// Add the finished `frame` into its parent, or as the top frame of the transaction.
+    fn finish(&mut self, frame: CallFrame) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => {
+                if let Some((_, top)) = self.transactions.last_mut() {
+                    *top = Some(frame);
+                }
+            }
+        }
+    }
+}
+
+impl Listener for CallListener {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Transaction(hash) => {
+                self.stack.clear();
+                self.transactions.push((hash, None));
+            }
+            Event::Enter {
+                call_type,
+                from,
+                to,
+                value,
+                gas,
+                input,
+            } => self.stack.push(CallFrame {
+                frame_type: FrameType::Call(call_type),
+                from,
+                to,
+                value,
+                gas: gas.into(),
+                gas_used: U256::zero(),
+                input: Bytes(input),
+                output: Bytes(Vec::new()),
+                error: None,
+                calls: Vec::new(),
+            }),
+            Event::Exit {
+                gas_used,
+                output,
+                error,
+            } => {
+                if let Some(mut frame) = self.stack.pop() {
+                    frame.gas_used = gas_used.into();
+                    frame.output = Bytes(output);
+                    frame.error = error.map(|error| String::from_utf8_lossy(&error).into_owned());
+                    self.finish(frame);
+                }
+            }
+            Event::SelfDestruct {
+                address,
+                refund_address,
+                balance,
+            } => {
+                if let Some(parent) = self.stack.last_mut() {
+                    parent.calls.push(CallFrame {
+                        frame_type: FrameType::SelfDestruct,
+                        from: address,
+                        to: refund_address,
+                        value: balance,
+                        gas: U256::zero(),
+                        gas_used: U256::zero(),
+                        input: Bytes(Vec::new()),
+                        output: Bytes(Vec::new()),
+                        error: None,
+                        calls: Vec::new(),
+                    });
+                }
+            }
+            Event::Step(_) => {}
+        }
+    }
+
+    fn step_filter(&self) -> StepFilter {
+        StepFilter::default()
+    }
+}
+
+/// An executed opcode of the struct logs.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: String,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<H256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// The struct logs of a traced transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTrace {
+    #[serde(skip)]
+    pub transaction_hash: H256,
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: String,
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// Collects the struct logs of each traced transaction.
+pub struct StepListener {
+    filter: StepFilter,
+    storage: bool,
+    /// The traced transactions.
+    pub transactions: Vec<StepTrace>,
+    /// The storage accessed by each call frame being executed.
+    storages: Vec<BTreeMap<H256, H256>>,
+}
+
+impl StepListener {
+    pub fn new(stack: bool, memory: bool, storage: bool) -> Self {
+        Self {
+            filter: StepFilter {
+                enabled: true,
+                stack,
+                memory,
+            },
+            storage,
+            transactions: Vec::new(),
+            storages: Vec::new(),
+        }
+    }
+}
+
+impl Listener for StepListener {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Transaction(transaction_hash) => {
+                self.storages.clear();
+                self.transactions.push(StepTrace {
+                    transaction_hash,
+                    ..Default::default()
+                });
+            }
+            Event::Enter { .. } => self.storages.push(BTreeMap::new()),
+            Event::Exit {
+                gas_used,
+                output,
+                error,
+            } => {
+                self.storages.pop();
+                if let (true, Some(transaction)) =
+                    (self.storages.is_empty(), self.transactions.last_mut())
+                {
+                    transaction.gas = gas_used;
+                    transaction.failed = error.is_some();
+                    transaction.return_value = hex::encode(output);
+                }
+            }
+            Event::SelfDestruct { .. } => {}
+            Event::Step(step) => {
+                let storage = match (self.storage, self.storages.last_mut()) {
+                    (true, Some(storage)) => {
+                        if let Some((index, value)) = step.storage {
+                            storage.insert(index, value);
+                        }
+                        Some(storage.clone())
+                    }
+                    _ => None,
+                };
+                if let Some(transaction) = self.transactions.last_mut() {
+                    transaction.struct_logs.push(StructLog {
+                        pc: step.pc,
+                        op: opcode_name(step.opcode),
+                        gas: step.gas,
+                        gas_cost: step.gas_cost,
+                        depth: step.depth,
+                        stack: step.stack,
+                        memory: step
+                            .memory
+                            .map(|memory| memory.chunks(32).map(hex::encode).collect()),
+                        storage,
+                    });
+                }
+            }
+        }
+    }
+
+    fn step_filter(&self) -> StepFilter {
+        self.filter
+    }
+}
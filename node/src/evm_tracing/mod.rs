@@ -0,0 +1,87 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The EVM tracing RPCs.
+//!
+//! The blocks are replayed by the `DebugRuntimeApi` of the tracing runtime, i.e. the runtime
+//! compiled with the `evm-tracing` feature and loaded by `--wasm-runtime-overrides`:
+//!
+//! - `debug_traceTransaction`/`debug_traceBlockByNumber` return the struct logs, or the call
+//!   frames with the `callTracer`;
+//! - `trace_filter` returns the flattened call frames of a block range, the traced blocks are
+//!   cached by the [`TraceCache`].
+
+mod debug;
+mod listener;
+mod opcodes;
+#[cfg(test)]
+mod tests;
+mod trace;
+
+use std::sync::{Arc, Mutex};
+
+use fc_rpc::internal_err;
+use jsonrpc_core::Result;
+use sc_client_api::BlockBackend;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+use sherpax_runtime::opaque::Block;
+use xp_evm_tracing::{DebugRuntimeApi, Listener};
+
+pub use self::{
+    debug::{EvmDebug, EvmDebugApi},
+    listener::{CallFrame, CallListener, StepListener},
+    trace::{EvmTrace, EvmTraceApi, TraceCache},
+};
+
+/// The config of the EVM tracing RPCs.
+#[derive(Clone)]
+pub struct EvmTracingConfig {
+    /// Enable `debug_traceTransaction` and `debug_traceBlockByNumber`.
+    pub debug: bool,
+    /// Enable `trace_filter`.
+    pub trace: bool,
+    /// The maximum number of traces returned by a `trace_filter` request.
+    pub trace_max_count: u32,
+    /// The maximum number of blocks replayed by a `trace_filter` request.
+    pub trace_max_block_range: u32,
+    /// The traced blocks of `trace_filter`, shared by all the RPC servers.
+    pub trace_cache: Arc<Mutex<TraceCache>>,
+}
+
+/// Replay the block `hash` with the `listener` receiving the events of the ethereum
+/// transaction `transaction_hash`, or all the ethereum transactions if `None`.
+pub fn replay_block<C>(
+    client: &C,
+    hash: H256,
+    transaction_hash: Option<H256>,
+    listener: &mut (dyn Listener + 'static),
+) -> Result<()>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C::Api: DebugRuntimeApi<Block>,
+{
+    let id = BlockId::Hash(hash);
+    let header = client
+        .header(id)
+        .map_err(|e| internal_err(format!("Fetch the header failed: {:?}", e)))?
+        .ok_or_else(|| internal_err(format!("Block {:?} not found", hash)))?;
+    let extrinsics = client
+        .block_body(&id)
+        .map_err(|e| internal_err(format!("Fetch the extrinsics failed: {:?}", e)))?
+        .ok_or_else(|| internal_err(format!("Block {:?} not found", hash)))?;
+    let parent = BlockId::Hash(*header.parent_hash());
+
+    let api = client.runtime_api();
+    api.initialize_block(&parent, &header)
+        .map_err(|e| internal_err(format!("Initialize the block failed: {:?}", e)))?;
+    let result = xp_evm_tracing::using(listener, || match transaction_hash {
+        Some(transaction_hash) => api.trace_transaction(&parent, extrinsics, transaction_hash),
+        None => api.trace_block(&parent, extrinsics),
+    });
+    result
+        .map_err(|e| internal_err(format!("Runtime api failed: {:?}", e)))?
+        .map_err(|e| internal_err(format!("Trace the block failed: {:?}", e)))
+}
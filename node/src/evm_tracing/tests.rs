@@ -0,0 +1,194 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::sync::Arc;
+
+use sp_core::{H160, H256, U256};
+
+use xp_evm_tracing::{CallType, Event, Listener, Step};
+
+use super::{
+    listener::{CallListener, FrameType, StepListener},
+    opcodes::opcode_name,
+    trace::{flatten, Action, TraceCache, TransactionInfo},
+};
+
+fn enter(call_type: CallType, from: u8, to: u8) -> Event {
+    Event::Enter {
+        call_type,
+        from: H160::repeat_byte(from),
+        to: H160::repeat_byte(to),
+        value: U256::zero(),
+        gas: 100_000,
+        input: vec![1, 2, 3],
+    }
+}
+
+fn exit(gas_used: u64, error: Option<&str>) -> Event {
+    Event::Exit {
+        gas_used,
+        output: vec![],
+        error: error.map(|error| error.as_bytes().to_vec()),
+    }
+}
+
+fn step(opcode: u8, depth: u32, storage: Option<(H256, H256)>) -> Event {
+    Event::Step(Step {
+        pc: 0,
+        opcode,
+        depth,
+        gas: 1_000,
+        gas_cost: 3,
+        stack: Some(vec![]),
+        memory: Some(vec![0; 64]),
+        storage,
+    })
+}
+
+fn call_listener(events: Vec<Event>) -> CallListener {
+    let mut listener = CallListener::default();
+    for event in events {
+        listener.event(event);
+    }
+    listener
+}
+
+#[test]
+fn call_listener_should_build_call_frames() {
+    let tx = H256::repeat_byte(9);
+    let listener = call_listener(vec![
+        Event::Transaction(tx),
+        enter(CallType::Call, 1, 2),
+        enter(CallType::StaticCall, 2, 3),
+        exit(500, None),
+        enter(CallType::DelegateCall, 2, 4),
+        Event::SelfDestruct {
+            address: H160::repeat_byte(2),
+            refund_address: H160::repeat_byte(1),
+            balance: 7.into(),
+        },
+        exit(700, Some("execution reverted")),
+        exit(21_000, None),
+    ]);
+
+    assert_eq!(listener.transactions.len(), 1);
+    let (hash, top) = &listener.transactions[0];
+    assert_eq!(*hash, tx);
+    let top = top.as_ref().unwrap();
+    assert_eq!(top.frame_type, FrameType::Call(CallType::Call));
+    assert_eq!(top.gas_used, 21_000.into());
+    assert_eq!(top.calls.len(), 2);
+    assert_eq!(top.calls[0].frame_type.name(), "STATICCALL");
+    assert_eq!(top.calls[0].to, H160::repeat_byte(3));
+    assert_eq!(top.calls[1].error.as_deref(), Some("execution reverted"));
+    assert_eq!(top.calls[1].calls[0].frame_type.name(), "SELFDESTRUCT");
+    assert_eq!(top.calls[1].calls[0].value, 7.into());
+}
+
+#[test]
+fn flatten_should_follow_the_trace_address() {
+    let listener = call_listener(vec![
+        Event::Transaction(H256::repeat_byte(9)),
+        enter(CallType::Call, 1, 2),
+        enter(CallType::Create2, 2, 3),
+        enter(CallType::Call, 3, 4),
+        exit(100, None),
+        exit(200, None),
+        enter(CallType::Call, 2, 5),
+        exit(300, Some("OutOfGas")),
+        exit(21_000, None),
+    ]);
+    let info = TransactionInfo {
+        block_hash: H256::repeat_byte(1),
+        block_number: 10,
+        transaction_hash: H256::repeat_byte(9),
+        transaction_position: 2,
+    };
+    let mut traces = Vec::new();
+    flatten(
+        listener.transactions[0].1.as_ref().unwrap(),
+        Vec::new(),
+        &info,
+        &mut traces,
+    );
+
+    let addresses = traces
+        .iter()
+        .map(|trace| trace.trace_address.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, vec![vec![], vec![0], vec![0, 0], vec![1]]);
+    assert_eq!(traces[0].subtraces, 2);
+    assert_eq!(traces[1].trace_type, "create");
+    assert!(matches!(traces[1].action, Action::Create { .. }));
+    assert!(traces[3].result.is_none());
+    assert_eq!(traces[3].error.as_deref(), Some("OutOfGas"));
+    assert!(traces.iter().all(|trace| trace.transaction_position == 2));
+}
+
+#[test]
+fn step_listener_should_collect_struct_logs() {
+    let (index, value) = (H256::repeat_byte(1), H256::repeat_byte(2));
+    let mut listener = StepListener::new(false, true, true);
+    for event in vec![
+        Event::Transaction(H256::repeat_byte(9)),
+        enter(CallType::Call, 1, 2),
+        step(0x60, 1, None),
+        step(0x55, 1, Some((index, value))),
+        enter(CallType::Call, 2, 3),
+        step(0x00, 2, None),
+        exit(0, None),
+        step(0xef, 1, None),
+        exit(21_000, Some("execution reverted")),
+    ] {
+        listener.event(event);
+    }
+
+    let trace = &listener.transactions[0];
+    assert_eq!(trace.gas, 21_000);
+    assert!(trace.failed);
+    let ops = trace
+        .struct_logs
+        .iter()
+        .map(|log| log.op.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ops,
+        vec!["PUSH1", "SSTORE", "STOP", "opcode 0xef not defined"]
+    );
+    assert_eq!(
+        trace.struct_logs[1].storage.as_ref().unwrap()[&index],
+        value
+    );
+    // the storage is accessed by the frame.
+    assert!(trace.struct_logs[2].storage.as_ref().unwrap().is_empty());
+    assert_eq!(trace.struct_logs[3].storage.as_ref().unwrap().len(), 1);
+    assert_eq!(trace.struct_logs[0].memory.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn opcode_names() {
+    assert_eq!(opcode_name(0x7f), "PUSH32");
+    assert_eq!(opcode_name(0x80), "DUP1");
+    assert_eq!(opcode_name(0x9f), "SWAP16");
+    assert_eq!(opcode_name(0xa4), "LOG4");
+    assert_eq!(opcode_name(0xfa), "STATICCALL");
+}
+
+#[test]
+fn trace_cache_should_evict_the_oldest_block() {
+    let mut cache = TraceCache::new(2);
+    let (a, b, c) = (
+        H256::repeat_byte(1),
+        H256::repeat_byte(2),
+        H256::repeat_byte(3),
+    );
+    cache.insert(a, Arc::new(vec![]));
+    cache.insert(b, Arc::new(vec![]));
+    cache.insert(c, Arc::new(vec![]));
+    assert!(cache.get(&a).is_none());
+    assert!(cache.get(&b).is_some());
+    assert!(cache.get(&c).is_some());
+
+    let mut disabled = TraceCache::new(0);
+    disabled.insert(a, Arc::new(vec![]));
+    assert!(disabled.get(&a).is_none());
+}
@@ -0,0 +1,404 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The `trace_filter` RPC in the format of OpenEthereum.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use fc_rpc::internal_err;
+use fc_rpc_core::types::{BlockNumber, Bytes};
+use fp_rpc::EthereumRuntimeRPCApi;
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sc_client_api::BlockBackend;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{H160, H256, U256};
+use sp_runtime::generic::BlockId;
+
+use sherpax_runtime::opaque::Block;
+use xp_evm_tracing::{CallType, DebugRuntimeApi};
+
+use super::{
+    listener::{CallFrame, CallListener, FrameType},
+    replay_block,
+};
+
+/// The filter of `trace_filter`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// The first block to trace, `latest` by default.
+    pub from_block: Option<BlockNumber>,
+    /// The last block to trace, `latest` by default.
+    pub to_block: Option<BlockNumber>,
+    /// The traces sent from these addresses, all by default.
+    pub from_address: Option<Vec<H160>>,
+    /// The traces sent to these addresses, all by default.
+    pub to_address: Option<Vec<H160>>,
+    /// The number of the matched traces to skip.
+    pub after: Option<u32>,
+    /// The maximum number of the returned traces.
+    pub count: Option<u32>,
+}
+
+impl TraceFilter {
+    fn matches(&self, trace: &Trace) -> bool {
+        let (from, to) = trace.addresses();
+        let contains = |addresses: &Option<Vec<H160>>, address| match addresses.as_deref() {
+            None | Some([]) => true,
+            Some(addresses) => addresses.contains(&address),
+        };
+        contains(&self.from_address, from) && contains(&self.to_address, to)
+    }
+}
+
+/// The action of a [`Trace`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Action {
+    #[serde(rename_all = "camelCase")]
+    Call {
+        call_type: &'static str,
+        from: H160,
+        to: H160,
+        gas: U256,
+        input: Bytes,
+        value: U256,
+    },
+    Create {
+        from: H160,
+        gas: U256,
+        init: Bytes,
+        value: U256,
+    },
+    #[serde(rename_all = "camelCase")]
+    Suicide {
+        address: H160,
+        refund_address: H160,
+        balance: U256,
+    },
+}
+
+/// The result of a succeeded [`Trace`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+    #[serde(rename_all = "camelCase")]
+    Call { gas_used: U256, output: Bytes },
+    #[serde(rename_all = "camelCase")]
+    Create {
+        address: H160,
+        code: Bytes,
+        gas_used: U256,
+    },
+}
+
+/// A flattened call frame.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    pub action: Action,
+    pub block_hash: H256,
+    pub block_number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TraceResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub subtraces: usize,
+    pub trace_address: Vec<usize>,
+    pub transaction_hash: H256,
+    pub transaction_position: u32,
+    #[serde(rename = "type")]
+    pub trace_type: &'static str,
+}
+
+impl Trace {
+    /// The sender and the receiver of the trace, the receiver of a create is the contract.
+    fn addresses(&self) -> (H160, H160) {
+        match (&self.action, &self.result) {
+            (Action::Call { from, to, .. }, _) => (*from, *to),
+            (Action::Create { from, .. }, Some(TraceResult::Create { address, .. })) => {
+                (*from, *address)
+            }
+            (Action::Create { from, .. }, _) => (*from, H160::zero()),
+            (
+                Action::Suicide {
+                    address,
+                    refund_address,
+                    ..
+                },
+                _,
+            ) => (*address, *refund_address),
+        }
+    }
+}
+
+/// The ethereum transaction of the flattened call frames.
+#[derive(Clone, Copy, Debug)]
+pub struct TransactionInfo {
+    pub block_hash: H256,
+    pub block_number: u64,
+    pub transaction_hash: H256,
+    pub transaction_position: u32,
+}
+
+/// Flatten the call `frame` and its sub calls in the depth-first order.
+pub fn flatten(
+    frame: &CallFrame,
+    trace_address: Vec<usize>,
+    info: &TransactionInfo,
+    traces: &mut Vec<Trace>,
+) {
+    let (action, result, trace_type) = match frame.frame_type {
+        FrameType::Call(CallType::Create) | FrameType::Call(CallType::Create2) => (
+            Action::Create {
+                from: frame.from,
+                gas: frame.gas,
+                init: frame.input.clone(),
+                value: frame.value,
+            },
+            TraceResult::Create {
+                address: frame.to,
+                code: frame.output.clone(),
+                gas_used: frame.gas_used,
+            },
+            "create",
+        ),
+        FrameType::Call(call_type) => (
+            Action::Call {
+                call_type: match call_type {
+                    CallType::CallCode => "callcode",
+                    CallType::DelegateCall => "delegatecall",
+                    CallType::StaticCall => "staticcall",
+                    _ => "call",
+                },
+                from: frame.from,
+                to: frame.to,
+                gas: frame.gas,
+                input: frame.input.clone(),
+                value: frame.value,
+            },
+            TraceResult::Call {
+                gas_used: frame.gas_used,
+                output: frame.output.clone(),
+            },
+            "call",
+        ),
+        FrameType::SelfDestruct => (
+            Action::Suicide {
+                address: frame.from,
+                refund_address: frame.to,
+                balance: frame.value,
+            },
+            TraceResult::Call {
+                gas_used: U256::zero(),
+                output: Bytes(Vec::new()),
+            },
+            "suicide",
+        ),
+    };
+    traces.push(Trace {
+        action,
+        block_hash: info.block_hash,
+        block_number: info.block_number,
+        result: frame.error.is_none().then(|| result),
+        error: frame.error.clone(),
+        subtraces: frame.calls.len(),
+        trace_address: trace_address.clone(),
+        transaction_hash: info.transaction_hash,
+        transaction_position: info.transaction_position,
+        trace_type,
+    });
+    for (index, call) in frame.calls.iter().enumerate() {
+        let mut trace_address = trace_address.clone();
+        trace_address.push(index);
+        flatten(call, trace_address, info, traces);
+    }
+}
+
+/// The cache of the traced blocks, the oldest cached block is evicted first.
+pub struct TraceCache {
+    size: usize,
+    blocks: HashMap<H256, Arc<Vec<Trace>>>,
+    order: VecDeque<H256>,
+}
+
+impl TraceCache {
+    /// Create a cache keeping the traces of at most `size` blocks.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Return the cached traces of the block `hash`.
+    pub fn get(&self, hash: &H256) -> Option<Arc<Vec<Trace>>> {
+        self.blocks.get(hash).cloned()
+    }
+
+    /// Cache the traces of the block `hash`.
+    pub fn insert(&mut self, hash: H256, traces: Arc<Vec<Trace>>) {
+        if self.size == 0 || self.blocks.contains_key(&hash) {
+            return;
+        }
+        while self.order.len() >= self.size {
+            if let Some(evicted) = self.order.pop_front() {
+                self.blocks.remove(&evicted);
+            }
+        }
+        self.order.push_back(hash);
+        self.blocks.insert(hash, traces);
+    }
+}
+
+/// The `trace_*` tracing RPCs.
+#[rpc(server)]
+pub trait EvmTraceApi {
+    /// Return the flattened call frames of the blocks matching `filter`.
+    #[rpc(name = "trace_filter")]
+    fn filter(&self, filter: TraceFilter) -> Result<Vec<Trace>>;
+}
+
+pub struct EvmTrace<C> {
+    client: Arc<C>,
+    cache: Arc<Mutex<TraceCache>>,
+    max_count: u32,
+    max_block_range: u32,
+}
+
+impl<C> EvmTrace<C> {
+    pub fn new(
+        client: Arc<C>,
+        cache: Arc<Mutex<TraceCache>>,
+        max_count: u32,
+        max_block_range: u32,
+    ) -> Self {
+        Self {
+            client,
+            cache,
+            max_count,
+            max_block_range,
+        }
+    }
+}
+
+impl<C> EvmTrace<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C: Send + Sync + 'static,
+    C::Api: DebugRuntimeApi<Block> + EthereumRuntimeRPCApi<Block>,
+{
+    fn block_number(&self, number: Option<BlockNumber>) -> Result<u32> {
+        let best = self.client.info().best_number;
+        match number {
+            Some(BlockNumber::Num(number)) => Ok(number.min(best as u64) as u32),
+            Some(BlockNumber::Earliest) => Ok(0),
+            None | Some(BlockNumber::Latest) | Some(BlockNumber::Pending) => Ok(best),
+            Some(_) => Err(internal_err(
+                "Block hash is not supported by `trace_filter`",
+            )),
+        }
+    }
+
+    /// Return the traces of the block `number`, which are cached after traced.
+    fn block_traces(&self, number: u32) -> Result<Arc<Vec<Trace>>> {
+        let hash = self
+            .client
+            .hash(number)
+            .map_err(|e| internal_err(format!("{:?}", e)))?
+            .ok_or_else(|| internal_err(format!("Block #{} not found", number)))?;
+        if let Some(traces) = self.cache.lock().expect("cache lock poisoned").get(&hash) {
+            return Ok(traces);
+        }
+
+        let block = self
+            .client
+            .runtime_api()
+            .current_block(&BlockId::Hash(hash))
+            .map_err(|e| internal_err(format!("Runtime api failed: {:?}", e)))?
+            .ok_or_else(|| internal_err(format!("Ethereum block #{} not found", number)))?;
+        let mut listener = CallListener::default();
+        if !block.transactions.is_empty() {
+            replay_block(&*self.client, hash, None, &mut listener)?;
+        }
+
+        let mut traces = Vec::new();
+        for (transaction_hash, frame) in listener.transactions {
+            let transaction_position = block
+                .transactions
+                .iter()
+                .position(|transaction| transaction.hash() == transaction_hash)
+                .unwrap_or_default() as u32;
+            let info = TransactionInfo {
+                block_hash: block.header.hash(),
+                block_number: number.into(),
+                transaction_hash,
+                transaction_position,
+            };
+            if let Some(frame) = frame {
+                flatten(&frame, Vec::new(), &info, &mut traces);
+            }
+        }
+
+        let traces = Arc::new(traces);
+        self.cache
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(hash, traces.clone());
+        Ok(traces)
+    }
+}
+
+impl<C> EvmTraceApi for EvmTrace<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block>,
+    C: Send + Sync + 'static,
+    C::Api: DebugRuntimeApi<Block> + EthereumRuntimeRPCApi<Block>,
+{
+    fn filter(&self, filter: TraceFilter) -> Result<Vec<Trace>> {
+        let from = self.block_number(filter.from_block.clone())?;
+        let to = self.block_number(filter.to_block.clone())?;
+        if from > to {
+            return Err(internal_err("`fromBlock` is greater than `toBlock`"));
+        }
+        // every block of the range is replayed unless cached
+        if to - from >= self.max_block_range {
+            return Err(internal_err(format!(
+                "The block range is greater than the maximum {}",
+                self.max_block_range
+            )));
+        }
+        let count = filter.count.unwrap_or(self.max_count);
+        if count > self.max_count {
+            return Err(internal_err(format!(
+                "`count` is greater than the maximum {}",
+                self.max_count
+            )));
+        }
+
+        let mut skipped = filter.after.unwrap_or_default();
+        let mut traces = Vec::new();
+        for number in from..=to {
+            for trace in self.block_traces(number)?.iter() {
+                if traces.len() == count as usize {
+                    return Ok(traces);
+                }
+                if !filter.matches(trace) {
+                    continue;
+                }
+                if skipped > 0 {
+                    skipped -= 1;
+                    continue;
+                }
+                traces.push(trace.clone());
+            }
+        }
+        Ok(traces)
+    }
+}
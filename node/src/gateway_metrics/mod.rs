@@ -0,0 +1,330 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The Prometheus metrics of the gateway health.
+//!
+//! The metrics are labeled by the chain and refreshed on the imported best blocks, at most once
+//! per [`REFRESH_INTERVAL`], through the gateway runtime APIs:
+//!
+//! - the best and confirmed heights of the relayed header chain;
+//! - the number and the total value of the unclaimed deposits;
+//! - the age of the open withdrawal proposal, since it is first seen by the node;
+//! - the number of the `Applying` and `Processing` withdrawals;
+//! - whether the trustee transition is in progress.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+use sc_client_api::BlockchainEvents;
+use sp_api::{ApiExt, ProvideRuntimeApi, RuntimeApiInfo};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+use substrate_prometheus_endpoint::{register, GaugeVec, Opts, PrometheusError, Registry, U64};
+
+use sherpax_runtime::{opaque::Block, AccountId, Balance, BlockNumber, BtcHash, Chain};
+use xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi;
+use xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi;
+use xpallet_gateway_dogecoin_rpc_runtime_api::XGatewayDogecoinApi;
+use xpallet_gateway_records_rpc_runtime_api::{WithdrawalState, XGatewayRecordsApi};
+
+/// The result type of the gateway metrics.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// The minimum interval between two refreshes, the withdrawals are scanned on each refresh.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The versions of the gateway runtime APIs providing the methods used by the metrics.
+const MIN_BITCOIN_API_VERSION: u32 = 2;
+const MIN_DOGECOIN_API_VERSION: u32 = 2;
+const MIN_COMMON_API_VERSION: u32 = 2;
+
+/// The gateway state of a chain at a block.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GatewayStatus {
+    pub best_height: u32,
+    pub confirmed_height: Option<u32>,
+    pub pending_deposit_count: u32,
+    pub pending_deposit_value: u64,
+    /// The txid of the open withdrawal proposal.
+    pub withdrawal_proposal: Option<BtcHash>,
+    pub applying_withdrawals: u32,
+    pub processing_withdrawals: u32,
+    pub trustee_transition: bool,
+}
+
+/// The gauges of the gateway health.
+pub struct GatewayMetrics {
+    best_height: GaugeVec<U64>,
+    confirmed_height: GaugeVec<U64>,
+    pending_deposits: GaugeVec<U64>,
+    pending_deposit_value: GaugeVec<U64>,
+    withdrawal_proposal_age: GaugeVec<U64>,
+    withdrawals: GaugeVec<U64>,
+    trustee_transition: GaugeVec<U64>,
+    /// The open withdrawal proposal of each chain and the block it is first seen.
+    proposals: BTreeMap<Chain, (BtcHash, BlockNumber)>,
+}
+
+fn gauge(
+    name: &str,
+    help: &str,
+    labels: &[&str],
+    registry: &Registry,
+) -> std::result::Result<GaugeVec<U64>, PrometheusError> {
+    register(GaugeVec::new(Opts::new(name, help), labels)?, registry)
+}
+
+impl GatewayMetrics {
+    pub fn register(registry: &Registry) -> std::result::Result<Self, PrometheusError> {
+        Ok(Self {
+            best_height: gauge(
+                "xgateway_best_header_height",
+                "The height of the best relayed header",
+                &["chain"],
+                registry,
+            )?,
+            confirmed_height: gauge(
+                "xgateway_confirmed_header_height",
+                "The height of the confirmed relayed header",
+                &["chain"],
+                registry,
+            )?,
+            pending_deposits: gauge(
+                "xgateway_pending_deposits",
+                "The number of the unclaimed deposits",
+                &["chain"],
+                registry,
+            )?,
+            pending_deposit_value: gauge(
+                "xgateway_pending_deposit_value",
+                "The total value of the unclaimed deposits in satoshi",
+                &["chain"],
+                registry,
+            )?,
+            withdrawal_proposal_age: gauge(
+                "xgateway_withdrawal_proposal_age",
+                "The number of blocks since the open withdrawal proposal is first seen",
+                &["chain"],
+                registry,
+            )?,
+            withdrawals: gauge(
+                "xgateway_withdrawals",
+                "The number of the withdrawals by state",
+                &["chain", "state"],
+                registry,
+            )?,
+            trustee_transition: gauge(
+                "xgateway_trustee_transition",
+                "Whether the trustee transition is in progress",
+                &["chain"],
+                registry,
+            )?,
+            proposals: BTreeMap::new(),
+        })
+    }
+
+    /// Refresh the gauges with the gateway state at the block.
+    pub fn update(&mut self, number: BlockNumber, statuses: &[(Chain, GatewayStatus)]) {
+        for (chain, status) in statuses {
+            let label = chain_label(*chain);
+            self.best_height
+                .with_label_values(&[label])
+                .set(status.best_height.into());
+            // The confirmed header is unknown until `ConfirmationNumber` headers are relayed.
+            self.confirmed_height
+                .with_label_values(&[label])
+                .set(status.confirmed_height.unwrap_or_default().into());
+            self.pending_deposits
+                .with_label_values(&[label])
+                .set(status.pending_deposit_count.into());
+            self.pending_deposit_value
+                .with_label_values(&[label])
+                .set(status.pending_deposit_value);
+            self.withdrawals
+                .with_label_values(&[label, "Applying"])
+                .set(status.applying_withdrawals.into());
+            self.withdrawals
+                .with_label_values(&[label, "Processing"])
+                .set(status.processing_withdrawals.into());
+            self.trustee_transition
+                .with_label_values(&[label])
+                .set(status.trustee_transition.into());
+
+            let age = match status.withdrawal_proposal {
+                Some(txid) => {
+                    let (_, seen) = self
+                        .proposals
+                        .entry(*chain)
+                        .and_modify(|proposal| {
+                            if proposal.0 != txid {
+                                *proposal = (txid, number);
+                            }
+                        })
+                        .or_insert((txid, number));
+                    number.saturating_sub(*seen)
+                }
+                None => {
+                    self.proposals.remove(chain);
+                    0
+                }
+            };
+            self.withdrawal_proposal_age
+                .with_label_values(&[label])
+                .set(age.into());
+        }
+    }
+}
+
+fn chain_label(chain: Chain) -> &'static str {
+    match chain {
+        Chain::Bitcoin => "Bitcoin",
+        Chain::Dogecoin => "Dogecoin",
+        _ => "Unknown",
+    }
+}
+
+/// Count the `Applying` and the `Processing` withdrawals.
+fn count_withdrawals(states: impl IntoIterator<Item = WithdrawalState>) -> (u32, u32) {
+    states
+        .into_iter()
+        .fold((0, 0), |(applying, processing), state| match state {
+            WithdrawalState::Applying => (applying + 1, processing),
+            WithdrawalState::Processing => (applying, processing + 1),
+            _ => (applying, processing),
+        })
+}
+
+/// Whether the metrics last refreshed at `last` are to be refreshed at `now`.
+fn should_refresh(last: Option<Instant>, now: Instant) -> bool {
+    last.map_or(true, |last| {
+        now.saturating_duration_since(last) >= REFRESH_INTERVAL
+    })
+}
+
+/// Ensure the runtime API `A` named `name` is at least of the version `min`.
+fn ensure_api_version<A, Api>(api: &Api, at: &BlockId<Block>, name: &str, min: u32) -> Result<()>
+where
+    A: RuntimeApiInfo + ?Sized,
+    Api: ApiExt<Block>,
+{
+    let version = api
+        .api_version::<A>(at)
+        .map_err(|e| format!("Runtime api error: {:?}", e))?;
+    match version {
+        Some(version) if version >= min => Ok(()),
+        _ => Err(format!(
+            "{} version {:?} is not supported, expect {}",
+            name, version, min
+        )),
+    }
+}
+
+/// The header chain, the unclaimed deposits and the withdrawal proposal of a gateway pallet,
+/// whose runtime APIs share the same method names.
+macro_rules! relay_status {
+    ($runtime_api:ident, $api:expr, $at:expr) => {{
+        let err = |e: sp_api::ApiError| format!("Runtime api error: {:?}", e);
+        let (best, confirmed) = $runtime_api::get_header_indexes($api, $at).map_err(err)?;
+        let (count, value) = $runtime_api::get_pending_deposit_stats($api, $at).map_err(err)?;
+        let proposal = $runtime_api::get_withdrawal_proposal($api, $at).map_err(err)?;
+        GatewayStatus {
+            best_height: best.height,
+            confirmed_height: confirmed.map(|index| index.height),
+            pending_deposit_count: count,
+            pending_deposit_value: value,
+            withdrawal_proposal: proposal.map(|proposal| proposal.tx.hash()),
+            ..Default::default()
+        }
+    }};
+}
+
+/// Read the gateway state of Bitcoin and Dogecoin at the block.
+pub fn gateway_status<C>(client: &C, at: &BlockId<Block>) -> Result<Vec<(Chain, GatewayStatus)>>
+where
+    C: ProvideRuntimeApi<Block>,
+    C::Api: XGatewayBitcoinApi<Block, AccountId>,
+    C::Api: XGatewayDogecoinApi<Block, AccountId>,
+    C::Api: XGatewayCommonApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: XGatewayRecordsApi<Block, AccountId, Balance, BlockNumber>,
+{
+    let api = client.runtime_api();
+    let err = |e: sp_api::ApiError| format!("Runtime api error: {:?}", e);
+    ensure_api_version::<dyn XGatewayBitcoinApi<Block, AccountId>, _>(
+        &*api,
+        at,
+        "XGatewayBitcoinApi",
+        MIN_BITCOIN_API_VERSION,
+    )?;
+    ensure_api_version::<dyn XGatewayDogecoinApi<Block, AccountId>, _>(
+        &*api,
+        at,
+        "XGatewayDogecoinApi",
+        MIN_DOGECOIN_API_VERSION,
+    )?;
+    ensure_api_version::<dyn XGatewayCommonApi<Block, AccountId, Balance, BlockNumber>, _>(
+        &*api,
+        at,
+        "XGatewayCommonApi",
+        MIN_COMMON_API_VERSION,
+    )?;
+
+    let statuses = vec![
+        (Chain::Bitcoin, relay_status!(XGatewayBitcoinApi, &*api, at)),
+        (
+            Chain::Dogecoin,
+            relay_status!(XGatewayDogecoinApi, &*api, at),
+        ),
+    ];
+    statuses
+        .into_iter()
+        .map(|(chain, mut status)| {
+            let withdrawals = api.withdrawal_list_by_chain(at, chain).map_err(err)?;
+            let (applying, processing) =
+                count_withdrawals(withdrawals.values().map(|withdrawal| withdrawal.state));
+            status.applying_withdrawals = applying;
+            status.processing_withdrawals = processing;
+            status.trustee_transition = api.trustee_transition_status(at, chain).map_err(err)?;
+            Ok((chain, status))
+        })
+        .collect()
+}
+
+/// Refresh the gateway metrics on the imported best blocks until the node exits.
+pub async fn run_gateway_metrics<C>(client: Arc<C>, registry: Registry)
+where
+    C: ProvideRuntimeApi<Block> + BlockchainEvents<Block>,
+    C: Send + Sync + 'static,
+    C::Api: XGatewayBitcoinApi<Block, AccountId>,
+    C::Api: XGatewayDogecoinApi<Block, AccountId>,
+    C::Api: XGatewayCommonApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: XGatewayRecordsApi<Block, AccountId, Balance, BlockNumber>,
+{
+    let mut metrics = match GatewayMetrics::register(&registry) {
+        Ok(metrics) => metrics,
+        Err(err) => {
+            log::warn!(target: "gateway-metrics", "Failed to register gateway metrics: {}", err);
+            return;
+        }
+    };
+
+    let mut last_refresh = None;
+    let mut imports = client.import_notification_stream();
+    while let Some(notification) = imports.next().await {
+        let now = Instant::now();
+        if !notification.is_new_best || !should_refresh(last_refresh, now) {
+            continue;
+        }
+        last_refresh = Some(now);
+        match gateway_status(&*client, &BlockId::hash(notification.hash)) {
+            Ok(statuses) => metrics.update(*notification.header.number(), &statuses),
+            Err(err) => {
+                log::debug!(target: "gateway-metrics", "Failed to read gateway status: {}", err)
+            }
+        }
+    }
+}
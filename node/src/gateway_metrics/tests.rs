@@ -0,0 +1,127 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+
+fn gauge_value(gauge: &GaugeVec<U64>, labels: &[&str]) -> u64 {
+    gauge.with_label_values(labels).get()
+}
+
+#[test]
+fn test_count_withdrawals() {
+    let states = vec![
+        WithdrawalState::Applying,
+        WithdrawalState::Processing,
+        WithdrawalState::Applying,
+        WithdrawalState::NormalFinish,
+        WithdrawalState::RootCancel,
+    ];
+    assert_eq!(count_withdrawals(states), (2, 1));
+    assert_eq!(count_withdrawals(vec![]), (0, 0));
+}
+
+#[test]
+fn test_should_refresh() {
+    let now = Instant::now();
+    assert!(should_refresh(None, now));
+    assert!(!should_refresh(Some(now), now));
+    assert!(!should_refresh(
+        Some(now),
+        now + REFRESH_INTERVAL - Duration::from_secs(1)
+    ));
+    assert!(should_refresh(Some(now), now + REFRESH_INTERVAL));
+}
+
+#[test]
+fn test_register_gateway_metrics() {
+    let registry = Registry::new();
+    assert!(GatewayMetrics::register(&registry).is_ok());
+    // The gauges can not be registered twice.
+    assert!(GatewayMetrics::register(&registry).is_err());
+}
+
+#[test]
+fn test_update_gateway_metrics() {
+    let registry = Registry::new();
+    let mut metrics = GatewayMetrics::register(&registry).unwrap();
+
+    let proposal = BtcHash::repeat_byte(1);
+    let btc_status = GatewayStatus {
+        best_height: 100,
+        confirmed_height: Some(94),
+        pending_deposit_count: 2,
+        pending_deposit_value: 300_000,
+        withdrawal_proposal: Some(proposal),
+        applying_withdrawals: 3,
+        processing_withdrawals: 1,
+        trustee_transition: true,
+    };
+    let doge_status = GatewayStatus {
+        best_height: 10,
+        ..Default::default()
+    };
+    metrics.update(
+        50,
+        &[
+            (Chain::Bitcoin, btc_status.clone()),
+            (Chain::Dogecoin, doge_status.clone()),
+        ],
+    );
+
+    assert_eq!(gauge_value(&metrics.best_height, &["Bitcoin"]), 100);
+    assert_eq!(gauge_value(&metrics.confirmed_height, &["Bitcoin"]), 94);
+    assert_eq!(gauge_value(&metrics.pending_deposits, &["Bitcoin"]), 2);
+    assert_eq!(
+        gauge_value(&metrics.pending_deposit_value, &["Bitcoin"]),
+        300_000
+    );
+    assert_eq!(
+        gauge_value(&metrics.withdrawals, &["Bitcoin", "Applying"]),
+        3
+    );
+    assert_eq!(
+        gauge_value(&metrics.withdrawals, &["Bitcoin", "Processing"]),
+        1
+    );
+    assert_eq!(gauge_value(&metrics.trustee_transition, &["Bitcoin"]), 1);
+    assert_eq!(
+        gauge_value(&metrics.withdrawal_proposal_age, &["Bitcoin"]),
+        0
+    );
+    assert_eq!(gauge_value(&metrics.best_height, &["Dogecoin"]), 10);
+    assert_eq!(gauge_value(&metrics.confirmed_height, &["Dogecoin"]), 0);
+    assert_eq!(gauge_value(&metrics.trustee_transition, &["Dogecoin"]), 0);
+
+    // The age of the proposal grows until it is replaced or finished.
+    metrics.update(60, &[(Chain::Bitcoin, btc_status.clone())]);
+    assert_eq!(
+        gauge_value(&metrics.withdrawal_proposal_age, &["Bitcoin"]),
+        10
+    );
+
+    let new_proposal = GatewayStatus {
+        withdrawal_proposal: Some(BtcHash::repeat_byte(2)),
+        ..btc_status.clone()
+    };
+    metrics.update(70, &[(Chain::Bitcoin, new_proposal)]);
+    assert_eq!(
+        gauge_value(&metrics.withdrawal_proposal_age, &["Bitcoin"]),
+        0
+    );
+    metrics.update(75, &[(Chain::Bitcoin, btc_status.clone())]);
+    metrics.update(80, &[(Chain::Bitcoin, btc_status)]);
+    assert_eq!(
+        gauge_value(&metrics.withdrawal_proposal_age, &["Bitcoin"]),
+        5
+    );
+
+    let finished = GatewayStatus {
+        withdrawal_proposal: None,
+        ..doge_status
+    };
+    metrics.update(90, &[(Chain::Bitcoin, finished)]);
+    assert_eq!(
+        gauge_value(&metrics.withdrawal_proposal_age, &["Bitcoin"]),
+        0
+    );
+    assert!(metrics.proposals.is_empty());
+}
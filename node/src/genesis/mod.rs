@@ -0,0 +1,512 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Build the chain spec from a genesis config file instead of the compiled-in snapshots.
+//!
+//! The config is a TOML or JSON file, see `node/res/genesis/mainnet.toml`. The files it refers
+//! to, i.e. the balances, vesting and genesis headers, are relative to the config file.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::{TryFrom, TryInto},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sc_service::{
+    config::{MultiaddrWithPeerId, TelemetryEndpoints},
+    ChainType,
+};
+use serde::{de, Deserialize, Deserializer};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::crypto::UncheckedInto;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+
+use sherpax_runtime::{AccountId, Balance, BlockNumber};
+
+use crate::bitcoin::{trustees, BtcGenesisParams, BtcTrusteeParams, Chain, TrusteeInfoConfig};
+use crate::chain_spec::{
+    sherpax_genesis, sherpax_properties, AssetId, AssetParams, ChainSpec, GenesisParams,
+    SHERPAX_CHAIN_ID,
+};
+use crate::cli::BuildGenesisCmd;
+
+/// The result type of building the genesis.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// A balance given as a decimal string, or as an integer if it fits in `u64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Amount(pub Balance);
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawAmount {
+            Number(u64),
+            String(String),
+        }
+
+        match RawAmount::deserialize(deserializer)? {
+            RawAmount::Number(n) => Ok(Amount(n.into())),
+            RawAmount::String(s) => s.parse().map(Amount).map_err(de::Error::custom),
+        }
+    }
+}
+
+/// The genesis config file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisConfigFile {
+    pub name: String,
+    pub id: String,
+    #[serde(default)]
+    pub chain_type: ChainType,
+    pub protocol_id: Option<String>,
+    #[serde(default)]
+    pub bootnodes: Vec<String>,
+    #[serde(default)]
+    pub telemetry: Vec<TelemetryConfig>,
+    /// The sudo account, the owner of the assets by default.
+    pub root: String,
+    pub authorities: Vec<AuthorityConfig>,
+    #[serde(default)]
+    pub technical_committee: Vec<String>,
+    #[serde(default = "default_ethereum_chain_id")]
+    pub ethereum_chain_id: u64,
+    #[serde(default)]
+    pub balances: BalancesConfig,
+    #[serde(default)]
+    pub vesting: VestingConfig,
+    #[serde(default)]
+    pub trustees: Vec<TrusteesConfig>,
+    pub bitcoin: GenesisHeaderConfig,
+    pub dogecoin: GenesisHeaderConfig,
+    #[serde(default)]
+    pub assets: Vec<AssetConfig>,
+    /// The directory of the config file, which the files are relative to.
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+}
+
+fn default_ethereum_chain_id() -> u64 {
+    SHERPAX_CHAIN_ID
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    pub url: String,
+    #[serde(default)]
+    pub verbosity: u8,
+}
+
+/// The account and the hex encoded session keys of an authority.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthorityConfig {
+    pub account: String,
+    pub aura: String,
+    pub grandpa: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalancesConfig {
+    /// The `BalancesConfig` json files.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    #[serde(default)]
+    pub accounts: Vec<BalanceConfig>,
+    /// The expected number of the endowed accounts, after merging the duplicates.
+    pub account_count: Option<usize>,
+    /// The expected total issuance.
+    pub total_issuance: Option<Amount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceConfig {
+    pub account: String,
+    pub amount: Amount,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VestingConfig {
+    /// The `VestingConfig` json files.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    /// The expected number of the vesting accounts.
+    pub account_count: Option<usize>,
+    /// The expected sum of the liquid balances.
+    pub total_liquid: Option<Amount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrusteesConfig {
+    pub chain: Chain,
+    pub min_trustee_count: u32,
+    pub max_trustee_count: u32,
+    #[serde(default)]
+    pub members: Vec<TrusteeConfig>,
+}
+
+/// The genesis trustee with the hex encoded public keys.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrusteeConfig {
+    pub account: String,
+    #[serde(default)]
+    pub about: String,
+    pub hot: String,
+    pub cold: String,
+}
+
+/// The genesis header params, either inline or a json file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum GenesisHeaderConfig {
+    File(PathBuf),
+    Inline(BtcGenesisParams),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetConfig {
+    pub id: AssetId,
+    /// The chain of the gateway asset.
+    pub chain: Option<Chain>,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(default = "default_sufficient")]
+    pub sufficient: bool,
+    pub min_balance: Amount,
+    /// The owner of the asset, the root account by default.
+    pub owner: Option<String>,
+}
+
+fn default_sufficient() -> bool {
+    true
+}
+
+impl BuildGenesisCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let config = GenesisConfigFile::load(&self.config)?;
+        let json = config.chain_spec()?.as_json(self.raw)?;
+        match &self.output {
+            Some(output) => fs::write(output, json)
+                .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?,
+            None => println!("{}", json),
+        }
+        Ok(())
+    }
+}
+
+impl GenesisConfigFile {
+    /// Load the config file, parsed as TOML or JSON by the extension.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|e| e.to_string()),
+            Some("json") => serde_json::from_str(&content).map_err(|e| e.to_string()),
+            _ => Err("The genesis config must be a .toml or .json file".into()),
+        }
+        .map_err(|e| format!("Invalid genesis config {}: {}", path.display(), e))?;
+        config.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Build the chain spec from the validated genesis params.
+    pub fn chain_spec(&self) -> Result<ChainSpec> {
+        let params = self.genesis_params()?;
+        let bootnodes = self
+            .bootnodes
+            .iter()
+            .map(|bootnode| {
+                MultiaddrWithPeerId::try_from(bootnode.clone())
+                    .map_err(|e| format!("Invalid bootnode {}: {}", bootnode, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let telemetry = if self.telemetry.is_empty() {
+            None
+        } else {
+            let endpoints = self
+                .telemetry
+                .iter()
+                .map(|t| (t.url.clone(), t.verbosity))
+                .collect();
+            Some(
+                TelemetryEndpoints::new(endpoints)
+                    .map_err(|e| format!("Invalid telemetry url: {}", e))?,
+            )
+        };
+
+        Ok(ChainSpec::from_genesis(
+            &self.name,
+            &self.id,
+            self.chain_type.clone(),
+            move || sherpax_genesis(params.clone()),
+            bootnodes,
+            telemetry,
+            self.protocol_id.as_deref(),
+            None,
+            Some(sherpax_properties()),
+            Default::default(),
+        ))
+    }
+
+    /// Parse and validate the genesis params.
+    pub fn genesis_params(&self) -> Result<GenesisParams> {
+        let root_key = parse_account(&self.root)?;
+        let balances = self.balances()?;
+        let vesting = self.vesting(&balances)?;
+        Ok(GenesisParams {
+            initial_authorities: self.authorities()?,
+            technical_committee: self
+                .technical_committee
+                .iter()
+                .map(|who| parse_account(who))
+                .collect::<Result<_>>()?,
+            balances,
+            vesting,
+            assets: self.assets(&root_key)?,
+            bitcoin: self.genesis_header(&self.bitcoin)?,
+            dogecoin: self.genesis_header(&self.dogecoin)?,
+            trustees: self.trustees()?,
+            ethereum_chain_id: self.ethereum_chain_id,
+            root_key,
+        })
+    }
+
+    fn read_json<T: serde::de::DeserializeOwned>(&self, file: &Path) -> Result<T> {
+        let path = self.base_dir.join(file);
+        let bytes =
+            fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_slice(&bytes).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    fn authorities(&self) -> Result<Vec<(AccountId, AuraId, GrandpaId)>> {
+        if self.authorities.is_empty() {
+            return Err("No genesis authority".into());
+        }
+        let mut keys = BTreeSet::new();
+        let mut authorities = vec![];
+        for authority in &self.authorities {
+            let aura = parse_key(&authority.aura)?;
+            let grandpa = parse_key(&authority.grandpa)?;
+            if !keys.insert(aura) || !keys.insert(grandpa) {
+                return Err(format!("Duplicate keys of authority {}", authority.account));
+            }
+            authorities.push((
+                parse_account(&authority.account)?,
+                aura.unchecked_into(),
+                grandpa.unchecked_into(),
+            ));
+        }
+        check_duplicates("authority", authorities.iter().map(|a| &a.0))?;
+        Ok(authorities)
+    }
+
+    /// Merge the balances of the same account and check the expected count and total.
+    fn balances(&self) -> Result<Vec<(AccountId, Balance)>> {
+        let mut all = vec![];
+        for file in &self.balances.files {
+            let config: sherpax_runtime::BalancesConfig = self.read_json(file)?;
+            all.extend(config.balances);
+        }
+        for balance in &self.balances.accounts {
+            all.push((parse_account(&balance.account)?, balance.amount.0));
+        }
+
+        let mut total_issuance: Balance = 0;
+        let mut balances = BTreeMap::<AccountId, Balance>::new();
+        for (who, amount) in all {
+            let balance = balances.entry(who).or_default();
+            *balance = balance
+                .checked_add(amount)
+                .ok_or("Balance overflows when building genesis")?;
+            total_issuance = total_issuance
+                .checked_add(amount)
+                .ok_or("Total issuance overflows when building genesis")?;
+        }
+
+        if let Some(count) = self.balances.account_count {
+            if balances.len() != count {
+                return Err(format!(
+                    "Total accounts {} mismatches the expected {}",
+                    balances.len(),
+                    count
+                ));
+            }
+        }
+        if let Some(Amount(expected)) = self.balances.total_issuance {
+            if total_issuance != expected {
+                return Err(format!(
+                    "Total issuance {} mismatches the expected {}",
+                    total_issuance, expected
+                ));
+            }
+        }
+        Ok(balances.into_iter().collect())
+    }
+
+    /// Each vesting account must be endowed with more than its liquid balance.
+    fn vesting(
+        &self,
+        balances: &[(AccountId, Balance)],
+    ) -> Result<Vec<(AccountId, BlockNumber, BlockNumber, Balance)>> {
+        let mut vesting = vec![];
+        for file in &self.vesting.files {
+            let config: sherpax_runtime::VestingConfig = self.read_json(file)?;
+            vesting.extend(config.vesting);
+        }
+        check_duplicates("vesting account", vesting.iter().map(|v| &v.0))?;
+
+        let balances = balances.iter().cloned().collect::<BTreeMap<_, _>>();
+        let mut total_liquid: Balance = 0;
+        for (who, _, _, liquid) in &vesting {
+            match balances.get(who) {
+                Some(balance) if liquid < balance => {}
+                _ => {
+                    return Err(format!(
+                        "Vesting account {} is not endowed with more than {}",
+                        who, liquid
+                    ))
+                }
+            }
+            total_liquid = total_liquid
+                .checked_add(*liquid)
+                .ok_or("Total liquid overflows when building genesis")?;
+        }
+
+        if let Some(count) = self.vesting.account_count {
+            if vesting.len() != count {
+                return Err(format!(
+                    "Total vesting accounts {} mismatches the expected {}",
+                    vesting.len(),
+                    count
+                ));
+            }
+        }
+        if let Some(Amount(expected)) = self.vesting.total_liquid {
+            if total_liquid != expected {
+                return Err(format!(
+                    "Total vesting liquid {} mismatches the expected {}",
+                    total_liquid, expected
+                ));
+            }
+        }
+        Ok(vesting)
+    }
+
+    fn trustees(&self) -> Result<Vec<(Chain, TrusteeInfoConfig, Vec<BtcTrusteeParams>)>> {
+        check_duplicates("trustee chain", self.trustees.iter().map(|t| &t.chain))?;
+        let mut result = vec![];
+        for config in &self.trustees {
+            let count = config.members.len() as u32;
+            if config.min_trustee_count > config.max_trustee_count
+                || count > config.max_trustee_count
+                || (count != 0 && count < config.min_trustee_count)
+            {
+                return Err(format!(
+                    "Invalid {:?} trustee count {}, expected [{}, {}]",
+                    config.chain, count, config.min_trustee_count, config.max_trustee_count
+                ));
+            }
+            let mut members = vec![];
+            for member in &config.members {
+                members.push((
+                    parse_account(&member.account)?,
+                    member.about.as_bytes().to_vec(),
+                    parse_trustee_key(&member.hot)?,
+                    parse_trustee_key(&member.cold)?,
+                ));
+            }
+            check_duplicates("trustee", members.iter().map(|m| &m.0))?;
+            check_duplicates("trustee key", members.iter().flat_map(|m| vec![&m.2, &m.3]))?;
+            let info = TrusteeInfoConfig {
+                min_trustee_count: config.min_trustee_count,
+                max_trustee_count: config.max_trustee_count,
+            };
+            result.push((config.chain, info, members));
+        }
+        Ok(result)
+    }
+
+    fn genesis_header(&self, config: &GenesisHeaderConfig) -> Result<BtcGenesisParams> {
+        let params = match config {
+            GenesisHeaderConfig::File(file) => self.read_json(file)?,
+            GenesisHeaderConfig::Inline(params) => params.clone(),
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    fn assets(&self, root_key: &AccountId) -> Result<Vec<AssetParams>> {
+        check_duplicates("asset", self.assets.iter().map(|a| &a.id))?;
+        check_duplicates(
+            "asset chain",
+            self.assets.iter().filter_map(|a| a.chain.as_ref()),
+        )?;
+        self.assets
+            .iter()
+            .map(|asset| {
+                Ok(AssetParams {
+                    id: asset.id,
+                    owner: match &asset.owner {
+                        Some(owner) => parse_account(owner)?,
+                        None => root_key.clone(),
+                    },
+                    chain: asset.chain,
+                    name: asset.name.as_bytes().to_vec(),
+                    symbol: asset.symbol.as_bytes().to_vec(),
+                    decimals: asset.decimals,
+                    sufficient: asset.sufficient,
+                    min_balance: asset.min_balance.0,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parse the ss58 or the 0x-prefixed hex account.
+fn parse_account(account: &str) -> Result<AccountId> {
+    account
+        .parse()
+        .map_err(|e| format!("Invalid account {}: {}", account, e))
+}
+
+fn parse_key(key: &str) -> Result<[u8; 32]> {
+    hex::decode(key.trim_start_matches("0x"))
+        .map_err(|e| e.to_string())
+        .and_then(|key| key.try_into().map_err(|_| "expected 32 bytes".into()))
+        .map_err(|e: String| format!("Invalid session key {}: {}", key, e))
+}
+
+/// The trustee key accepted by `setup_trustee`.
+fn parse_trustee_key(key: &str) -> Result<Vec<u8>> {
+    let key = hex::decode(key.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid trustee key {}: {}", key, e))?;
+    trustees::bitcoin::BtcTrusteeType::try_from(key.clone())
+        .map(Into::into)
+        .map_err(|_| format!("Invalid trustee key 0x{}", hex::encode(&key)))
+}
+
+fn check_duplicates<'a, T: Ord + std::fmt::Debug + 'a>(
+    what: &str,
+    items: impl IntoIterator<Item = &'a T>,
+) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    for item in items {
+        if !seen.insert(item) {
+            return Err(format!("Duplicate {} {:?}", what, item));
+        }
+    }
+    Ok(())
+}
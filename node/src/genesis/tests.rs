@@ -0,0 +1,281 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+
+const ALICE: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+const BOB: &str = "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+
+fn genesis_config(extra: &str) -> Result<GenesisConfigFile> {
+    let config = format!(
+        r#"
+name = "Test"
+id = "test"
+root = "{alice}"
+bitcoin = "../genesis_config/gateway/btc_genesis_params_testnet.json"
+dogecoin = "../genesis_config/gateway/dogecoin_genesis_params_testnet.json"
+
+[[authorities]]
+account = "{alice}"
+aura = "{alice}"
+grandpa = "0x88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"
+
+{extra}
+"#,
+        alice = ALICE,
+        extra = extra,
+    );
+    let mut config: GenesisConfigFile = toml::from_str(&config).map_err(|e| e.to_string())?;
+    config.base_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("res/genesis");
+    Ok(config)
+}
+
+#[test]
+fn test_parse_genesis_config() {
+    let config = genesis_config(&format!(
+        r#"
+[balances]
+account_count = 2
+total_issuance = "3000000000000000000000"
+
+[[balances.accounts]]
+account = "{alice}"
+amount = "1000000000000000000000"
+
+[[balances.accounts]]
+account = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
+amount = 1000000000000000000
+
+[[balances.accounts]]
+account = "{bob}"
+amount = "1999000000000000000000"
+
+[[trustees]]
+chain = "Bitcoin"
+min_trustee_count = 1
+max_trustee_count = 15
+
+[[trustees.members]]
+account = "{alice}"
+about = "Alice"
+hot = "0x0483f579dd2380bd31355d066086e1b4d46b518987c1f8a64d4c0101560280eae2b16f3068e94333e11ee63770936eca9692a25f76012511d38ac30ece20f07dca"
+cold = "0x0400849497d4f88ebc3e1bc2583677c5abdbd3b63640b3c5c50cd4628a33a2a2cab6b69094b5a213da80f9ef730fab39de770ca124f2d9a9cb161856be54b9adc5"
+
+[[assets]]
+id = 1
+chain = "Bitcoin"
+name = "SBTC"
+symbol = "SBTC"
+decimals = 8
+min_balance = 1
+"#,
+        alice = ALICE,
+        bob = BOB,
+    ))
+    .unwrap();
+
+    let params = config.genesis_params().unwrap();
+    let alice: AccountId = ALICE.parse().unwrap();
+    assert_eq!(params.root_key, alice);
+    assert_eq!(params.ethereum_chain_id, SHERPAX_CHAIN_ID);
+    // Bob's ss58 and hex accounts are merged
+    assert_eq!(
+        params.balances,
+        vec![
+            (BOB.parse().unwrap(), 2_000_000_000_000_000_000_000),
+            (alice.clone(), 1_000_000_000_000_000_000_000),
+        ]
+    );
+    assert_eq!(params.trustees.len(), 1);
+    assert_eq!(params.trustees[0].2[0].0, alice);
+    assert_eq!(params.assets[0].owner, alice);
+    assert_eq!(params.assets[0].chain, Some(Chain::Bitcoin));
+    assert_eq!(params.bitcoin.height, 72586);
+}
+
+#[test]
+fn test_genesis_totals_mismatch() {
+    let balances = format!(
+        r#"
+[[balances.accounts]]
+account = "{}"
+amount = 100
+"#,
+        ALICE
+    );
+
+    let mut config = genesis_config(&balances).unwrap();
+    config.balances.account_count = Some(2);
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Total accounts"));
+
+    config.balances.account_count = Some(1);
+    config.balances.total_issuance = Some(Amount(101));
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Total issuance"));
+
+    config.balances.total_issuance = Some(Amount(100));
+    assert!(config.genesis_params().is_ok());
+}
+
+#[test]
+fn test_genesis_duplicates() {
+    let asset = r#"
+[[assets]]
+id = 1
+name = "SBTC"
+symbol = "SBTC"
+decimals = 8
+min_balance = 1
+"#;
+    let config = genesis_config(&format!("{}{}", asset, asset)).unwrap();
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Duplicate asset"));
+
+    let trustees = format!(
+        r#"
+[[trustees]]
+chain = "Bitcoin"
+min_trustee_count = 1
+max_trustee_count = 15
+
+[[trustees.members]]
+account = "{alice}"
+hot = "0x0483f579dd2380bd31355d066086e1b4d46b518987c1f8a64d4c0101560280eae2b16f3068e94333e11ee63770936eca9692a25f76012511d38ac30ece20f07dca"
+cold = "0x0400849497d4f88ebc3e1bc2583677c5abdbd3b63640b3c5c50cd4628a33a2a2cab6b69094b5a213da80f9ef730fab39de770ca124f2d9a9cb161856be54b9adc5"
+
+[[trustees.members]]
+account = "{bob}"
+hot = "0x0483f579dd2380bd31355d066086e1b4d46b518987c1f8a64d4c0101560280eae2b16f3068e94333e11ee63770936eca9692a25f76012511d38ac30ece20f07dca"
+cold = "0x042122032ae9656f9a133405ffe02101469a8d62002270a33ceccf0e40dda54d08c989b55f1b6b46a8dee284cf6737de0a377e410bcfd361a015528ae80a349529"
+"#,
+        alice = ALICE,
+        bob = BOB,
+    );
+    let config = genesis_config(&trustees).unwrap();
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Duplicate trustee key"));
+
+    let mut config = genesis_config(&trustees.replace("20f07dca\"", "20f07d\"")).unwrap();
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Invalid trustee key"));
+
+    config.trustees.clear();
+    config.authorities.push(AuthorityConfig {
+        account: ALICE.into(),
+        aura: BOB.into(),
+        grandpa: ALICE.into(),
+    });
+    assert!(config
+        .genesis_params()
+        .unwrap_err()
+        .contains("Duplicate keys of authority"));
+}
+
+#[test]
+fn test_load_mainnet_genesis_config() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("res/genesis/mainnet.toml");
+    let config = GenesisConfigFile::load(&path).unwrap();
+    assert_eq!(config.id, "sherpax_singleton");
+    assert_eq!(config.chain_type, ChainType::Live);
+    assert_eq!(config.authorities.len(), 2);
+    assert_eq!(
+        config.balances.total_issuance,
+        Some(Amount(21_000_000_000_000_000_000_000_000))
+    );
+    assert_eq!(config.assets.len(), 10);
+
+    let root: AccountId = config.root.parse().unwrap();
+    let assets = config.assets(&root).unwrap();
+    assert_eq!(assets[9].chain, Some(Chain::Dogecoin));
+    assert!(config.genesis_header(&config.bitcoin).is_ok());
+    assert!(config.genesis_header(&config.dogecoin).is_ok());
+    assert!(config.trustees().is_ok());
+
+    // the genesis storage is built from the config
+    let storage = sp_runtime::BuildStorage::build_storage(&config.chain_spec().unwrap()).unwrap();
+    assert!(storage
+        .top
+        .contains_key(sp_core::storage::well_known_keys::CODE));
+    assert_ne!(genesis_root(storage), sp_core::H256::zero());
+}
+
+/// Return the storage root of the genesis `storage` without the runtime code.
+fn genesis_root(mut storage: sp_core::storage::Storage) -> sp_core::H256 {
+    use sp_runtime::traits::{BlakeTwo256, Hash};
+
+    assert!(storage.children_default.is_empty());
+    storage.top.remove(sp_core::storage::well_known_keys::CODE);
+    BlakeTwo256::trie_root(
+        storage.top.into_iter().collect(),
+        sp_runtime::StateVersion::V0,
+    )
+}
+
+#[test]
+fn test_mainnet_genesis_root() {
+    use sp_runtime::BuildStorage;
+
+    let res = Path::new(env!("CARGO_MANIFEST_DIR")).join("res/genesis");
+    let config = GenesisConfigFile::load(&res.join("mainnet.toml")).unwrap();
+    let root = format!(
+        "{:?}",
+        genesis_root(config.chain_spec().unwrap().build_storage().unwrap())
+    );
+
+    // the root checked against the mainnet chain spec by `test_mainnet_genesis_storage`
+    let path = res.join("mainnet.root");
+    if std::env::var_os("SHERPAX_PIN_GENESIS_ROOT").is_some() {
+        std::fs::write(&path, format!("{}\n", root)).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "{} is missing, pin it with `SHERPAX_PIN_GENESIS_ROOT=1`",
+            path.display()
+        )
+    });
+    assert_eq!(
+        root,
+        expected.trim(),
+        "The genesis storage built from mainnet.toml changed"
+    );
+}
+
+#[test]
+#[ignore = "requires the mainnet chain spec `node/res/sherpax-raw.json`, see the README"]
+fn test_mainnet_genesis_storage() {
+    use sp_runtime::BuildStorage;
+
+    let res = Path::new(env!("CARGO_MANIFEST_DIR")).join("res");
+    let config = GenesisConfigFile::load(&res.join("genesis/mainnet.toml")).unwrap();
+    let built = config.chain_spec().unwrap().build_storage().unwrap();
+    let mainnet = ChainSpec::from_json_file(res.join("sherpax-raw.json"))
+        .unwrap()
+        .build_storage()
+        .unwrap();
+
+    // the pallet prefixes of the different keys, for the diagnosis
+    let mut prefixes = BTreeSet::new();
+    for key in built.top.keys().chain(mainnet.top.keys()) {
+        if key.as_slice() != sp_core::storage::well_known_keys::CODE
+            && built.top.get(key) != mainnet.top.get(key)
+        {
+            prefixes.insert(hex::encode(&key[..key.len().min(16)]));
+        }
+    }
+    assert!(
+        prefixes.is_empty(),
+        "The genesis storage differs under {:?}",
+        prefixes
+    );
+    assert_eq!(genesis_root(built), genesis_root(mainnet));
+}
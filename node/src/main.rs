@@ -7,7 +7,12 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod evm_tracing;
+mod gateway_metrics;
+mod genesis;
+mod relayer;
 mod rpc;
+mod trustee;
 
 fn main() -> sc_cli::Result<()> {
     command::run()
@@ -0,0 +1,142 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use light_bitcoin::{
+    chain::Block as BtcBlock,
+    serialization::{deserialize, Deserializable, Reader},
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use sherpax_runtime::{h256_rev, hash_rev, BtcHash};
+use xpallet_gateway_bitcoin::{BtcHeader, PartialMerkleTree, Transaction};
+
+use super::Result;
+
+/// The bitcoin data source of the relayer.
+///
+/// The hashes are in the internal byte order, as they are stored in `XGatewayBitcoin`.
+pub trait BitcoinRpc {
+    /// Return the height of the best block.
+    fn block_count(&self) -> Result<u32>;
+
+    /// Return the hash of the best chain block at `height`.
+    fn block_hash(&self, height: u32) -> Result<BtcHash>;
+
+    /// Return the header of the block.
+    fn block_header(&self, hash: &BtcHash) -> Result<BtcHeader>;
+
+    /// Return the transactions of the block.
+    fn block_transactions(&self, hash: &BtcHash) -> Result<Vec<Transaction>>;
+
+    /// Return the merkle proof of the transaction in the block.
+    fn tx_out_proof(&self, txid: &BtcHash, block_hash: &BtcHash) -> Result<PartialMerkleTree>;
+
+    /// Return the transaction if bitcoind can find it, which requires `-txindex`
+    /// for the transactions out of the mempool.
+    fn raw_transaction(&self, txid: &BtcHash) -> Result<Option<Transaction>>;
+}
+
+/// The JSON-RPC client of a bitcoind.
+pub struct Bitcoind {
+    url: String,
+    auth: Option<String>,
+}
+
+impl Bitcoind {
+    pub fn new(url: String, user: Option<String>, password: Option<String>) -> Self {
+        let auth = user.map(|user| {
+            let credentials = format!("{}:{}", user, password.unwrap_or_default());
+            format!("Basic {}", base64::encode(credentials))
+        });
+        Self { url, auth }
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let mut request = ureq::post(&self.url);
+        if let Some(auth) = &self.auth {
+            request = request.set("Authorization", auth);
+        }
+        let body = json!({
+            "jsonrpc": "1.0",
+            "id": "btc-relayer",
+            "method": method,
+            "params": params,
+        });
+        // bitcoind responds the JSON-RPC errors with HTTP error status codes
+        let response: Value = match request.send_json(body) {
+            Ok(response) => response.into_json(),
+            Err(ureq::Error::Status(_, response)) => response.into_json(),
+            Err(err) => return Err(format!("Failed to request bitcoind `{}`: {}", method, err)),
+        }
+        .map_err(|e| format!("Invalid response of bitcoind `{}`: {}", method, e))?;
+
+        match response.get("error") {
+            Some(error) if !error.is_null() => {
+                Err(format!("Bitcoind `{}` error: {}", method, error))
+            }
+            _ => Ok(response.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+
+    fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let result = self.request(method, params)?;
+        serde_json::from_value(result)
+            .map_err(|e| format!("Invalid result of bitcoind `{}`: {}", method, e))
+    }
+}
+
+impl BitcoinRpc for Bitcoind {
+    fn block_count(&self) -> Result<u32> {
+        self.call("getblockcount", json!([]))
+    }
+
+    fn block_hash(&self, height: u32) -> Result<BtcHash> {
+        let hash: String = self.call("getblockhash", json!([height]))?;
+        Ok(h256_rev(&hash))
+    }
+
+    fn block_header(&self, hash: &BtcHash) -> Result<BtcHeader> {
+        let header: String = self.call("getblockheader", json!([display_hash(hash), false]))?;
+        decode_hex(&header)
+    }
+
+    fn block_transactions(&self, hash: &BtcHash) -> Result<Vec<Transaction>> {
+        let block: String = self.call("getblock", json!([display_hash(hash), 0]))?;
+        decode_hex::<BtcBlock>(&block).map(|block| block.transactions)
+    }
+
+    fn tx_out_proof(&self, txid: &BtcHash, block_hash: &BtcHash) -> Result<PartialMerkleTree> {
+        let proof: String = self.call(
+            "gettxoutproof",
+            json!([[display_hash(txid)], display_hash(block_hash)]),
+        )?;
+        let proof = hex::decode(proof).map_err(|e| format!("Invalid hex: {}", e))?;
+        // the proof is a serialized `CMerkleBlock`, i.e. the block header and the partial tree
+        const HEADER_SIZE: usize = 80;
+        if proof.len() < HEADER_SIZE {
+            return Err("Invalid merkle block of `gettxoutproof`".into());
+        }
+        deserialize(Reader::new(&proof[HEADER_SIZE..]))
+            .map_err(|e| format!("Invalid merkle proof: {:?}", e))
+    }
+
+    fn raw_transaction(&self, txid: &BtcHash) -> Result<Option<Transaction>> {
+        match self.call::<String>("getrawtransaction", json!([display_hash(txid), false])) {
+            Ok(tx) => decode_hex(&tx).map(Some),
+            Err(err) => {
+                log::debug!(target: "btc-relayer", "Failed to get tx {:?}: {}", txid, err);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Return the user-visible (byte reversed) hex of the hash.
+fn display_hash(hash: &BtcHash) -> String {
+    hex::encode(hash_rev(*hash).as_bytes())
+}
+
+fn decode_hex<T: Deserializable>(data: &str) -> Result<T> {
+    let bytes = hex::decode(data).map_err(|e| format!("Invalid hex: {}", e))?;
+    deserialize(Reader::new(&bytes)).map_err(|e| format!("Invalid bitcoin data: {:?}", e))
+}
@@ -0,0 +1,280 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use futures::channel::mpsc::UnboundedSender;
+use light_bitcoin::serialization::{
+    serialize, serialize_with_flags, SERIALIZE_TRANSACTION_WITNESS,
+};
+use sc_client_api::AuxStore;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{sr25519, Pair};
+use sp_runtime::{generic, generic::BlockId, OpaqueExtrinsic, SaturatedConversion};
+
+use sherpax_runtime::{
+    opaque::Block, AccountId, BlockHashCount, BtcHash, BtcHeaderIndex, Call, Index, Runtime,
+    SignedExtra, UncheckedExtrinsic, XGatewayBitcoin, VERSION,
+};
+use substrate_frame_rpc_system::AccountNonceApi;
+use xpallet_gateway_bitcoin::{types::BtcRelayedTxInfo, Address, BtcHeader, Transaction};
+use xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi;
+
+use super::Result;
+
+/// The version of `XGatewayBitcoinApi` providing the methods used by the relayer.
+const MIN_BITCOIN_API_VERSION: u32 = 2;
+/// The aux storage key of the recently scanned blocks.
+const SCANNED_BLOCKS_KEY: &[u8] = b"btc_relayer_scanned_blocks";
+
+/// The extrinsics submitted by the relayer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelayCall {
+    PushHeader(BtcHeader),
+    PushTransaction {
+        tx: Transaction,
+        relayed_info: BtcRelayedTxInfo,
+        prev_tx: Option<Transaction>,
+    },
+}
+
+impl From<RelayCall> for Call {
+    fn from(call: RelayCall) -> Self {
+        let call = match call {
+            RelayCall::PushHeader(header) => xpallet_gateway_bitcoin::Call::push_header {
+                header: serialize(&header).into(),
+            },
+            RelayCall::PushTransaction {
+                tx,
+                relayed_info,
+                prev_tx,
+            } => xpallet_gateway_bitcoin::Call::push_transaction {
+                raw_tx: serialize_with_flags(&tx, SERIALIZE_TRANSACTION_WITNESS).into(),
+                relayed_info: relayed_info.encode(),
+                prev_tx: prev_tx
+                    .map(|tx| serialize_with_flags(&tx, SERIALIZE_TRANSACTION_WITNESS).into()),
+            },
+        };
+        Call::XGatewayBitcoin(call)
+    }
+}
+
+/// The state of `XGatewayBitcoin`, the extrinsic submission and the scan progress persisted in
+/// the local node needed by the relayer.
+pub trait GatewayChain {
+    /// Return the best index and the confirmed index of the relayed header chain.
+    fn header_indexes(&self) -> Result<(BtcHeaderIndex, Option<BtcHeaderIndex>)>;
+
+    /// Return true if the header has been relayed.
+    fn has_header(&self, hash: &BtcHash) -> Result<bool>;
+
+    /// Return true if the transaction has been processed successfully.
+    fn is_tx_processed(&self, txid: &BtcHash) -> Result<bool>;
+
+    /// Return the (hot, cold) addresses of the current and the last trustee sessions.
+    fn trustee_address_pairs(&self) -> Result<Vec<(Address, Address)>>;
+
+    /// Return the txid of the pending withdrawal proposal.
+    fn withdrawal_proposal_txid(&self) -> Result<Option<BtcHash>>;
+
+    /// Return the nonce of the relayer account.
+    fn account_nonce(&self) -> Result<Index>;
+
+    /// Sign the call with the nonce and submit it to the transaction pool.
+    fn submit(&self, call: RelayCall, nonce: Index) -> Result<()>;
+
+    /// Return the recently scanned blocks persisted by `store_scanned_blocks`.
+    fn scanned_blocks(&self) -> Result<Vec<BtcHeaderIndex>>;
+
+    /// Persist the recently scanned blocks, so that the scan resumes from them on restart.
+    fn store_scanned_blocks(&self, blocks: &[BtcHeaderIndex]) -> Result<()>;
+}
+
+/// The local chain accessed through the client, the signed extrinsics are sent to the task
+/// submitting them to the transaction pool.
+pub struct SubstrateChain<C> {
+    client: Arc<C>,
+    extrinsics: UnboundedSender<OpaqueExtrinsic>,
+    signer: sr25519::Pair,
+}
+
+impl<C> SubstrateChain<C> {
+    pub fn new(
+        client: Arc<C>,
+        extrinsics: UnboundedSender<OpaqueExtrinsic>,
+        signer: sr25519::Pair,
+    ) -> Self {
+        Self {
+            client,
+            extrinsics,
+            signer,
+        }
+    }
+
+    /// Return true if the submitting task has exited.
+    pub fn is_closed(&self) -> bool {
+        self.extrinsics.is_closed()
+    }
+
+    fn account_id(&self) -> AccountId {
+        self.signer.public().into()
+    }
+}
+
+impl<C> SubstrateChain<C>
+where
+    C: HeaderBackend<Block>,
+{
+    fn best_block(&self) -> BlockId<Block> {
+        BlockId::hash(self.client.info().best_hash)
+    }
+
+    /// Create a signed extrinsic which is mortal from the best block.
+    fn create_extrinsic(&self, call: Call, nonce: Index) -> Result<UncheckedExtrinsic> {
+        let info = self.client.info();
+        let period = BlockHashCount::get()
+            .checked_next_power_of_two()
+            .map(|c| c / 2)
+            .unwrap_or(2) as u64;
+        let extra: SignedExtra = (
+            frame_system::CheckNonZeroSender::<Runtime>::new(),
+            frame_system::CheckSpecVersion::<Runtime>::new(),
+            frame_system::CheckTxVersion::<Runtime>::new(),
+            frame_system::CheckGenesis::<Runtime>::new(),
+            frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(
+                period,
+                info.best_number.saturated_into(),
+            )),
+            frame_system::CheckNonce::<Runtime>::from(nonce),
+            frame_system::CheckWeight::<Runtime>::new(),
+            pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+        );
+        let payload = generic::SignedPayload::from_raw(
+            call.clone(),
+            extra.clone(),
+            (
+                (),
+                VERSION.spec_version,
+                VERSION.transaction_version,
+                info.genesis_hash,
+                info.best_hash,
+                (),
+                (),
+                (),
+            ),
+        );
+        let signature = payload.using_encoded(|payload| self.signer.sign(payload));
+        Ok(UncheckedExtrinsic::new_signed(
+            call,
+            self.account_id().into(),
+            signature.into(),
+            extra,
+        ))
+    }
+}
+
+impl<C> GatewayChain for SubstrateChain<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+    C::Api: XGatewayBitcoinApi<Block, AccountId>,
+    C::Api: AccountNonceApi<Block, AccountId, Index>,
+{
+    fn header_indexes(&self) -> Result<(BtcHeaderIndex, Option<BtcHeaderIndex>)> {
+        let api = self.client.runtime_api();
+        let at = self.best_block();
+        let version = api
+            .api_version::<dyn XGatewayBitcoinApi<Block, AccountId>>(&at)
+            .map_err(|e| format!("Runtime api error: {:?}", e))?;
+        match version {
+            Some(version) if version >= MIN_BITCOIN_API_VERSION => {}
+            _ => {
+                return Err(format!(
+                    "XGatewayBitcoinApi version {:?} is not supported, expect {}",
+                    version, MIN_BITCOIN_API_VERSION
+                ))
+            }
+        }
+        api.get_header_indexes(&at)
+            .map_err(|e| format!("Runtime api error: {:?}", e))
+    }
+
+    fn has_header(&self, hash: &BtcHash) -> Result<bool> {
+        self.client
+            .runtime_api()
+            .get_btc_block_header(&self.best_block(), *hash)
+            .map(|header| header.is_some())
+            .map_err(|e| format!("Runtime api error: {:?}", e))
+    }
+
+    fn is_tx_processed(&self, txid: &BtcHash) -> Result<bool> {
+        self.client
+            .runtime_api()
+            .get_tx_state(&self.best_block(), *txid)
+            .map(|state| {
+                state.map_or(false, |state| {
+                    state.result == xpallet_gateway_bitcoin::types::BtcTxResult::Success
+                })
+            })
+            .map_err(|e| format!("Runtime api error: {:?}", e))
+    }
+
+    fn trustee_address_pairs(&self) -> Result<Vec<(Address, Address)>> {
+        let pairs = self
+            .client
+            .runtime_api()
+            .get_trustee_address_pairs(&self.best_block())
+            .map_err(|e| format!("Runtime api error: {:?}", e))?;
+        pairs
+            .into_iter()
+            .map(|(hot, cold)| {
+                let hot = XGatewayBitcoin::verify_btc_address(&hot);
+                let cold = XGatewayBitcoin::verify_btc_address(&cold);
+                hot.and_then(|hot| cold.map(|cold| (hot, cold)))
+                    .map_err(|e| format!("Invalid trustee address: {:?}", e))
+            })
+            .collect()
+    }
+
+    fn withdrawal_proposal_txid(&self) -> Result<Option<BtcHash>> {
+        self.client
+            .runtime_api()
+            .get_withdrawal_proposal(&self.best_block())
+            .map(|proposal| proposal.map(|proposal| proposal.tx.hash()))
+            .map_err(|e| format!("Runtime api error: {:?}", e))
+    }
+
+    fn account_nonce(&self) -> Result<Index> {
+        self.client
+            .runtime_api()
+            .account_nonce(&self.best_block(), self.account_id())
+            .map_err(|e| format!("Runtime api error: {:?}", e))
+    }
+
+    fn submit(&self, call: RelayCall, nonce: Index) -> Result<()> {
+        let xt = self.create_extrinsic(call.into(), nonce)?;
+        let xt = OpaqueExtrinsic::from_bytes(&xt.encode())
+            .map_err(|e| format!("Invalid extrinsic: {:?}", e))?;
+        self.extrinsics
+            .unbounded_send(xt)
+            .map_err(|e| format!("Failed to submit extrinsic: {:?}", e))
+    }
+    fn scanned_blocks(&self) -> Result<Vec<BtcHeaderIndex>> {
+        let encoded = self
+            .client
+            .get_aux(SCANNED_BLOCKS_KEY)
+            .map_err(|e| format!("Aux storage error: {:?}", e))?;
+        match encoded {
+            Some(encoded) => Decode::decode(&mut &encoded[..])
+                .map_err(|e| format!("Invalid scanned blocks: {:?}", e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn store_scanned_blocks(&self, blocks: &[BtcHeaderIndex]) -> Result<()> {
+        let encoded = blocks.encode();
+        self.client
+            .insert_aux(&[(SCANNED_BLOCKS_KEY, &encoded[..])], &[])
+            .map_err(|e| format!("Aux storage error: {:?}", e))
+    }
+}
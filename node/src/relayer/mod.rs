@@ -0,0 +1,353 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! A built-in bitcoin relayer.
+//!
+//! The relayer polls a bitcoind through its JSON-RPC endpoint and submits with a local key:
+//!
+//! - the bitcoin headers missing past the `BestIndex` of `XGatewayBitcoin` (`push_header`);
+//! - the transactions of the confirmed blocks paying the trustee hot address or spending the
+//!   trustee UTXOs, with their merkle proofs (`push_transaction`).
+//!
+//! The blocking JSON-RPC calls are made on a dedicated thread, the signed extrinsics are sent
+//! back to an async task submitting them to the transaction pool.
+//!
+//! The recently scanned blocks are persisted in the aux storage of the node, the scan resumes
+//! from them on restart and from the fork point after a bitcoin reorganization.
+
+mod bitcoind;
+mod chain;
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{channel::mpsc, StreamExt};
+use sc_client_api::AuxStore;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::sr25519;
+use sp_runtime::generic::BlockId;
+
+use sherpax_runtime::{opaque::Block, AccountId, BtcHash, BtcHeaderIndex, Index};
+use xp_gateway_bitcoin::{extract_output_addr, is_trustee_addr};
+use xpallet_gateway_bitcoin::{types::BtcRelayedTxInfo, Address, PartialMerkleTree, Transaction};
+
+pub use self::{
+    bitcoind::{BitcoinRpc, Bitcoind},
+    chain::{GatewayChain, RelayCall, SubstrateChain},
+};
+
+/// The maximum number of headers relayed in one round.
+const MAX_HEADERS_PER_ROUND: u32 = 100;
+/// The maximum number of confirmed blocks scanned for transactions in one round.
+const MAX_BLOCKS_PER_ROUND: u32 = 10;
+/// The maximum depth of a bitcoin fork that the relayer follows.
+const MAX_FORK_DEPTH: u32 = 100;
+/// The number of rounds to wait for the submitted extrinsics before resubmitting.
+const MAX_STALLED_ROUNDS: u32 = 10;
+/// The depth below which the scanned trustee transactions are pruned, the transactions
+/// spending them fall back to `getrawtransaction`.
+const MAX_TRUSTEE_TX_DEPTH: u32 = 4320;
+
+/// The result type of the relayer.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// The config of the built-in bitcoin relayer.
+#[derive(Clone)]
+pub struct BtcRelayerConfig {
+    /// The JSON-RPC endpoint of the bitcoind.
+    pub url: String,
+    /// The JSON-RPC username of the bitcoind.
+    pub user: Option<String>,
+    /// The JSON-RPC password of the bitcoind.
+    pub password: Option<String>,
+    /// The key signing the relayed extrinsics.
+    pub signer: sr25519::Pair,
+    /// The interval between two polls of the bitcoind.
+    pub interval: Duration,
+}
+
+/// Run the built-in bitcoin relayer until the node exits.
+///
+/// The relayer polls the bitcoind on a dedicated thread, which stops once this task is dropped.
+pub async fn run_btc_relayer<C, P>(client: Arc<C>, pool: Arc<P>, config: BtcRelayerConfig)
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+    C: Send + Sync + 'static,
+    C::Api: xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, AccountId>,
+    C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
+    P: TransactionPool<Block = Block> + 'static,
+{
+    let (sender, mut extrinsics) = mpsc::unbounded();
+    let interval = config.interval;
+    let chain = SubstrateChain::new(client.clone(), sender, config.signer);
+    let spawned = std::thread::Builder::new()
+        .name("btc-relayer".into())
+        .spawn(move || {
+            let bitcoind = Bitcoind::new(config.url, config.user, config.password);
+            let mut relayer = BtcRelayer::new(bitcoind, chain);
+
+            log::info!(target: "btc-relayer", "Bitcoin relayer started, interval: {:?}", interval);
+            while !relayer.chain.is_closed() {
+                if let Err(err) = relayer.relay_once() {
+                    log::warn!(target: "btc-relayer", "Failed to relay bitcoin data: {}", err);
+                }
+                std::thread::sleep(interval);
+            }
+        });
+    if let Err(err) = spawned {
+        log::error!(target: "btc-relayer", "Failed to spawn the relayer thread: {}", err);
+        return;
+    }
+
+    while let Some(xt) = extrinsics.next().await {
+        let at = BlockId::hash(client.info().best_hash);
+        if let Err(err) = pool.submit_one(&at, TransactionSource::Local, xt).await {
+            log::warn!(target: "btc-relayer", "Failed to submit extrinsic: {:?}", err);
+        }
+    }
+}
+
+/// The relayer from a bitcoind to the `XGatewayBitcoin` pallet.
+pub struct BtcRelayer<R, S> {
+    bitcoind: R,
+    chain: S,
+    /// The nonce of the next extrinsic, the extrinsics below it may be still in the pool.
+    next_nonce: Option<Index>,
+    /// The number of rounds waiting for the submitted extrinsics.
+    stalled_rounds: u32,
+    /// The recently scanned confirmed blocks, at most `MAX_FORK_DEPTH` of them.
+    scanned: VecDeque<BtcHeaderIndex>,
+    /// The scanned transactions paying the trustee addresses with their blocks, spent by later
+    /// trustee transactions.
+    trustee_txs: HashMap<BtcHash, (BtcHeaderIndex, Transaction)>,
+}
+
+impl<R: BitcoinRpc, S: GatewayChain> BtcRelayer<R, S> {
+    /// Create a relayer, the transactions are scanned from the persisted scanned blocks, or from
+    /// the current confirmed block if nothing has been scanned.
+    pub fn new(bitcoind: R, chain: S) -> Self {
+        let scanned = chain.scanned_blocks().unwrap_or_else(|err| {
+            log::warn!(target: "btc-relayer", "Failed to load the scanned blocks: {}", err);
+            Vec::new()
+        });
+        Self {
+            bitcoind,
+            chain,
+            next_nonce: None,
+            stalled_rounds: 0,
+            scanned: scanned.into(),
+            trustee_txs: HashMap::new(),
+        }
+    }
+
+    /// Relay the missing headers and the trustee transactions of the confirmed blocks.
+    ///
+    /// Nothing is relayed while the extrinsics of the last round are still pending.
+    pub fn relay_once(&mut self) -> Result<()> {
+        let nonce = self.chain.account_nonce()?;
+        if let Some(next_nonce) = self.next_nonce {
+            if next_nonce > nonce && self.stalled_rounds < MAX_STALLED_ROUNDS {
+                self.stalled_rounds += 1;
+                return Ok(());
+            }
+        }
+        self.next_nonce = Some(nonce);
+        self.stalled_rounds = 0;
+
+        let (best, confirmed) = self.chain.header_indexes()?;
+        self.relay_headers(best)?;
+        if let Some(confirmed) = confirmed {
+            self.relay_transactions(confirmed)?;
+        }
+        Ok(())
+    }
+
+    fn submit(&mut self, call: RelayCall) -> Result<()> {
+        let nonce = self.next_nonce.unwrap_or_default();
+        self.chain.submit(call, nonce)?;
+        self.next_nonce = Some(nonce + 1);
+        Ok(())
+    }
+
+    /// Submit the bitcoind headers past the fork point of the relayed header chain.
+    fn relay_headers(&mut self, best: BtcHeaderIndex) -> Result<()> {
+        let tip = self.bitcoind.block_count()?;
+        let mut fork_height = best.height.min(tip);
+        loop {
+            let hash = self.bitcoind.block_hash(fork_height)?;
+            if self.chain.has_header(&hash)? {
+                break;
+            }
+            if fork_height == 0 || best.height - fork_height >= MAX_FORK_DEPTH {
+                return Err(format!(
+                    "No common header with bitcoind below height {}",
+                    best.height
+                ));
+            }
+            fork_height -= 1;
+        }
+
+        let end = tip.min(fork_height + MAX_HEADERS_PER_ROUND);
+        for height in fork_height + 1..=end {
+            let hash = self.bitcoind.block_hash(height)?;
+            let header = self.bitcoind.block_header(&hash)?;
+            log::debug!(target: "btc-relayer", "Relay header #{} ({:?})", height, hash);
+            self.submit(RelayCall::PushHeader(header))?;
+        }
+        Ok(())
+    }
+
+    /// Submit the trustee transactions of the blocks up to the confirmed index.
+    fn relay_transactions(&mut self, confirmed: BtcHeaderIndex) -> Result<()> {
+        let trustee_pairs = self.chain.trustee_address_pairs()?;
+        let (hot, _) = match trustee_pairs.first() {
+            Some(pair) => *pair,
+            None => return Ok(()),
+        };
+        let proposal = self.chain.withdrawal_proposal_txid()?;
+        let fork_height = self.handle_reorg()?;
+
+        let start = match self.scanned.back() {
+            Some(scanned) => scanned.height + 1,
+            // nothing has been scanned, or all the scanned blocks are orphaned
+            None => fork_height.unwrap_or(confirmed.height),
+        };
+        let end = confirmed
+            .height
+            .min(start.saturating_add(MAX_BLOCKS_PER_ROUND - 1));
+        for height in start..=end {
+            let block_hash = self.bitcoind.block_hash(height)?;
+            let block = BtcHeaderIndex {
+                hash: block_hash,
+                height,
+            };
+            let transactions = self.bitcoind.block_transactions(&block_hash)?;
+            for tx in transactions {
+                let pays_hot = tx.outputs.iter().any(|output| {
+                    extract_output_addr(output, hot.network)
+                        .map_or(false, |addr| addr.hash == hot.hash)
+                });
+                let is_proposal = proposal == Some(tx.hash());
+                let prev_tx = self.trustee_prev_tx(&tx);
+                if !pays_hot && !is_proposal && prev_tx.is_none() {
+                    continue;
+                }
+                let prev_tx = match prev_tx {
+                    Some(prev_tx) => Some(prev_tx),
+                    // the input address of a deposit without OP_RETURN and of a withdrawal
+                    // spending an unscanned UTXO is extracted from `prev_tx`, which is only
+                    // available with `-txindex`.
+                    None => self
+                        .bitcoind
+                        .raw_transaction(&tx.inputs[0].previous_output.hash)
+                        .unwrap_or(None),
+                };
+                if pays_trustee(&tx, &trustee_pairs) {
+                    self.trustee_txs.insert(tx.hash(), (block, tx.clone()));
+                }
+                self.relay_transaction(block_hash, tx, prev_tx)?;
+            }
+            self.scanned.push_back(block);
+            if self.scanned.len() > MAX_FORK_DEPTH as usize {
+                self.scanned.pop_front();
+            }
+            self.chain
+                .store_scanned_blocks(self.scanned.make_contiguous())?;
+        }
+        self.prune_trustee_txs(end);
+        Ok(())
+    }
+
+    /// Drop the trustee transactions deeper than `MAX_TRUSTEE_TX_DEPTH` below `height`.
+    fn prune_trustee_txs(&mut self, height: u32) {
+        self.trustee_txs
+            .retain(|_, (block, _)| block.height.saturating_add(MAX_TRUSTEE_TX_DEPTH) > height);
+    }
+
+    /// Drop the scanned blocks reorganized by bitcoind to rescan from the fork point, the trustee
+    /// transactions of the orphaned blocks are dropped too.
+    ///
+    /// Return the height of the lowest orphaned block if there is a reorganization.
+    fn handle_reorg(&mut self) -> Result<Option<u32>> {
+        let tip = self.bitcoind.block_count()?;
+        let mut fork_height = None;
+        while let Some(scanned) = self.scanned.back().copied() {
+            if scanned.height <= tip && self.bitcoind.block_hash(scanned.height)? == scanned.hash {
+                break;
+            }
+            fork_height = Some(scanned.height);
+            self.scanned.pop_back();
+        }
+        let fork_height = match fork_height {
+            Some(fork_height) => fork_height,
+            None => return Ok(None),
+        };
+        log::warn!(
+            target: "btc-relayer",
+            "Scanned blocks since #{} are reorganized, rescan from the fork point",
+            fork_height
+        );
+        self.chain
+            .store_scanned_blocks(self.scanned.make_contiguous())?;
+
+        let blocks = self
+            .trustee_txs
+            .values()
+            .map(|(block, _)| (block.height, block.hash))
+            .collect::<HashSet<_>>();
+        let mut orphaned = HashSet::new();
+        for (height, hash) in blocks {
+            if self.bitcoind.block_hash(height)? != hash {
+                orphaned.insert(hash);
+            }
+        }
+        self.trustee_txs
+            .retain(|_, (block, _)| !orphaned.contains(&block.hash));
+        Ok(Some(fork_height))
+    }
+
+    /// Return the previous transaction if the first input of `tx` spends a scanned trustee UTXO.
+    fn trustee_prev_tx(&self, tx: &Transaction) -> Option<Transaction> {
+        let outpoint = &tx.inputs.first()?.previous_output;
+        self.trustee_txs
+            .get(&outpoint.hash)
+            .map(|(_, tx)| tx.clone())
+    }
+
+    fn relay_transaction(
+        &mut self,
+        block_hash: BtcHash,
+        tx: Transaction,
+        prev_tx: Option<Transaction>,
+    ) -> Result<()> {
+        let txid = tx.hash();
+        if self.chain.is_tx_processed(&txid)? {
+            return Ok(());
+        }
+        let merkle_proof: PartialMerkleTree = self.bitcoind.tx_out_proof(&txid, &block_hash)?;
+        log::info!(target: "btc-relayer", "Relay tx {:?} in block {:?}", txid, block_hash);
+        self.submit(RelayCall::PushTransaction {
+            tx,
+            relayed_info: BtcRelayedTxInfo {
+                block_hash,
+                merkle_proof,
+            },
+            prev_tx,
+        })
+    }
+}
+
+fn pays_trustee(tx: &Transaction, trustee_pairs: &[(Address, Address)]) -> bool {
+    tx.outputs.iter().any(|output| {
+        trustee_pairs.iter().any(|&(hot, cold)| {
+            extract_output_addr(output, hot.network)
+                .map_or(false, |addr| is_trustee_addr(addr, (hot, cold)))
+        })
+    })
+}
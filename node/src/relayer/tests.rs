@@ -0,0 +1,455 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::{cell::RefCell, collections::BTreeSet};
+
+use light_bitcoin::{
+    chain::{OutPoint, TransactionInput, TransactionOutput},
+    keys::Network,
+    primitives::{Bytes, Compact},
+    serialization::{deserialize, Reader},
+};
+
+use super::*;
+use xpallet_gateway_bitcoin::BtcHeader;
+
+/// A regtest bitcoind stand-in keeping the blocks in memory.
+#[derive(Clone, Default)]
+struct MockBitcoind {
+    blocks: Vec<(BtcHeader, Vec<Transaction>)>,
+}
+
+impl MockBitcoind {
+    fn push_block(&mut self, transactions: Vec<Transaction>) -> BtcHash {
+        let previous_header_hash = self
+            .blocks
+            .last()
+            .map(|(header, _)| header.hash())
+            .unwrap_or_default();
+        // the stand-in doesn't check the merkle root, the first txid just distinguishes forks
+        let merkle_root_hash = transactions.first().map(|tx| tx.hash()).unwrap_or_default();
+        let header = BtcHeader {
+            version: 1,
+            previous_header_hash,
+            merkle_root_hash,
+            time: 1_600_000_000 + self.blocks.len() as u32,
+            bits: Compact::new(0x207fffff),
+            nonce: self.blocks.len() as u32,
+        };
+        let hash = header.hash();
+        self.blocks.push((header, transactions));
+        hash
+    }
+
+    fn block(&self, hash: &BtcHash) -> Result<&(BtcHeader, Vec<Transaction>)> {
+        self.blocks
+            .iter()
+            .find(|(header, _)| header.hash() == *hash)
+            .ok_or_else(|| "Block not found".into())
+    }
+}
+
+impl BitcoinRpc for MockBitcoind {
+    fn block_count(&self) -> Result<u32> {
+        Ok(self.blocks.len() as u32 - 1)
+    }
+
+    fn block_hash(&self, height: u32) -> Result<BtcHash> {
+        self.blocks
+            .get(height as usize)
+            .map(|(header, _)| header.hash())
+            .ok_or_else(|| "Block height out of range".into())
+    }
+
+    fn block_header(&self, hash: &BtcHash) -> Result<BtcHeader> {
+        self.block(hash).map(|(header, _)| header.clone())
+    }
+
+    fn block_transactions(&self, hash: &BtcHash) -> Result<Vec<Transaction>> {
+        self.block(hash)
+            .map(|(_, transactions)| transactions.clone())
+    }
+
+    fn tx_out_proof(&self, txid: &BtcHash, _block_hash: &BtcHash) -> Result<PartialMerkleTree> {
+        // tx count (1), hashes ([txid]) and flag bits ([0b1])
+        let mut proof = 1u32.to_le_bytes().to_vec();
+        proof.push(1);
+        proof.extend_from_slice(txid.as_bytes());
+        proof.extend_from_slice(&[1, 1]);
+        deserialize(Reader::new(&proof)).map_err(|e| format!("{:?}", e))
+    }
+
+    fn raw_transaction(&self, txid: &BtcHash) -> Result<Option<Transaction>> {
+        Ok(self
+            .blocks
+            .iter()
+            .flat_map(|(_, transactions)| transactions)
+            .find(|tx| tx.hash() == *txid)
+            .cloned())
+    }
+}
+
+#[derive(Default)]
+struct MockChain {
+    headers: BTreeSet<BtcHash>,
+    best: BtcHeaderIndex,
+    confirmed: Option<BtcHeaderIndex>,
+    processed: BTreeSet<BtcHash>,
+    trustee_pairs: Vec<(Address, Address)>,
+    nonce: Index,
+    submitted: RefCell<Vec<(RelayCall, Index)>>,
+    scanned: RefCell<Vec<BtcHeaderIndex>>,
+}
+
+impl MockChain {
+    fn import(&mut self, bitcoind: &MockBitcoind, best: u32, confirmed: u32) {
+        for (header, _) in &bitcoind.blocks[..=best as usize] {
+            self.headers.insert(header.hash());
+        }
+        self.best = BtcHeaderIndex {
+            hash: bitcoind.block_hash(best).unwrap(),
+            height: best,
+        };
+        self.confirmed = Some(BtcHeaderIndex {
+            hash: bitcoind.block_hash(confirmed).unwrap(),
+            height: confirmed,
+        });
+    }
+
+    fn take_submitted(&self) -> Vec<(RelayCall, Index)> {
+        self.submitted.borrow_mut().drain(..).collect()
+    }
+}
+
+impl GatewayChain for MockChain {
+    fn header_indexes(&self) -> Result<(BtcHeaderIndex, Option<BtcHeaderIndex>)> {
+        Ok((self.best, self.confirmed))
+    }
+
+    fn has_header(&self, hash: &BtcHash) -> Result<bool> {
+        Ok(self.headers.contains(hash))
+    }
+
+    fn is_tx_processed(&self, txid: &BtcHash) -> Result<bool> {
+        Ok(self.processed.contains(txid))
+    }
+
+    fn trustee_address_pairs(&self) -> Result<Vec<(Address, Address)>> {
+        Ok(self.trustee_pairs.clone())
+    }
+
+    fn withdrawal_proposal_txid(&self) -> Result<Option<BtcHash>> {
+        Ok(None)
+    }
+
+    fn account_nonce(&self) -> Result<Index> {
+        Ok(self.nonce)
+    }
+
+    fn submit(&self, call: RelayCall, nonce: Index) -> Result<()> {
+        self.submitted.borrow_mut().push((call, nonce));
+        Ok(())
+    }
+
+    fn scanned_blocks(&self) -> Result<Vec<BtcHeaderIndex>> {
+        Ok(self.scanned.borrow().clone())
+    }
+
+    fn store_scanned_blocks(&self, blocks: &[BtcHeaderIndex]) -> Result<()> {
+        *self.scanned.borrow_mut() = blocks.to_vec();
+        Ok(())
+    }
+}
+
+fn p2pkh_output(hash: u8, value: u64) -> TransactionOutput {
+    let mut script = vec![0x76, 0xa9, 0x14];
+    script.extend_from_slice(&[hash; 20]);
+    script.extend_from_slice(&[0x88, 0xac]);
+    TransactionOutput {
+        value,
+        script_pubkey: Bytes::from(script),
+    }
+}
+
+fn address_of(output: &TransactionOutput) -> Address {
+    extract_output_addr(output, Network::Testnet).unwrap()
+}
+
+fn transaction(previous_output: OutPoint, outputs: Vec<TransactionOutput>) -> Transaction {
+    Transaction {
+        version: 1,
+        inputs: vec![TransactionInput {
+            previous_output,
+            script_sig: Bytes::new(),
+            sequence: 0xffff_ffff,
+            script_witness: vec![],
+        }],
+        outputs,
+        lock_time: 0,
+    }
+}
+
+fn coinbase() -> OutPoint {
+    OutPoint {
+        hash: Default::default(),
+        index: u32::MAX,
+    }
+}
+
+fn outpoint(tx: &Transaction, index: u32) -> OutPoint {
+    OutPoint {
+        hash: tx.hash(),
+        index,
+    }
+}
+
+fn setup() -> (MockBitcoind, MockChain) {
+    let mut bitcoind = MockBitcoind::default();
+    for _ in 0..=5 {
+        bitcoind.push_block(vec![]);
+    }
+    let mut chain = MockChain::default();
+    chain.trustee_pairs = vec![(
+        address_of(&p2pkh_output(1, 0)),
+        address_of(&p2pkh_output(2, 0)),
+    )];
+    chain.import(&bitcoind, 2, 1);
+    (bitcoind, chain)
+}
+
+#[test]
+fn test_relay_missing_headers() {
+    let (bitcoind, chain) = setup();
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+
+    let submitted = relayer.chain.take_submitted();
+    let expected = (3..=5)
+        .zip(0..)
+        .map(|(height, nonce)| {
+            let header = relayer.bitcoind.blocks[height].0.clone();
+            (RelayCall::PushHeader(header), nonce)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(submitted, expected);
+
+    // wait for the submitted headers
+    relayer.relay_once().unwrap();
+    assert!(relayer.chain.take_submitted().is_empty());
+
+    // the submitted headers are imported
+    let bitcoind = relayer.bitcoind.clone();
+    relayer.chain.import(&bitcoind, 5, 4);
+    relayer.chain.nonce = 3;
+    relayer.relay_once().unwrap();
+    assert!(relayer.chain.take_submitted().is_empty());
+}
+
+#[test]
+fn test_relay_headers_from_fork_point() {
+    let (mut bitcoind, mut chain) = setup();
+    // the relayed best header #2 is reorganized by bitcoind
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![transaction(coinbase(), vec![])]);
+    bitcoind.push_block(vec![]);
+    chain.confirmed = None;
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 2);
+    assert_eq!(
+        submitted[0].0,
+        RelayCall::PushHeader(relayer.bitcoind.blocks[2].0.clone())
+    );
+}
+
+#[test]
+fn test_relay_trustee_transactions() {
+    let (mut bitcoind, mut chain) = setup();
+    let funding = transaction(coinbase(), vec![p2pkh_output(9, 100_000)]);
+    let deposit = transaction(
+        outpoint(&funding, 0),
+        vec![p2pkh_output(1, 50_000), p2pkh_output(9, 40_000)],
+    );
+    let irrelevance = transaction(outpoint(&funding, 0), vec![p2pkh_output(8, 90_000)]);
+    let withdrawal = transaction(outpoint(&deposit, 0), vec![p2pkh_output(7, 40_000)]);
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![funding.clone(), deposit.clone(), irrelevance]);
+    let block_3 = bitcoind.push_block(vec![withdrawal.clone()]);
+    chain.import(&bitcoind, 3, 2);
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 1);
+    match &submitted[0] {
+        (RelayCall::PushTransaction { tx, prev_tx, .. }, 0) => {
+            assert_eq!(tx, &deposit);
+            assert_eq!(prev_tx, &Some(funding));
+        }
+        other => panic!("Unexpected call: {:?}", other),
+    }
+
+    let bitcoind = relayer.bitcoind.clone();
+    relayer.chain.import(&bitcoind, 3, 3);
+    relayer.chain.nonce = 1;
+    relayer.relay_once().unwrap();
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 1);
+    match &submitted[0] {
+        (
+            RelayCall::PushTransaction {
+                tx,
+                relayed_info,
+                prev_tx,
+            },
+            1,
+        ) => {
+            assert_eq!(tx, &withdrawal);
+            assert_eq!(relayed_info.block_hash, block_3);
+            assert_eq!(prev_tx, &Some(deposit));
+        }
+        other => panic!("Unexpected call: {:?}", other),
+    }
+}
+
+#[test]
+fn test_skip_processed_transactions() {
+    let (mut bitcoind, mut chain) = setup();
+    let deposit = transaction(coinbase(), vec![p2pkh_output(1, 50_000)]);
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![deposit.clone()]);
+    chain.import(&bitcoind, 2, 2);
+    chain.processed.insert(deposit.hash());
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    assert!(relayer.chain.take_submitted().is_empty());
+}
+
+#[test]
+fn test_rescan_reorganized_blocks() {
+    let (mut bitcoind, mut chain) = setup();
+    let deposit = transaction(coinbase(), vec![p2pkh_output(1, 50_000)]);
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![deposit.clone()]);
+    bitcoind.push_block(vec![]);
+    chain.import(&bitcoind, 3, 2);
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    assert_eq!(relayer.chain.take_submitted().len(), 1);
+    assert!(relayer.trustee_txs.contains_key(&deposit.hash()));
+
+    // the scanned block #2 is reorganized by bitcoind
+    let another = transaction(coinbase(), vec![p2pkh_output(1, 60_000)]);
+    relayer.bitcoind.blocks.truncate(2);
+    relayer.bitcoind.push_block(vec![another.clone()]);
+    relayer.bitcoind.push_block(vec![]);
+    let bitcoind = relayer.bitcoind.clone();
+    relayer.chain.import(&bitcoind, 3, 2);
+    relayer.chain.nonce = 1;
+    relayer.relay_once().unwrap();
+
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 1);
+    match &submitted[0] {
+        (RelayCall::PushTransaction { tx, .. }, 1) => assert_eq!(tx, &another),
+        other => panic!("Unexpected call: {:?}", other),
+    }
+    assert!(!relayer.trustee_txs.contains_key(&deposit.hash()));
+    assert!(relayer.trustee_txs.contains_key(&another.hash()));
+}
+
+#[test]
+fn test_resume_from_scanned_blocks() {
+    let (mut bitcoind, mut chain) = setup();
+    let deposit = transaction(coinbase(), vec![p2pkh_output(1, 50_000)]);
+    bitcoind.blocks.truncate(3);
+    bitcoind.push_block(vec![deposit.clone()]);
+    bitcoind.push_block(vec![]);
+    chain.import(&bitcoind, 4, 2);
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    assert!(relayer.chain.take_submitted().is_empty());
+    assert_eq!(relayer.chain.scanned.borrow().len(), 1);
+
+    // the restarted relayer scans the block #3 confirmed while it was stopped
+    let BtcRelayer {
+        bitcoind,
+        mut chain,
+        ..
+    } = relayer;
+    chain.import(&bitcoind, 4, 4);
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 1);
+    match &submitted[0] {
+        (RelayCall::PushTransaction { tx, .. }, 0) => assert_eq!(tx, &deposit),
+        other => panic!("Unexpected call: {:?}", other),
+    }
+    let scanned = relayer.chain.scanned.borrow().clone();
+    assert_eq!(
+        scanned.iter().map(|block| block.height).collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+}
+
+#[test]
+fn test_rescan_from_fork_point() {
+    let (mut bitcoind, mut chain) = setup();
+    let deposit = transaction(coinbase(), vec![p2pkh_output(1, 50_000)]);
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![deposit.clone()]);
+    bitcoind.push_block(vec![]);
+    bitcoind.push_block(vec![]);
+    chain.import(&bitcoind, 4, 2);
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    assert_eq!(relayer.chain.take_submitted().len(), 1);
+    let bitcoind = relayer.bitcoind.clone();
+    relayer.chain.import(&bitcoind, 4, 4);
+    relayer.chain.nonce = 1;
+    relayer.relay_once().unwrap();
+    assert!(relayer.chain.take_submitted().is_empty());
+
+    // the scanned blocks since #3 are reorganized by bitcoind
+    let another = transaction(coinbase(), vec![p2pkh_output(1, 60_000)]);
+    relayer.bitcoind.blocks.truncate(3);
+    relayer.bitcoind.push_block(vec![another.clone()]);
+    relayer.bitcoind.push_block(vec![]);
+    let bitcoind = relayer.bitcoind.clone();
+    relayer.chain.import(&bitcoind, 4, 4);
+    relayer.relay_once().unwrap();
+
+    // the deposit in #2 below the fork point is not relayed again
+    let submitted = relayer.chain.take_submitted();
+    assert_eq!(submitted.len(), 1);
+    match &submitted[0] {
+        (RelayCall::PushTransaction { tx, .. }, 1) => assert_eq!(tx, &another),
+        other => panic!("Unexpected call: {:?}", other),
+    }
+    assert!(relayer.trustee_txs.contains_key(&deposit.hash()));
+    assert!(relayer.trustee_txs.contains_key(&another.hash()));
+}
+
+#[test]
+fn test_prune_trustee_transactions() {
+    let (mut bitcoind, mut chain) = setup();
+    let deposit = transaction(coinbase(), vec![p2pkh_output(1, 50_000)]);
+    bitcoind.blocks.truncate(2);
+    bitcoind.push_block(vec![deposit.clone()]);
+    chain.import(&bitcoind, 2, 2);
+
+    let mut relayer = BtcRelayer::new(bitcoind, chain);
+    relayer.relay_once().unwrap();
+    assert!(relayer.trustee_txs.contains_key(&deposit.hash()));
+
+    relayer.prune_trustee_txs(2 + MAX_TRUSTEE_TX_DEPTH - 1);
+    assert!(relayer.trustee_txs.contains_key(&deposit.hash()));
+    relayer.prune_trustee_txs(2 + MAX_TRUSTEE_TX_DEPTH);
+    assert!(relayer.trustee_txs.is_empty());
+}
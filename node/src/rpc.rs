@@ -25,7 +25,9 @@ use jsonrpc_pubsub::manager::SubscriptionManager;
 use sc_client_api::{
     backend::{AuxStore, Backend, StateBackend, StorageProvider},
     client::BlockchainEvents,
+    BlockBackend,
 };
+use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
 use sc_network::NetworkService;
 use sc_rpc::SubscriptionTaskExecutor;
 use sc_transaction_pool::{ChainApi, Pool};
@@ -33,6 +35,8 @@ use sherpax_runtime::Hash;
 use sp_runtime::traits::BlakeTwo256;
 use std::collections::BTreeMap;
 
+use crate::evm_tracing::EvmTracingConfig;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
@@ -64,6 +68,10 @@ pub struct FullDeps<C, P, A: ChainApi> {
     pub overrides: Arc<OverrideHandle<Block>>,
     /// Cache for Ethereum block data.
     pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+    /// Manual seal command sink, only available with the `--sealing` development mode.
+    pub command_sink: Option<futures::channel::mpsc::Sender<EngineCommand<Hash>>>,
+    /// The config of the EVM tracing RPCs.
+    pub evm_tracing: EvmTracingConfig,
 }
 
 pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
@@ -110,7 +118,7 @@ where
     BE: Backend<Block> + 'static,
     BE::State: StateBackend<BlakeTwo256>,
     C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
-    C: BlockchainEvents<Block>,
+    C: BlockchainEvents<Block> + BlockBackend<Block>,
     C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
@@ -118,6 +126,7 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
+    C::Api: xp_evm_tracing::DebugRuntimeApi<Block>,
     C::Api: xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, AccountId>,
     C::Api: xpallet_gateway_dogecoin_rpc_runtime_api::XGatewayDogecoinApi<Block, AccountId>,
     C::Api: xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<
@@ -132,15 +141,26 @@ where
         Balance,
         BlockNumber,
     >,
+    C::Api: pallet_assets_rpc_runtime_api::AssetsApi<Block, AccountId, Balance>,
+    C::Api: pallet_assets_bridge_rpc_runtime_api::AssetsBridgeApi<
+        Block,
+        AccountId,
+        Balance,
+        BlockNumber,
+    >,
     P: TransactionPool<Block = Block> + Sync + Send + 'static,
     A: ChainApi<Block = Block> + 'static,
 {
+    use pallet_assets_bridge_rpc::{AssetsBridge, AssetsBridgeApi};
+    use pallet_assets_rpc::{Assets, AssetsApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
     use xpallet_gateway_bitcoin_rpc::{XGatewayBitcoin, XGatewayBitcoinApi};
     use xpallet_gateway_common_rpc::{XGatewayCommon, XGatewayCommonApi};
     use xpallet_gateway_dogecoin_rpc::{XGatewayDogecoin, XGatewayDogecoinApi};
-    use xpallet_gateway_records_rpc::{XGatewayRecords, XGatewayRecordsApi};
+    use xpallet_gateway_records_rpc::{
+        XGatewayRecords, XGatewayRecordsApi, XGatewayRecordsPubSub, XGatewayRecordsPubSubApi,
+    };
 
     let mut io = jsonrpc_core::IoHandler::default();
 
@@ -158,6 +178,8 @@ where
         fee_history_cache,
         overrides,
         block_data_cache,
+        command_sink,
+        evm_tracing,
     } = deps;
 
     io.extend_with(SystemApi::to_delegate(FullSystem::new(
@@ -170,6 +192,26 @@ where
         client.clone(),
     )));
 
+    // evm tracing api
+    {
+        use crate::evm_tracing::{EvmDebug, EvmDebugApi, EvmTrace, EvmTraceApi};
+
+        if evm_tracing.debug {
+            io.extend_with(EvmDebugApi::to_delegate(EvmDebug::new(
+                client.clone(),
+                backend.clone(),
+            )));
+        }
+        if evm_tracing.trace {
+            io.extend_with(EvmTraceApi::to_delegate(EvmTrace::new(
+                client.clone(),
+                evm_tracing.trace_cache,
+                evm_tracing.trace_max_count,
+                evm_tracing.trace_max_block_range,
+            )));
+        }
+    }
+
     // eth api
     {
         use fc_rpc::{
@@ -220,7 +262,7 @@ where
             network,
             SubscriptionManager::<HexEncodedIdProvider>::with_id_provider(
                 HexEncodedIdProvider::default(),
-                Arc::new(subscription_task_executor),
+                Arc::new(subscription_task_executor.clone()),
             ),
             overrides,
         )));
@@ -235,7 +277,21 @@ where
     io.extend_with(XGatewayRecordsApi::to_delegate(XGatewayRecords::new(
         client.clone(),
     )));
-    io.extend_with(XGatewayCommonApi::to_delegate(XGatewayCommon::new(client)));
+    io.extend_with(XGatewayRecordsPubSubApi::to_delegate(
+        XGatewayRecordsPubSub::new(
+            client.clone(),
+            SubscriptionManager::new(Arc::new(subscription_task_executor)),
+        ),
+    ));
+    io.extend_with(XGatewayCommonApi::to_delegate(XGatewayCommon::new(
+        client.clone(),
+    )));
+    io.extend_with(AssetsApi::to_delegate(Assets::new(client.clone())));
+    io.extend_with(AssetsBridgeApi::to_delegate(AssetsBridge::new(client)));
+
+    if let Some(command_sink) = command_sink {
+        io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink)));
+    }
 
     io
 }
@@ -3,12 +3,17 @@
 
 use sc_client_api::{BlockBackend, ExecutorProvider};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_manual_seal::{
+    consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+    rpc::EngineCommand,
+    ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
-use sc_telemetry::{Telemetry, TelemetryWorker};
-use sherpax_runtime::{self, opaque::Block, RuntimeApi};
+use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker};
+use sherpax_runtime::{self, opaque::Block, Hash, RuntimeApi};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use std::{sync::Arc, time::Duration};
 
@@ -25,16 +30,22 @@ use sc_service::BasePath;
 use std::{collections::BTreeMap, sync::Mutex};
 use substrate_prometheus_endpoint::Registry;
 
+use crate::cli::Sealing;
+
 // Our native executor instance.
 pub struct ExecutorDispatch;
 
 impl sc_executor::NativeExecutionDispatch for ExecutorDispatch {
     /// Only enable the benchmarking host functions when we actually want to benchmark.
     #[cfg(feature = "runtime-benchmarks")]
-    type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
-    /// Otherwise we only use the default Substrate host functions.
+    type ExtendHostFunctions = (
+        frame_benchmarking::benchmarking::HostFunctions,
+        xp_evm_tracing::evm_tracing_ext::HostFunctions,
+    );
+    /// Otherwise we only use the default Substrate host functions and the EVM tracing ones
+    /// required by the tracing runtime.
     #[cfg(not(feature = "runtime-benchmarks"))]
-    type ExtendHostFunctions = ();
+    type ExtendHostFunctions = xp_evm_tracing::evm_tracing_ext::HostFunctions;
 
     fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
         sherpax_runtime::api::dispatch(method, data)
@@ -91,6 +102,7 @@ fn set_prometheus_registry(config: &mut Configuration) -> Result<(), ServiceErro
 
 pub fn new_partial(
     config: &mut Configuration,
+    sealing: Option<Sealing>,
 ) -> Result<
     sc_service::PartialComponents<
         FullClient,
@@ -176,10 +188,21 @@ pub fn new_partial(
         frontier_backend.clone(),
     );
 
-    let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+    let import_queue = if sealing.is_some() {
+        // the manual sealed blocks are imported without the Aura verification.
+        sc_consensus_manual_seal::import_queue(
+            Box::new(FrontierBlockImport::new(
+                client.clone(),
+                client.clone(),
+                frontier_backend.clone(),
+            )),
+            &task_manager.spawn_essential_handle(),
+            config.prometheus_registry(),
+        )
+    } else {
+        let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 
-    let import_queue = sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(
-        ImportQueueParams {
+        sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
             block_import: frontier_block_import.clone(),
             justification_import: Some(Box::new(grandpa_block_import.clone())),
             client: client.clone(),
@@ -201,8 +224,8 @@ pub fn new_partial(
             registry: config.prometheus_registry(),
             check_for_equivocation: Default::default(),
             telemetry: telemetry.as_ref().map(|x| x.handle()),
-        },
-    )?;
+        })?
+    };
 
     Ok(sc_service::PartialComponents {
         client,
@@ -230,7 +253,12 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+    mut config: Configuration,
+    sealing: Option<Sealing>,
+    evm_tracing: crate::evm_tracing::EvmTracingConfig,
+    btc_relayer: Option<crate::relayer::BtcRelayerConfig>,
+) -> Result<TaskManager, ServiceError> {
     let sc_service::PartialComponents {
         client,
         backend,
@@ -240,7 +268,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
         select_chain,
         transaction_pool,
         other: (consensus_result, filter_pool, frontier_backend, mut telemetry, fee_history_cache),
-    } = new_partial(&mut config)?;
+    } = new_partial(&mut config, sealing)?;
 
     if let Some(url) = &config.keystore_remote {
         match remote_keystore(url) {
@@ -298,6 +326,15 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
     let overrides = crate::rpc::overrides_handle(client.clone());
     let fee_history_limit = 2048;
 
+    // The `engine_createBlock`/`engine_finalizeBlock` commands of the manual-seal.
+    let (command_sink, commands_stream) = match sealing {
+        Some(_) => {
+            let (sink, stream) = futures::channel::mpsc::channel(1000);
+            (Some(sink), Some(stream))
+        }
+        None => (None, None),
+    };
+
     let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
         task_manager.spawn_handle(),
         overrides.clone(),
@@ -332,6 +369,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
                 fee_history_cache: fee_history_cache.clone(),
                 overrides: overrides.clone(),
                 block_data_cache: block_data_cache.clone(),
+                command_sink: command_sink.clone(),
+                evm_tracing: evm_tracing.clone(),
             };
 
             Ok(crate::rpc::create_full(
@@ -399,6 +438,39 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
         EthTask::ethereum_schema_cache_task(Arc::clone(&client), Arc::clone(&frontier_backend)),
     );
 
+    if let Some(registry) = prometheus_registry.as_ref() {
+        task_manager.spawn_handle().spawn(
+            "gateway-metrics",
+            Some("gateway"),
+            crate::gateway_metrics::run_gateway_metrics(client.clone(), registry.clone()),
+        );
+    }
+
+    if let Some(btc_relayer) = btc_relayer {
+        task_manager.spawn_handle().spawn(
+            "btc-relayer",
+            Some("relayer"),
+            crate::relayer::run_btc_relayer(client.clone(), transaction_pool.clone(), btc_relayer),
+        );
+    }
+
+    if let (Some(sealing), Some(commands_stream)) = (sealing, commands_stream) {
+        spawn_manual_seal(
+            &task_manager,
+            client,
+            transaction_pool,
+            select_chain,
+            frontier_backend,
+            prometheus_registry.as_ref(),
+            telemetry.as_ref().map(|x| x.handle()),
+            sealing,
+            commands_stream,
+        );
+
+        network_starter.start_network();
+        return Ok(task_manager);
+    }
+
     let (block_import, grandpa_link) = consensus_result;
 
     if role.is_authority() {
@@ -501,3 +573,74 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
     network_starter.start_network();
     Ok(task_manager)
 }
+
+/// Spawns the manual-seal authoring of the development node instead of Aura and GRANDPA,
+/// the blocks are sealed by the `sealing` and the `engine_*` RPC commands.
+fn spawn_manual_seal(
+    task_manager: &TaskManager,
+    client: Arc<FullClient>,
+    transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+    select_chain: FullSelectChain,
+    frontier_backend: Arc<fc_db::Backend<Block>>,
+    prometheus_registry: Option<&Registry>,
+    telemetry: Option<TelemetryHandle>,
+    sealing: Sealing,
+    rpc_commands: futures::channel::mpsc::Receiver<EngineCommand<Hash>>,
+) {
+    let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+        task_manager.spawn_handle(),
+        client.clone(),
+        transaction_pool.clone(),
+        prometheus_registry,
+        telemetry,
+    );
+
+    let seal_command = |create_empty| EngineCommand::SealNewBlock {
+        create_empty,
+        finalize: true,
+        parent_hash: None,
+        sender: None,
+    };
+    let sealing_commands = match sealing {
+        Sealing::Manual => futures::stream::empty().boxed(),
+        Sealing::Instant => transaction_pool
+            .pool()
+            .validated_pool()
+            .import_notification_stream()
+            .map(move |_| seal_command(false))
+            .boxed(),
+        Sealing::Interval(millis) => futures::stream::unfold((), move |()| async move {
+            futures_timer::Delay::new(Duration::from_millis(millis)).await;
+            Some((seal_command(true), ()))
+        })
+        .boxed(),
+    };
+
+    let inherent_client = client.clone();
+    let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+        block_import: FrontierBlockImport::new(client.clone(), client.clone(), frontier_backend),
+        env: proposer_factory,
+        client: client.clone(),
+        pool: transaction_pool,
+        commands_stream: futures::stream::select(rpc_commands, sealing_commands),
+        select_chain,
+        // the Aura slot digest is required by the runtime.
+        consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+        create_inherent_data_providers: move |_, ()| {
+            let client = inherent_client.clone();
+            async move {
+                let timestamp = SlotTimestampProvider::new_aura(client)
+                    .map_err(|e| format!("Create the timestamp provider failed: {:?}", e))?;
+                Ok(timestamp)
+            }
+        },
+    });
+
+    // the manual-seal authoring task is considered essential, i.e. if it
+    // fails we take down the service with it.
+    task_manager.spawn_essential_handle().spawn_blocking(
+        "manual-seal",
+        Some("block-authoring"),
+        manual_seal,
+    );
+}
@@ -0,0 +1,642 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The trustee utilities signing the withdrawals of the taproot hot address.
+//!
+//! The hot address is a MAST of the aggregated keys of the trustee combinations, a withdrawal
+//! is spent through the leaf `<agg pubkey> OP_CHECKSIG` of the signers, whose schnorr signature
+//! is produced by MuSig2 in two rounds over a signing session file shared by the signers.
+
+mod musig2;
+mod tx;
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use light_bitcoin::{
+    chain::{Transaction, TransactionOutput},
+    mast::{key::PublicKey as MastPublicKey, Mast},
+    serialization::{
+        deserialize, serialize, serialize_with_flags, Reader, SERIALIZE_TRANSACTION_WITNESS,
+    },
+};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sp_core::hashing::sha2_256;
+
+use sherpax_runtime::{BtcAssetId, XGatewayBitcoin};
+
+use crate::cli::{
+    TrusteeCreateTxCmd, TrusteeKeyParams, TrusteeNodeParams, TrusteeSessionParams, TrusteeSignCmd,
+    TrusteeSubcommand, TrusteeVerifyCmd,
+};
+
+/// The result type of the trustee utilities.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// The signing session of a withdrawal tx shared by the signers.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    /// The hex encoded unsigned tx.
+    pub tx: String,
+    /// The withdrawals processed by the tx.
+    pub withdrawal_ids: Vec<u32>,
+    /// The outputs spent by the inputs of the tx.
+    pub prevouts: Vec<Prevout>,
+    /// The hot public keys of the current trustees, in the session order.
+    pub trustees: Vec<String>,
+    /// The threshold of the trustee session.
+    pub threshold: u32,
+    /// The hot public keys of the signers, in the session order.
+    pub signers: Vec<String>,
+    /// The public nonces of each input by signer.
+    pub nonces: BTreeMap<String, Vec<String>>,
+    /// The partial signatures of each input by signer.
+    pub partial_sigs: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prevout {
+    pub value: u64,
+    pub script_pubkey: String,
+}
+
+impl TrusteeSubcommand {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        match self {
+            TrusteeSubcommand::GenerateKey(params) => generate_key(params)?,
+            TrusteeSubcommand::Withdrawals(params) => {
+                let withdrawals = withdrawal_list(params)?;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&withdrawals).map_err(|e| e.to_string())?
+                );
+            }
+            TrusteeSubcommand::CreateTx(cmd) => create_tx(cmd)?,
+            TrusteeSubcommand::Nonce(cmd) => add_nonces(cmd)?,
+            TrusteeSubcommand::Sign(cmd) => add_partial_sigs(cmd)?,
+            TrusteeSubcommand::Combine(params) => {
+                let tx = combine(&read_session(params)?)?;
+                let tx: Vec<u8> = serialize_with_flags(&tx, SERIALIZE_TRANSACTION_WITNESS).into();
+                println!("{}", hex::encode(tx));
+            }
+            TrusteeSubcommand::Verify(cmd) => verify(cmd)?,
+        }
+        Ok(())
+    }
+}
+
+/// BIP-340 tagged hash.
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag = sha2_256(tag.as_bytes());
+    let mut preimage = tag.to_vec();
+    preimage.extend_from_slice(&tag);
+    preimage.extend_from_slice(data);
+    sha2_256(&preimage)
+}
+
+fn generate_key(params: &TrusteeKeyParams) -> Result<()> {
+    if params.key_file.exists() {
+        return Err(format!(
+            "The key file {} already exists",
+            params.key_file.display()
+        ));
+    }
+    let secret = musig2::random_secret();
+    write_secret(
+        &params.key_file,
+        hex::encode(secret.secret_bytes()).as_bytes(),
+    )?;
+    let public = PublicKey::from_secret_key(&Secp256k1::signing_only(), &secret);
+    // `check_trustee_entity` accepts the uncompressed public key
+    println!("0x{}", hex::encode(public.serialize_uncompressed()));
+    Ok(())
+}
+
+fn read_key(params: &TrusteeKeyParams) -> Result<SecretKey> {
+    let key = fs::read_to_string(&params.key_file)
+        .map_err(|e| format!("Failed to read the key file: {}", e))?;
+    let key = decode_hex(key.trim())?;
+    SecretKey::from_slice(&key).map_err(|e| format!("Invalid secret key: {}", e))
+}
+
+fn read_session(params: &TrusteeSessionParams) -> Result<Session> {
+    let session = fs::read_to_string(&params.session)
+        .map_err(|e| format!("Failed to read the session file: {}", e))?;
+    serde_json::from_str(&session).map_err(|e| format!("Invalid session file: {}", e))
+}
+
+fn write_session(params: &TrusteeSessionParams, session: &Session) -> Result<()> {
+    write_json(&params.session, session)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write the secret keys or nonces, which are only accessible by the owner.
+fn write_secret(path: &Path, contents: &[u8]) -> Result<()> {
+    let err = |e: std::io::Error| format!("Failed to write {}: {}", path.display(), e);
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to the created file
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(err)?;
+        }
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(err)
+}
+
+fn write_nonces(path: &Path, nonces: &BTreeMap<String, Vec<String>>) -> Result<()> {
+    let json = serde_json::to_string_pretty(nonces).map_err(|e| e.to_string())?;
+    write_secret(path, json.as_bytes())
+}
+
+fn decode_hex(data: &str) -> Result<Vec<u8>> {
+    hex::decode(data.trim_start_matches("0x")).map_err(|e| format!("Invalid hex: {}", e))
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<PublicKey> {
+    PublicKey::from_slice(&decode_hex(pubkey)?)
+        .map_err(|e| format!("Invalid public key {}: {}", pubkey, e))
+}
+
+/// Call the JSON-RPC method of the sherpax node.
+fn rpc_call(node: &TrusteeNodeParams, method: &str, params: Value) -> Result<Value> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let response: Value = ureq::post(&node.url)
+        .send_json(body)
+        .map_err(|e| format!("Failed to request `{}`: {}", method, e))?
+        .into_json()
+        .map_err(|e| format!("Invalid response of `{}`: {}", method, e))?;
+    match response.get("error") {
+        Some(error) if !error.is_null() => Err(format!("`{}` error: {}", method, error)),
+        _ => Ok(response.get("result").cloned().unwrap_or(Value::Null)),
+    }
+}
+
+/// Return the applying withdrawals by id with their fees.
+fn withdrawal_list(node: &TrusteeNodeParams) -> Result<BTreeMap<u32, Value>> {
+    let list = rpc_call(
+        node,
+        "xgatewaycommon_withdrawalListWithFeeInfo",
+        json!([BtcAssetId::get()]),
+    )?;
+    let list: BTreeMap<String, Value> =
+        serde_json::from_value(list).map_err(|e| format!("Invalid withdrawal list: {}", e))?;
+    list.into_iter()
+        .filter(|(_, withdrawal)| withdrawal[0]["state"] == "Applying")
+        .map(|(id, withdrawal)| {
+            id.parse()
+                .map(|id| (id, withdrawal))
+                .map_err(|_| format!("Invalid withdrawal id {}", id))
+        })
+        .collect()
+}
+
+fn json_u64(value: &Value) -> Result<u64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        value => value.as_u64(),
+    }
+    .ok_or_else(|| format!("Invalid amount {}", value))
+}
+
+fn create_tx(cmd: &TrusteeCreateTxCmd) -> Result<()> {
+    let session_info = rpc_call(
+        &cmd.node,
+        "xgatewaycommon_trusteeSessionInfo",
+        json!(["Bitcoin", -1]),
+    )?;
+    let hot_address = session_info["hotAddress"]["addr"]
+        .as_str()
+        .ok_or("Invalid trustee session info")?;
+    let hot_address = XGatewayBitcoin::verify_btc_address(hot_address.as_bytes())
+        .map_err(|e| format!("Invalid hot address: {:?}", e))?;
+    let threshold = session_info["threshold"]
+        .as_u64()
+        .ok_or("Invalid trustee session info")? as u32;
+
+    let trustees = cmd
+        .trustees
+        .iter()
+        .map(|pubkey| parse_pubkey(pubkey).map(|_| pubkey.clone()))
+        .collect::<Result<Vec<_>>>()?;
+    let mut signers = cmd
+        .signers
+        .iter()
+        .map(|signer| {
+            let pubkey = parse_pubkey(signer)?;
+            trustees
+                .iter()
+                .position(|trustee| parse_pubkey(trustee).ok() == Some(pubkey))
+                .ok_or_else(|| format!("The signer {} is not a trustee", signer))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    signers.sort_unstable();
+    signers.dedup();
+    if (signers.len() as u32) < threshold {
+        return Err(format!(
+            "The signers {} are less than the threshold {}",
+            signers.len(),
+            threshold
+        ));
+    }
+    signers.truncate(threshold as usize);
+
+    let mut withdrawals = withdrawal_list(&cmd.node)?;
+    if !cmd.withdrawal_ids.is_empty() {
+        withdrawals.retain(|id, _| cmd.withdrawal_ids.contains(id));
+        if withdrawals.len() != cmd.withdrawal_ids.len() {
+            return Err("Some of the withdrawals are not applying".into());
+        }
+    }
+    if withdrawals.is_empty() {
+        return Err("No applying withdrawal".into());
+    }
+    let outputs = withdrawals
+        .values()
+        .map(|withdrawal| {
+            let address = withdrawal[0]["addr"].as_str().unwrap_or_default();
+            let address = XGatewayBitcoin::verify_btc_address(address.as_bytes())
+                .map_err(|e| format!("Invalid withdrawal address {}: {:?}", address, e))?;
+            // `check_withdraw_tx` expects the balance minus the withdrawal fee
+            let balance = json_u64(&withdrawal[0]["balance"])?;
+            let fee = json_u64(&withdrawal[1]["fee"])?;
+            let value = balance
+                .checked_sub(fee)
+                .ok_or("The withdrawal balance is less than the fee")?;
+            Ok((address, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let utxos = cmd
+        .utxos
+        .iter()
+        .map(|utxo| tx::parse_utxo(utxo))
+        .collect::<Result<Vec<_>>>()?;
+    let tx = tx::build_withdrawal_tx(&utxos, &outputs, &hot_address, cmd.miner_fee)?;
+    let hot_script = hex::encode(tx::address_script(&hot_address));
+    let tx: Vec<u8> = serialize(&tx).into();
+
+    let session = Session {
+        tx: hex::encode(tx),
+        withdrawal_ids: withdrawals.keys().copied().collect(),
+        prevouts: utxos
+            .iter()
+            .map(|(_, value)| Prevout {
+                value: *value,
+                script_pubkey: hot_script.clone(),
+            })
+            .collect(),
+        trustees,
+        threshold,
+        signers: signers
+            .into_iter()
+            .map(|index| cmd.trustees[index].clone())
+            .collect(),
+        ..Default::default()
+    };
+    write_session(&cmd.session, &session)?;
+    println!("Withdrawal tx: {}", session.tx);
+    Ok(())
+}
+
+/// The unsigned tx, the prevouts, the leaf script and its control block of a session.
+struct SigningContext {
+    tx: Transaction,
+    prevouts: Vec<TransactionOutput>,
+    key_agg: musig2::KeyAgg,
+    leaf_script: Vec<u8>,
+    control_block: Vec<u8>,
+}
+
+impl SigningContext {
+    fn new(session: &Session) -> Result<Self> {
+        let tx_bytes = decode_hex(&session.tx)?;
+        let tx: Transaction = deserialize(Reader::new(&tx_bytes))
+            .map_err(|e| format!("Invalid session tx: {:?}", e))?;
+        let prevouts = session
+            .prevouts
+            .iter()
+            .map(|prevout| {
+                Ok(TransactionOutput {
+                    value: prevout.value,
+                    script_pubkey: decode_hex(&prevout.script_pubkey)?.into(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let trustees = session
+            .trustees
+            .iter()
+            .map(|pubkey| {
+                MastPublicKey::parse_slice(&decode_hex(pubkey)?)
+                    .map_err(|e| format!("Invalid trustee key {}: {:?}", pubkey, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mast = Mast::new(trustees, session.threshold)
+            .map_err(|e| format!("Failed to build the mast: {:?}", e))?;
+
+        // the trustee indexes of the leaves are 1-based
+        let signer_indexes = session
+            .signers
+            .iter()
+            .map(|signer| {
+                session
+                    .trustees
+                    .iter()
+                    .position(|trustee| trustee == signer)
+                    .map(|index| index + 1)
+                    .ok_or_else(|| format!("The signer {} is not a trustee", signer))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let leaf = mast
+            .indexs
+            .iter()
+            .position(|indexes| {
+                let mut indexes = indexes.clone();
+                indexes.sort_unstable();
+                indexes
+                    .iter()
+                    .map(|i| *i as usize)
+                    .eq(signer_indexes.iter().copied())
+            })
+            .ok_or("The signers are not a leaf of the hot address")?;
+        let leaf_pubkey = &mast.pubkeys[leaf];
+
+        let signers = session
+            .signers
+            .iter()
+            .map(|signer| parse_pubkey(signer))
+            .collect::<Result<Vec<_>>>()?;
+        let key_agg = musig2::KeyAgg::new(signers)?;
+        let leaf_x_only = leaf_pubkey.x_coor();
+        if key_agg.x_only() != leaf_x_only {
+            return Err("The aggregated key of the signers mismatches the leaf key".into());
+        }
+        let control_block = mast
+            .generate_merkle_proof(leaf_pubkey)
+            .map_err(|e| format!("Failed to generate the control block: {:?}", e))?;
+
+        Ok(Self {
+            tx,
+            prevouts,
+            key_agg,
+            leaf_script: tx::leaf_script(&leaf_x_only),
+            control_block,
+        })
+    }
+
+    fn sighashes(&self) -> Result<Vec<[u8; 32]>> {
+        (0..self.tx.inputs.len())
+            .map(|index| {
+                tx::script_path_sighash(&self.tx, &self.prevouts, index, &self.leaf_script)
+            })
+            .collect()
+    }
+}
+
+/// The file storing the secret nonces of the key, by the session tx.
+fn nonce_file(key: &TrusteeKeyParams) -> std::path::PathBuf {
+    let mut path = key.key_file.clone().into_os_string();
+    path.push(".nonces.json");
+    path.into()
+}
+
+fn signer_of(session: &Session, secret: &SecretKey) -> Result<String> {
+    let public = PublicKey::from_secret_key(&Secp256k1::signing_only(), secret);
+    session
+        .signers
+        .iter()
+        .find(|signer| parse_pubkey(signer).ok() == Some(public))
+        .cloned()
+        .ok_or_else(|| "The key is not a signer of the session".into())
+}
+
+fn add_nonces(cmd: &TrusteeSignCmd) -> Result<()> {
+    let secret = read_key(&cmd.key)?;
+    let mut session = read_session(&cmd.session)?;
+    let signer = signer_of(&session, &secret)?;
+    let context = SigningContext::new(&session)?;
+
+    let sec_nonces = context
+        .tx
+        .inputs
+        .iter()
+        .map(|_| musig2::SecNonce::generate())
+        .collect::<Vec<_>>();
+    let path = nonce_file(&cmd.key);
+    let mut stored: BTreeMap<String, Vec<String>> = match fs::read_to_string(&path) {
+        Ok(stored) => serde_json::from_str(&stored).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(format!("Failed to read the nonces: {}", e)),
+    };
+    // the replaced nonces might have been used by a partial signature of the session
+    if stored.contains_key(&session.tx) {
+        return Err(format!(
+            "The nonces of the session already exist in {}",
+            path.display()
+        ));
+    }
+    stored.insert(
+        session.tx.clone(),
+        sec_nonces
+            .iter()
+            .map(|nonce| hex::encode(nonce.to_bytes()))
+            .collect(),
+    );
+    write_nonces(&path, &stored)?;
+
+    let pub_nonces = sec_nonces
+        .iter()
+        .map(|nonce| hex::encode(nonce.public()))
+        .collect();
+    session.nonces.insert(signer, pub_nonces);
+    // the partial signatures are invalidated by the new nonces
+    session.partial_sigs.clear();
+    write_session(&cmd.session, &session)
+}
+
+fn aggregated_nonces(session: &Session, inputs: usize) -> Result<Vec<musig2::AggNonce>> {
+    (0..inputs)
+        .map(|index| {
+            let pub_nonces = session
+                .signers
+                .iter()
+                .map(|signer| {
+                    let nonces = session
+                        .nonces
+                        .get(signer)
+                        .ok_or_else(|| format!("Missing the nonces of {}", signer))?;
+                    nonces
+                        .get(index)
+                        .ok_or_else(|| format!("Missing the nonce of input {}", index))
+                        .and_then(|nonce| decode_hex(nonce))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            musig2::AggNonce::new(&pub_nonces)
+        })
+        .collect()
+}
+
+fn add_partial_sigs(cmd: &TrusteeSignCmd) -> Result<()> {
+    let secret = read_key(&cmd.key)?;
+    let mut session = read_session(&cmd.session)?;
+    let signer = signer_of(&session, &secret)?;
+    let context = SigningContext::new(&session)?;
+    let sighashes = context.sighashes()?;
+    let agg_nonces = aggregated_nonces(&session, sighashes.len())?;
+
+    // the secret nonces are removed before signing, so that they are never reused
+    let path = nonce_file(&cmd.key);
+    let mut stored: BTreeMap<String, Vec<String>> = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read the nonces: {}", e))
+        .and_then(|stored| serde_json::from_str(&stored).map_err(|e| e.to_string()))?;
+    let sec_nonces = stored
+        .remove(&session.tx)
+        .ok_or("No nonce of the session, run `nonce` first")?;
+    write_nonces(&path, &stored)?;
+
+    let partial_sigs = sec_nonces
+        .iter()
+        .zip(agg_nonces.iter().zip(&sighashes))
+        .map(|(sec_nonce, (agg_nonce, sighash))| {
+            let sec_nonce = musig2::SecNonce::from_bytes(&decode_hex(sec_nonce)?)?;
+            musig2::partial_sign(&secret, sec_nonce, agg_nonce, &context.key_agg, sighash)
+                .map(hex::encode)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    session.partial_sigs.insert(signer, partial_sigs);
+    write_session(&cmd.session, &session)
+}
+
+/// Combine the partial signatures into the witnesses of the tx.
+fn combine(session: &Session) -> Result<Transaction> {
+    let context = SigningContext::new(session)?;
+    let sighashes = context.sighashes()?;
+    let agg_nonces = aggregated_nonces(session, sighashes.len())?;
+
+    let mut tx = context.tx.clone();
+    for (index, (agg_nonce, sighash)) in agg_nonces.iter().zip(&sighashes).enumerate() {
+        let partial_sigs = session
+            .signers
+            .iter()
+            .map(|signer| {
+                let sig = session
+                    .partial_sigs
+                    .get(signer)
+                    .and_then(|sigs| sigs.get(index))
+                    .ok_or_else(|| format!("Missing the partial signature of {}", signer))?;
+                let sig = decode_hex(sig)?;
+                <[u8; 32]>::try_from(sig.as_slice())
+                    .map_err(|_| format!("Invalid partial signature of {}", signer))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let sig = musig2::aggregate(&partial_sigs, agg_nonce, &context.key_agg, sighash)?;
+        tx.inputs[index].script_witness = vec![
+            sig.to_vec().into(),
+            context.leaf_script.clone().into(),
+            context.control_block.clone().into(),
+        ];
+    }
+    Ok(tx)
+}
+
+fn verify(cmd: &TrusteeVerifyCmd) -> Result<()> {
+    let session = read_session(&cmd.session)?;
+    let tx = combine(&session)?;
+    let tx: Vec<u8> = serialize_with_flags(&tx, SERIALIZE_TRANSACTION_WITNESS).into();
+    let valid = rpc_call(
+        &cmd.node,
+        "xgatewaybitcoin_verifyTxValid",
+        json!([hex::encode(tx), session.withdrawal_ids, false]),
+    )?;
+    match valid.as_bool() {
+        Some(true) => {
+            println!("The withdrawal tx is valid");
+            Ok(())
+        }
+        _ => Err(format!("The withdrawal tx is invalid: {}", valid)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use light_bitcoin::chain::{OutPoint, TransactionInput};
+
+    #[test]
+    fn test_key_agg_matches_mast_leaf() {
+        let secp = Secp256k1::signing_only();
+        let trustees = (0..3)
+            .map(|_| {
+                let pubkey = PublicKey::from_secret_key(&secp, &musig2::random_secret());
+                hex::encode(pubkey.serialize())
+            })
+            .collect::<Vec<_>>();
+        let tx = Transaction {
+            version: 2,
+            inputs: vec![TransactionInput {
+                previous_output: OutPoint {
+                    hash: Default::default(),
+                    index: 0,
+                },
+                script_sig: Default::default(),
+                sequence: 0xffff_ffff,
+                script_witness: vec![],
+            }],
+            outputs: vec![],
+            lock_time: 0,
+        };
+        let tx: Vec<u8> = serialize(&tx).into();
+        let mast_keys = trustees
+            .iter()
+            .map(|pubkey| MastPublicKey::parse_slice(&decode_hex(pubkey).unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        let mast = Mast::new(mast_keys, 2).unwrap();
+
+        for signers in [[0, 1], [0, 2], [1, 2]] {
+            let session = Session {
+                tx: hex::encode(&tx),
+                trustees: trustees.clone(),
+                threshold: 2,
+                signers: signers.iter().map(|i| trustees[*i].clone()).collect(),
+                ..Default::default()
+            };
+            let context = SigningContext::new(&session).unwrap();
+            let x_only = context.key_agg.x_only();
+            assert!(mast.pubkeys.iter().any(|leaf| leaf.x_coor() == x_only));
+            assert_eq!(context.leaf_script, tx::leaf_script(&x_only));
+        }
+
+        // the signers must be a leaf of the trustees in the session order
+        let session = Session {
+            tx: hex::encode(&tx),
+            trustees: trustees.clone(),
+            threshold: 2,
+            signers: vec![trustees[1].clone(), trustees[0].clone()],
+            ..Default::default()
+        };
+        assert!(SigningContext::new(&session).is_err());
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! MuSig2 (BIP-327) two-round multi-signature producing a BIP-340 schnorr signature for an
+//! aggregated taproot leaf key.
+
+use rand::{rngs::OsRng, RngCore};
+use secp256k1::{schnorr, Message, PublicKey, Secp256k1, SecretKey, XOnlyPublicKey};
+
+use super::{tagged_hash, Result};
+
+/// The aggregated key and the coefficients of the signers.
+pub struct KeyAgg {
+    pubkeys: Vec<PublicKey>,
+    coefficients: Vec<[u8; 32]>,
+    agg_pubkey: PublicKey,
+}
+
+impl KeyAgg {
+    /// Aggregate the keys, the order of `pubkeys` matters.
+    pub fn new(pubkeys: Vec<PublicKey>) -> Result<Self> {
+        let secp = Secp256k1::verification_only();
+        let serialized = pubkeys
+            .iter()
+            .flat_map(|pk| pk.serialize())
+            .collect::<Vec<_>>();
+        let list_hash = tagged_hash("KeyAgg list", &serialized);
+        let second = pubkeys.iter().find(|pk| **pk != pubkeys[0]).copied();
+
+        let mut coefficients = Vec::with_capacity(pubkeys.len());
+        let mut points = Vec::with_capacity(pubkeys.len());
+        for pubkey in &pubkeys {
+            let coefficient = if Some(*pubkey) == second {
+                one()
+            } else {
+                let mut data = list_hash.to_vec();
+                data.extend_from_slice(&pubkey.serialize());
+                tagged_hash("KeyAgg coefficient", &data)
+            };
+            let mut point = *pubkey;
+            point
+                .mul_assign(&secp, &coefficient)
+                .map_err(|e| format!("Invalid key coefficient: {}", e))?;
+            coefficients.push(coefficient);
+            points.push(point);
+        }
+        let agg_pubkey = combine(&points)?;
+        Ok(Self {
+            pubkeys,
+            coefficients,
+            agg_pubkey,
+        })
+    }
+
+    /// The x-only aggregated key.
+    pub fn x_only(&self) -> [u8; 32] {
+        x_only(&self.agg_pubkey)
+    }
+
+    fn coefficient(&self, pubkey: &PublicKey) -> Result<[u8; 32]> {
+        self.pubkeys
+            .iter()
+            .position(|pk| pk == pubkey)
+            .map(|index| self.coefficients[index])
+            .ok_or_else(|| "The key is not a signer".into())
+    }
+}
+
+/// The secret nonce pair of a signer, which must never be reused.
+pub struct SecNonce(SecretKey, SecretKey);
+
+impl SecNonce {
+    /// Generate a fresh random nonce pair.
+    pub fn generate() -> Self {
+        Self(random_secret(), random_secret())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 64 {
+            return Err("Invalid secret nonce".into());
+        }
+        let k1 = SecretKey::from_slice(&bytes[..32]).map_err(|e| e.to_string())?;
+        let k2 = SecretKey::from_slice(&bytes[32..]).map_err(|e| e.to_string())?;
+        Ok(Self(k1, k2))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.0.secret_bytes().to_vec();
+        bytes.extend_from_slice(&self.1.secret_bytes());
+        bytes
+    }
+
+    /// The public nonce `R1 || R2` of 66 bytes.
+    pub fn public(&self) -> Vec<u8> {
+        let secp = Secp256k1::signing_only();
+        let mut bytes = PublicKey::from_secret_key(&secp, &self.0)
+            .serialize()
+            .to_vec();
+        bytes.extend_from_slice(&PublicKey::from_secret_key(&secp, &self.1).serialize());
+        bytes
+    }
+}
+
+/// The aggregated public nonces of all signers.
+pub struct AggNonce(PublicKey, PublicKey);
+
+impl AggNonce {
+    pub fn new(pubnonces: &[Vec<u8>]) -> Result<Self> {
+        let mut r1 = Vec::with_capacity(pubnonces.len());
+        let mut r2 = Vec::with_capacity(pubnonces.len());
+        for pubnonce in pubnonces {
+            if pubnonce.len() != 66 {
+                return Err("Invalid public nonce".into());
+            }
+            r1.push(PublicKey::from_slice(&pubnonce[..33]).map_err(|e| e.to_string())?);
+            r2.push(PublicKey::from_slice(&pubnonce[33..]).map_err(|e| e.to_string())?);
+        }
+        Ok(Self(combine(&r1)?, combine(&r2)?))
+    }
+
+    /// Return the final nonce `R` and the nonce coefficient `b` for signing the message.
+    fn final_nonce(&self, key_agg: &KeyAgg, msg: &[u8; 32]) -> Result<(PublicKey, [u8; 32])> {
+        let secp = Secp256k1::verification_only();
+        let mut data = self.0.serialize().to_vec();
+        data.extend_from_slice(&self.1.serialize());
+        data.extend_from_slice(&key_agg.x_only());
+        data.extend_from_slice(msg);
+        let b = tagged_hash("MuSig/noncecoef", &data);
+
+        let mut r2 = self.1;
+        r2.mul_assign(&secp, &b)
+            .map_err(|e| format!("Invalid nonce coefficient: {}", e))?;
+        Ok((combine(&[self.0, r2])?, b))
+    }
+}
+
+/// Produce the partial signature of `secret` over `msg`, consuming the secret nonce.
+pub fn partial_sign(
+    secret: &SecretKey,
+    sec_nonce: SecNonce,
+    agg_nonce: &AggNonce,
+    key_agg: &KeyAgg,
+    msg: &[u8; 32],
+) -> Result<[u8; 32]> {
+    let secp = Secp256k1::signing_only();
+    let pubkey = PublicKey::from_secret_key(&secp, secret);
+    let coefficient = key_agg.coefficient(&pubkey)?;
+    let (final_nonce, b) = agg_nonce.final_nonce(key_agg, msg)?;
+    let e = challenge(&final_nonce, key_agg, msg);
+
+    // k = k1 + b * k2, negated if R has an odd y
+    let SecNonce(mut k, mut k2) = sec_nonce;
+    k2.mul_assign(&b).map_err(|e| e.to_string())?;
+    k.add_assign(&k2.secret_bytes())
+        .map_err(|e| e.to_string())?;
+    if has_odd_y(&final_nonce) {
+        k.negate_assign();
+    }
+
+    // d = g * sk, negated if Q has an odd y
+    let mut d = *secret;
+    if has_odd_y(&key_agg.agg_pubkey) {
+        d.negate_assign();
+    }
+
+    // s = k + e * a * d
+    d.mul_assign(&coefficient).map_err(|e| e.to_string())?;
+    d.mul_assign(&e).map_err(|e| e.to_string())?;
+    k.add_assign(&d.secret_bytes()).map_err(|e| e.to_string())?;
+    Ok(k.secret_bytes())
+}
+
+/// Aggregate the partial signatures into a BIP-340 signature and verify it.
+pub fn aggregate(
+    partial_sigs: &[[u8; 32]],
+    agg_nonce: &AggNonce,
+    key_agg: &KeyAgg,
+    msg: &[u8; 32],
+) -> Result<[u8; 64]> {
+    let (final_nonce, _) = agg_nonce.final_nonce(key_agg, msg)?;
+    let (first, rest) = partial_sigs.split_first().ok_or("No partial signature")?;
+    let mut s = SecretKey::from_slice(first).map_err(|e| e.to_string())?;
+    for partial_sig in rest {
+        s.add_assign(partial_sig).map_err(|e| e.to_string())?;
+    }
+
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(&x_only(&final_nonce));
+    sig[32..].copy_from_slice(&s.secret_bytes());
+    verify(&sig, &key_agg.x_only(), msg)?;
+    Ok(sig)
+}
+
+/// Verify the BIP-340 signature.
+pub fn verify(sig: &[u8; 64], pubkey: &[u8; 32], msg: &[u8; 32]) -> Result<()> {
+    let secp = Secp256k1::verification_only();
+    let sig = schnorr::Signature::from_slice(sig).map_err(|e| e.to_string())?;
+    let pubkey = XOnlyPublicKey::from_slice(pubkey).map_err(|e| e.to_string())?;
+    let msg = Message::from_slice(msg).map_err(|e| e.to_string())?;
+    secp.verify_schnorr(&sig, &msg, &pubkey)
+        .map_err(|_| "Invalid schnorr signature".into())
+}
+
+/// Generate a random secret key.
+pub fn random_secret() -> SecretKey {
+    loop {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        if let Ok(secret) = SecretKey::from_slice(&bytes) {
+            return secret;
+        }
+    }
+}
+
+fn challenge(final_nonce: &PublicKey, key_agg: &KeyAgg, msg: &[u8; 32]) -> [u8; 32] {
+    let mut data = x_only(final_nonce).to_vec();
+    data.extend_from_slice(&key_agg.x_only());
+    data.extend_from_slice(msg);
+    tagged_hash("BIP0340/challenge", &data)
+}
+
+fn combine(points: &[PublicKey]) -> Result<PublicKey> {
+    let (first, rest) = points.split_first().ok_or("No point to combine")?;
+    rest.iter().try_fold(*first, |acc, point| {
+        acc.combine(point).map_err(|e| e.to_string())
+    })
+}
+
+fn has_odd_y(point: &PublicKey) -> bool {
+    point.serialize()[0] == 0x03
+}
+
+fn x_only(point: &PublicKey) -> [u8; 32] {
+    let mut x = [0u8; 32];
+    x.copy_from_slice(&point.serialize()[1..]);
+    x
+}
+
+fn one() -> [u8; 32] {
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    one
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_musig2_sign_and_aggregate() {
+        let secp = Secp256k1::signing_only();
+        let secrets = (0..3).map(|_| random_secret()).collect::<Vec<_>>();
+        let pubkeys = secrets
+            .iter()
+            .map(|secret| PublicKey::from_secret_key(&secp, secret))
+            .collect::<Vec<_>>();
+        let key_agg = KeyAgg::new(pubkeys).unwrap();
+        let msg = tagged_hash("test", b"withdrawal");
+
+        let sec_nonces = secrets
+            .iter()
+            .map(|_| SecNonce::generate())
+            .collect::<Vec<_>>();
+        let pubnonces = sec_nonces.iter().map(SecNonce::public).collect::<Vec<_>>();
+        let agg_nonce = AggNonce::new(&pubnonces).unwrap();
+
+        let partial_sigs = secrets
+            .iter()
+            .zip(sec_nonces)
+            .map(|(secret, sec_nonce)| {
+                partial_sign(secret, sec_nonce, &agg_nonce, &key_agg, &msg).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(aggregate(&partial_sigs, &agg_nonce, &key_agg, &msg).is_ok());
+
+        // a missing partial signature doesn't make a valid signature
+        assert!(aggregate(&partial_sigs[..2], &agg_nonce, &key_agg, &msg).is_err());
+    }
+
+    fn pubkey(hex: &str) -> PublicKey {
+        PublicKey::from_slice(&hex::decode(hex).unwrap()).unwrap()
+    }
+
+    fn bytes32(hex: &str) -> [u8; 32] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_key_agg_vectors() {
+        // `key_agg_vectors.json` of BIP-327
+        let pubkeys = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        ]
+        .map(pubkey);
+        let cases: [(&[usize], &str); 4] = [
+            (
+                &[0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                &[2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                &[0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+        for (indices, expected) in cases {
+            let key_agg = KeyAgg::new(indices.iter().map(|i| pubkeys[*i]).collect()).unwrap();
+            assert_eq!(key_agg.x_only(), bytes32(expected));
+        }
+    }
+
+    #[test]
+    fn test_nonce_agg_vector() {
+        // the first valid case of `nonce_agg_vectors.json` of BIP-327
+        let pubnonces = [
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E666\
+             03BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A6\
+             0248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+        ]
+        .map(|hex| hex::decode(hex).unwrap());
+        let agg_nonce = AggNonce::new(&pubnonces).unwrap();
+        let mut aggregated = agg_nonce.0.serialize().to_vec();
+        aggregated.extend_from_slice(&agg_nonce.1.serialize());
+        assert_eq!(
+            hex::encode_upper(aggregated),
+            "035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B\
+             024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8"
+        );
+    }
+
+    #[test]
+    fn test_partial_sign_vectors() {
+        // `sign_verify_vectors.json` of BIP-327, except the cases of an infinite nonce
+        let secret = SecretKey::from_slice(&bytes32(
+            "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671",
+        ))
+        .unwrap();
+        let sec_nonce = || {
+            SecNonce::from_bytes(
+                &hex::decode(
+                    "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61\
+                     FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7",
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        let pubkeys = [
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+        ]
+        .map(pubkey);
+        let pubnonces = [
+            "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA\
+             0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798\
+             0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE93\
+             03E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        ]
+        .map(|hex| hex::decode(hex).unwrap());
+        assert_eq!(sec_nonce().public(), pubnonces[0]);
+        let msg = bytes32("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+
+        let cases: [(&[usize], &str); 3] = [
+            (
+                &[0, 1, 2],
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                &[1, 0, 2],
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                &[1, 2, 0],
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+        ];
+        for (indices, expected) in cases {
+            let key_agg = KeyAgg::new(indices.iter().map(|i| pubkeys[*i]).collect()).unwrap();
+            let pubnonces = indices
+                .iter()
+                .map(|i| pubnonces[*i].clone())
+                .collect::<Vec<_>>();
+            let agg_nonce = AggNonce::new(&pubnonces).unwrap();
+            let partial_sig =
+                partial_sign(&secret, sec_nonce(), &agg_nonce, &key_agg, &msg).unwrap();
+            assert_eq!(partial_sig, bytes32(expected));
+        }
+    }
+
+    #[test]
+    fn test_sec_nonce_codec() {
+        let sec_nonce = SecNonce::generate();
+        let decoded = SecNonce::from_bytes(&sec_nonce.to_bytes()).unwrap();
+        assert_eq!(decoded.public(), sec_nonce.public());
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The withdrawal transaction of the taproot hot address and its BIP-341 script path sighash.
+
+use light_bitcoin::{
+    chain::{OutPoint, Transaction, TransactionInput, TransactionOutput},
+    keys::{Address, AddressTypes, Type},
+    primitives::Bytes,
+    serialization::serialize,
+};
+use sp_core::hashing::sha2_256;
+
+use sherpax_runtime::h256_rev;
+
+use super::{tagged_hash, Result};
+
+/// The leaf version of tapscript.
+const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+const OP_CHECKSIG: u8 = 0xac;
+
+/// Parse the UTXO in the format of `txid:vout:value`.
+pub fn parse_utxo(utxo: &str) -> Result<(OutPoint, u64)> {
+    let parts = utxo.split(':').collect::<Vec<_>>();
+    if parts.len() != 3 || parts[0].len() != 64 || hex::decode(parts[0]).is_err() {
+        return Err(format!("Invalid utxo `{}`, expect `txid:vout:value`", utxo));
+    }
+    let index = parts[1]
+        .parse()
+        .map_err(|_| format!("Invalid vout of utxo `{}`", utxo))?;
+    let value = parts[2]
+        .parse()
+        .map_err(|_| format!("Invalid value of utxo `{}`", utxo))?;
+    let outpoint = OutPoint {
+        hash: h256_rev(parts[0]),
+        index,
+    };
+    Ok((outpoint, value))
+}
+
+/// Return the `scriptPubKey` paying to the address.
+pub fn address_script(address: &Address) -> Vec<u8> {
+    match (address.hash, address.kind) {
+        (AddressTypes::Legacy(hash), Type::P2SH) => {
+            let mut script = vec![0xa9, 0x14];
+            script.extend_from_slice(hash.as_bytes());
+            script.push(0x87);
+            script
+        }
+        (AddressTypes::Legacy(hash), _) => {
+            let mut script = vec![0x76, 0xa9, 0x14];
+            script.extend_from_slice(hash.as_bytes());
+            script.extend_from_slice(&[0x88, OP_CHECKSIG]);
+            script
+        }
+        (AddressTypes::WitnessV0KeyHash(hash), _) => witness_program(0x00, hash.as_bytes()),
+        (AddressTypes::WitnessV0ScriptHash(hash), _) => witness_program(0x00, hash.as_bytes()),
+        (AddressTypes::WitnessV1Taproot(hash), _) => witness_program(0x51, hash.as_bytes()),
+    }
+}
+
+fn witness_program(version: u8, program: &[u8]) -> Vec<u8> {
+    let mut script = vec![version, program.len() as u8];
+    script.extend_from_slice(program);
+    script
+}
+
+/// Build the unsigned withdrawal tx spending the `utxos` of the hot address, the rest of the
+/// inputs minus the miner fee is changed to the hot address.
+pub fn build_withdrawal_tx(
+    utxos: &[(OutPoint, u64)],
+    withdrawals: &[(Address, u64)],
+    hot_address: &Address,
+    miner_fee: u64,
+) -> Result<Transaction> {
+    let input_value = utxos
+        .iter()
+        .try_fold(0u64, |acc, (_, value)| acc.checked_add(*value))
+        .ok_or("Overflow of the input value")?;
+    let output_value = withdrawals
+        .iter()
+        .try_fold(miner_fee, |acc, (_, value)| acc.checked_add(*value))
+        .ok_or("Overflow of the output value")?;
+    let change = input_value.checked_sub(output_value).ok_or_else(|| {
+        format!(
+            "Insufficient inputs, input value: {}, withdrawal value with the miner fee: {}",
+            input_value, output_value
+        )
+    })?;
+
+    let inputs = utxos
+        .iter()
+        .map(|(outpoint, _)| TransactionInput {
+            previous_output: outpoint.clone(),
+            script_sig: Bytes::new(),
+            sequence: 0xffff_ffff,
+            script_witness: vec![],
+        })
+        .collect();
+    let mut outputs = withdrawals
+        .iter()
+        .map(|(address, value)| TransactionOutput {
+            value: *value,
+            script_pubkey: address_script(address).into(),
+        })
+        .collect::<Vec<_>>();
+    if change > 0 {
+        outputs.push(TransactionOutput {
+            value: change,
+            script_pubkey: address_script(hot_address).into(),
+        });
+    }
+    Ok(Transaction {
+        version: 2,
+        inputs,
+        outputs,
+        lock_time: 0,
+    })
+}
+
+/// The tapscript `<x-only pubkey> OP_CHECKSIG` of an aggregated key, as in `AggPubkeyInfo`.
+pub fn leaf_script(x_only: &[u8; 32]) -> Vec<u8> {
+    let mut script = vec![0x20];
+    script.extend_from_slice(x_only);
+    script.push(OP_CHECKSIG);
+    script
+}
+
+/// Return the BIP-341 sighash (`SIGHASH_DEFAULT`) of spending the input with the leaf script.
+pub fn script_path_sighash(
+    tx: &Transaction,
+    prevouts: &[TransactionOutput],
+    input_index: usize,
+    leaf_script: &[u8],
+) -> Result<[u8; 32]> {
+    taproot_sighash(tx, prevouts, input_index, Some(leaf_script))
+}
+
+/// Return the BIP-341 sighash (`SIGHASH_DEFAULT`) of the key path spending, or of the script
+/// path spending with the leaf script.
+fn taproot_sighash(
+    tx: &Transaction,
+    prevouts: &[TransactionOutput],
+    input_index: usize,
+    leaf_script: Option<&[u8]>,
+) -> Result<[u8; 32]> {
+    if prevouts.len() != tx.inputs.len() || input_index >= tx.inputs.len() {
+        return Err("The prevouts mismatch the inputs".into());
+    }
+
+    let mut outpoints = vec![];
+    let mut sequences = vec![];
+    for input in &tx.inputs {
+        outpoints.extend_from_slice(&serialize(&input.previous_output));
+        sequences.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+    let mut amounts = vec![];
+    let mut script_pubkeys = vec![];
+    for prevout in prevouts {
+        amounts.extend_from_slice(&prevout.value.to_le_bytes());
+        push_with_compact_size(&mut script_pubkeys, &prevout.script_pubkey);
+    }
+    let outputs = tx
+        .outputs
+        .iter()
+        .flat_map(|output| serialize(output).to_vec())
+        .collect::<Vec<_>>();
+
+    // epoch and hash type
+    let mut data = vec![0x00, 0x00];
+    data.extend_from_slice(&tx.version.to_le_bytes());
+    data.extend_from_slice(&tx.lock_time.to_le_bytes());
+    data.extend_from_slice(&sha2_256(&outpoints));
+    data.extend_from_slice(&sha2_256(&amounts));
+    data.extend_from_slice(&sha2_256(&script_pubkeys));
+    data.extend_from_slice(&sha2_256(&sequences));
+    data.extend_from_slice(&sha2_256(&outputs));
+    // spend type without annex
+    data.push(if leaf_script.is_some() { 0x02 } else { 0x00 });
+    data.extend_from_slice(&(input_index as u32).to_le_bytes());
+    if let Some(leaf_script) = leaf_script {
+        data.extend_from_slice(&tap_leaf_hash(leaf_script));
+        // key version and no OP_CODESEPARATOR
+        data.push(0x00);
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+    }
+    Ok(tagged_hash("TapSighash", &data))
+}
+
+fn tap_leaf_hash(leaf_script: &[u8]) -> [u8; 32] {
+    let mut data = vec![TAPSCRIPT_LEAF_VERSION];
+    push_with_compact_size(&mut data, leaf_script);
+    tagged_hash("TapLeaf", &data)
+}
+
+fn push_with_compact_size(buf: &mut Vec<u8>, data: &[u8]) {
+    match data.len() {
+        len @ 0..=0xfc => buf.push(len as u8),
+        len @ 0xfd..=0xffff => {
+            buf.push(0xfd);
+            buf.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        len => {
+            buf.push(0xfe);
+            buf.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+    }
+    buf.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use light_bitcoin::serialization::{deserialize, Reader};
+
+    /// The unsigned tx and the spent outputs of the `keyPathSpending` test vector of BIP-341.
+    fn bip341_vector() -> (Transaction, Vec<TransactionOutput>) {
+        let tx = hex::decode(
+            "02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c0100000000\
+             00000000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000ff\
+             fffffff8e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000ffff\
+             fffff0689180aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffff\
+             ffaa5202bdf6d8ccd2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff\
+             956149bdc66faa968eb2be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e6\
+             64b9773b88c09c32cb70a2a3e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa\
+             6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb\
+             6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af10100000000ffffffff0200ca9a\
+             3b000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac807840cb0000000020ac9a\
+             87f5594be208f8532db38cff670c450ed2fea8fcdefcc9a663f78bab962b0065cd1d",
+        )
+        .unwrap();
+        let tx = deserialize(Reader::new(&tx)).unwrap();
+        let prevouts = [
+            (
+                420000000,
+                "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+            ),
+            (
+                462000000,
+                "5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            ),
+            (
+                294000000,
+                "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+            ),
+            (
+                504000000,
+                "5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+            ),
+            (
+                630000000,
+                "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+            ),
+            (378000000, "00147dd65592d0ab2fe0d0257d571abf032cd9db93dc"),
+            (
+                672000000,
+                "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+            ),
+            (
+                546000000,
+                "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+            ),
+            (
+                588000000,
+                "512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+            ),
+        ]
+        .iter()
+        .map(|(value, script)| TransactionOutput {
+            value: *value,
+            script_pubkey: hex::decode(script).unwrap().into(),
+        })
+        .collect();
+        (tx, prevouts)
+    }
+
+    #[test]
+    fn test_key_path_sighash_vector() {
+        let (tx, prevouts) = bip341_vector();
+        // the input #4 is signed with `SIGHASH_DEFAULT`
+        assert_eq!(
+            hex::encode(taproot_sighash(&tx, &prevouts, 4, None).unwrap()),
+            "4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef"
+        );
+        assert!(taproot_sighash(&tx, &prevouts[1..], 4, None).is_err());
+        assert!(taproot_sighash(&tx, &prevouts, 9, None).is_err());
+    }
+
+    #[test]
+    fn test_tap_leaf_hash_vector() {
+        // the single leaf of the `scriptPubKey` test vector of BIP-341
+        let x_only =
+            hex::decode("d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8")
+                .unwrap();
+        let script = leaf_script(&x_only.try_into().unwrap());
+        assert_eq!(
+            hex::encode(tap_leaf_hash(&script)),
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"
+        );
+    }
+
+    #[test]
+    fn test_script_path_sighash() {
+        let (tx, prevouts) = bip341_vector();
+        let x_only =
+            hex::decode("d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8")
+                .unwrap();
+        let script = leaf_script(&x_only.try_into().unwrap());
+        // the sighashes extend the messages of the vector with the leaf hash above
+        assert_eq!(
+            hex::encode(script_path_sighash(&tx, &prevouts, 0, &script).unwrap()),
+            "92a27cc807a9aae06bd66cf8405f3a12483ec32d8615f32c448f46a0653fb396"
+        );
+        assert_eq!(
+            hex::encode(script_path_sighash(&tx, &prevouts, 4, &script).unwrap()),
+            "a2889022d272e9735136530b46f73635c2f596b0b44faa89d2f6d685981dedcd"
+        );
+    }
+}
@@ -5,7 +5,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -24,7 +24,19 @@ const CHAINS: [Chain; 5] = [
 ];
 
 /// The blockchain types.
-#[derive(PartialEq, Eq, Ord, PartialOrd, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Chain {
     /// ChainX
@@ -55,7 +67,7 @@ impl Chain {
 /// Trait for doing some stuff on the registration/deregistration of a foreign asset.
 pub trait RegistrarHandler {
     /// Called when a new asset is added or a deregistered asset is recovered.
-    fn on_register(_asset_id: &AssetId, _has_mining_rights: bool) -> DispatchResult {
+    fn on_register(_asset_id: &AssetId) -> DispatchResult {
         Ok(())
     }
 
@@ -67,8 +79,8 @@ pub trait RegistrarHandler {
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 impl RegistrarHandler for Tuple {
-    fn on_register(asset_id: &AssetId, has_mining_rights: bool) -> DispatchResult {
-        for_tuples!( #( Tuple::on_register(asset_id, has_mining_rights)?; )* );
+    fn on_register(asset_id: &AssetId) -> DispatchResult {
+        for_tuples!( #( Tuple::on_register(asset_id)?; )* );
         Ok(())
     }
 
@@ -0,0 +1,143 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Primitives of the EVM tracing.
+//!
+//! The runtime compiled with the `evm-tracing` feature replays the extrinsics of a block
+//! by the [`DebugRuntimeApi`], the EVM events of the traced ethereum transactions are
+//! forwarded to the node by the [`evm_tracing_ext`] host functions, where they are
+//! collected by the [`Listener`] registered with [`using`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "evm-tracing")]
+pub mod tracer;
+
+use codec::{Decode, Encode};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime_interface::{pass_by::PassByCodec, runtime_interface};
+use sp_std::vec::Vec;
+
+/// The type of a call frame.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum CallType {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+}
+
+/// An executed opcode of the traced transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Step {
+    /// The program counter.
+    pub pc: u64,
+    /// The opcode.
+    pub opcode: u8,
+    /// The depth of the call frame, starting from 1.
+    pub depth: u32,
+    /// The remaining gas before the opcode.
+    pub gas: u64,
+    /// The gas cost of the opcode.
+    pub gas_cost: u64,
+    /// The stack before the opcode, if enabled by the [`StepFilter`].
+    pub stack: Option<Vec<H256>>,
+    /// The memory before the opcode, if enabled by the [`StepFilter`].
+    pub memory: Option<Vec<u8>>,
+    /// The storage slot read or written by the opcode.
+    pub storage: Option<(H256, H256)>,
+}
+
+/// The events of the EVM tracing.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum Event {
+    /// The ethereum transaction of the following events.
+    Transaction(H256),
+    /// Enter a call frame.
+    Enter {
+        call_type: CallType,
+        from: H160,
+        to: H160,
+        value: U256,
+        gas: u64,
+        input: Vec<u8>,
+    },
+    /// Exit the current call frame, `error` is `None` if the frame succeeded.
+    Exit {
+        gas_used: u64,
+        output: Vec<u8>,
+        error: Option<Vec<u8>>,
+    },
+    /// The contract `address` self destructs.
+    SelfDestruct {
+        address: H160,
+        refund_address: H160,
+        balance: U256,
+    },
+    /// An executed opcode, only sent if the steps are enabled by the [`StepFilter`].
+    Step(Step),
+}
+
+/// The opcode steps wanted by the listener.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, PassByCodec, RuntimeDebug)]
+pub struct StepFilter {
+    /// Send the [`Event::Step`] events.
+    pub enabled: bool,
+    /// Include the stack in the steps.
+    pub stack: bool,
+    /// Include the memory in the steps.
+    pub memory: bool,
+}
+
+/// The listener of the EVM tracing events in the node.
+#[cfg(feature = "std")]
+pub trait Listener {
+    /// Handle the event sent by the runtime.
+    fn event(&mut self, event: Event);
+
+    /// The opcode steps wanted by the listener.
+    fn step_filter(&self) -> StepFilter;
+}
+
+#[cfg(feature = "std")]
+environmental::environmental!(listener: dyn Listener + 'static);
+
+/// Execute `f` with the `listener` receiving the EVM tracing events.
+#[cfg(feature = "std")]
+pub fn using<R, F: FnOnce() -> R>(listener: &mut (dyn Listener + 'static), f: F) -> R {
+    listener::using(listener, f)
+}
+
+/// The host functions forwarding the EVM tracing events from the runtime to the node.
+#[runtime_interface]
+pub trait EvmTracingExt {
+    /// Send the SCALE encoded [`Event`] to the listener.
+    fn event(event: Vec<u8>) {
+        if let Ok(event) = Event::decode(&mut &event[..]) {
+            listener::with(|listener| listener.event(event));
+        }
+    }
+
+    /// Return the opcode steps wanted by the listener.
+    fn step_filter() -> StepFilter {
+        listener::with(|listener| listener.step_filter()).unwrap_or_default()
+    }
+}
+
+sp_api::decl_runtime_apis! {
+    /// The api replaying the extrinsics of a block with the ethereum transactions traced,
+    /// which fails unless the runtime is compiled with the `evm-tracing` feature.
+    pub trait DebugRuntimeApi {
+        /// Replay the `extrinsics` until the ethereum transaction `transaction_hash`,
+        /// which is traced.
+        fn trace_transaction(
+            extrinsics: Vec<Block::Extrinsic>,
+            transaction_hash: H256,
+        ) -> Result<(), DispatchError>;
+
+        /// Replay the `extrinsics` with all the ethereum transactions traced.
+        fn trace_block(extrinsics: Vec<Block::Extrinsic>) -> Result<(), DispatchError>;
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The tracer of the tracing runtime, which converts the events of the `evm` crates into
+//! the [`Event`]s and sends them to the node.
+
+extern crate alloc;
+
+use alloc::{format, rc::Rc};
+use core::cell::RefCell;
+
+use codec::Encode;
+use evm::{
+    tracing::{self as evm_tracing, Event as EvmEvent},
+    CreateScheme, ExitReason,
+};
+use evm_gasometer::tracing::{self as gasometer_tracing, Event as GasometerEvent, Snapshot};
+use evm_runtime::tracing::{self as runtime_tracing, Event as RuntimeEvent};
+use sp_core::{H160, H256, U256};
+use sp_std::vec::Vec;
+
+use crate::{evm_tracing_ext, CallType, Event, Step, StepFilter};
+
+/// A call frame being executed.
+#[derive(Default)]
+struct Frame {
+    /// The gas limit of the gasometer of the frame, known after the first opcode.
+    gas_limit: Option<u64>,
+    /// Whether the frame has executed an opcode, the gasometer events before it
+    /// belong to the parent frame.
+    started: bool,
+    /// The gas used by the frame so far.
+    gas_used: u64,
+}
+
+/// Forwards the EVM events of the traced transactions to the node.
+#[derive(Default)]
+pub struct EvmTracer {
+    filter: StepFilter,
+    frames: Vec<Frame>,
+    /// The intrinsic gas of the transaction, which is counted in the top frame.
+    intrinsic_gas: u64,
+    /// The last step waiting for its gas cost, with the gas used by the frame before it.
+    step: Option<(Step, u64)>,
+}
+
+impl EvmTracer {
+    pub fn new() -> Self {
+        Self {
+            filter: evm_tracing_ext::step_filter(),
+            ..Default::default()
+        }
+    }
+
+    /// Trace the ethereum transaction `transaction_hash` executed by `f`.
+    pub fn trace<R, F: FnOnce() -> R>(self, transaction_hash: H256, f: F) -> R {
+        Self::emit(Event::Transaction(transaction_hash));
+
+        let tracer = Rc::new(RefCell::new(self));
+        let mut evm_listener = Proxy(tracer.clone());
+        let mut gasometer_listener = Proxy(tracer.clone());
+        let mut runtime_listener = Proxy(tracer.clone());
+
+        let result = evm_tracing::using(&mut evm_listener, || {
+            gasometer_tracing::using(&mut gasometer_listener, || {
+                runtime_tracing::using(&mut runtime_listener, f)
+            })
+        });
+        tracer.borrow_mut().finish();
+        result
+    }
+
+    fn emit(event: Event) {
+        evm_tracing_ext::event(event.encode());
+    }
+
+    fn enter(
+        &mut self,
+        call_type: CallType,
+        from: H160,
+        to: H160,
+        value: U256,
+        gas: u64,
+        input: &[u8],
+    ) {
+        self.flush_step();
+        self.frames.push(Frame::default());
+        Self::emit(Event::Enter {
+            call_type,
+            from,
+            to,
+            value,
+            gas,
+            input: input.to_vec(),
+        });
+    }
+
+    fn exit(&mut self, reason: &ExitReason, output: &[u8]) {
+        self.flush_step();
+        let frame = match self.frames.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let error = match reason {
+            ExitReason::Succeed(_) => None,
+            ExitReason::Revert(_) => Some(b"execution reverted".to_vec()),
+            ExitReason::Error(err) => Some(format!("{:?}", err).into_bytes()),
+            ExitReason::Fatal(err) => Some(format!("{:?}", err).into_bytes()),
+        };
+        let mut gas_used = match (reason, frame.gas_limit) {
+            // the frame failed with an error consumes all the gas.
+            (ExitReason::Error(_), Some(gas_limit)) => gas_limit,
+            _ => frame.gas_used,
+        };
+        if self.frames.is_empty() {
+            gas_used = gas_used.saturating_add(self.intrinsic_gas);
+        }
+        Self::emit(Event::Exit {
+            gas_used,
+            output: output.to_vec(),
+            error,
+        });
+    }
+
+    /// Send the pending step, whose gas cost is known now.
+    fn flush_step(&mut self) {
+        if let Some((step, _)) = self.step.take() {
+            Self::emit(Event::Step(step));
+        }
+    }
+
+    /// Close the frames which were not exited, e.g. the execution was aborted.
+    fn finish(&mut self) {
+        self.flush_step();
+        while !self.frames.is_empty() {
+            self.exit(&ExitReason::Fatal(evm::ExitFatal::NotSupported), &[]);
+        }
+    }
+
+    fn on_evm_event(&mut self, event: EvmEvent) {
+        match event {
+            EvmEvent::Call {
+                code_address,
+                transfer,
+                input,
+                target_gas,
+                is_static,
+                context,
+            } => {
+                let call_type = if is_static {
+                    CallType::StaticCall
+                } else if context.address == code_address {
+                    CallType::Call
+                } else if transfer.is_none() {
+                    CallType::DelegateCall
+                } else {
+                    CallType::CallCode
+                };
+                let from = match call_type {
+                    CallType::DelegateCall | CallType::CallCode => context.address,
+                    _ => context.caller,
+                };
+                self.enter(
+                    call_type,
+                    from,
+                    code_address,
+                    context.apparent_value,
+                    target_gas.unwrap_or_default(),
+                    input,
+                );
+            }
+            EvmEvent::Create {
+                caller,
+                address,
+                scheme,
+                value,
+                init_code,
+                target_gas,
+            } => {
+                let call_type = match scheme {
+                    CreateScheme::Create2 { .. } => CallType::Create2,
+                    _ => CallType::Create,
+                };
+                self.enter(
+                    call_type,
+                    caller,
+                    address,
+                    value,
+                    target_gas.unwrap_or_default(),
+                    init_code,
+                );
+            }
+            EvmEvent::Suicide {
+                address,
+                target,
+                balance,
+            } => {
+                self.flush_step();
+                Self::emit(Event::SelfDestruct {
+                    address,
+                    refund_address: target,
+                    balance,
+                });
+            }
+            EvmEvent::Exit {
+                reason,
+                return_value,
+            } => self.exit(reason, return_value),
+            // the transaction is followed by the `Call`/`Create` event of the top frame.
+            _ => {}
+        }
+    }
+
+    fn on_gasometer_event(&mut self, event: GasometerEvent) {
+        let snapshot = match event {
+            GasometerEvent::RecordTransaction { cost, .. } => {
+                if self.frames.is_empty() {
+                    self.intrinsic_gas = cost;
+                }
+                return;
+            }
+            GasometerEvent::RecordCost { snapshot, .. }
+            | GasometerEvent::RecordRefund { snapshot, .. }
+            | GasometerEvent::RecordStipend { snapshot, .. }
+            | GasometerEvent::RecordDynamicCost { snapshot, .. } => snapshot,
+        };
+        if let (Some(frame), Some(snapshot)) = (self.frames.last_mut(), snapshot) {
+            if !frame.started
+                || *frame.gas_limit.get_or_insert(snapshot.gas_limit) != snapshot.gas_limit
+            {
+                return;
+            }
+            frame.gas_used = used_gas(&snapshot);
+            if let Some((step, gas_used_before)) = self.step.as_mut() {
+                step.gas_cost = frame.gas_used.saturating_sub(*gas_used_before);
+            }
+        }
+    }
+
+    fn on_runtime_event(&mut self, event: RuntimeEvent) {
+        match event {
+            RuntimeEvent::Step {
+                opcode,
+                position,
+                stack,
+                memory,
+                ..
+            } => {
+                self.flush_step();
+                let depth = self.frames.len() as u32;
+                let frame = match self.frames.last_mut() {
+                    Some(frame) => frame,
+                    None => return,
+                };
+                frame.started = true;
+                if !self.filter.enabled {
+                    return;
+                }
+                let gas = frame
+                    .gas_limit
+                    .map(|gas_limit| gas_limit.saturating_sub(frame.gas_used))
+                    .unwrap_or_default();
+                let step = Step {
+                    pc: position.as_ref().map(|pc| *pc as u64).unwrap_or_default(),
+                    opcode: opcode.0,
+                    depth,
+                    gas,
+                    gas_cost: 0,
+                    stack: self.filter.stack.then(|| stack.data().clone()),
+                    memory: self.filter.memory.then(|| memory.data().clone()),
+                    storage: None,
+                };
+                self.step = Some((step, frame.gas_used));
+            }
+            RuntimeEvent::SLoad { index, value, .. }
+            | RuntimeEvent::SStore { index, value, .. } => {
+                if let Some((step, _)) = self.step.as_mut() {
+                    step.storage = Some((index, value));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn used_gas(snapshot: &Snapshot) -> u64 {
+    snapshot.used_gas.saturating_add(snapshot.memory_gas)
+}
+
+/// The listener of the `evm` crates sharing the same tracer.
+struct Proxy(Rc<RefCell<EvmTracer>>);
+
+impl evm_tracing::EventListener for Proxy {
+    fn event(&mut self, event: EvmEvent) {
+        self.0.borrow_mut().on_evm_event(event);
+    }
+}
+
+impl gasometer_tracing::EventListener for Proxy {
+    fn event(&mut self, event: GasometerEvent) {
+        self.0.borrow_mut().on_gasometer_event(event);
+    }
+}
+
+impl runtime_tracing::EventListener for Proxy {
+    fn event(&mut self, event: RuntimeEvent) {
+        self.0.borrow_mut().on_runtime_event(event);
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_std::prelude::Vec;
+
+use light_bitcoin::{
+    keys::{Address, AddressTypes, Network, Type},
+    primitives::{H160, H256},
+};
+
+/// The reason why an address is not a valid withdrawal destination.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddressError {
+    /// The address can't be decoded.
+    InvalidEncoding,
+    /// The address belongs to another network.
+    NetworkMismatch,
+    /// The address is valid, but its script type is not supported.
+    UnsupportedType,
+}
+
+/// The version byte of the base58 address for the given network and type.
+///
+/// Only `p2pkh` and `p2sh` have base58 addresses.
+pub fn legacy_address_version(network: Network, kind: Type) -> Option<u8> {
+    match (network, kind) {
+        (Network::Mainnet, Type::P2PKH) => Some(0x00),
+        (Network::Mainnet, Type::P2SH) => Some(0x05),
+        (Network::Testnet, Type::P2PKH) => Some(0x6f),
+        (Network::Testnet, Type::P2SH) => Some(0xc4),
+        _ => None,
+    }
+}
+
+/// Check the base58 address `layout` (the decoded bytes including the version byte)
+/// parsed as `address` belongs to the `network`.
+pub fn check_legacy_address(
+    layout: &[u8],
+    address: &Address,
+    network: Network,
+) -> Result<(), AddressError> {
+    match address.hash {
+        AddressTypes::Legacy(_) => {}
+        _ => return Err(AddressError::UnsupportedType),
+    }
+    let version = layout.first().ok_or(AddressError::InvalidEncoding)?;
+    match legacy_address_version(network, address.kind) {
+        Some(expected) if expected == *version => Ok(()),
+        Some(_) => Err(AddressError::NetworkMismatch),
+        None => Err(AddressError::UnsupportedType),
+    }
+}
+
+/// The human-readable part of the segwit addresses of the network.
+pub fn segwit_hrp(network: Network) -> Option<&'static [u8]> {
+    match network {
+        Network::Mainnet => Some(b"bc"),
+        Network::Testnet => Some(b"tb"),
+        _ => None,
+    }
+}
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LEN: usize = 6;
+const MAX_ADDRESS_LEN: usize = 90;
+/// BIP-173, used by the witness version 0.
+const BECH32_CONST: u32 = 1;
+/// BIP-350, used by the witness version 1 and above.
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+fn polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk: u32 = 1;
+    for v in values {
+        let b = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(*v);
+        for (i, g) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_v: u32 = (1 << to) - 1;
+    let mut ret = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    for value in data {
+        let v = u32::from(*value);
+        if v >> from != 0 {
+            return None;
+        }
+        acc = (acc << from) | v;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push(((acc >> bits) & max_v) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to - bits)) & max_v) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_v) != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+/// Decode a bech32 (witness version 0) or bech32m (witness version 1 and above)
+/// encoded segwit address, returns the human-readable part, the witness version
+/// and the witness program.
+fn decode_segwit(addr: &[u8]) -> Option<(Vec<u8>, u8, Vec<u8>)> {
+    if addr.len() > MAX_ADDRESS_LEN {
+        return None;
+    }
+    // mixed case is not allowed
+    let has_lower = addr.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = addr.iter().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return None;
+    }
+    let addr = addr.to_ascii_lowercase();
+
+    let sep = addr.iter().rposition(|c| *c == b'1')?;
+    if sep == 0 || sep + 1 + CHECKSUM_LEN > addr.len() {
+        return None;
+    }
+    let (hrp, data) = (&addr[..sep], &addr[sep + 1..]);
+    if hrp.iter().any(|c| !(33..=126).contains(c)) {
+        return None;
+    }
+    let data = data
+        .iter()
+        .map(|c| CHARSET.iter().position(|x| x == c).map(|p| p as u8))
+        .collect::<Option<Vec<u8>>>()?;
+
+    let mut values = Vec::with_capacity(hrp.len() * 2 + 1 + data.len());
+    values.extend(hrp.iter().map(|c| c >> 5));
+    values.push(0);
+    values.extend(hrp.iter().map(|c| c & 0x1f));
+    values.extend_from_slice(&data);
+    let checksum = polymod(&values);
+
+    let (version, payload) = data[..data.len() - CHECKSUM_LEN].split_first()?;
+    let expected = if *version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+    if *version > 16 || checksum != expected {
+        return None;
+    }
+    let program = convert_bits(payload, 5, 8, false)?;
+    if program.len() < 2 || program.len() > 40 {
+        return None;
+    }
+    if *version == 0 && program.len() != 20 && program.len() != 32 {
+        return None;
+    }
+    Some((hrp.to_vec(), *version, program))
+}
+
+/// Parse the segwit address of the `network`.
+///
+/// Supports `p2wpkh`, `p2wsh` (bech32) and `p2tr` (bech32m) destinations,
+/// other witness versions and program lengths are rejected as unsupported.
+pub fn parse_segwit_address(addr: &[u8], network: Network) -> Result<Address, AddressError> {
+    let (hrp, version, program) = decode_segwit(addr).ok_or(AddressError::InvalidEncoding)?;
+    match segwit_hrp(network) {
+        Some(expected) if expected == hrp.as_slice() => {}
+        _ if [Network::Mainnet, Network::Testnet]
+            .iter()
+            .any(|n| segwit_hrp(*n) == Some(hrp.as_slice())) =>
+        {
+            return Err(AddressError::NetworkMismatch)
+        }
+        _ => return Err(AddressError::InvalidEncoding),
+    }
+
+    let (kind, hash) = match (version, program.len()) {
+        (0, 20) => (
+            Type::P2WPKH,
+            AddressTypes::WitnessV0KeyHash(H160::from_slice(&program)),
+        ),
+        (0, 32) => (
+            Type::P2WSH,
+            AddressTypes::WitnessV0ScriptHash(H256::from_slice(&program)),
+        ),
+        (1, 32) => (
+            Type::P2TR,
+            AddressTypes::WitnessV1Taproot(H256::from_slice(&program)),
+        ),
+        _ => return Err(AddressError::UnsupportedType),
+    };
+    Ok(Address {
+        kind,
+        network,
+        hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_segwit_address() {
+        // BIP-173 and BIP-350 test vectors
+        let p2wpkh = b"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4";
+        let addr = parse_segwit_address(p2wpkh, Network::Mainnet).unwrap();
+        assert_eq!(addr.kind, Type::P2WPKH);
+        assert_eq!(
+            parse_segwit_address(p2wpkh, Network::Testnet),
+            Err(AddressError::NetworkMismatch)
+        );
+
+        let p2wsh = b"tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7";
+        let addr = parse_segwit_address(p2wsh, Network::Testnet).unwrap();
+        assert_eq!(addr.kind, Type::P2WSH);
+
+        let p2tr = b"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0";
+        let addr = parse_segwit_address(p2tr, Network::Mainnet).unwrap();
+        assert_eq!(addr.kind, Type::P2TR);
+
+        // witness version 1 must use bech32m
+        let p2tr_bech32 = b"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd";
+        assert_eq!(
+            parse_segwit_address(p2tr_bech32, Network::Mainnet),
+            Err(AddressError::InvalidEncoding)
+        );
+        // witness version 0 must use bech32
+        let p2wpkh_bech32m = b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh";
+        assert_eq!(
+            parse_segwit_address(p2wpkh_bech32m, Network::Mainnet),
+            Err(AddressError::InvalidEncoding)
+        );
+        // witness version 0 program must be 20 or 32 bytes
+        let v0_16 = b"BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P";
+        assert_eq!(
+            parse_segwit_address(v0_16, Network::Mainnet),
+            Err(AddressError::InvalidEncoding)
+        );
+        // witness version 16 is valid, but not supported
+        let v16 = b"BC1SW50QGDZ25J";
+        assert_eq!(
+            parse_segwit_address(v16, Network::Mainnet),
+            Err(AddressError::UnsupportedType)
+        );
+        // mixed case
+        let mixed = b"tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7";
+        assert_eq!(
+            parse_segwit_address(mixed, Network::Testnet),
+            Err(AddressError::InvalidEncoding)
+        );
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use light_bitcoin::keys::{Address, AddressTypes, Network, Type};
+
+/// The reason why an address is not a valid withdrawal destination.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AddressError {
+    /// The address can't be decoded.
+    InvalidEncoding,
+    /// The address belongs to another network.
+    NetworkMismatch,
+    /// The address is valid, but its script type is not supported.
+    UnsupportedType,
+}
+
+/// The version byte of the base58 address for the given dogecoin network and type.
+///
+/// Dogecoin has no segwit, only `p2pkh` and `p2sh` are supported.
+pub fn legacy_address_version(network: Network, kind: Type) -> Option<u8> {
+    match (network, kind) {
+        (Network::DogeCoinMainnet, Type::P2PKH) => Some(0x1e),
+        (Network::DogeCoinMainnet, Type::P2SH) => Some(0x16),
+        (Network::DogeCoinTestnet, Type::P2PKH) => Some(0x71),
+        (Network::DogeCoinTestnet, Type::P2SH) => Some(0xc4),
+        _ => None,
+    }
+}
+
+/// Check the base58 address `layout` (the decoded bytes including the version byte)
+/// parsed as `address` belongs to the dogecoin `network`.
+pub fn check_legacy_address(
+    layout: &[u8],
+    address: &Address,
+    network: Network,
+) -> Result<(), AddressError> {
+    match address.hash {
+        AddressTypes::Legacy(_) => {}
+        _ => return Err(AddressError::UnsupportedType),
+    }
+    let version = layout.first().ok_or(AddressError::InvalidEncoding)?;
+    match legacy_address_version(network, address.kind) {
+        Some(expected) if expected == *version => Ok(()),
+        Some(_) => Err(AddressError::NetworkMismatch),
+        None => Err(AddressError::UnsupportedType),
+    }
+}
@@ -5,11 +5,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
+mod address;
 mod detector;
 mod extractor;
 mod types;
 mod utils;
 
+pub use self::address::{check_legacy_address, legacy_address_version, AddressError};
 pub use self::detector::DogeTxTypeDetector;
 pub use self::extractor::{AccountExtractor, OpReturnExtractor};
 pub use self::types::{DogeDepositInfo, DogeTxMetaType, DogeTxType};
@@ -1,13 +1,24 @@
+use codec::Encode;
+use ethereum::{EIP658ReceiptData, LegacyTransaction, TransactionAction, TransactionSignature};
+use ethereum_types::{Bloom, BloomInput};
+use fp_rpc::TransactionStatus;
 use frame_support::{
-    parameter_types,
-    traits::{Currency, Imbalance, OnUnbalanced},
+    parameter_types, storage,
+    traits::{
+        fungibles::{Balanced, CreditOf},
+        Currency, Get, Imbalance, OnUnbalanced, PalletInfoAccess,
+    },
 };
+use pallet_asset_tx_payment::HandleCredit;
+use pallet_ethereum::{Receipt, Transaction as EthereumTransaction};
+use pallet_evm::Log;
 use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{FixedPointNumber, Perquintill};
 use sp_staking::SessionIndex;
 use sp_std::marker::PhantomData;
 
-use crate::{AccountId, Authorship, Balances, Vec};
+use crate::{AccountId, Assets, Authorship, Balances, Ethereum, Runtime, System, Vec};
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
@@ -48,6 +59,17 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
     }
 }
 
+/// The fees paid in assets, 100% to author.
+pub struct AssetsToBlockAuthor;
+impl HandleCredit<AccountId, Assets> for AssetsToBlockAuthor {
+    fn handle_credit(credit: CreditOf<AccountId, Assets>) {
+        if let Some(author) = Authorship::author() {
+            // the credit is dropped (burned) if the author can't hold the asset
+            let _ = Assets::resolve(&author, credit);
+        }
+    }
+}
+
 /// A convertor from aura id. Since this pallet does not have stash/controller, this is
 /// just identity.
 pub struct IdentityAura;
@@ -72,3 +94,80 @@ impl<T: pallet_session::Config> pallet_session::SessionManager<T::ValidatorId>
     fn end_session(_: SessionIndex) {}
     fn start_session(_: SessionIndex) {}
 }
+
+type PendingTransaction = (EthereumTransaction, TransactionStatus, Receipt);
+
+/// Put the evm logs of the bridge operations into the ethereum block, as the receipts of
+/// synthetic transactions appended to `pallet_ethereum::Pending`, so that `eth_getLogs` and
+/// the logs bloom of the block cover them.
+///
+/// The synthetic transactions are never executed, they use no gas and carry a dummy signature.
+pub struct EthereumReceiptLogs;
+impl pallet_assets_bridge::OnEvmLogs for EthereumReceiptLogs {
+    fn on_evm_logs(from: H160, to: H160, logs: Vec<Log>) {
+        if logs.is_empty() {
+            return;
+        }
+        let chain_id = <Runtime as pallet_evm::Config>::ChainId::get();
+        let signature = match TransactionSignature::new(
+            chain_id.saturating_mul(2).saturating_add(35),
+            H256::from_low_u64_be(1),
+            H256::from_low_u64_be(1),
+        ) {
+            Some(signature) => signature,
+            None => return,
+        };
+
+        // `Pending` is private to `pallet_ethereum`, it is read and written by its key.
+        let key = storage::storage_prefix(Ethereum::name().as_bytes(), b"Pending");
+        let mut pending: Vec<PendingTransaction> = storage::unhashed::get_or_default(&key);
+        let transaction_index = pending.len() as u32;
+        // the used gas of the receipts is cumulative
+        let used_gas = pending
+            .last()
+            .map(|(_, _, receipt)| match receipt {
+                Receipt::Legacy(data) | Receipt::EIP2930(data) | Receipt::EIP1559(data) => {
+                    data.used_gas
+                }
+            })
+            .unwrap_or_default();
+
+        let transaction = EthereumTransaction::Legacy(LegacyTransaction {
+            nonce: U256::from(transaction_index),
+            gas_price: U256::zero(),
+            gas_limit: U256::zero(),
+            action: TransactionAction::Call(to),
+            value: U256::zero(),
+            // the hash is unique by the block number and the index
+            input: (System::block_number(), transaction_index).encode(),
+            signature,
+        });
+
+        let mut logs_bloom = Bloom::default();
+        for log in &logs {
+            logs_bloom.accrue(BloomInput::Raw(&log.address[..]));
+            for topic in &log.topics {
+                logs_bloom.accrue(BloomInput::Raw(&topic[..]));
+            }
+        }
+
+        let status = TransactionStatus {
+            transaction_hash: transaction.hash(),
+            transaction_index,
+            from,
+            to: Some(to),
+            contract_address: None,
+            logs: logs.clone(),
+            logs_bloom,
+        };
+        let receipt = Receipt::Legacy(EIP658ReceiptData {
+            status_code: 1,
+            used_gas,
+            logs_bloom,
+            logs,
+        });
+
+        pending.push((transaction, status, receipt));
+        storage::unhashed::put(&key, &pending);
+    }
+}
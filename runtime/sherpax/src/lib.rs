@@ -20,7 +20,7 @@ use sp_runtime::{
         Verify,
     },
     transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, DispatchError, MultiSignature, Percent,
+    ApplyExtrinsicResult, DispatchError, DispatchResult, MultiSignature, Percent, RuntimeDebug,
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 #[cfg(feature = "std")]
@@ -32,8 +32,8 @@ use static_assertions::const_assert;
 pub use frame_support::{
     construct_runtime, parameter_types,
     traits::{
-        ConstBool, ConstU32, EnsureOneOf, EqualPrivilegeOnly, FindAuthor, Get, KeyOwnerProofSystem,
-        LockIdentifier, OnRuntimeUpgrade, Randomness,
+        ConstBool, ConstU32, EnsureOneOf, EqualPrivilegeOnly, FindAuthor, Get, InstanceFilter,
+        KeyOwnerProofSystem, LockIdentifier, OnRuntimeUpgrade, Randomness,
     },
     weights::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -51,7 +51,7 @@ pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 // evm
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use fp_rpc::TransactionStatus;
 use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
 #[cfg(feature = "std")]
@@ -130,7 +130,9 @@ pub use xp_runtime::Memo;
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
 pub use xpallet_gateway_bitcoin::{
-    hash_rev, BtcHeader, BtcHeaderInfo, BtcNetwork, BtcParams, BtcWithdrawalProposal,
+    hash_rev,
+    types::{BtcHeaderIndex, BtcTxState},
+    BtcAddress, BtcHeader, BtcHeaderInfo, BtcNetwork, BtcParams, BtcWithdrawalProposal,
     Compact as BtcCompact, H256 as BtcHash,
 };
 pub use xpallet_gateway_common::{
@@ -140,9 +142,11 @@ pub use xpallet_gateway_common::{
     },
 };
 pub use xpallet_gateway_dogecoin::{
-    BlockHeader, DogeHeaderInfo, DogeParams, DogeWithdrawalProposal,
+    types::DogeHeaderIndex, BlockHeader, DogeHeaderInfo, DogeParams, DogeWithdrawalProposal,
+};
+pub use xpallet_gateway_records::{
+    GatewayEvent, Withdrawal, WithdrawalLimit, WithdrawalRecordId, WithdrawalState,
 };
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalLimit, WithdrawalRecordId};
 use xpallet_support::traits::MultisigAddressFor;
 
 // To learn more about runtime versioning and what each of the following value means:
@@ -156,10 +160,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 43,
+    spec_version: 44,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 3,
+    transaction_version: 4,
     state_version: 0,
 };
 
@@ -356,6 +360,14 @@ impl pallet_transaction_payment::Config for Runtime {
     type FeeMultiplierUpdate = impls::SlowAdjustingFeeUpdate<Runtime>;
 }
 
+impl pallet_asset_tx_payment::Config for Runtime {
+    type Fungibles = Assets;
+    type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+        pallet_assets::FeeRateToAssetBalance<Balances, Runtime, ConvertInto>,
+        impls::AssetsToBlockAuthor,
+    >;
+}
+
 impl pallet_sudo::Config for Runtime {
     type Event = Event;
     type Call = Call;
@@ -724,14 +736,18 @@ impl pallet_assets::Config for Runtime {
     type AssetId = u32;
     type Currency = Balances;
     type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type FeeRateOrigin = EnsureRootOrHalfCouncil;
     type AssetDeposit = AssetDeposit;
     type AssetAccountDeposit = AssetAccountDeposit;
     type MetadataDepositBase = MetadataDepositBase;
     type MetadataDepositPerByte = MetadataDepositPerByte;
     type ApprovalDeposit = ApprovalDeposit;
     type StringLimit = StringLimit;
-    type Freezer = XGatewayRecords;
+    // The pallets lock the balances through the named locks of `pallet_assets`.
+    type Freezer = ();
     type Extra = ();
+    type MaxLocks = ConstU32<16>;
+    type MaxBatchTransfers = ConstU32<100>;
     type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
@@ -746,15 +762,36 @@ impl pallet_vesting::Config for Runtime {
     const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
+impl xpallet_assets_vesting::Config for Runtime {
+    type Event = Event;
+    type BlockNumberToBalance = ConvertInto;
+    type ForceVestingOrigin = EnsureRootOrHalfCouncil;
+    type WeightInfo = xpallet_assets_vesting::weights::SubstrateWeight<Runtime>;
+    const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
 parameter_types! {
     // 0x1111111111111111111111111111111111111111
     pub EvmCaller: H160 = H160::from_slice(&[17u8;20][..]);
     pub ClaimBond: Balance = UNITS;
+    // 0x0000000000000000000000000000000000000801, the `NativeErc20` precompile
+    pub NativeTokenAddress: Option<H160> = Some(H160::from_low_u64_be(2049));
+    pub const MaxBridgeAssets: u32 = 256;
 }
 impl pallet_assets_bridge::Config for Runtime {
     type Event = Event;
     type EvmCaller = EvmCaller;
     type ClaimBond = ClaimBond;
+    type RegisterOrigin = EnsureRootOrHalfCouncil;
+    type PauseOrigin = EnsureOneOf<
+        EnsureRoot<AccountId>,
+        pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
+    >;
+    type UnregisterOrigin = EnsureRoot<AccountId>;
+    type NativeTokenAddress = NativeTokenAddress;
+    type OnEvmLogs = impls::EthereumReceiptLogs;
+    type MaxAssets = MaxBridgeAssets;
+    type WeightInfo = pallet_assets_bridge::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -775,6 +812,176 @@ impl pallet_multisig::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    // One storage item; key size 32, value size 8.
+    pub const ProxyDepositBase: Balance = deposit(1, 8);
+    // Additional storage item size of 33 bytes.
+    pub const ProxyDepositFactor: Balance = deposit(0, 33);
+    pub const MaxProxies: u16 = 32;
+    // One storage item; key size 32, value size 16.
+    pub const AnnouncementDepositBase: Balance = deposit(1, 16);
+    // Additional storage item size of 68 bytes.
+    pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+    pub const MaxPending: u16 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+    /// All calls.
+    Any,
+    /// All calls except the transfers of the native token and the assets.
+    NonTransfer,
+    /// The calls of the council, the democracy and the treasury.
+    Governance,
+    /// The calls of a trustee of the chain, the proxy also takes the place of the trustee in
+    /// the trustee multisig of the chain.
+    GatewayTrustee(Chain),
+    /// The deposits, withdrawals and teleports of the assets bridge, and the withdrawals of
+    /// the gateways.
+    AssetsBridge,
+    /// Reject the announcements of the proxies.
+    CancelProxy,
+}
+
+impl Default for ProxyType {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+impl InstanceFilter<Call> for ProxyType {
+    fn filter(&self, c: &Call) -> bool {
+        match self {
+            ProxyType::Any => true,
+            ProxyType::NonTransfer => !matches!(
+                c,
+                Call::Balances(..)
+                    | Call::Assets(..)
+                    | Call::Vesting(pallet_vesting::Call::vested_transfer { .. })
+                    | Call::XAssetsVesting(xpallet_assets_vesting::Call::vested_transfer { .. })
+                    | Call::Evm(..)
+                    | Call::Ethereum(..)
+                    | Call::AssetsBridge(..)
+                    | Call::XGatewayCommon(xpallet_gateway_common::Call::withdraw { .. })
+                    | Call::Sudo(..)
+            ),
+            ProxyType::Governance => matches!(
+                c,
+                Call::Democracy(..)
+                    | Call::Council(..)
+                    | Call::TechnicalCommittee(..)
+                    | Call::Elections(..)
+                    | Call::Treasury(..)
+                    | Call::Bounties(..)
+                    | Call::Tips(..)
+                    | Call::Utility(..)
+            ),
+            ProxyType::GatewayTrustee(chain) => match c {
+                Call::XGatewayCommon(xpallet_gateway_common::Call::setup_trustee {
+                    chain: call_chain,
+                    ..
+                }) => call_chain == chain,
+                Call::XGatewayBitcoin(
+                    xpallet_gateway_bitcoin::Call::create_taproot_withdraw_tx { .. },
+                ) => *chain == Chain::Bitcoin,
+                Call::XGatewayDogecoin(
+                    xpallet_gateway_dogecoin::Call::create_dogecoin_withdraw_tx { .. },
+                ) => *chain == Chain::Dogecoin,
+                Call::Utility(..) => true,
+                _ => false,
+            },
+            ProxyType::AssetsBridge => matches!(
+                c,
+                Call::AssetsBridge(pallet_assets_bridge::Call::deposit { .. })
+                    | Call::AssetsBridge(pallet_assets_bridge::Call::withdraw { .. })
+                    | Call::AssetsBridge(pallet_assets_bridge::Call::teleport { .. })
+                    | Call::XGatewayCommon(xpallet_gateway_common::Call::withdraw { .. })
+                    | Call::XGatewayCommon(xpallet_gateway_common::Call::cancel_withdrawal { .. })
+                    | Call::Utility(..)
+            ),
+            ProxyType::CancelProxy => matches!(
+                c,
+                Call::Proxy(pallet_proxy::Call::reject_announcement { .. })
+                    | Call::Utility(..)
+                    | Call::Multisig(..)
+            ),
+        }
+    }
+
+    fn is_superset(&self, o: &Self) -> bool {
+        match (self, o) {
+            (x, y) if x == y => true,
+            (ProxyType::Any, _) => true,
+            (_, ProxyType::Any) => false,
+            (
+                ProxyType::NonTransfer,
+                ProxyType::Governance | ProxyType::GatewayTrustee(_) | ProxyType::CancelProxy,
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+impl pallet_proxy::Config for Runtime {
+    type Event = Event;
+    type Call = Call;
+    type Currency = Balances;
+    type ProxyType = ProxyType;
+    type ProxyDepositBase = ProxyDepositBase;
+    type ProxyDepositFactor = ProxyDepositFactor;
+    type MaxProxies = MaxProxies;
+    type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+    type MaxPending = MaxPending;
+    type CallHasher = BlakeTwo256;
+    type AnnouncementDepositBase = AnnouncementDepositBase;
+    type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+/// The `GatewayTrustee` proxies acting for the trustees, the first one of the chain without
+/// the delay is chosen if a trustee has several.
+pub struct GatewayTrusteeProxy;
+impl xpallet_gateway_common::traits::TrusteeProxy<AccountId> for GatewayTrusteeProxy {
+    fn trustee_proxy(who: &AccountId, chain: Chain) -> Option<AccountId> {
+        Proxy::proxies(who)
+            .0
+            .into_iter()
+            .find(|proxy| proxy.proxy_type == ProxyType::GatewayTrustee(chain) && proxy.delay == 0)
+            .map(|proxy| proxy.delegate)
+    }
+
+    fn insert_trustee_proxy(who: &AccountId, chain: Chain, proxy: &AccountId) -> DispatchResult {
+        // the proxy accounts of the trustees were free, the deposit is left unchanged
+        pallet_proxy::Proxies::<Runtime>::try_mutate(who, |(proxies, _)| {
+            let definition = pallet_proxy::ProxyDefinition {
+                delegate: proxy.clone(),
+                proxy_type: ProxyType::GatewayTrustee(chain),
+                delay: 0,
+            };
+            let index = proxies
+                .binary_search(&definition)
+                .err()
+                .ok_or(pallet_proxy::Error::<Runtime>::Duplicate)?;
+            proxies
+                .try_insert(index, definition)
+                .map_err(|_| pallet_proxy::Error::<Runtime>::TooMany)?;
+            Ok(())
+        })
+    }
+}
+
 parameter_types! {
     pub const BtcAssetId: AssetId = 1;
     pub const DogeAssetId: AssetId = 9;
@@ -788,6 +995,28 @@ impl xpallet_gateway_records::Config for Runtime {
     type WeightInfo = xpallet_gateway_records::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const AssetsRegistrarPalletId: PalletId = PalletId(*b"pcx/asrg");
+}
+
+/// Stop bridging the deregistered assets into evm, the bridge is left paused after
+/// the asset is recovered until `PauseOrigin` unpauses it.
+pub struct AssetsBridgeRegistrarHandler;
+impl xp_assets_registrar::RegistrarHandler for AssetsBridgeRegistrarHandler {
+    fn on_deregister(asset_id: &AssetId) -> DispatchResult {
+        AssetsBridge::pause_until_unpause(*asset_id);
+        Ok(())
+    }
+}
+
+impl xpallet_assets_registrar::Config for Runtime {
+    type Event = Event;
+    type PalletId = AssetsRegistrarPalletId;
+    type RegistrarOrigin = EnsureRootOrHalfCouncil;
+    type RegistrarHandler = AssetsBridgeRegistrarHandler;
+    type WeightInfo = xpallet_assets_registrar::weights::SubstrateWeight<Runtime>;
+}
+
 pub struct MultisigProvider;
 impl MultisigAddressFor<AccountId> for MultisigProvider {
     fn calc_multisig(who: &[AccountId], threshold: u16) -> AccountId {
@@ -795,12 +1024,22 @@ impl MultisigAddressFor<AccountId> for MultisigProvider {
     }
 }
 
+parameter_types! {
+    pub const RelayerBond: Balance = 100 * UNITS;
+    pub const RelayerEraLength: BlockNumber = 7 * DAYS;
+    pub const RelayerPalletId: PalletId = PalletId(*b"pcx/rlyr");
+    pub const RelayerUnbondingPeriod: BlockNumber = 7 * DAYS;
+    pub const RelayerTransitionPeriod: BlockNumber = 30 * DAYS;
+    pub const MaxRelayerRewardsPerBlock: u32 = 100;
+}
+
 impl xpallet_gateway_common::Config for Runtime {
     type Event = Event;
     type Validator = ();
     type DetermineMultisigAddress = MultisigProvider;
     type CouncilOrigin =
         pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+    type TrusteeProxy = GatewayTrusteeProxy;
     type Bitcoin = XGatewayBitcoin;
     type BitcoinTrustee = XGatewayBitcoin;
     type BitcoinTrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Runtime>;
@@ -811,6 +1050,12 @@ impl xpallet_gateway_common::Config for Runtime {
     type DogecoinTrusteeSessionProvider = trustees::dogecoin::DogeTrusteeSessionManager<Runtime>;
     type DogecoinTotalSupply = XGatewayDogecoin;
     type DogecoinWithdrawalProposal = XGatewayDogecoin;
+    type RelayerBond = RelayerBond;
+    type RelayerEraLength = RelayerEraLength;
+    type RelayerPalletId = RelayerPalletId;
+    type RelayerUnbondingPeriod = RelayerUnbondingPeriod;
+    type RelayerTransitionPeriod = RelayerTransitionPeriod;
+    type MaxRelayerRewardsPerBlock = MaxRelayerRewardsPerBlock;
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
@@ -824,6 +1069,7 @@ impl xpallet_gateway_bitcoin::Config for Runtime {
     type TrusteeInfoUpdate = XGatewayCommon;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type RelayerRegistry = XGatewayCommon;
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
@@ -837,6 +1083,7 @@ impl xpallet_gateway_dogecoin::Config for Runtime {
     type TrusteeInfoUpdate = XGatewayCommon;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type RelayerRegistry = XGatewayCommon;
     type WeightInfo = xpallet_gateway_dogecoin::weights::SubstrateWeight<Runtime>;
 }
 
@@ -857,6 +1104,9 @@ construct_runtime!(
         TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
         Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>} = 12,
         Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>} = 13,
+        AssetTxPayment: pallet_asset_tx_payment::{Pallet} = 14,
+        XAssetsRegistrar: xpallet_assets_registrar::{Pallet, Call, Storage, Event<T>} = 15,
+        XAssetsVesting: xpallet_assets_vesting::{Pallet, Call, Storage, Event<T>} = 16,
 
         // Consensus stuff
         Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
@@ -879,6 +1129,7 @@ construct_runtime!(
         // Handy utilities.
         Utility: pallet_utility::{Pallet, Call, Event} = 40,
         Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 41,
+        Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 42,
 
         // Ethereum compatibility
         EthereumChainId: pallet_ethereum_chain_id::{Pallet, Call, Storage, Config} = 50,
@@ -904,9 +1155,14 @@ mod benches {
     define_benchmarks!(
         [frame_benchmarking, BaselineBench::<Runtime>]
         [frame_system, SystemBench::<Runtime>]
+        [xpallet_assets_registrar, XAssetsRegistrar]
+        [xpallet_assets_vesting, XAssetsVesting]
         [xpallet_gateway_records, XGatewayRecords]
         [xpallet_gateway_common,  XGatewayCommon]
         [xpallet_gateway_bitcoin, XGatewayBitcoin]
+        [xpallet_gateway_dogecoin, XGatewayDogecoin]
+        [pallet_assets, Assets]
+        [pallet_assets_bridge, AssetsBridge]
     );
 }
 
@@ -953,7 +1209,7 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -967,7 +1223,15 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
-    DogeCoinGatewayMigration,
+    (
+        DogeCoinGatewayMigration,
+        AssetsBridgeMigration,
+        GatewayPendingDepositMigration,
+        AssetLocksMigration,
+        TrusteeProxyMigration,
+        AssetsRegistrarMigration,
+        RelayerRegistryMigration,
+    ),
 >;
 
 pub struct DogeCoinGatewayMigration;
@@ -986,6 +1250,97 @@ impl frame_support::traits::OnRuntimeUpgrade for DogeCoinGatewayMigration {
     }
 }
 
+pub struct AssetsBridgeMigration;
+impl frame_support::traits::OnRuntimeUpgrade for AssetsBridgeMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        frame_support::log::info!("🔍️ AssetsBridgeMigration start");
+        // Migrate emergencies to the paused directions and count the registered assets
+        let weight = pallet_assets_bridge::migrations::apply::<Runtime>();
+        frame_support::log::info!("🚀 AssetsBridgeMigration end");
+        weight
+    }
+}
+
+pub struct GatewayPendingDepositMigration;
+impl frame_support::traits::OnRuntimeUpgrade for GatewayPendingDepositMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        let mut weight = 0;
+        frame_support::log::info!("🔍️ GatewayPendingDepositMigration start");
+        // Initialize the running total of pending deposits
+        weight += xpallet_gateway_bitcoin::migrations::pending_total::apply::<Runtime>();
+        weight += xpallet_gateway_dogecoin::migrations::pending_total::apply::<Runtime>();
+        frame_support::log::info!("🚀 GatewayPendingDepositMigration end");
+        weight
+    }
+}
+
+pub struct AssetLocksMigration;
+impl frame_support::traits::OnRuntimeUpgrade for AssetLocksMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        frame_support::log::info!("🔍️ AssetLocksMigration start");
+        // Hold the locked assets of the pending withdrawals in the named locks of Assets
+        let weight = xpallet_gateway_records::migrations::asset_locks::apply::<Runtime>();
+        frame_support::log::info!("🚀 AssetLocksMigration end");
+        weight
+    }
+}
+
+pub struct TrusteeProxyMigration;
+impl frame_support::traits::OnRuntimeUpgrade for TrusteeProxyMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        frame_support::log::info!("🔍️ TrusteeProxyMigration start");
+        // Move the proxy accounts of the trustees into the `GatewayTrustee` proxies
+        let weight = xpallet_gateway_common::migrations::trustee_proxy::apply::<Runtime>();
+        frame_support::log::info!("🚀 TrusteeProxyMigration end");
+        weight
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+        let proxies =
+            xpallet_gateway_common::migrations::trustee_proxy::legacy_proxies::<Runtime>();
+        Self::set_temp_storage(proxies, "trustee_proxies");
+        Ok(())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+        let proxies =
+            Self::get_temp_storage::<Vec<(AccountId, Chain, AccountId)>>("trustee_proxies")
+                .ok_or("The trustee proxies before the upgrade are not found")?;
+        // fail if a proxy account is lost
+        xpallet_gateway_common::migrations::trustee_proxy::ensure_proxies::<Runtime>(&proxies)
+    }
+}
+
+pub struct AssetsRegistrarMigration;
+impl frame_support::traits::OnRuntimeUpgrade for AssetsRegistrarMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        frame_support::log::info!("🔍️ AssetsRegistrarMigration start");
+        // Adopt the genesis assets of the gateways into the registrar
+        let weight = xpallet_assets_registrar::migrations::adopt_genesis_assets::<Runtime>(&[
+            (BtcAssetId::get(), Chain::Bitcoin),
+            (DogeAssetId::get(), Chain::Dogecoin),
+        ]);
+        frame_support::log::info!("🚀 AssetsRegistrarMigration end");
+        weight
+    }
+}
+
+pub struct RelayerRegistryMigration;
+impl frame_support::traits::OnRuntimeUpgrade for RelayerRegistryMigration {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+        frame_support::log::info!("🔍️ RelayerRegistryMigration start");
+        // Keep the relayers before the registry relaying during the transition period,
+        // after `TrusteeProxyMigration` which puts the storage version `1`
+        let weight = xpallet_gateway_common::migrations::relayer_registry::apply::<Runtime>();
+        frame_support::log::info!("🚀 RelayerRegistryMigration end");
+        weight
+    }
+}
+
 impl fp_self_contained::SelfContainedCall for Call {
     type SignedInfo = H160;
 
@@ -1033,6 +1388,95 @@ impl fp_self_contained::SelfContainedCall for Call {
     }
 }
 
+/// Decode the deposit and withdrawal events of the gateway pallets in the current block.
+fn decode_gateway_events() -> Vec<GatewayEvent<AccountId, AssetId, Balance>> {
+    macro_rules! relay_event {
+        ($pallet:ident, $chain:expr, $asset_id:expr, $event:expr) => {
+            match $event {
+                $pallet::Event::Deposited(tx_hash, who, amount) => Some(GatewayEvent::Deposited {
+                    chain: $chain,
+                    tx_hash,
+                    who,
+                    asset_id: $asset_id,
+                    amount,
+                }),
+                $pallet::Event::UnclaimedDeposit(tx_hash, address) => {
+                    Some(GatewayEvent::UnclaimedDeposit {
+                        chain: $chain,
+                        tx_hash,
+                        asset_id: $asset_id,
+                        address,
+                    })
+                }
+                $pallet::Event::WithdrawalProposalCreated(proposer, withdrawal_ids) => {
+                    Some(GatewayEvent::WithdrawalProposalCreated {
+                        chain: $chain,
+                        proposer,
+                        withdrawal_ids,
+                    })
+                }
+                $pallet::Event::Withdrawn(tx_hash, withdrawal_ids, total) => {
+                    Some(GatewayEvent::Withdrawn {
+                        chain: $chain,
+                        tx_hash,
+                        withdrawal_ids,
+                        total,
+                    })
+                }
+                _ => None,
+            }
+        };
+    }
+
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::XGatewayBitcoin(event) => relay_event!(
+                xpallet_gateway_bitcoin,
+                Chain::Bitcoin,
+                BtcAssetId::get(),
+                event
+            ),
+            Event::XGatewayDogecoin(event) => relay_event!(
+                xpallet_gateway_dogecoin,
+                Chain::Dogecoin,
+                DogeAssetId::get(),
+                event
+            ),
+            Event::XGatewayRecords(event) => {
+                use xpallet_gateway_records::Event as RecordsEvent;
+                let state_changed = |withdrawal_id, state| {
+                    Some(GatewayEvent::WithdrawalStateChanged {
+                        withdrawal_id,
+                        state,
+                    })
+                };
+                match event {
+                    RecordsEvent::WithdrawalCreated(withdrawal_id, record) => {
+                        Some(GatewayEvent::WithdrawalCreated {
+                            withdrawal_id,
+                            applicant: record.applicant().clone(),
+                            asset_id: record.asset_id(),
+                            balance: record.balance(),
+                        })
+                    }
+                    RecordsEvent::WithdrawalProcessed(id) => {
+                        state_changed(id, WithdrawalState::Processing)
+                    }
+                    RecordsEvent::WithdrawalRecovered(id) => {
+                        state_changed(id, WithdrawalState::Applying)
+                    }
+                    RecordsEvent::WithdrawalCanceled(id, state)
+                    | RecordsEvent::WithdrawalFinished(id, state) => state_changed(id, state),
+                    // The deposits are reported by the events of the relay pallets.
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -1309,6 +1753,30 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_assets_rpc_runtime_api::AssetsApi<Block, AccountId, Balance> for Runtime {
+        fn locks(who: AccountId) -> BTreeMap<AssetId, Vec<pallet_assets::AssetLock<Balance>>> {
+            Assets::locks_of(&who)
+        }
+    }
+
+    impl pallet_assets_bridge_rpc_runtime_api::AssetsBridgeApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn evm_account(account: AccountId) -> Option<H160> {
+            AssetsBridge::evm_accounts(account)
+        }
+
+        fn substrate_account(evm_address: H160) -> Option<AccountId> {
+            AssetsBridge::sub_accounts(evm_address)
+        }
+
+        fn registered_assets() -> Vec<pallet_assets_bridge::RegisteredAsset<AssetId, BlockNumber>> {
+            AssetsBridge::registered_assets()
+        }
+
+        fn balance(asset_id: AssetId, account: AccountId) -> pallet_assets_bridge::BridgeBalance<Balance> {
+            AssetsBridge::bridge_balance(asset_id, &account)
+        }
+    }
+
     impl xpallet_gateway_records_rpc_runtime_api::XGatewayRecordsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
         fn withdrawal_list() -> BTreeMap<u32, Withdrawal<AccountId, AssetId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawal_list()
@@ -1317,6 +1785,14 @@ impl_runtime_apis! {
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<u32, Withdrawal<AccountId, AssetId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawals_list_by_chain(chain)
         }
+
+        fn withdrawals_of(ids: Vec<u32>) -> BTreeMap<u32, Withdrawal<AccountId, AssetId, Balance, BlockNumber>> {
+            XGatewayRecords::withdrawals_of(ids)
+        }
+
+        fn gateway_events() -> Vec<GatewayEvent<AccountId, AssetId, Balance>> {
+            decode_gateway_events()
+        }
     }
 
     impl xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, AccountId> for Runtime {
@@ -1340,6 +1816,22 @@ impl_runtime_apis! {
         fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo> {
             XGatewayBitcoin::get_btc_block_header(txid)
         }
+
+        fn get_header_indexes() -> (BtcHeaderIndex, Option<BtcHeaderIndex>) {
+            XGatewayBitcoin::get_header_indexes()
+        }
+
+        fn get_pending_deposit_stats() -> (u32, u64) {
+            XGatewayBitcoin::get_pending_deposit_stats()
+        }
+
+        fn get_tx_state(txid: H256) -> Option<BtcTxState> {
+            XGatewayBitcoin::get_tx_state(txid)
+        }
+
+        fn get_trustee_address_pairs() -> Vec<(BtcAddress, BtcAddress)> {
+            XGatewayBitcoin::get_trustee_address_pairs()
+        }
     }
 
     impl xpallet_gateway_dogecoin_rpc_runtime_api::XGatewayDogecoinApi<Block, AccountId> for Runtime {
@@ -1363,6 +1855,14 @@ impl_runtime_apis! {
         fn get_doge_block_header(txid: H256) -> Option<DogeHeaderInfo> {
             XGatewayDogecoin::get_doge_block_header(txid)
         }
+
+        fn get_header_indexes() -> (DogeHeaderIndex, Option<DogeHeaderIndex>) {
+            XGatewayDogecoin::get_header_indexes()
+        }
+
+        fn get_pending_deposit_stats() -> (u32, u64) {
+            XGatewayDogecoin::get_pending_deposit_stats()
+        }
     }
 
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance, BlockNumber> for Runtime {
@@ -1396,7 +1896,7 @@ impl_runtime_apis! {
             XGatewayCommon::trustee_multisigs()
         }
 
-        fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps<AccountId>> {
+        fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps> {
             XGatewayCommon::trustee_intention_props_of(who, chain)
         }
 
@@ -1425,6 +1925,91 @@ impl_runtime_apis! {
             let _ = XGatewayCommon::generate_multisig_addr(chain, &info.0)?;
             Ok(info)
         }
+
+        fn trustee_transition_status(chain: Chain) -> bool {
+            XGatewayCommon::trustee_transition_status(chain)
+        }
+    }
+
+    impl xpallet_gateway_common_rpc_runtime_api::XGatewayHealthApi<Block> for Runtime {
+        fn health_report(max_items: u32) -> Vec<xpallet_gateway_common::types::GatewayHealth> {
+            use xpallet_gateway_common::types::GatewayHealth;
+            vec![
+                GatewayHealth::new(
+                    "XGatewayRecords",
+                    XGatewayRecords::check_invariants(max_items),
+                ),
+                GatewayHealth::new(
+                    "XGatewayCommon",
+                    XGatewayCommon::check_invariants(max_items),
+                ),
+                GatewayHealth::new(
+                    "XGatewayBitcoin",
+                    XGatewayBitcoin::check_invariants(max_items),
+                ),
+                GatewayHealth::new(
+                    "XGatewayDogecoin",
+                    XGatewayDogecoin::check_invariants(max_items),
+                ),
+            ]
+        }
+    }
+
+    impl xp_evm_tracing::DebugRuntimeApi<Block> for Runtime {
+        fn trace_transaction(
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+            transaction_hash: H256,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                use xp_evm_tracing::tracer::EvmTracer;
+
+                for ext in extrinsics {
+                    match &ext.0.function {
+                        Call::Ethereum(transact { transaction }) if transaction.hash() == transaction_hash => {
+                            EvmTracer::new().trace(transaction_hash, || Executive::apply_extrinsic(ext));
+                            return Ok(());
+                        }
+                        _ => {
+                            let _ = Executive::apply_extrinsic(ext);
+                        }
+                    }
+                }
+                Err(sp_runtime::DispatchError::Other("Ethereum transaction not found in the block"))
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = (extrinsics, transaction_hash);
+                Err(sp_runtime::DispatchError::Other("Missing the `evm-tracing` feature of the runtime"))
+            }
+        }
+
+        fn trace_block(
+            extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            #[cfg(feature = "evm-tracing")]
+            {
+                use xp_evm_tracing::tracer::EvmTracer;
+
+                for ext in extrinsics {
+                    match &ext.0.function {
+                        Call::Ethereum(transact { transaction }) => {
+                            let transaction_hash = transaction.hash();
+                            EvmTracer::new().trace(transaction_hash, || Executive::apply_extrinsic(ext));
+                        }
+                        _ => {
+                            let _ = Executive::apply_extrinsic(ext);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            #[cfg(not(feature = "evm-tracing"))]
+            {
+                let _ = extrinsics;
+                Err(sp_runtime::DispatchError::Other("Missing the `evm-tracing` feature of the runtime"))
+            }
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -1440,10 +2025,14 @@ impl_runtime_apis! {
 
             let mut list = Vec::<BenchmarkList>::new();
 
+            list_benchmark!(list, extra, xpallet_assets_registrar, XAssetsRegistrar);
+            list_benchmark!(list, extra, xpallet_assets_vesting, XAssetsVesting);
             list_benchmark!(list, extra, xpallet_gateway_records, XGatewayRecords);
             list_benchmark!(list, extra, xpallet_gateway_common, XGatewayCommon);
             list_benchmark!(list, extra, xpallet_gateway_bitcoin, XGatewayBitcoin);
             list_benchmark!(list, extra, xpallet_gateway_dogecoin, XGatewayDogecoin);
+            list_benchmark!(list, extra, pallet_assets, Assets);
+            list_benchmark!(list, extra, pallet_assets_bridge, AssetsBridge);
 
             let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1477,10 +2066,14 @@ impl_runtime_apis! {
             let mut batches = Vec::<BenchmarkBatch>::new();
             let params = (&config, &whitelist);
 
+            add_benchmark!(params, batches, xpallet_assets_registrar, XAssetsRegistrar);
+            add_benchmark!(params, batches, xpallet_assets_vesting, XAssetsVesting);
             add_benchmark!(params, batches, xpallet_gateway_records, XGatewayRecords);
             add_benchmark!(params, batches, xpallet_gateway_common, XGatewayCommon);
             add_benchmark!(params, batches, xpallet_gateway_bitcoin, XGatewayBitcoin);
             add_benchmark!(params, batches, xpallet_gateway_dogecoin, XGatewayDogecoin);
+            add_benchmark!(params, batches, pallet_assets, Assets);
+            add_benchmark!(params, batches, pallet_assets_bridge, AssetsBridge);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
@@ -1,17 +1,65 @@
-use fp_evm::Context;
+use fp_evm::{Context, ExitError, ExitRevert, PrecompileFailure};
+use frame_support::traits::Contains;
 use pallet_evm::{Precompile, PrecompileResult, PrecompileSet};
 use pallet_evm_precompile_blake2::Blake2F;
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
-use pallet_evm_precompile_dispatch::Dispatch;
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 use sp_core::H160;
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, prelude::*};
+
+use crate::Call;
+
+mod btc_spv;
+mod dispatch;
+mod native_erc20;
+#[cfg(test)]
+mod tests;
+
+pub use btc_spv::BtcSpv;
+pub use dispatch::Dispatch;
+pub use native_erc20::NativeErc20;
+
+/// bytes4(keccak256("Error(string)"))
+const SELECTOR_ERROR_STRING: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The runtime calls which EVM contracts may dispatch through the `Dispatch` precompile.
+///
+/// Only the asset transfers and the cross-chain calls of the users are allowed, all the
+/// privileged calls (e.g. `Sudo`, `Multisig`, the trustee and the bridge admin calls) and the
+/// wrapper calls which could smuggle them in are denied.
+pub struct DispatchCallFilter;
+impl Contains<Call> for DispatchCallFilter {
+    fn contains(call: &Call) -> bool {
+        matches!(
+            call,
+            Call::Assets(
+                pallet_assets::Call::transfer { .. }
+                    | pallet_assets::Call::transfer_keep_alive { .. }
+                    | pallet_assets::Call::transfer_with_memo { .. }
+                    | pallet_assets::Call::transfer_keep_alive_with_memo { .. }
+                    | pallet_assets::Call::batch_transfer_with_memo { .. }
+                    | pallet_assets::Call::approve_transfer { .. }
+                    | pallet_assets::Call::cancel_approval { .. }
+                    | pallet_assets::Call::transfer_approved { .. }
+                    | pallet_assets::Call::touch { .. }
+                    | pallet_assets::Call::refund { .. }
+            ) | Call::AssetsBridge(pallet_assets_bridge::Call::teleport { .. })
+                | Call::XGatewayCommon(
+                    xpallet_gateway_common::Call::withdraw { .. }
+                        | xpallet_gateway_common::Call::cancel_withdrawal { .. }
+                )
+        )
+    }
+}
 
 /// We include the nine Istanbul precompiles
 /// (https://github.com/ethereum/go-ethereum/blob/3c46f557/core/vm/contracts.go#L69)
-/// as well as a special precompile for dispatching Substrate extrinsics
+/// as well as a special precompile for dispatching the Substrate calls allowed by
+/// `DispatchCallFilter`
+/// and the SherpaX specific precompiles for verifying Bitcoin transactions (SPV) and mirroring
+/// the native currency as a read-only erc20.
 pub struct SherpaXPrecompiles<R>(PhantomData<R>);
 
 impl<R> SherpaXPrecompiles<R>
@@ -25,7 +73,7 @@ where
     /// Return all addresses that contain precompiles. This can be used to populate dummy code
     /// under the precompile.
     pub fn used_addresses() -> sp_std::vec::Vec<H160> {
-        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026]
+        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 2048, 2049]
             .into_iter()
             .map(hash)
             .collect()
@@ -39,7 +87,9 @@ where
 impl<R> PrecompileSet for SherpaXPrecompiles<R>
 where
     R: pallet_evm::Config,
-    Dispatch<R>: Precompile,
+    Dispatch<R, DispatchCallFilter>: Precompile,
+    BtcSpv<R>: Precompile,
+    NativeErc20<R>: Precompile,
 {
     fn execute(
         &self,
@@ -64,12 +114,19 @@ where
             a if a == hash(1024) => {
                 Some(Sha3FIPS256::execute(input, target_gas, context, is_static))
             }
-            a if a == hash(1025) => Some(Dispatch::<R>::execute(
+            a if a == hash(1025) => Some(Dispatch::<R, DispatchCallFilter>::execute(
                 input, target_gas, context, is_static,
             )),
             a if a == hash(1026) => Some(ECRecoverPublicKey::execute(
                 input, target_gas, context, is_static,
             )),
+            // SherpaX specific precompiles :
+            a if a == hash(2048) => {
+                Some(BtcSpv::<R>::execute(input, target_gas, context, is_static))
+            }
+            a if a == hash(2049) => Some(NativeErc20::<R>::execute(
+                input, target_gas, context, is_static,
+            )),
             _ => None,
         }
     }
@@ -78,6 +135,39 @@ where
     }
 }
 
+fn ensure_gas(cost: u64, target_gas: Option<u64>) -> Result<(), PrecompileFailure> {
+    match target_gas {
+        Some(gas) if cost > gas => Err(PrecompileFailure::Error {
+            exit_status: ExitError::OutOfGas,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Build a revert with the solidity `Error(string)` output.
+fn revert(reason: &str, cost: u64) -> PrecompileFailure {
+    let reason = reason.as_bytes();
+    let padded_len = (reason.len() + 31) / 32 * 32;
+    let mut output = Vec::with_capacity(4 + 32 + 32 + padded_len);
+    output.extend_from_slice(&SELECTOR_ERROR_STRING);
+    output.extend_from_slice(&encode_u256(32));
+    output.extend_from_slice(&encode_u256(reason.len() as u64));
+    output.extend_from_slice(reason);
+    output.resize(4 + 32 + 32 + padded_len, 0);
+
+    PrecompileFailure::Revert {
+        exit_status: ExitRevert::Reverted,
+        output,
+        cost,
+    }
+}
+
+fn encode_u256(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
 fn hash(a: u64) -> H160 {
     H160::from_low_u64_be(a)
 }
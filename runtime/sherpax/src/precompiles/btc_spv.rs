@@ -0,0 +1,138 @@
+//! Bitcoin SPV verification precompile.
+//!
+//! Exposes the header chain relayed into `XGatewayBitcoin` to EVM contracts:
+//!
+//! ```solidity
+//! interface BtcSpv {
+//!     // `blockHash` is in the internal byte order (reverse of the block explorer display),
+//!     // `rawTx` is the bitcoin serialized transaction and `merkleProof` is the bitcoin
+//!     // serialized partial merkle tree (the tail of `gettxoutproof` after the block header).
+//!     function verifyTransaction(bytes32 blockHash, bytes calldata rawTx, bytes calldata merkleProof)
+//!         external view returns (bool);
+//!     // Returns 0 if there is no confirmed header yet.
+//!     function confirmedHeight() external view returns (uint32);
+//! }
+//! ```
+
+use fp_evm::{Context, ExitSucceed, Precompile, PrecompileOutput, PrecompileResult};
+use frame_support::traits::Get;
+use pallet_evm::GasWeightMapping;
+use sp_std::marker::PhantomData;
+
+use xpallet_gateway_bitcoin::{deserialize, PartialMerkleTree, Reader, Transaction, H256};
+
+use super::{encode_u256, ensure_gas, revert};
+
+/// bytes4(keccak256("verifyTransaction(bytes32,bytes,bytes)"))
+const SELECTOR_VERIFY_TRANSACTION: [u8; 4] = [0xae, 0x70, 0x29, 0x0a];
+/// bytes4(keccak256("confirmedHeight()"))
+const SELECTOR_CONFIRMED_HEIGHT: [u8; 4] = [0xc9, 0xe1, 0x80, 0xea];
+
+/// The base cost of decoding the input, same as the `Sha256` precompile.
+const BASE_GAS: u64 = 60;
+/// Hashing the tx and the merkle nodes, same as the `Sha256` precompile.
+const WORD_GAS: u64 = 12;
+
+pub struct BtcSpv<R>(PhantomData<R>);
+
+impl<R> Precompile for BtcSpv<R>
+where
+    R: pallet_evm::Config + xpallet_gateway_bitcoin::Config,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+        _is_static: bool,
+    ) -> PrecompileResult {
+        if input.len() < 4 {
+            return Err(revert("input too short", 0));
+        }
+        let (selector, args) = input.split_at(4);
+
+        match selector {
+            s if s == SELECTOR_VERIFY_TRANSACTION => Self::verify_transaction(args, target_gas),
+            s if s == SELECTOR_CONFIRMED_HEIGHT => Self::confirmed_height(target_gas),
+            _ => Err(revert("unknown selector", 0)),
+        }
+    }
+}
+
+impl<R> BtcSpv<R>
+where
+    R: pallet_evm::Config + xpallet_gateway_bitcoin::Config,
+{
+    fn verify_transaction(args: &[u8], target_gas: Option<u64>) -> PrecompileResult {
+        // Headers, MainChain, ConfirmedIndex
+        let words = (args.len() as u64 + 31) / 32;
+        let cost = Self::db_reads_cost(3)
+            .saturating_add(BASE_GAS)
+            .saturating_add(WORD_GAS.saturating_mul(words));
+        ensure_gas(cost, target_gas)?;
+
+        let block_hash =
+            H256::from_slice(read_word(args, 0).ok_or_else(|| revert("bad block hash", cost))?);
+        let raw_tx = read_bytes(args, 1).ok_or_else(|| revert("bad raw tx", cost))?;
+        let raw_proof = read_bytes(args, 2).ok_or_else(|| revert("bad merkle proof", cost))?;
+
+        let tx: Transaction =
+            deserialize(Reader::new(raw_tx)).map_err(|_| revert("deserialize tx failed", cost))?;
+        let merkle_proof: PartialMerkleTree = deserialize(Reader::new(raw_proof))
+            .map_err(|_| revert("deserialize merkle proof failed", cost))?;
+
+        let included =
+            xpallet_gateway_bitcoin::Pallet::<R>::verify_tx_inclusion(tx, block_hash, merkle_proof)
+                .is_ok();
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output: encode_u256(included as u64).to_vec(),
+            logs: Default::default(),
+        })
+    }
+
+    fn confirmed_height(target_gas: Option<u64>) -> PrecompileResult {
+        let cost = Self::db_reads_cost(1).saturating_add(BASE_GAS);
+        ensure_gas(cost, target_gas)?;
+
+        let height = xpallet_gateway_bitcoin::Pallet::<R>::confirmed_height().unwrap_or_default();
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output: encode_u256(height.into()).to_vec(),
+            logs: Default::default(),
+        })
+    }
+
+    fn db_reads_cost(reads: u64) -> u64 {
+        let weight = <R as frame_system::Config>::DbWeight::get().reads(reads);
+        <R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+    }
+}
+
+/// Read the `index`-th 32-bytes word of the abi encoded arguments.
+fn read_word(args: &[u8], index: usize) -> Option<&[u8]> {
+    let start = index.checked_mul(32)?;
+    args.get(start..start.checked_add(32)?)
+}
+
+/// Read the word as an offset or a length, which must fit in `usize`.
+fn read_usize(args: &[u8], offset: usize) -> Option<usize> {
+    let word = args.get(offset..offset.checked_add(32)?)?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(value)).ok()
+}
+
+/// Read the dynamic `bytes` argument whose offset is at the `index`-th head word.
+fn read_bytes(args: &[u8], index: usize) -> Option<&[u8]> {
+    let offset = read_usize(args, index.checked_mul(32)?)?;
+    let len = read_usize(args, offset)?;
+    let start = offset.checked_add(32)?;
+    args.get(start..start.checked_add(len)?)
+}
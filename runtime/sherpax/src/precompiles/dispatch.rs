@@ -0,0 +1,80 @@
+//! Substrate call dispatch precompile with a call filter.
+//!
+//! The input is the SCALE encoded runtime `Call`, which is dispatched with the signed origin of
+//! the account mapped from the EVM caller if it passes the filter `F`. The storage changes of a
+//! failed dispatch are rolled back and the revert output carries the dispatch error.
+
+use codec::{Decode, DecodeLimit};
+use fp_evm::{Context, ExitSucceed, Precompile, PrecompileOutput, PrecompileResult};
+use frame_support::{
+    dispatch::{DispatchErrorWithPostInfo, Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    storage::{with_transaction, TransactionOutcome},
+    traits::Contains,
+    weights::{DispatchClass, Pays},
+};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_std::marker::PhantomData;
+
+use super::{ensure_gas, revert};
+
+/// The maximum depth of the nested calls in the input.
+const MAX_DECODE_DEPTH: u32 = 8;
+/// The cost of decoding and filtering the call, same as the `Sha256` precompile.
+const DECODE_GAS: u64 = 60;
+
+pub struct Dispatch<R, F>(PhantomData<(R, F)>);
+
+impl<R, F> Precompile for Dispatch<R, F>
+where
+    R: pallet_evm::Config,
+    R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+    <R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+    F: Contains<R::Call>,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        is_static: bool,
+    ) -> PrecompileResult {
+        ensure_gas(DECODE_GAS, target_gas)?;
+        if is_static {
+            return Err(revert("can't dispatch in static call", DECODE_GAS));
+        }
+        let call = R::Call::decode_all_with_depth_limit(MAX_DECODE_DEPTH, &mut &*input)
+            .map_err(|_| revert("decode call failed", DECODE_GAS))?;
+
+        let info = call.get_dispatch_info();
+        if info.pays_fee != Pays::Yes || info.class != DispatchClass::Normal {
+            return Err(revert("call not dispatchable", DECODE_GAS));
+        }
+        if !F::contains(&call) {
+            return Err(revert("call not allowed", DECODE_GAS));
+        }
+        // Charge the declared weight upfront, the unused weight is refunded after the dispatch.
+        let max_cost = R::GasWeightMapping::weight_to_gas(info.weight).saturating_add(DECODE_GAS);
+        ensure_gas(max_cost, target_gas)?;
+
+        let origin = R::AddressMapping::into_account_id(context.caller);
+        let result = with_transaction(|| match call.dispatch(Some(origin).into()) {
+            Ok(post_info) => TransactionOutcome::Commit(Ok(post_info)),
+            Err(err) => TransactionOutcome::Rollback(Err(err)),
+        });
+        match result {
+            Ok(post_info) => {
+                let weight = post_info.actual_weight.unwrap_or(info.weight);
+                Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Stopped,
+                    cost: R::GasWeightMapping::weight_to_gas(weight).saturating_add(DECODE_GAS),
+                    output: Default::default(),
+                    logs: Default::default(),
+                })
+            }
+            Err(DispatchErrorWithPostInfo { post_info, error }) => {
+                let weight = post_info.actual_weight.unwrap_or(info.weight);
+                let cost = R::GasWeightMapping::weight_to_gas(weight).saturating_add(DECODE_GAS);
+                Err(revert(error.into(), cost))
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! The read-only erc20 mirror of the native currency.
+//!
+//! It is the address of the `Transfer` logs of the teleports of `AssetsBridge`, so that the
+//! ethereum tooling can follow the native balances as an erc20 token:
+//!
+//! ```solidity
+//! interface NativeErc20 {
+//!     function name() external view returns (string memory);
+//!     function symbol() external view returns (string memory);
+//!     function decimals() external view returns (uint8);
+//!     function totalSupply() external view returns (uint256);
+//!     // The free balance of the account mapped from `owner`, same as `eth_getBalance`.
+//!     function balanceOf(address owner) external view returns (uint256);
+//! }
+//! ```
+//!
+//! The native currency is transferred by the value of the evm transactions, so the transfers
+//! through the mirror are rejected.
+
+use fp_evm::{Context, ExitSucceed, Precompile, PrecompileOutput, PrecompileResult};
+use frame_support::traits::{Currency, Get};
+use pallet_evm::GasWeightMapping;
+use sp_core::{H160, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{marker::PhantomData, prelude::*};
+
+use super::{encode_u256, ensure_gas, revert};
+
+/// bytes4(keccak256("name()"))
+const SELECTOR_NAME: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+/// bytes4(keccak256("symbol()"))
+const SELECTOR_SYMBOL: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+/// bytes4(keccak256("decimals()"))
+const SELECTOR_DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+/// bytes4(keccak256("totalSupply()"))
+const SELECTOR_TOTAL_SUPPLY: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+/// bytes4(keccak256("balanceOf(address)"))
+const SELECTOR_BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+const NAME: &str = "SherpaX";
+const SYMBOL: &str = "KSX";
+const DECIMALS: u64 = 18;
+
+/// The base cost of decoding the input, same as the `Sha256` precompile.
+const BASE_GAS: u64 = 60;
+
+pub struct NativeErc20<R>(PhantomData<R>);
+
+impl<R> Precompile for NativeErc20<R>
+where
+    R: pallet_evm::Config,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+        _is_static: bool,
+    ) -> PrecompileResult {
+        if input.len() < 4 {
+            return Err(revert("input too short", 0));
+        }
+        let (selector, args) = input.split_at(4);
+
+        match selector {
+            s if s == SELECTOR_NAME => Self::returns(encode_string(NAME), 0, target_gas),
+            s if s == SELECTOR_SYMBOL => Self::returns(encode_string(SYMBOL), 0, target_gas),
+            s if s == SELECTOR_DECIMALS => {
+                Self::returns(encode_u256(DECIMALS).to_vec(), 0, target_gas)
+            }
+            s if s == SELECTOR_TOTAL_SUPPLY => {
+                let total: u128 = <<R as pallet_evm::Config>::Currency as Currency<
+                    R::AccountId,
+                >>::total_issuance()
+                .unique_saturated_into();
+                Self::returns(encode_balance(U256::from(total)), 1, target_gas)
+            }
+            s if s == SELECTOR_BALANCE_OF => {
+                let owner = match args.get(..32) {
+                    Some(word) if word[..12].iter().all(|b| *b == 0) => {
+                        H160::from_slice(&word[12..])
+                    }
+                    _ => return Err(revert("bad owner", BASE_GAS)),
+                };
+                let balance = pallet_evm::Pallet::<R>::account_basic(&owner).balance;
+                Self::returns(encode_balance(balance), 1, target_gas)
+            }
+            _ => Err(revert("read-only native token", 0)),
+        }
+    }
+}
+
+impl<R> NativeErc20<R>
+where
+    R: pallet_evm::Config,
+{
+    fn returns(output: Vec<u8>, reads: u64, target_gas: Option<u64>) -> PrecompileResult {
+        let weight = <R as frame_system::Config>::DbWeight::get().reads(reads);
+        let cost = <R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+            .saturating_add(BASE_GAS);
+        ensure_gas(cost, target_gas)?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost,
+            output,
+            logs: Default::default(),
+        })
+    }
+}
+
+fn encode_balance(balance: U256) -> Vec<u8> {
+    let mut output = [0u8; 32];
+    balance.to_big_endian(&mut output);
+    output.to_vec()
+}
+
+/// The abi encoded `string` return value.
+fn encode_string(value: &str) -> Vec<u8> {
+    let padded_len = (value.len() + 31) / 32 * 32;
+    let mut output = Vec::with_capacity(32 + 32 + padded_len);
+    output.extend_from_slice(&encode_u256(32));
+    output.extend_from_slice(&encode_u256(value.len() as u64));
+    output.extend_from_slice(value.as_bytes());
+    output.resize(32 + 32 + padded_len, 0);
+    output
+}
@@ -0,0 +1,283 @@
+use codec::Encode;
+use fp_evm::{Context, ExitError, ExitReason, ExitRevert, ExitSucceed, PrecompileFailure};
+use frame_support::{
+    assert_ok,
+    traits::{Currency, GenesisBuild, OnFinalize},
+    weights::GetDispatchInfo,
+};
+use pallet_evm::{AddressMapping, GasWeightMapping, Runner};
+use sp_core::{H160, U256};
+
+use super::*;
+use crate::{
+    AccountId, Assets, AssetsBridge, Balances, Chain, Ethereum, EvmCaller, NativeTokenAddress,
+    Origin, Runtime, SherpaXGasWeightMapping, System, UNITS,
+};
+
+const ASSET_ID: u32 = 100;
+const GAS_LIMIT: u64 = 1_000_000;
+
+fn caller() -> H160 {
+    H160::from_low_u64_be(0x1234)
+}
+
+fn mapped_account() -> AccountId {
+    <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(caller())
+}
+
+fn owner() -> AccountId {
+    AccountId::from([1u8; 32])
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::default()
+        .build_storage::<Runtime>()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            ASSET_ID,
+            owner().into(),
+            true,
+            1
+        ));
+        assert_ok!(Assets::mint(
+            Origin::signed(owner()),
+            ASSET_ID,
+            mapped_account().into(),
+            1_000
+        ));
+    });
+    ext
+}
+
+fn call_dispatch(call: Call) -> pallet_evm::CallInfo {
+    <Runtime as pallet_evm::Config>::Runner::call(
+        caller(),
+        hash(1025),
+        call.encode(),
+        U256::zero(),
+        GAS_LIMIT,
+        None,
+        None,
+        None,
+        vec![],
+        false,
+        <Runtime as pallet_evm::Config>::config(),
+    )
+    .expect("the evm call is valid")
+}
+
+fn assert_reverted(info: &pallet_evm::CallInfo, reason: &str) {
+    assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+    assert_eq!(&info.value[..4], &SELECTOR_ERROR_STRING);
+    assert_eq!(&info.value[68..68 + reason.len()], reason.as_bytes());
+}
+
+fn transfer_call(amount: u128) -> Call {
+    Call::Assets(pallet_assets::Call::transfer {
+        id: ASSET_ID,
+        target: owner().into(),
+        amount,
+    })
+}
+
+#[test]
+fn dispatch_allowed_call() {
+    new_test_ext().execute_with(|| {
+        let info = call_dispatch(transfer_call(400));
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+        assert_eq!(Assets::balance(ASSET_ID, mapped_account()), 600);
+        assert_eq!(Assets::balance(ASSET_ID, owner()), 400);
+    });
+}
+
+#[test]
+fn dispatch_denied_calls() {
+    new_test_ext().execute_with(|| {
+        let sudo = Call::Sudo(pallet_sudo::Call::sudo {
+            call: Box::new(transfer_call(400)),
+        });
+        let force_transfer = Call::Assets(pallet_assets::Call::force_transfer {
+            id: ASSET_ID,
+            source: mapped_account().into(),
+            dest: owner().into(),
+            amount: 400,
+        });
+        let batch = Call::Utility(pallet_utility::Call::batch {
+            calls: vec![transfer_call(400)],
+        });
+        let trustee_election =
+            Call::XGatewayCommon(xpallet_gateway_common::Call::cancel_trustee_election {
+                chain: Chain::Bitcoin,
+            });
+        for call in [sudo, force_transfer, batch, trustee_election] {
+            let info = call_dispatch(call);
+            assert_reverted(&info, "call not allowed");
+        }
+        assert_eq!(Assets::balance(ASSET_ID, mapped_account()), 1_000);
+    });
+}
+
+#[test]
+fn dispatch_failed_call_reverts() {
+    new_test_ext().execute_with(|| {
+        let info = call_dispatch(transfer_call(2_000));
+        assert_reverted(&info, "BalanceLow");
+        assert_eq!(Assets::balance(ASSET_ID, mapped_account()), 1_000);
+    });
+}
+
+#[test]
+fn dispatch_charges_the_call_weight() {
+    new_test_ext().execute_with(|| {
+        let call = transfer_call(400);
+        let cost = SherpaXGasWeightMapping::weight_to_gas(call.get_dispatch_info().weight);
+        let context = Context {
+            address: hash(1025),
+            caller: caller(),
+            apparent_value: U256::zero(),
+        };
+        assert_eq!(
+            Dispatch::<Runtime, DispatchCallFilter>::execute(
+                &call.encode(),
+                Some(cost),
+                &context,
+                false
+            ),
+            Err(PrecompileFailure::Error {
+                exit_status: ExitError::OutOfGas
+            })
+        );
+        assert_eq!(Assets::balance(ASSET_ID, mapped_account()), 1_000);
+
+        let output = Dispatch::<Runtime, DispatchCallFilter>::execute(
+            &call.encode(),
+            Some(GAS_LIMIT),
+            &context,
+            false,
+        )
+        .unwrap();
+        assert!(output.cost > 0 && output.cost <= cost + 60);
+        assert_eq!(Assets::balance(ASSET_ID, mapped_account()), 600);
+
+        // no state change in the static call
+        assert!(Dispatch::<Runtime, DispatchCallFilter>::execute(
+            &call.encode(),
+            Some(GAS_LIMIT),
+            &context,
+            true
+        )
+        .is_err());
+    });
+}
+
+fn call_native_erc20(input: Vec<u8>) -> pallet_evm::CallInfo {
+    <Runtime as pallet_evm::Config>::Runner::call(
+        caller(),
+        hash(2049),
+        input,
+        U256::zero(),
+        GAS_LIMIT,
+        None,
+        None,
+        None,
+        vec![],
+        false,
+        <Runtime as pallet_evm::Config>::config(),
+    )
+    .expect("the evm call is valid")
+}
+
+#[test]
+fn native_erc20_reads_the_native_balances() {
+    new_test_ext().execute_with(|| {
+        Balances::make_free_balance_be(&mapped_account(), 10 * UNITS);
+
+        let info = call_native_erc20(pallet_assets_bridge::balance_of_encode(caller()));
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(
+            pallet_assets_bridge::u128_decode(&info.value),
+            Some(10 * UNITS)
+        );
+
+        let info = call_native_erc20(vec![0x31, 0x3c, 0xe5, 0x67]);
+        assert_eq!(pallet_assets_bridge::u128_decode(&info.value), Some(18));
+
+        let info = call_native_erc20(vec![0x95, 0xd8, 0x9b, 0x41]);
+        assert_eq!(&info.value[64..67], b"KSX");
+    });
+}
+
+#[test]
+fn native_erc20_rejects_the_transfers() {
+    new_test_ext().execute_with(|| {
+        Balances::make_free_balance_be(&mapped_account(), 10 * UNITS);
+
+        let info = call_native_erc20(pallet_assets_bridge::transfer_encode(
+            H160::from_low_u64_be(0x5678),
+            UNITS,
+        ));
+        assert_reverted(&info, "read-only native token");
+        assert_eq!(Balances::free_balance(&mapped_account()), 10 * UNITS);
+    });
+}
+
+#[test]
+fn teleport_logs_should_be_in_the_ethereum_receipts() {
+    new_test_ext().execute_with(|| {
+        let token = NativeTokenAddress::get().unwrap();
+        assert_eq!(token, hash(2049));
+        Balances::make_free_balance_be(&owner(), 10 * UNITS);
+
+        assert_ok!(AssetsBridge::teleport(
+            Origin::signed(owner()),
+            UNITS,
+            pallet_assets_bridge::ActionType::Direct(caller()),
+        ));
+        assert_ok!(AssetsBridge::teleport(
+            Origin::signed(owner()),
+            2 * UNITS,
+            pallet_assets_bridge::ActionType::Direct(caller()),
+        ));
+        <Ethereum as OnFinalize<_>>::on_finalize(System::block_number());
+
+        let logs = [
+            pallet_assets_bridge::transfer_log(token, H160::zero(), caller(), UNITS),
+            pallet_assets_bridge::transfer_log(token, H160::zero(), caller(), 2 * UNITS),
+        ];
+        let block = Ethereum::current_block().unwrap();
+        let statuses = Ethereum::current_transaction_statuses().unwrap();
+        let receipts = Ethereum::current_receipts().unwrap();
+        assert_eq!(block.transactions.len(), 2);
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(receipts.len(), 2);
+
+        for (index, log) in logs.iter().enumerate() {
+            let status = &statuses[index];
+            assert_eq!(status.transaction_index, index as u32);
+            assert_eq!(status.transaction_hash, block.transactions[index].hash());
+            assert_eq!(status.from, EvmCaller::get());
+            assert_eq!(status.to, Some(token));
+            assert_eq!(status.logs, vec![log.clone()]);
+            match &receipts[index] {
+                pallet_ethereum::Receipt::Legacy(data) => {
+                    assert_eq!(data.status_code, 1);
+                    assert_eq!(data.logs, vec![log.clone()]);
+                }
+                _ => panic!("the receipts are legacy"),
+            }
+        }
+        // the transaction hashes are unique
+        assert_ne!(statuses[0].transaction_hash, statuses[1].transaction_hash);
+
+        // `eth_getLogs` filters the blocks by the logs bloom
+        let bloom = block.header.logs_bloom;
+        assert!(bloom.contains_input(ethereum_types::BloomInput::Raw(&token[..])));
+        assert!(bloom.contains_input(ethereum_types::BloomInput::Raw(
+            &pallet_assets_bridge::transfer_topic()[..]
+        )));
+    });
+}
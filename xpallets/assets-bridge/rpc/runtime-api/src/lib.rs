@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use sp_std::vec::Vec;
+
+use codec::Codec;
+
+pub use pallet_assets_bridge::{BridgeBalance, Direction, RegisteredAsset};
+pub use sherpax_primitives::AssetId;
+pub use sp_core::H160;
+
+sp_api::decl_runtime_apis! {
+    pub trait AssetsBridgeApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        fn evm_account(account: AccountId) -> Option<H160>;
+
+        fn substrate_account(evm_address: H160) -> Option<AccountId>;
+
+        fn registered_assets() -> Vec<RegisteredAsset<AssetId, BlockNumber>>;
+
+        fn balance(asset_id: AssetId, account: AccountId) -> BridgeBalance<Balance>;
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::{runtime_error_into_rpc_err, Result};
+
+use pallet_assets_bridge_rpc_runtime_api::{
+    AssetId, AssetsBridgeApi as AssetsBridgeRuntimeApi, BridgeBalance, Direction, RegisteredAsset,
+    H160,
+};
+
+pub struct AssetsBridge<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> AssetsBridge<C, B> {
+    /// Create new `AssetsBridge` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[rpc]
+pub trait AssetsBridgeApi<BlockHash, AccountId, Balance, BlockNumber>
+where
+    Balance: Display + FromStr,
+{
+    /// Return the mapped evm address of the substrate account
+    #[rpc(name = "assetsbridge_evmAccount")]
+    fn evm_account(&self, account: AccountId, at: Option<BlockHash>) -> Result<Option<H160>>;
+
+    /// Return the mapped substrate account of the evm address
+    #[rpc(name = "assetsbridge_substrateAccount")]
+    fn substrate_account(
+        &self,
+        evm_address: H160,
+        at: Option<BlockHash>,
+    ) -> Result<Option<AccountId>>;
+
+    /// Return all the registered assets with the pause status
+    #[rpc(name = "assetsbridge_registeredAssets")]
+    fn registered_assets(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<RpcRegisteredAsset<BlockNumber>>>;
+
+    /// Return the substrate assets and erc20 tokens balance of the account
+    #[rpc(name = "assetsbridge_balance")]
+    fn balance(
+        &self,
+        asset_id: AssetId,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<RpcBridgeBalance<Balance>>;
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    AssetsBridgeApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber>
+    for AssetsBridge<C, Block>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AssetsBridgeRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    Block: BlockT,
+    AccountId: Clone + Display + FromStr + Codec,
+    Balance: Clone + Display + FromStr + Codec,
+    BlockNumber: Clone + Display + Codec,
+{
+    fn evm_account(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<H160>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.evm_account(&at, account)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn substrate_account(
+        &self,
+        evm_address: H160,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.substrate_account(&at, evm_address)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn registered_assets(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<RpcRegisteredAsset<BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.registered_assets(&at)
+            .map(|assets| assets.into_iter().map(Into::into).collect())
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn balance(
+        &self,
+        asset_id: AssetId,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RpcBridgeBalance<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.balance(&at, asset_id, account)
+            .map(Into::into)
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRegisteredAsset<BlockNumber> {
+    pub asset_id: AssetId,
+    pub erc20: H160,
+    pub native: bool,
+    pub back_foreign: bool,
+    pub paused: Vec<RpcPausedDirection<BlockNumber>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPausedDirection<BlockNumber> {
+    pub direction: Direction,
+    /// None means until unpause
+    pub expired_at: Option<BlockNumber>,
+}
+
+impl<BlockNumber> From<RegisteredAsset<AssetId, BlockNumber>> for RpcRegisteredAsset<BlockNumber> {
+    fn from(asset: RegisteredAsset<AssetId, BlockNumber>) -> Self {
+        Self {
+            asset_id: asset.asset_id,
+            erc20: asset.erc20,
+            native: asset.native,
+            back_foreign: asset.back_foreign,
+            paused: asset
+                .paused
+                .into_iter()
+                .map(|(direction, expired_at)| RpcPausedDirection {
+                    direction,
+                    expired_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBridgeBalance<Balance: Display + FromStr> {
+    pub evm_address: Option<H160>,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub substrate: Balance,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub erc20: Balance,
+}
+
+impl<Balance: Display + FromStr> From<BridgeBalance<Balance>> for RpcBridgeBalance<Balance> {
+    fn from(balance: BridgeBalance<Balance>) -> Self {
+        Self {
+            evm_address: balance.evm_address,
+            substrate: balance.substrate,
+            erc20: balance.erc20,
+        }
+    }
+}
@@ -45,3 +45,121 @@ pub fn burn_from_encode(account: H160, amount: u128) -> Vec<u8> {
 
     v
 }
+
+pub fn transfer_encode(to: H160, amount: u128) -> Vec<u8> {
+    // signature ++ to ++ amount
+    let length = 16 + 20 + 32;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("transfer(address,uint256)"))
+    // 0xa9059cbb
+    let sig_transfer = [169u8, 5, 156, 187];
+
+    // first 16-bytes
+    v.extend_from_slice(&sig_transfer[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+
+    // second 20-bytes
+    v.extend_from_slice(&to[..]);
+
+    // third 32-bytes
+    v.extend_from_slice(&[0u8; 16][..]);
+    v.extend_from_slice(&amount.to_be_bytes()[..]);
+
+    v
+}
+
+pub fn transfer_from_encode(from: H160, to: H160, amount: u128) -> Vec<u8> {
+    // signature ++ from ++ to ++ amount
+    let length = 16 + 20 + 12 + 20 + 32;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("transferFrom(address,address,uint256)"))
+    // 0x23b872dd
+    let sig_transfer_from = [35u8, 184, 114, 221];
+
+    // first 16-bytes
+    v.extend_from_slice(&sig_transfer_from[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+
+    // second 20-bytes
+    v.extend_from_slice(&from[..]);
+
+    // third 32-bytes
+    v.extend_from_slice(&[0u8; 12][..]);
+    v.extend_from_slice(&to[..]);
+
+    // fourth 32-bytes
+    v.extend_from_slice(&[0u8; 16][..]);
+    v.extend_from_slice(&amount.to_be_bytes()[..]);
+
+    v
+}
+
+pub fn balance_of_encode(account: H160) -> Vec<u8> {
+    // signature ++ account
+    let length = 16 + 20;
+    let mut v = Vec::with_capacity(length);
+
+    // bytes4(keccak256(bytes("balanceOf(address)"))
+    // 0x70a08231
+    let sig_balance_of = [112u8, 160, 130, 49];
+
+    // first 16-bytes
+    v.extend_from_slice(&sig_balance_of[..]);
+    v.extend_from_slice(&[0u8; 12][..]);
+
+    // second 20-bytes
+    v.extend_from_slice(&account[..]);
+
+    v
+}
+
+/// Decode the returned `uint256`, which must fit in `u128`.
+pub fn u128_decode(output: &[u8]) -> Option<u128> {
+    if output.len() != 32 || output[..16].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let mut amount = [0u8; 16];
+    amount.copy_from_slice(&output[16..]);
+    Some(u128::from_be_bytes(amount))
+}
+
+/// Decode the returned `bool`.
+/// Note: some erc20 tokens return nothing on `transfer` and `transferFrom`.
+pub fn bool_decode(output: &[u8]) -> bool {
+    match output.len() {
+        0 => true,
+        32 => output[..31].iter().all(|b| *b == 0) && output[31] == 1,
+        _ => false,
+    }
+}
+
+/// The topic of the erc20 event `Transfer(address,address,uint256)`.
+pub fn transfer_topic() -> H256 {
+    // keccak256(bytes("Transfer(address,address,uint256)"))
+    // 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef
+    H256::from(keccak_256(b"Transfer(address,address,uint256)"))
+}
+
+/// Build the erc20 `Transfer` log of `contract`, the zero address means mint or burn.
+pub fn transfer_log(contract: H160, from: H160, to: H160, amount: u128) -> Log {
+    // topics: signature ++ indexed from ++ indexed to
+    let topics = vec![transfer_topic(), H256::from(from), H256::from(to)];
+
+    // data: amount
+    let mut data = Vec::with_capacity(32);
+    data.extend_from_slice(&[0u8; 16][..]);
+    data.extend_from_slice(&amount.to_be_bytes()[..]);
+
+    Log {
+        address: contract,
+        topics,
+        data,
+    }
+}
+
+/// Whether the log is a `Transfer` event of `contract`.
+pub fn is_transfer_log(log: &Log, contract: H160) -> bool {
+    log.address == contract && log.topics.first() == Some(&transfer_topic())
+}
@@ -0,0 +1,237 @@
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::fungibles::{Create, Mutate};
+use frame_system::RawOrigin;
+use sp_core::crypto::KeyTypeId;
+
+use super::*;
+use crate::Pallet as AssetsBridge;
+
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"brdg");
+const EVM_ACCOUNT: H160 = H160([0x22; 20]);
+const ERC20: H160 = H160([1; 20]);
+// A contract which returns the 32 bytes word `1` for any call,
+// which is `true` and the balance `1` for the erc20 abi.
+const ERC20_CODE: [u8; 10] = [0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+
+/// Generate an ecdsa key in the keystore, returns the evm address of the key and
+/// the signature of the digest built from the address.
+fn sign(digest: impl Fn(H160) -> [u8; 32]) -> (H160, EcdsaSignature) {
+    let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+    let sign = |digest: &[u8; 32]| {
+        sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, digest)
+            .expect("the key was generated; qed")
+    };
+    let address = eip712_recover(&sign(&[0u8; 32]), &[0u8; 32]).expect("valid signature; qed");
+    (address, sign(&digest(address)))
+}
+
+fn claim<T: Config>(who: &T::AccountId, eth_address: H160) {
+    <T as pallet_assets::Config>::Currency::make_free_balance_be(
+        who,
+        ReserveBalanceOf::<T>::max_value() / 2u32.into(),
+    );
+    <T as pallet_assets::Config>::Currency::reserve(who, T::ClaimBond::get()).unwrap();
+    SubAccounts::<T>::insert(eth_address, who);
+    EvmAccounts::<T>::insert(who, eth_address);
+}
+
+/// Create the asset and register it with the `ERC20_CODE` contract.
+fn register_asset<T: Config>() -> T::AssetId {
+    let asset_id = T::AssetId::max_value();
+    let owner = AddressMappingOf::<T>::into_account_id(T::EvmCaller::get());
+    // the asset may have been created
+    let _ = <pallet_assets::Pallet<T> as Create<T::AccountId>>::create(
+        asset_id,
+        owner,
+        true,
+        1u32.into(),
+    );
+    pallet_evm::AccountCodes::<T>::insert(ERC20, ERC20_CODE.to_vec());
+    Erc20s::<T>::insert(asset_id, ERC20);
+    AssetIds::<T>::insert(ERC20, asset_id);
+    asset_id
+}
+
+benchmarks! {
+    where_clause {
+        where DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>
+    }
+
+    claim_account {
+        let caller: T::AccountId = whitelisted_caller();
+        <T as pallet_assets::Config>::Currency::make_free_balance_be(
+            &caller,
+            ReserveBalanceOf::<T>::max_value() / 2u32.into(),
+        );
+        let (eth_address, signature) = sign(|_| AssetsBridge::<T>::claim_digest(&caller, 0));
+    }: _(RawOrigin::Signed(caller.clone()), eth_address, signature)
+    verify {
+        assert_eq!(AssetsBridge::<T>::evm_accounts(&caller), Some(eth_address));
+    }
+
+    dissolve {
+        let caller: T::AccountId = whitelisted_caller();
+        claim::<T>(&caller, EVM_ACCOUNT);
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(AssetsBridge::<T>::evm_accounts(&caller).is_none());
+    }
+
+    dissolve_with_signature {
+        let caller: T::AccountId = whitelisted_caller();
+        let who: T::AccountId = account("claimer", 0, 0);
+        let (eth_address, signature) =
+            sign(|address| AssetsBridge::<T>::dissolve_digest(&who, address, 0));
+        claim::<T>(&who, eth_address);
+    }: _(RawOrigin::Signed(caller), eth_address, signature)
+    verify {
+        assert!(AssetsBridge::<T>::sub_accounts(eth_address).is_none());
+    }
+
+    deposit {
+        let caller: T::AccountId = whitelisted_caller();
+        claim::<T>(&caller, EVM_ACCOUNT);
+        let asset_id = register_asset::<T>();
+        let amount: T::Balance = 1_000u32.into();
+        pallet_assets::Pallet::<T>::mint_into(asset_id, &caller, amount)?;
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+    verify {
+        assert!(pallet_assets::Pallet::<T>::balance(asset_id, &caller).is_zero());
+    }
+
+    withdraw {
+        let caller: T::AccountId = whitelisted_caller();
+        claim::<T>(&caller, EVM_ACCOUNT);
+        let asset_id = register_asset::<T>();
+        let amount: T::Balance = 1_000u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+    verify {
+        assert_eq!(pallet_assets::Pallet::<T>::balance(asset_id, &caller), amount);
+    }
+
+    teleport {
+        let caller: T::AccountId = whitelisted_caller();
+        claim::<T>(&caller, EVM_ACCOUNT);
+        <T as pallet_evm::Config>::Currency::make_free_balance_be(
+            &caller,
+            BalanceOf::<T>::max_value() / 2u32.into(),
+        );
+        let amount: BalanceOf<T> = 1_000u32.into();
+    }: _(RawOrigin::Signed(caller), amount, ActionType::FromSubToEth)
+    verify {
+        let evm_account = AddressMappingOf::<T>::into_account_id(EVM_ACCOUNT);
+        assert!(!<T as pallet_evm::Config>::Currency::free_balance(&evm_account).is_zero());
+    }
+
+    register {
+        let asset_id = T::AssetId::max_value() - 1u32.into();
+        let erc20 = H160([2; 20]);
+    }: _(RawOrigin::Root, asset_id, erc20)
+    verify {
+        assert_eq!(AssetsBridge::<T>::erc20s(asset_id), Some(erc20));
+    }
+
+    register_native {
+        let asset_id = T::AssetId::max_value() - 2u32.into();
+        let erc20 = H160([3; 20]);
+    }: _(RawOrigin::Root, asset_id, erc20, 1u32.into(), false)
+    verify {
+        assert_eq!(AssetsBridge::<T>::erc20s(asset_id), Some(erc20));
+    }
+
+    pause {
+        let n in 1 .. T::MaxAssets::get();
+        let asset_id = register_asset::<T>();
+        for i in 1..n {
+            let erc20 = H160::from_low_u64_be(i as u64);
+            AssetIds::<T>::insert(erc20, T::AssetId::max_value() - (i + 2).into());
+        }
+    }: _(RawOrigin::Root, None)
+    verify {
+        assert!(AssetsBridge::<T>::is_in_emergency(asset_id));
+        assert_eq!(AssetIds::<T>::count(), n);
+    }
+
+    unpause {
+        let asset_id = register_asset::<T>();
+        AssetsBridge::<T>::do_pause(asset_id, T::BlockNumber::max_value());
+    }: _(RawOrigin::Root, Some(asset_id))
+    verify {
+        assert!(!AssetsBridge::<T>::is_in_emergency(asset_id));
+    }
+
+    pause_direction {
+        let asset_id = register_asset::<T>();
+    }: _(RawOrigin::Root, asset_id, Direction::Deposit, Some(10u32.into()))
+    verify {
+        assert!(AssetsBridge::<T>::is_paused(asset_id, Direction::Deposit));
+    }
+
+    unpause_direction {
+        let asset_id = register_asset::<T>();
+        Paused::<T>::insert(asset_id, Direction::Deposit, T::BlockNumber::max_value());
+    }: _(RawOrigin::Root, asset_id, Direction::Deposit)
+    verify {
+        assert!(!AssetsBridge::<T>::is_paused(asset_id, Direction::Deposit));
+    }
+
+    back_foreign {
+        let asset_id = register_asset::<T>();
+    }: _(RawOrigin::Root, asset_id, false)
+    verify {
+        assert!(AssetsBridge::<T>::is_in_back_foreign(asset_id));
+    }
+
+    set_admin {
+        let admin: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Root, T::Lookup::unlookup(admin.clone()))
+    verify {
+        assert_eq!(AssetsBridge::<T>::admin_key(), Some(admin));
+    }
+
+    set_legacy_claim {
+    }: _(RawOrigin::Root, false)
+    verify {
+        assert!(!AssetsBridge::<T>::legacy_claim());
+    }
+
+    force_unregister {
+        let asset_id = register_asset::<T>();
+    }: _(RawOrigin::Root, asset_id)
+    verify {
+        assert!(AssetsBridge::<T>::erc20s(asset_id).is_none());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+    use sp_keystore::{testing::KeyStore, KeystoreExt};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_benchmarks() {
+        let mut ext = new_test_ext();
+        ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+        ext.execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_claim_account());
+            assert_ok!(Pallet::<Test>::test_benchmark_dissolve());
+            assert_ok!(Pallet::<Test>::test_benchmark_dissolve_with_signature());
+            assert_ok!(Pallet::<Test>::test_benchmark_deposit());
+            assert_ok!(Pallet::<Test>::test_benchmark_withdraw());
+            assert_ok!(Pallet::<Test>::test_benchmark_teleport());
+            assert_ok!(Pallet::<Test>::test_benchmark_register());
+            assert_ok!(Pallet::<Test>::test_benchmark_register_native());
+            assert_ok!(Pallet::<Test>::test_benchmark_pause());
+            assert_ok!(Pallet::<Test>::test_benchmark_unpause());
+            assert_ok!(Pallet::<Test>::test_benchmark_pause_direction());
+            assert_ok!(Pallet::<Test>::test_benchmark_unpause_direction());
+            assert_ok!(Pallet::<Test>::test_benchmark_back_foreign());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_admin());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_legacy_claim());
+            assert_ok!(Pallet::<Test>::test_benchmark_force_unregister());
+        });
+    }
+}
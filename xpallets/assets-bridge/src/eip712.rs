@@ -0,0 +1,78 @@
+use super::*;
+
+/// The EIP-712 domain type.
+pub const EIP712_DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+/// The typed struct signed by the evm address to claim a substrate account.
+pub const CLAIM_TYPE: &[u8] = b"Claim(bytes substrateAccount,uint256 nonce)";
+/// The typed struct signed by the evm address to dissolve a substrate account.
+pub const DISSOLVE_TYPE: &[u8] =
+    b"Dissolve(bytes substrateAccount,address evmAddress,uint256 nonce)";
+
+/// The EIP-712 domain name.
+pub const DOMAIN_NAME: &[u8] = b"SherpaX Assets Bridge";
+/// The EIP-712 domain version.
+pub const DOMAIN_VERSION: &[u8] = b"1";
+
+fn u256_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn address_word(address: H160) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_bytes());
+    word
+}
+
+/// Constructs the EIP-712 domain separator.
+///
+/// `verifying_contract` is a pseudo-address standing for this pallet,
+/// there is no contract deployed at it.
+pub fn domain_separator(chain_id: u64, verifying_contract: H160) -> [u8; 32] {
+    let mut data = Vec::with_capacity(5 * 32);
+    data.extend_from_slice(&keccak_256(EIP712_DOMAIN_TYPE));
+    data.extend_from_slice(&keccak_256(DOMAIN_NAME));
+    data.extend_from_slice(&keccak_256(DOMAIN_VERSION));
+    data.extend_from_slice(&u256_word(chain_id.into()));
+    data.extend_from_slice(&address_word(verifying_contract));
+    keccak_256(&data)
+}
+
+/// The `hashStruct` of `Claim`.
+pub fn claim_hash(substrate_account: &[u8], nonce: u64) -> [u8; 32] {
+    let mut data = Vec::with_capacity(3 * 32);
+    data.extend_from_slice(&keccak_256(CLAIM_TYPE));
+    data.extend_from_slice(&keccak_256(substrate_account));
+    data.extend_from_slice(&u256_word(nonce.into()));
+    keccak_256(&data)
+}
+
+/// The `hashStruct` of `Dissolve`.
+pub fn dissolve_hash(substrate_account: &[u8], evm_address: H160, nonce: u64) -> [u8; 32] {
+    let mut data = Vec::with_capacity(4 * 32);
+    data.extend_from_slice(&keccak_256(DISSOLVE_TYPE));
+    data.extend_from_slice(&keccak_256(substrate_account));
+    data.extend_from_slice(&address_word(evm_address));
+    data.extend_from_slice(&u256_word(nonce.into()));
+    keccak_256(&data)
+}
+
+/// Constructs the digest that Ethereum RPC's `eth_signTypedData_v4` would sign.
+pub fn typed_data_digest(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(2 + 2 * 32);
+    data.extend_from_slice(b"\x19\x01");
+    data.extend_from_slice(domain_separator);
+    data.extend_from_slice(struct_hash);
+    keccak_256(&data)
+}
+
+/// Attempts to recover the Ethereum address from a typed data signature signed by
+/// using the Ethereum RPC's `eth_signTypedData_v4`.
+pub fn eip712_recover(s: &EcdsaSignature, digest: &[u8; 32]) -> Option<H160> {
+    let mut res = H160::default();
+    res.0
+        .copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, digest).ok()?[..])[12..]);
+    Some(res)
+}
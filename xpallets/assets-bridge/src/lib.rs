@@ -3,31 +3,47 @@
 //! ## Overview
 //!
 //! Bridge between pallet-assets and Erc20 tokens
+//!
+//! The bridge operations emit the erc20 `Transfer` logs as `pallet_evm::Event::Log`,
+//! so that the indexers of the substrate events can follow the balance changes of the
+//! bridged tokens. The logs of each operation, together with the logs of the erc20 calls,
+//! are also passed to `OnEvmLogs`, which the runtime uses to put them into the ethereum
+//! receipts, so that `eth_getLogs` returns them.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod abi;
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 pub use abi::*;
+pub mod eip712;
+pub use eip712::*;
+pub mod migrations;
 pub mod recover;
 pub use recover::*;
+pub mod weights;
+pub use weights::WeightInfo;
 
 use codec::Encode;
 use frame_support::{
+    dispatch::PostDispatchInfo,
     ensure,
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement, IsType},
+    traits::{Currency, ExistenceRequirement, IsType, ReservableCurrency},
     transactional,
 };
-use sp_core::{ecdsa, H160, U256};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{ecdsa, H160, H256, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
-use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto, Zero};
-use sp_std::vec::Vec;
+use sp_runtime::traits::{Bounded, Saturating, StaticLookup, UniqueSaturatedInto, Zero};
+use sp_std::{vec, vec::Vec};
 
-use pallet_evm::{AddressMapping, ExitReason, Runner};
+use pallet_evm::{AddressMapping, CallInfo, ExitReason, GasWeightMapping, Log, Runner};
 
 pub type EcdsaSignature = ecdsa::Signature;
 pub type AddressMappingOf<T> = <T as pallet_evm::Config>::AddressMapping;
@@ -38,6 +54,12 @@ pub type ReserveBalanceOf<T> = <<T as pallet_assets::Config>::Currency as Curren
     <T as frame_system::Config>::AccountId,
 >>::Balance;
 
+/// The gas limit of each evm call of the bridge.
+pub const EVM_GAS_LIMIT: u64 = 3_000_000;
+/// The max evm calls of `deposit` and `withdraw`, the evm native erc20 needs
+/// `balanceOf`, `transfer(From)` and `balanceOf`.
+pub const MAX_EVM_CALLS: u64 = 3;
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
 pub enum ActionType<AssetId> {
     Direct(H160),
@@ -46,20 +68,80 @@ pub enum ActionType<AssetId> {
     BackForeign(AssetId),
 }
 
+/// The directions of assets bridge which can be paused separately.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// `deposit`: substrate assets into erc20 tokens
+    Deposit,
+    /// `withdraw`: erc20 tokens into substrate assets
+    Withdraw,
+    /// `teleport(BackForeign)`: substrate assets back to foreign chain
+    Teleport,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 3] = [Direction::Deposit, Direction::Withdraw, Direction::Teleport];
+}
+
+/// The registered asset of assets bridge.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct RegisteredAsset<AssetId, BlockNumber> {
+    pub asset_id: AssetId,
+    pub erc20: H160,
+    /// Whether the erc20 is evm native, see `register_native`.
+    pub native: bool,
+    /// Whether the asset can back foreign chain.
+    pub back_foreign: bool,
+    /// The paused directions and when the pause expires, None means until unpause.
+    pub paused: Vec<(Direction, Option<BlockNumber>)>,
+}
+
+/// The substrate assets and erc20 tokens balance of an account.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct BridgeBalance<Balance> {
+    /// The mapped evm address of the account.
+    pub evm_address: Option<H160>,
+    pub substrate: Balance,
+    pub erc20: Balance,
+}
+
+/// The handler of the evm logs of a bridge operation, i.e. the logs of the erc20 calls and the
+/// `Transfer` logs deposited by the bridge.
+pub trait OnEvmLogs {
+    /// Handle the `logs` of a bridge operation made by `from` on the contract `to`.
+    fn on_evm_logs(from: H160, to: H160, logs: Vec<Log>);
+}
+
+impl OnEvmLogs for () {
+    fn on_evm_logs(_from: H160, _to: H160, _logs: Vec<Log>) {}
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::traits::fungibles::Mutate;
-    use frame_support::traits::ReservableCurrency;
+    use frame_support::traits::fungibles::{Create, Mutate};
     use frame_system::pallet_prelude::*;
 
+    /// The storage version, `1` since the emergencies are the paused directions,
+    /// `2` since the registered assets are counted.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::remove_expired_pauses(now)
+        }
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_assets::Config + pallet_evm::Config {
         /// The overarching event type.
@@ -70,6 +152,26 @@ pub mod pallet {
         /// How much should be locked up in order to claim account.
         #[pallet::constant]
         type ClaimBond: Get<ReserveBalanceOf<Self>>;
+        /// The origin which may register assets and manage back foreign assets,
+        /// besides the admin key.
+        type RegisterOrigin: EnsureOrigin<Self::Origin>;
+        /// The origin which may pause and unpause assets, besides the admin key.
+        type PauseOrigin: EnsureOrigin<Self::Origin>;
+        /// The origin which may force unregister assets.
+        type UnregisterOrigin: EnsureOrigin<Self::Origin>;
+        /// The erc20 contract mirroring the native currency, e.g. a wrapped token, which is
+        /// the address of the `Transfer` logs of the teleports. No log is emitted if `None`.
+        #[pallet::constant]
+        type NativeTokenAddress: Get<Option<H160>>;
+        /// The handler of the evm logs of the bridge operations, e.g. putting them into the
+        /// ethereum receipts.
+        type OnEvmLogs: OnEvmLogs;
+        /// The maximum number of the registered assets, which bounds the weight of pausing
+        /// all assets.
+        #[pallet::constant]
+        type MaxAssets: Get<u32>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     /// The Substrate Account for Evm Addresses
@@ -98,7 +200,16 @@ pub mod pallet {
     /// AssetIds: map H160 => Option<AssetId>
     #[pallet::storage]
     #[pallet::getter(fn asset_ids)]
-    pub type AssetIds<T: Config> = StorageMap<_, Twox64Concat, H160, T::AssetId, OptionQuery>;
+    pub type AssetIds<T: Config> =
+        CountedStorageMap<_, Twox64Concat, H160, T::AssetId, OptionQuery>;
+
+    /// The locked amounts of Evm native Erc20 tokens escrowed by `EvmCaller`,
+    /// only exists for the Erc20 registered by `register_native`.
+    ///
+    /// Escrows: map AssetId => Option<u128>
+    #[pallet::storage]
+    #[pallet::getter(fn escrows)]
+    pub type Escrows<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, u128, OptionQuery>;
 
     /// The Assets can back foreign chain
     ///
@@ -112,10 +223,44 @@ pub mod pallet {
     #[pallet::getter(fn admin_key)]
     pub(super) type Admin<T: Config> = StorageValue<_, T::AccountId>;
 
-    /// The Assets in emergency
+    /// The paused directions of Assets, the pause expires at the block number.
+    ///
+    /// Paused: double_map AssetId, Direction => Option<BlockNumber>
+    #[pallet::storage]
+    #[pallet::getter(fn paused)]
+    pub type Paused<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AssetId,
+        Twox64Concat,
+        Direction,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// The timed pauses which expire at the block number.
+    ///
+    /// PauseExpiries: map BlockNumber => Vec<(AssetId, Direction)>
+    #[pallet::storage]
+    pub type PauseExpiries<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<(T::AssetId, Direction)>, ValueQuery>;
+
+    /// The EIP-712 nonces of Evm Addresses, used for replay protection
+    ///
+    /// Nonces: map H160 => u64
+    #[pallet::storage]
+    #[pallet::getter(fn nonces)]
+    pub type Nonces<T: Config> = StorageMap<_, Twox64Concat, H160, u64, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultLegacyClaim() -> bool {
+        true
+    }
+
+    /// Whether the deprecated `personal_sign` claim signature is still accepted
     #[pallet::storage]
-    #[pallet::getter(fn emergencies)]
-    pub(super) type Emergencies<T: Config> = StorageValue<_, Vec<T::AssetId>, ValueQuery>;
+    #[pallet::getter(fn legacy_claim)]
+    pub type LegacyClaim<T: Config> = StorageValue<_, bool, ValueQuery, DefaultLegacyClaim>;
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -168,6 +313,14 @@ pub mod pallet {
         UnPausedAll,
         // (asset_id, remove)
         BackForeign(T::AssetId, bool),
+        // (enabled)
+        SetLegacyClaim(bool),
+        /// (asset_id, erc20_contract)
+        RegisterNative(T::AssetId, H160),
+        // (asset_id, direction, expired_at)
+        DirectionPaused(T::AssetId, Direction, Option<T::BlockNumber>),
+        // (asset_id, direction)
+        DirectionUnPaused(T::AssetId, Direction),
     }
 
     /// Error for evm accounts module.
@@ -197,12 +350,24 @@ pub mod pallet {
         ExecutedFailed,
         /// Require admin authority
         RequireAdmin,
-        /// Ban deposit and withdraw when in emergency
+        /// Ban deposit, withdraw or teleport when the direction is paused (in emergency)
         InEmergency,
         /// Ban back to foreign
         BanBackForeign,
         /// Zero balance
         ZeroBalance,
+        /// The duration of the pause is zero
+        ZeroDuration,
+        /// The escrowed Erc20 tokens is not enough
+        InsufficientEscrow,
+        /// The escrowed Erc20 tokens overflow
+        EscrowOverflow,
+        /// The Erc20 balance change of escrow mismatches the amount
+        EscrowMismatch,
+        /// The escrowed Erc20 tokens are not released
+        EscrowNotEmpty,
+        /// The registered assets reach `MaxAssets`
+        TooManyAssets,
     }
 
     #[pallet::call]
@@ -215,8 +380,10 @@ pub mod pallet {
         /// Note: for general users
         ///
         /// - `eth_address`: The address to bind to the caller's account
-        /// - `eth_signature`: A signature generated by the address to prove ownership
-        #[pallet::weight(100_000_000u64)]
+        /// - `eth_signature`: A signature generated by the address to prove ownership,
+        ///    the EIP-712 typed data `Claim(bytes substrateAccount,uint256 nonce)` or
+        ///    the deprecated `personal_sign` data when `LegacyClaim` is enabled.
+        #[pallet::weight(<T as Config>::WeightInfo::claim_account())]
         #[transactional]
         pub fn claim_account(
             origin: OriginFor<T>,
@@ -235,14 +402,26 @@ pub mod pallet {
                 Error::<T>::EthAddressHasMapped
             );
 
-            // recover evm address from signature
-            let address = eth_recover(&eth_signature, &who.using_encoded(to_ascii_hex), &[][..])
+            // recover evm address from typed data signature
+            let nonce = Self::nonces(eth_address);
+            let address = eip712_recover(&eth_signature, &Self::claim_digest(&who, nonce))
                 .ok_or(Error::<T>::BadSignature)?;
 
-            ensure!(eth_address == address, Error::<T>::InvalidSignature);
+            if eth_address != address {
+                // deprecated: the legacy signature has no replay protection
+                ensure!(Self::legacy_claim(), Error::<T>::InvalidSignature);
+
+                let address =
+                    eth_recover(&eth_signature, &who.using_encoded(to_ascii_hex), &[][..])
+                        .ok_or(Error::<T>::BadSignature)?;
+
+                ensure!(eth_address == address, Error::<T>::InvalidSignature);
+            }
 
             <T as pallet_assets::Config>::Currency::reserve(&who, T::ClaimBond::get())?;
 
+            Nonces::<T>::insert(eth_address, nonce.saturating_add(1));
+
             SubAccounts::<T>::insert(eth_address, &who);
             EvmAccounts::<T>::insert(&who, eth_address);
 
@@ -253,7 +432,7 @@ pub mod pallet {
 
         /// Dissolve substrate accounts and EVM accounts.
         /// Note: for general users
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::dissolve())]
         #[transactional]
         pub fn dissolve(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
@@ -265,22 +444,59 @@ pub mod pallet {
                 Error::<T>::EthAddressHasNotMapped
             );
 
-            <T as pallet_assets::Config>::Currency::unreserve(&who, T::ClaimBond::get());
+            Self::do_dissolve(who, evm_account);
 
-            SubAccounts::<T>::remove(&evm_account);
-            EvmAccounts::<T>::remove(&who);
+            Ok(())
+        }
 
-            Self::deposit_event(Event::Dissolve(who));
+        /// Dissolve substrate accounts and EVM accounts by the EVM account.
+        /// Note: for general users
+        ///
+        /// - `eth_address`: The mapped address to unbind
+        /// - `eth_signature`: A signature generated by the address of the EIP-712 typed data
+        ///    `Dissolve(bytes substrateAccount,address evmAddress,uint256 nonce)`
+        #[pallet::weight(<T as Config>::WeightInfo::dissolve_with_signature())]
+        #[transactional]
+        pub fn dissolve_with_signature(
+            origin: OriginFor<T>,
+            eth_address: H160,
+            eth_signature: EcdsaSignature,
+        ) -> DispatchResult {
+            let _ = ensure_signed(origin)?;
+
+            let who = Self::sub_accounts(eth_address).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+
+            ensure!(
+                Self::evm_accounts(&who) == Some(eth_address),
+                Error::<T>::AccountIdHasNotMapped
+            );
+
+            // recover evm address from typed data signature
+            let nonce = Self::nonces(eth_address);
+            let address = eip712_recover(
+                &eth_signature,
+                &Self::dissolve_digest(&who, eth_address, nonce),
+            )
+            .ok_or(Error::<T>::BadSignature)?;
+
+            ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+            Nonces::<T>::insert(eth_address, nonce.saturating_add(1));
+
+            Self::do_dissolve(who, eth_address);
 
             Ok(())
         }
 
         /// Deposit substrate assets into evm erc20 contracts.
-        /// Note: for general users
+        /// Note: for general users, who pay the fee of the weight with the used gas
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Deposit amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::deposit()
+                .saturating_add(Pallet::<T>::evm_weight(MAX_EVM_CALLS * EVM_GAS_LIMIT))
+        )]
         #[transactional]
         pub fn deposit(
             origin: OriginFor<T>,
@@ -288,7 +504,10 @@ pub mod pallet {
             amount: T::Balance,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(
+                !Self::is_paused(asset_id, Direction::Deposit),
+                Error::<T>::InEmergency
+            );
             ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
             // 1. check evm account
@@ -297,12 +516,24 @@ pub mod pallet {
             // 2. burn asset
             let _ = pallet_assets::Pallet::<T>::burn_from(asset_id, &who, amount)?;
 
-            // 3. mint erc20
+            // 3. mint erc20, or release the escrowed erc20 if it is evm native
             let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
 
-            let inputs = mint_into_encode(evm_account, amount.unique_saturated_into());
-
-            Self::call_evm(erc20, inputs)?;
+            let used_gas = if Self::is_native_erc20(asset_id) {
+                Self::release_erc20(asset_id, erc20, evm_account, amount.unique_saturated_into())?
+            } else {
+                let inputs = mint_into_encode(evm_account, amount.unique_saturated_into());
+
+                let info = Self::call_evm(erc20, inputs)?;
+                Self::ensure_transfer_log(
+                    &info.logs,
+                    erc20,
+                    H160::zero(),
+                    evm_account,
+                    amount.unique_saturated_into(),
+                );
+                info.used_gas.low_u64()
+            };
 
             Self::deposit_event(Event::DepositExecuted(
                 asset_id,
@@ -312,15 +543,23 @@ pub mod pallet {
                 erc20,
             ));
 
-            Ok(Pays::No.into())
+            Ok(PostDispatchInfo {
+                actual_weight: Some(
+                    <T as Config>::WeightInfo::deposit().saturating_add(Self::evm_weight(used_gas)),
+                ),
+                pays_fee: Pays::Yes,
+            })
         }
 
         /// Withdraw from evm erc20 contracts into substrate assets
-        /// Note: for general users
+        /// Note: for general users, who pay the fee of the weight with the used gas
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Withdraw amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::withdraw()
+                .saturating_add(Pallet::<T>::evm_weight(MAX_EVM_CALLS * EVM_GAS_LIMIT))
+        )]
         #[transactional]
         pub fn withdraw(
             origin: OriginFor<T>,
@@ -328,18 +567,33 @@ pub mod pallet {
             amount: T::Balance,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(
+                !Self::is_paused(asset_id, Direction::Withdraw),
+                Error::<T>::InEmergency
+            );
             ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
             // 1. check evm account
             let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
 
-            // 2. burn erc20
+            // 2. burn erc20, or escrow the erc20 if it is evm native
             let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
 
-            let inputs = burn_from_encode(evm_account, amount.unique_saturated_into());
-
-            Self::call_evm(erc20, inputs)?;
+            let used_gas = if Self::is_native_erc20(asset_id) {
+                Self::lock_erc20(asset_id, erc20, evm_account, amount.unique_saturated_into())?
+            } else {
+                let inputs = burn_from_encode(evm_account, amount.unique_saturated_into());
+
+                let info = Self::call_evm(erc20, inputs)?;
+                Self::ensure_transfer_log(
+                    &info.logs,
+                    erc20,
+                    evm_account,
+                    H160::zero(),
+                    amount.unique_saturated_into(),
+                );
+                info.used_gas.low_u64()
+            };
 
             // 3. mint asset
             let _ = pallet_assets::Pallet::<T>::mint_into(asset_id, &who, amount)?;
@@ -352,7 +606,13 @@ pub mod pallet {
                 erc20,
             ));
 
-            Ok(Pays::No.into())
+            Ok(PostDispatchInfo {
+                actual_weight: Some(
+                    <T as Config>::WeightInfo::withdraw()
+                        .saturating_add(Self::evm_weight(used_gas)),
+                ),
+                pays_fee: Pays::Yes,
+            })
         }
 
         /// Teleport native currency between substrate account and evm address
@@ -366,7 +626,7 @@ pub mod pallet {
         ///    (3) FromEthToSub: transfer from mapped evm address to substrate account
         /// - companion with `relay`:
         ///    (4) BackForeign(asset_id): transfer assets back foreign chain
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::teleport())]
         #[transactional]
         pub fn teleport(
             origin: OriginFor<T>,
@@ -376,33 +636,41 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
-            let (from, to, back_foreign) = match action {
+            // the evm addresses of the `Transfer` log, the zero address means mint or burn.
+            let (from, to, log) = match action {
                 ActionType::Direct(unchecked) => (
                     who.clone(),
                     AddressMappingOf::<T>::into_account_id(unchecked),
-                    false,
-                ),
-                ActionType::FromSubToEth => (
-                    who.clone(),
-                    Self::evm_accounts(&who)
-                        .map(AddressMappingOf::<T>::into_account_id)
-                        .ok_or(Error::<T>::EthAddressHasNotMapped)?,
-                    false,
-                ),
-                ActionType::FromEthToSub => (
-                    Self::evm_accounts(&who)
-                        .map(AddressMappingOf::<T>::into_account_id)
-                        .ok_or(Error::<T>::EthAddressHasNotMapped)?,
-                    who.clone(),
-                    false,
+                    Some((H160::zero(), unchecked)),
                 ),
+                ActionType::FromSubToEth => {
+                    let evm_account =
+                        Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+                    (
+                        who.clone(),
+                        AddressMappingOf::<T>::into_account_id(evm_account),
+                        Some((H160::zero(), evm_account)),
+                    )
+                }
+                ActionType::FromEthToSub => {
+                    let evm_account =
+                        Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+                    (
+                        AddressMappingOf::<T>::into_account_id(evm_account),
+                        who.clone(),
+                        Some((evm_account, H160::zero())),
+                    )
+                }
                 ActionType::BackForeign(asset_id) => {
                     // ensure asset_id registered in back_foreign list
                     ensure!(
                         Self::is_in_back_foreign(asset_id),
                         Error::<T>::BanBackForeign
                     );
-                    ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+                    ensure!(
+                        !Self::is_paused(asset_id, Direction::Teleport),
+                        Error::<T>::InEmergency
+                    );
 
                     let amount: u128 = amount.unique_saturated_into();
                     // burn asset first, then relay will transfer back `who`.
@@ -412,17 +680,26 @@ pub mod pallet {
                         amount.unique_saturated_into(),
                     )?;
 
-                    (who.clone(), who.clone(), true)
+                    (who.clone(), who.clone(), None)
                 }
             };
 
-            if !back_foreign {
+            if let Some((log_from, log_to)) = log {
                 <T as pallet_evm::Config>::Currency::transfer(
                     &from,
                     &to,
                     amount,
                     ExistenceRequirement::AllowDeath,
                 )?;
+
+                if let Some(token) = T::NativeTokenAddress::get() {
+                    Self::deposit_log(transfer_log(
+                        token,
+                        log_from,
+                        log_to,
+                        amount.unique_saturated_into(),
+                    ));
+                }
             }
 
             Self::deposit_event(Event::Teleport(who, amount, action));
@@ -431,18 +708,19 @@ pub mod pallet {
         }
 
         /// Register substrate assets and erc20 contracts
-        /// Note: for admin
+        /// Note: for admin or `RegisterOrigin`
         ///
         /// - `asset_id`: The asset id
         /// - `erc20`: The erc20 contract address
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::register())]
         pub fn register(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
             erc20: H160,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            T::RegisterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
 
             // ensure asset_id and erc20 address has not been mapped
             ensure!(
@@ -453,6 +731,10 @@ pub mod pallet {
                 !AssetIds::<T>::contains_key(&erc20),
                 Error::<T>::ContractAddressHasMapped
             );
+            ensure!(
+                AssetIds::<T>::count() < T::MaxAssets::get(),
+                Error::<T>::TooManyAssets
+            );
 
             Erc20s::<T>::insert(asset_id, erc20);
             AssetIds::<T>::insert(erc20, asset_id);
@@ -462,90 +744,218 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
-        /// Pause assets bridge deposit and withdraw
-        /// Note: for admin
+        /// Register evm native erc20 contracts, and create the substrate assets for them.
+        /// `withdraw` escrows the erc20 by `transferFrom` (need `approve` the `EvmCaller`)
+        /// and mints the assets, `deposit` burns the assets and releases the escrowed erc20.
+        /// Note: for admin or `RegisterOrigin`
         ///
-        /// - `asset_id`: None will pause all, Some(id) will pause the specified asset
-        #[pallet::weight(100_000_000u64)]
-        pub fn pause(
+        /// - `asset_id`: The asset id to create
+        /// - `erc20`: The erc20 contract address
+        /// - `min_balance`: The minimum balance of the created asset
+        /// - `is_sufficient`: Whether the created asset is sufficient to keep the account alive
+        #[pallet::weight(<T as Config>::WeightInfo::register_native())]
+        #[transactional]
+        pub fn register_native(
             origin: OriginFor<T>,
-            asset_id: Option<T::AssetId>,
+            asset_id: T::AssetId,
+            erc20: H160,
+            min_balance: T::Balance,
+            is_sufficient: bool,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            T::RegisterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
 
-            Emergencies::<T>::try_mutate(|emergencies| {
-                if let Some(id) = asset_id {
-                    // ensure asset_id and erc20 address has not been mapped
-                    ensure!(
-                        Erc20s::<T>::contains_key(&id),
-                        Error::<T>::AssetIdHasNotMapped
-                    );
-                    if !Self::is_in_emergency(id) {
-                        emergencies.push(id);
+            // ensure asset_id and erc20 address has not been mapped
+            ensure!(
+                !Erc20s::<T>::contains_key(&asset_id),
+                Error::<T>::AssetIdHasMapped
+            );
+            ensure!(
+                !AssetIds::<T>::contains_key(&erc20),
+                Error::<T>::ContractAddressHasMapped
+            );
+            ensure!(
+                AssetIds::<T>::count() < T::MaxAssets::get(),
+                Error::<T>::TooManyAssets
+            );
 
-                        Self::deposit_event(Event::Paused(id));
-                    }
-                } else {
-                    emergencies.truncate(0);
-                    for id in AssetIds::<T>::iter_values() {
-                        emergencies.push(id);
-                    }
+            // the asset is owned by the evm caller, only the bridge can mint it.
+            let owner = AddressMappingOf::<T>::into_account_id(T::EvmCaller::get());
+            <pallet_assets::Pallet<T> as Create<T::AccountId>>::create(
+                asset_id,
+                owner,
+                is_sufficient,
+                min_balance,
+            )?;
+
+            Erc20s::<T>::insert(asset_id, erc20);
+            AssetIds::<T>::insert(erc20, asset_id);
+            Escrows::<T>::insert(asset_id, 0);
 
-                    Self::deposit_event(Event::PausedAll);
+            Self::deposit_event(Event::RegisterNative(asset_id, erc20));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Pause assets bridge deposit, withdraw and teleport until unpause
+        /// Note: for admin or `PauseOrigin`
+        ///
+        /// - `asset_id`: None will pause all, Some(id) will pause the specified asset
+        #[pallet::weight(<T as Config>::WeightInfo::pause(
+            asset_id.map_or(T::MaxAssets::get(), |_| 1)
+        ))]
+        pub fn pause(
+            origin: OriginFor<T>,
+            asset_id: Option<T::AssetId>,
+        ) -> DispatchResultWithPostInfo {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
+
+            if let Some(id) = asset_id {
+                // ensure asset_id and erc20 address has been mapped
+                ensure!(
+                    Erc20s::<T>::contains_key(&id),
+                    Error::<T>::AssetIdHasNotMapped
+                );
+                if !Direction::ALL.iter().all(|&d| Self::is_paused(id, d)) {
+                    Self::do_pause(id, T::BlockNumber::max_value());
+
+                    Self::deposit_event(Event::Paused(id));
                 }
 
                 Ok(Pays::No.into())
-            })
+            } else {
+                let mut count = 0u32;
+                for id in AssetIds::<T>::iter_values() {
+                    Self::do_pause(id, T::BlockNumber::max_value());
+                    count += 1;
+                }
+
+                Self::deposit_event(Event::PausedAll);
+
+                Ok(PostDispatchInfo {
+                    actual_weight: Some(<T as Config>::WeightInfo::pause(count)),
+                    pays_fee: Pays::No,
+                })
+            }
         }
 
-        /// Unpause assets bridge deposit and withdraw
-        /// Note: for admin
+        /// Unpause assets bridge deposit, withdraw and teleport
+        /// Note: for admin or `PauseOrigin`
         ///
         /// - `asset_id`: None will unpause all, Some(id) will unpause the specified asset
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::unpause())]
         pub fn unpause(
             origin: OriginFor<T>,
             asset_id: Option<T::AssetId>,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
 
-            Emergencies::<T>::try_mutate(|emergencies| {
-                if let Some(id) = asset_id {
-                    // ensure asset_id and erc20 address has been mapped
-                    ensure!(
-                        Erc20s::<T>::contains_key(&id),
-                        Error::<T>::AssetIdHasNotMapped
-                    );
+            if let Some(id) = asset_id {
+                // ensure asset_id and erc20 address has been mapped
+                ensure!(
+                    Erc20s::<T>::contains_key(&id),
+                    Error::<T>::AssetIdHasNotMapped
+                );
 
-                    if Self::is_in_emergency(id) {
-                        emergencies.retain(|&emergency| emergency != id);
-
-                        Self::deposit_event(Event::UnPaused(id));
-                    }
-                } else {
-                    emergencies.truncate(0);
+                if Self::is_in_emergency(id) {
+                    let _ = Paused::<T>::remove_prefix(id, None);
 
-                    Self::deposit_event(Event::UnPausedAll);
+                    Self::deposit_event(Event::UnPaused(id));
                 }
+            } else {
+                let _ = Paused::<T>::remove_all(None);
 
-                Ok(Pays::No.into())
-            })
+                Self::deposit_event(Event::UnPausedAll);
+            }
+
+            Ok(Pays::No.into())
+        }
+
+        /// Pause one direction of the assets bridge, the pause auto-expires
+        /// after `duration` blocks.
+        /// Note: for admin or `PauseOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `direction`: The direction to pause
+        /// - `duration`: None will pause until unpause
+        #[pallet::weight(<T as Config>::WeightInfo::pause_direction())]
+        pub fn pause_direction(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            direction: Direction,
+            duration: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
+
+            ensure!(
+                Erc20s::<T>::contains_key(&asset_id),
+                Error::<T>::AssetIdHasNotMapped
+            );
+            ensure!(duration != Some(Zero::zero()), Error::<T>::ZeroDuration);
+
+            let expired_at =
+                duration.map(|d| frame_system::Pallet::<T>::block_number().saturating_add(d));
+            Paused::<T>::insert(
+                asset_id,
+                direction,
+                expired_at.unwrap_or_else(T::BlockNumber::max_value),
+            );
+            if let Some(expired_at) = expired_at {
+                PauseExpiries::<T>::append(expired_at, (asset_id, direction));
+            }
+
+            Self::deposit_event(Event::DirectionPaused(asset_id, direction, expired_at));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Unpause one direction of the assets bridge
+        /// Note: for admin or `PauseOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `direction`: The direction to unpause
+        #[pallet::weight(<T as Config>::WeightInfo::unpause_direction())]
+        pub fn unpause_direction(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            direction: Direction,
+        ) -> DispatchResultWithPostInfo {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
+
+            ensure!(
+                Erc20s::<T>::contains_key(&asset_id),
+                Error::<T>::AssetIdHasNotMapped
+            );
+
+            if Paused::<T>::take(asset_id, direction).is_some() {
+                Self::deposit_event(Event::DirectionUnPaused(asset_id, direction));
+            }
+
+            Ok(Pays::No.into())
         }
 
         /// Add assets which can back add_back_foreign chain
-        /// Note: for admin
+        /// Note: for admin or `RegisterOrigin`
         ///
         /// - `asset_id`:
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::back_foreign())]
         pub fn back_foreign(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
             remove: bool,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            T::RegisterOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(Self::ensure_admin)?;
 
             BackForeign::<T>::try_mutate(|foreigns| {
                 if remove {
@@ -564,7 +974,7 @@ pub mod pallet {
 
         /// Set this pallet admin key
         /// Note: for super admin
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_admin())]
         pub fn set_admin(
             origin: OriginFor<T>,
             new_admin: <T::Lookup as StaticLookup>::Source,
@@ -586,14 +996,27 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
-        /// Force unregister substrate assets and erc20 contracts
+        /// Enable or disable the deprecated `personal_sign` claim signature
         /// Note: for super admin
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_legacy_claim())]
+        pub fn set_legacy_claim(origin: OriginFor<T>, enabled: bool) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            LegacyClaim::<T>::put(enabled);
+
+            Self::deposit_event(Event::SetLegacyClaim(enabled));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Force unregister substrate assets and erc20 contracts
+        /// Note: for `UnregisterOrigin`
+        #[pallet::weight(<T as Config>::WeightInfo::force_unregister())]
         pub fn force_unregister(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            T::UnregisterOrigin::ensure_origin(origin)?;
 
             let erc20 = Self::erc20s(&asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
 
@@ -601,16 +1024,18 @@ pub mod pallet {
                 AssetIds::<T>::contains_key(&erc20),
                 Error::<T>::ContractAddressHasMapped
             );
+            // the escrowed erc20 can only be released by `deposit` of the registered asset
+            ensure!(
+                Self::escrows(&asset_id).unwrap_or_default() == 0,
+                Error::<T>::EscrowNotEmpty
+            );
 
             Erc20s::<T>::remove(&asset_id);
             AssetIds::<T>::remove(&erc20);
+            Escrows::<T>::remove(&asset_id);
 
             // clear emergency
-            if Self::is_in_emergency(asset_id) {
-                Emergencies::<T>::mutate(|emergencies| {
-                    emergencies.retain(|&emergency| emergency != asset_id);
-                })
-            }
+            let _ = Paused::<T>::remove_prefix(asset_id, None);
 
             Self::deposit_event(Event::ForceUnRegister(asset_id, erc20));
 
@@ -623,13 +1048,13 @@ impl<T: Config> Pallet<T>
 where
     DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
 {
-    fn call_evm(erc20: H160, inputs: Vec<u8>) -> DispatchResult {
+    fn call_evm(erc20: H160, inputs: Vec<u8>) -> Result<CallInfo, DispatchError> {
         let info = T::Runner::call(
             T::EvmCaller::get(),
             erc20,
             inputs,
             U256::default(),
-            3_000_000,
+            EVM_GAS_LIMIT,
             None,
             None,
             None,
@@ -639,18 +1064,241 @@ where
         )?;
 
         match info.exit_reason {
-            ExitReason::Succeed(_) => Ok(()),
+            ExitReason::Succeed(_) => {
+                if !info.logs.is_empty() {
+                    T::OnEvmLogs::on_evm_logs(T::EvmCaller::get(), erc20, info.logs.clone());
+                }
+                Ok(info)
+            }
             _ => Err(Error::<T>::ExecutedFailed.into()),
         }
     }
 
-    fn is_in_emergency(asset_id: T::AssetId) -> bool {
-        Self::emergencies()
+    /// Deposit the `Transfer` log of the erc20 if the contract call didn't emit one,
+    /// so that the evm indexers see the balance change of the bridge.
+    fn ensure_transfer_log(logs: &[Log], erc20: H160, from: H160, to: H160, amount: u128) {
+        if !logs.iter().any(|log| is_transfer_log(log, erc20)) {
+            Self::deposit_log(transfer_log(erc20, from, to, amount));
+        }
+    }
+
+    /// The substrate assets and erc20 tokens balance of `who`,
+    /// the erc20 balance is read from the mapped evm address.
+    pub fn bridge_balance(asset_id: T::AssetId, who: &T::AccountId) -> BridgeBalance<T::Balance> {
+        let evm_address = Self::evm_accounts(who);
+        let erc20 = match (Self::erc20s(asset_id), evm_address) {
+            (Some(erc20), Some(address)) => Self::call_evm(erc20, balance_of_encode(address))
+                .ok()
+                .and_then(|info| u128_decode(&info.value))
+                .unwrap_or_default(),
+            _ => 0,
+        };
+
+        BridgeBalance {
+            evm_address,
+            substrate: pallet_assets::Pallet::<T>::balance(asset_id, who),
+            erc20: erc20.unique_saturated_into(),
+        }
+    }
+
+    /// The erc20 balance of the `EvmCaller` and the used gas.
+    fn escrow_balance(erc20: H160) -> Result<(u128, u64), DispatchError> {
+        let info = Self::call_evm(erc20, balance_of_encode(T::EvmCaller::get()))?;
+        let balance = u128_decode(&info.value).ok_or(Error::<T>::ExecutedFailed)?;
+
+        Ok((balance, info.used_gas.low_u64()))
+    }
+
+    /// Escrow the evm native erc20 from `from` into the `EvmCaller`, returns the used gas.
+    fn lock_erc20(
+        asset_id: T::AssetId,
+        erc20: H160,
+        from: H160,
+        amount: u128,
+    ) -> Result<u64, DispatchError> {
+        Escrows::<T>::try_mutate(asset_id, |escrow| {
+            let locked = escrow.as_mut().ok_or(Error::<T>::AssetIdHasNotMapped)?;
+            *locked = locked
+                .checked_add(amount)
+                .ok_or(Error::<T>::EscrowOverflow)?;
+
+            let (before, before_gas) = Self::escrow_balance(erc20)?;
+
+            let inputs = transfer_from_encode(from, T::EvmCaller::get(), amount);
+            let info = Self::call_evm(erc20, inputs)?;
+            ensure!(bool_decode(&info.value), Error::<T>::ExecutedFailed);
+
+            // reject the erc20 with transfer fee or rebase
+            let (after, after_gas) = Self::escrow_balance(erc20)?;
+            ensure!(
+                after.checked_sub(before) == Some(amount),
+                Error::<T>::EscrowMismatch
+            );
+
+            Ok(before_gas
+                .saturating_add(info.used_gas.low_u64())
+                .saturating_add(after_gas))
+        })
+    }
+
+    /// Release the escrowed evm native erc20 from the `EvmCaller` to `to`, returns the used gas.
+    fn release_erc20(
+        asset_id: T::AssetId,
+        erc20: H160,
+        to: H160,
+        amount: u128,
+    ) -> Result<u64, DispatchError> {
+        Escrows::<T>::try_mutate(asset_id, |escrow| {
+            let locked = escrow.as_mut().ok_or(Error::<T>::AssetIdHasNotMapped)?;
+            *locked = locked
+                .checked_sub(amount)
+                .ok_or(Error::<T>::InsufficientEscrow)?;
+
+            let (before, before_gas) = Self::escrow_balance(erc20)?;
+
+            let inputs = transfer_encode(to, amount);
+            let info = Self::call_evm(erc20, inputs)?;
+            ensure!(bool_decode(&info.value), Error::<T>::ExecutedFailed);
+
+            let (after, after_gas) = Self::escrow_balance(erc20)?;
+            ensure!(
+                before.checked_sub(after) == Some(amount),
+                Error::<T>::EscrowMismatch
+            );
+
+            Ok(before_gas
+                .saturating_add(info.used_gas.low_u64())
+                .saturating_add(after_gas))
+        })
+    }
+
+    fn ensure_admin(origin: <T as frame_system::Config>::Origin) -> DispatchResult {
+        let who = frame_system::ensure_signed(origin)?;
+        ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The weight of the evm calls of the bridge which used `gas`.
+    pub fn evm_weight(gas: u64) -> Weight {
+        <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas)
+    }
+
+    /// Deposit the evm log as `pallet_evm::Event::Log`, same as the logs of the evm calls
+    /// made by the bridge, and pass it to `OnEvmLogs` as well.
+    fn deposit_log(log: Log) {
+        T::OnEvmLogs::on_evm_logs(T::EvmCaller::get(), log.address, vec![log.clone()]);
+        let event: <T as pallet_evm::Config>::Event = pallet_evm::Event::<T>::Log(log).into();
+        frame_system::Pallet::<T>::deposit_event(event);
+    }
+
+    /// Whether the direction of the asset is paused and the pause has not expired.
+    pub fn is_paused(asset_id: T::AssetId, direction: Direction) -> bool {
+        Self::paused(asset_id, direction).map_or(false, |expired_at| {
+            frame_system::Pallet::<T>::block_number() < expired_at
+        })
+    }
+
+    /// Whether any direction of the asset is paused.
+    pub fn is_in_emergency(asset_id: T::AssetId) -> bool {
+        Direction::ALL
             .iter()
-            .any(|&emergency| emergency == asset_id)
+            .any(|&direction| Self::is_paused(asset_id, direction))
+    }
+
+    /// All the registered assets with the pause status.
+    pub fn registered_assets() -> Vec<RegisteredAsset<T::AssetId, T::BlockNumber>> {
+        Erc20s::<T>::iter()
+            .map(|(asset_id, erc20)| RegisteredAsset {
+                asset_id,
+                erc20,
+                native: Self::is_native_erc20(asset_id),
+                back_foreign: Self::is_in_back_foreign(asset_id),
+                paused: Direction::ALL
+                    .iter()
+                    .filter(|&&direction| Self::is_paused(asset_id, direction))
+                    .map(|&direction| {
+                        let expired_at = Self::paused(asset_id, direction)
+                            .filter(|&at| at != T::BlockNumber::max_value());
+                        (direction, expired_at)
+                    })
+                    .collect(),
+            })
+            .collect()
     }
 
     fn is_in_back_foreign(asset_id: T::AssetId) -> bool {
         Self::back_foreign_assets().iter().any(|&id| id == asset_id)
     }
+
+    fn is_native_erc20(asset_id: T::AssetId) -> bool {
+        Escrows::<T>::contains_key(asset_id)
+    }
+
+    /// Remove the timed pauses which expire at `now`, unless they are paused again.
+    fn remove_expired_pauses(now: T::BlockNumber) -> Weight {
+        let expiries = PauseExpiries::<T>::take(now);
+        let count = expiries.len() as Weight;
+        for (asset_id, direction) in expiries {
+            if Self::paused(asset_id, direction) == Some(now) {
+                Paused::<T>::remove(asset_id, direction);
+                Self::deposit_event(Event::DirectionUnPaused(asset_id, direction));
+            }
+        }
+        <T as frame_system::Config>::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    /// Pause all the directions of the asset until unpause, if the asset is mapped.
+    ///
+    /// Used to stop bridging the assets which are deregistered.
+    pub fn pause_until_unpause(asset_id: T::AssetId) {
+        if Erc20s::<T>::contains_key(&asset_id)
+            && !Direction::ALL
+                .iter()
+                .all(|&d| Self::paused(asset_id, d) == Some(T::BlockNumber::max_value()))
+        {
+            Self::do_pause(asset_id, T::BlockNumber::max_value());
+            Self::deposit_event(Event::Paused(asset_id));
+        }
+    }
+
+    fn do_pause(asset_id: T::AssetId, expired_at: T::BlockNumber) {
+        for direction in Direction::ALL {
+            Paused::<T>::insert(asset_id, direction, expired_at);
+        }
+    }
+
+    /// The EIP-712 domain separator of this chain.
+    pub fn eip712_domain_separator() -> [u8; 32] {
+        domain_separator(
+            <T as pallet_evm::Config>::ChainId::get(),
+            T::EvmCaller::get(),
+        )
+    }
+
+    /// The EIP-712 digest to sign for claiming `who`.
+    pub fn claim_digest(who: &T::AccountId, nonce: u64) -> [u8; 32] {
+        typed_data_digest(
+            &Self::eip712_domain_separator(),
+            &who.using_encoded(|account| claim_hash(account, nonce)),
+        )
+    }
+
+    /// The EIP-712 digest to sign for dissolving `who` and `eth_address`.
+    pub fn dissolve_digest(who: &T::AccountId, eth_address: H160, nonce: u64) -> [u8; 32] {
+        typed_data_digest(
+            &Self::eip712_domain_separator(),
+            &who.using_encoded(|account| dissolve_hash(account, eth_address, nonce)),
+        )
+    }
+
+    fn do_dissolve(who: T::AccountId, evm_account: H160) {
+        <T as pallet_assets::Config>::Currency::unreserve(&who, T::ClaimBond::get());
+
+        SubAccounts::<T>::remove(&evm_account);
+        EvmAccounts::<T>::remove(&who);
+
+        Self::deposit_event(Event::Dissolve(who));
+    }
 }
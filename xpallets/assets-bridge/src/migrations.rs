@@ -0,0 +1,71 @@
+use crate::{AssetIds, Config, Direction, Pallet, Paused};
+use frame_support::{
+    log::info,
+    migration::take_storage_value,
+    traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+};
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+/// Apply all of the migrations due to role-based governance.
+///
+/// ### Warning
+///
+/// Use with care and run at your own risk.
+pub fn apply<T: Config>() -> Weight {
+    let on_chain_version = Pallet::<T>::on_chain_storage_version();
+    if on_chain_version >= 2 {
+        info!(
+            target: "runtime::assets_bridge",
+            "✅ The assets bridge pallet has been migrated, skip."
+        );
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+
+    info!(
+        target: "runtime::assets_bridge",
+        "✅ Running migration for assets bridge pallet..."
+    );
+
+    let mut weight = 0;
+    if on_chain_version < 1 {
+        weight = migrate_emergencies::<T>();
+    }
+    weight = weight.saturating_add(count_assets::<T>());
+    StorageVersion::new(2).put::<Pallet<T>>();
+    weight.saturating_add(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Migrate from the old emergencies, which pause all directions until unpause.
+pub fn migrate_emergencies<T: Config>() -> Weight {
+    let emergencies =
+        take_storage_value::<Vec<T::AssetId>>(Pallet::<T>::name().as_bytes(), b"Emergencies", b"")
+            .unwrap_or_default();
+
+    for asset_id in emergencies.iter() {
+        for direction in Direction::ALL {
+            Paused::<T>::insert(asset_id, direction, T::BlockNumber::max_value());
+        }
+    }
+
+    let count = emergencies.len() * Direction::ALL.len();
+    info!(
+        target: "runtime::assets_bridge",
+        "✅ Migration for emergencies done, {} paused directions.",
+        count,
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(1, count as Weight + 1)
+}
+
+/// Initialize the counter of the registered assets, which was a plain map.
+pub fn count_assets<T: Config>() -> Weight {
+    let count = AssetIds::<T>::initialize_counter();
+
+    info!(
+        target: "runtime::assets_bridge",
+        "✅ Migration for the asset counter done, {} registered assets.",
+        count,
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count as Weight, 1)
+}
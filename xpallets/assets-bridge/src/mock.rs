@@ -10,6 +10,7 @@ pub use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
     AccountId32,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -96,6 +97,9 @@ parameter_types! {
     // 0x1111111111111111111111111111111111111111
     pub EvmCaller: H160 = H160::from_slice(&[17u8;20][..]);
     pub ClaimBond: u128 = 2;
+    // 0x0000000000000000000000000000000000000801
+    pub NativeTokenAddress: Option<H160> = Some(H160::from_low_u64_be(2049));
+    pub const MaxAssets: u32 = 3;
 }
 
 impl pallet_assets::Config for Test {
@@ -104,6 +108,7 @@ impl pallet_assets::Config for Test {
     type AssetId = u32;
     type Currency = Balances;
     type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+    type FeeRateOrigin = frame_system::EnsureRoot<AccountId32>;
     type AssetDeposit = AssetDeposit;
     type MetadataDepositBase = MetadataDepositBase;
     type MetadataDepositPerByte = MetadataDepositPerByte;
@@ -112,6 +117,8 @@ impl pallet_assets::Config for Test {
     type Freezer = ();
     type WeightInfo = ();
     type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
     type AssetAccountDeposit = ();
 }
 
@@ -138,6 +145,30 @@ impl assets_bridge::Config for Test {
     type Event = Event;
     type EvmCaller = EvmCaller;
     type ClaimBond = ClaimBond;
+    type RegisterOrigin = frame_system::EnsureRoot<AccountId32>;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId32>;
+    type UnregisterOrigin = frame_system::EnsureRoot<AccountId32>;
+    type NativeTokenAddress = NativeTokenAddress;
+    type OnEvmLogs = RecordedLogs;
+    type MaxAssets = MaxAssets;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static LOGS: RefCell<Vec<(H160, H160, Vec<pallet_evm::Log>)>> = RefCell::new(Vec::new());
+}
+
+/// Record the evm logs passed to `OnEvmLogs`.
+pub struct RecordedLogs;
+impl RecordedLogs {
+    pub fn take() -> Vec<(H160, H160, Vec<pallet_evm::Log>)> {
+        LOGS.with(|logs| logs.borrow_mut().drain(..).collect())
+    }
+}
+impl crate::OnEvmLogs for RecordedLogs {
+    fn on_evm_logs(from: H160, to: H160, logs: Vec<pallet_evm::Log>) {
+        LOGS.with(|recorded| recorded.borrow_mut().push((from, to, logs)));
+    }
 }
 
 pub const ALICE: [u8; 32] = [1u8; 32];
@@ -1,7 +1,11 @@
 use crate::mock::*;
 use crate::{to_ascii_hex, EcdsaSignature};
-use frame_support::{assert_noop, assert_ok};
-use sp_core::{H160, U256};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, Get, GetStorageVersion, Hooks, ReservableCurrency, StorageVersion},
+    weights::{GetDispatchInfo, Pays},
+};
+use sp_core::{ecdsa, Pair, H160, H256, U256};
 
 use ethabi::{Function, Param, ParamType, Token};
 use hex_literal::hex;
@@ -22,6 +26,24 @@ const PUBKEY: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a5
 const ERC20_1: [u8; 20] = [1u8; 20];
 const ERC20_2: [u8; 20] = [2u8; 20];
 const MAPPING_ACCOUNT: &str = "5Fghzk1AJt88PeFEzuRfXzbPchiBbsVGTTXcdx599VdZzkTA";
+// The well-known development private key and its address.
+const EVM_SECRET: [u8; 32] =
+    hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+const EVM_SECRET_ADDR: [u8; 20] = hex!["f39fd6e51aad88f6f4ce6ab8827279cfffb92266"];
+
+fn sign_typed_data(digest: [u8; 32]) -> EcdsaSignature {
+    ecdsa::Pair::from_seed(&EVM_SECRET).sign_prehashed(&digest)
+}
+
+fn paused_assets() -> Vec<u32> {
+    let mut assets = crate::Paused::<Test>::iter_keys()
+        .map(|(id, _)| id)
+        .filter(|&id| AssetsBridge::is_in_emergency(id))
+        .collect::<Vec<_>>();
+    assets.sort_unstable();
+    assets.dedup();
+    assets
+}
 
 pub fn mint_into_abi() -> Function {
     #[allow(deprecated)]
@@ -221,7 +243,7 @@ fn pause_after_pause_should_work() {
         // 1. pause(1)
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(1)));
         expect_event(AssetsBridgeEvent::Paused(1));
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -231,12 +253,12 @@ fn pause_after_pause_should_work() {
         // 2. pause(1)
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(1)));
         expect_event(AssetsBridgeEvent::Paused(1));
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         // 3. pause all
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), None));
         expect_event(AssetsBridgeEvent::PausedAll);
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         // 4. pause(2)
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(2)));
@@ -249,6 +271,95 @@ fn pause_after_pause_should_work() {
     })
 }
 
+#[test]
+fn pause_all_should_charge_the_registered_assets() {
+    new_test_ext().execute_with(|| {
+        for (asset_id, erc20) in [(1, ERC20_1), (2, ERC20_2)] {
+            assert_ok!(AssetsBridge::register(
+                Origin::signed(ALICE.into()),
+                asset_id,
+                H160::from_slice(&erc20)
+            ));
+        }
+
+        let call = crate::Call::<Test>::pause { asset_id: None };
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            <() as crate::WeightInfo>::pause(3)
+        );
+        let info = AssetsBridge::pause(Origin::signed(ALICE.into()), None).unwrap();
+        assert_eq!(
+            info.actual_weight,
+            Some(<() as crate::WeightInfo>::pause(2))
+        );
+        assert_eq!(info.pays_fee, Pays::No);
+    })
+}
+
+#[test]
+fn register_should_be_bounded_by_max_assets() {
+    new_test_ext().execute_with(|| {
+        for i in 1..=3u8 {
+            assert_ok!(AssetsBridge::register(
+                Origin::signed(ALICE.into()),
+                i.into(),
+                H160::repeat_byte(i)
+            ));
+        }
+        assert_eq!(crate::AssetIds::<Test>::count(), 3);
+
+        assert_noop!(
+            AssetsBridge::register(Origin::signed(ALICE.into()), 4, H160::repeat_byte(4)),
+            Error::<Test>::TooManyAssets
+        );
+        assert_noop!(
+            AssetsBridge::register_native(
+                Origin::signed(ALICE.into()),
+                4,
+                H160::repeat_byte(4),
+                1,
+                false
+            ),
+            Error::<Test>::TooManyAssets
+        );
+
+        assert_ok!(AssetsBridge::force_unregister(Origin::root(), 3));
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            4,
+            H160::repeat_byte(4)
+        ));
+    })
+}
+
+#[test]
+fn count_assets_migration_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            2,
+            H160::from_slice(&ERC20_2)
+        ));
+        // the counter of the plain map of version 1
+        frame_support::storage::unhashed::kill(&frame_support::storage::storage_prefix(
+            b"AssetsBridge",
+            b"CounterForAssetIds",
+        ));
+        StorageVersion::new(1).put::<AssetsBridge>();
+        assert_eq!(crate::AssetIds::<Test>::count(), 0);
+
+        crate::migrations::apply::<Test>();
+
+        assert_eq!(crate::AssetIds::<Test>::count(), 2);
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 2);
+    })
+}
+
 #[test]
 fn unpause_should_work() {
     new_test_ext().execute_with(|| {
@@ -317,24 +428,24 @@ fn unpause_after_unpause_should_work() {
         expect_event(AssetsBridgeEvent::Register(2, H160::from_slice(&ERC20_2)));
 
         assert_ok!(AssetsBridge::unpause(Origin::signed(ALICE.into()), Some(1)));
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_ok!(AssetsBridge::unpause(Origin::signed(ALICE.into()), Some(2)));
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_noop!(
             AssetsBridge::pause(Origin::signed(ALICE.into()), Some(3)),
             Error::<Test>::AssetIdHasNotMapped
         );
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(1)));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(2)));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -353,7 +464,7 @@ fn unpause_after_unpause_should_work() {
             AssetsBridge::withdraw(Origin::signed(BOB.into()), 2, 1),
             Error::<Test>::EthAddressHasNotMapped
         );
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_ok!(AssetsBridge::unpause(Origin::signed(ALICE.into()), None));
         expect_event(AssetsBridgeEvent::UnPausedAll);
@@ -362,7 +473,7 @@ fn unpause_after_unpause_should_work() {
             AssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 1),
             Error::<Test>::EthAddressHasNotMapped
         );
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
     })
 }
 
@@ -383,7 +494,7 @@ fn more_pause_and_unpause_should_work() {
         ));
         expect_event(AssetsBridgeEvent::Register(2, H160::from_slice(&ERC20_2)));
 
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -398,7 +509,7 @@ fn more_pause_and_unpause_should_work() {
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), None));
         expect_event(AssetsBridgeEvent::PausedAll);
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -413,7 +524,7 @@ fn more_pause_and_unpause_should_work() {
         assert_ok!(AssetsBridge::unpause(Origin::signed(ALICE.into()), Some(2)));
         expect_event(AssetsBridgeEvent::UnPaused(2));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -428,7 +539,7 @@ fn more_pause_and_unpause_should_work() {
         assert_ok!(AssetsBridge::unpause(Origin::signed(ALICE.into()), None));
         expect_event(AssetsBridgeEvent::UnPausedAll);
 
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_noop!(
             AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
@@ -460,7 +571,7 @@ fn force_unregister_should_work() {
 
         assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), None));
         expect_event(AssetsBridgeEvent::PausedAll);
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_ok!(AssetsBridge::force_unregister(Origin::root(), 1));
         expect_event(AssetsBridgeEvent::ForceUnRegister(
@@ -470,6 +581,746 @@ fn force_unregister_should_work() {
 
         assert_eq!(AssetsBridge::erc20s(1), None);
         assert_eq!(AssetsBridge::asset_ids(H160::from_slice(&ERC20_1)), None);
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
+    })
+}
+
+#[test]
+fn eip712_claim_digest() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            AssetsBridge::eip712_domain_separator(),
+            hex!["d02a95a20752542f8d86ee1aa0f539847934bd208730b1436eb04f49f92193a8"]
+        );
+        assert_eq!(
+            AssetsBridge::claim_digest(&ALICE.into(), 0),
+            hex!["4c870c3ccfe033f4308936f4f850ccf8bd986da2d4ca42dfb6c3fb55d68773a2"]
+        );
+    })
+}
+
+#[test]
+fn claim_account_with_typed_data_should_work() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_SECRET_ADDR);
+        let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 0));
+
+        assert_noop!(
+            AssetsBridge::claim_account(Origin::signed(BOB.into()), address, signature.clone()),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(ALICE.into()),
+            address,
+            signature.clone()
+        ));
+        expect_event(AssetsBridgeEvent::ClaimAccount(ALICE.into(), address));
+        assert_eq!(
+            AssetsBridge::evm_accounts(AccountId32::from(ALICE)),
+            Some(address)
+        );
+        assert_eq!(AssetsBridge::nonces(address), 1);
+        assert_eq!(Balances::reserved_balance(AccountId32::from(ALICE)), 2);
+
+        assert_ok!(AssetsBridge::dissolve(Origin::signed(ALICE.into())));
+        expect_event(AssetsBridgeEvent::Dissolve(ALICE.into()));
+
+        // replay
+        assert_noop!(
+            AssetsBridge::claim_account(Origin::signed(ALICE.into()), address, signature),
+            Error::<Test>::InvalidSignature
+        );
+
+        let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 1));
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(ALICE.into()),
+            address,
+            signature
+        ));
+        assert_eq!(AssetsBridge::nonces(address), 2);
+    })
+}
+
+#[test]
+fn claim_account_with_legacy_signature() {
+    new_test_ext().execute_with(|| {
+        let who = AccountId32::from_str(SUB_ACCOUNT).unwrap();
+        let address = H160::from_slice(&EVM_ADDR);
+        let signature = EcdsaSignature::from_slice(&SIGNATURE).unwrap();
+        let _ = Balances::deposit_creating(&who, 1000);
+
+        assert_ok!(AssetsBridge::set_legacy_claim(Origin::root(), false));
+        expect_event(AssetsBridgeEvent::SetLegacyClaim(false));
+
+        assert_noop!(
+            AssetsBridge::claim_account(Origin::signed(who.clone()), address, signature.clone()),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_ok!(AssetsBridge::set_legacy_claim(Origin::root(), true));
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(who.clone()),
+            address,
+            signature
+        ));
+        expect_event(AssetsBridgeEvent::ClaimAccount(who, address));
+        assert_eq!(AssetsBridge::nonces(address), 1);
+    })
+}
+
+#[test]
+fn dissolve_with_signature_should_work() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_SECRET_ADDR);
+        let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 0));
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(ALICE.into()),
+            address,
+            signature
+        ));
+
+        // signed with the stale nonce
+        let signature = sign_typed_data(AssetsBridge::dissolve_digest(&ALICE.into(), address, 0));
+        assert_noop!(
+            AssetsBridge::dissolve_with_signature(Origin::signed(BOB.into()), address, signature),
+            Error::<Test>::InvalidSignature
+        );
+
+        let signature = sign_typed_data(AssetsBridge::dissolve_digest(&ALICE.into(), address, 1));
+        assert_ok!(AssetsBridge::dissolve_with_signature(
+            Origin::signed(BOB.into()),
+            address,
+            signature.clone()
+        ));
+        expect_event(AssetsBridgeEvent::Dissolve(ALICE.into()));
+        assert_eq!(AssetsBridge::evm_accounts(AccountId32::from(ALICE)), None);
+        assert_eq!(AssetsBridge::sub_accounts(address), None);
+        assert_eq!(AssetsBridge::nonces(address), 2);
+        assert_eq!(Balances::reserved_balance(AccountId32::from(ALICE)), 0);
+
+        assert_noop!(
+            AssetsBridge::dissolve_with_signature(Origin::signed(BOB.into()), address, signature),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+    })
+}
+
+fn erc20_abi(name: &str, inputs: Vec<ParamType>) -> Function {
+    #[allow(deprecated)]
+    Function {
+        name: name.to_owned(),
+        inputs: inputs
+            .into_iter()
+            .map(|kind| Param {
+                name: Default::default(),
+                kind,
+                internal_type: None,
+            })
+            .collect(),
+        outputs: vec![],
+        constant: Some(false),
+        state_mutability: Default::default(),
+    }
+}
+
+#[test]
+fn erc20_abi_encode() {
+    let from = H160::from_slice(&EVM_ADDR);
+    let to = H160::from_slice(&ERC20_1);
+    let amount = Token::Uint(U256::from(100_000_000));
+
+    let transfer = erc20_abi("transfer", vec![ParamType::Address, ParamType::Uint(256)]);
+    assert_eq!(
+        crate::transfer_encode(to, 100_000_000u128),
+        transfer
+            .encode_input(&[Token::Address(to), amount.clone()])
+            .unwrap()
+    );
+
+    let transfer_from = erc20_abi(
+        "transferFrom",
+        vec![ParamType::Address, ParamType::Address, ParamType::Uint(256)],
+    );
+    assert_eq!(
+        crate::transfer_from_encode(from, to, 100_000_000u128),
+        transfer_from
+            .encode_input(&[Token::Address(from), Token::Address(to), amount])
+            .unwrap()
+    );
+
+    let balance_of = erc20_abi("balanceOf", vec![ParamType::Address]);
+    assert_eq!(
+        crate::balance_of_encode(from),
+        balance_of.encode_input(&[Token::Address(from)]).unwrap()
+    );
+
+    let mut output = [0u8; 32];
+    U256::from(100_000_000).to_big_endian(&mut output);
+    assert_eq!(crate::u128_decode(&output), Some(100_000_000));
+    assert_eq!(crate::u128_decode(&[0xffu8; 32]), None);
+    assert!(crate::bool_decode(&[]));
+    assert!(!crate::bool_decode(&output));
+}
+
+#[test]
+fn register_native_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            AssetsBridge::register_native(
+                Origin::signed(BOB.into()),
+                1,
+                H160::from_slice(&ERC20_1),
+                1,
+                false
+            ),
+            Error::<Test>::RequireAdmin
+        );
+
+        assert_ok!(AssetsBridge::register_native(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1),
+            1,
+            false
+        ));
+        expect_event(AssetsBridgeEvent::RegisterNative(
+            1,
+            H160::from_slice(&ERC20_1),
+        ));
+        assert_eq!(AssetsBridge::escrows(1), Some(0));
+        assert_eq!(Assets::maybe_total_supply(1), Some(0));
+
+        assert_noop!(
+            AssetsBridge::register_native(
+                Origin::signed(ALICE.into()),
+                2,
+                H160::from_slice(&ERC20_1),
+                1,
+                false
+            ),
+            Error::<Test>::ContractAddressHasMapped
+        );
+
+        crate::Escrows::<Test>::insert(1, 100);
+        assert_noop!(
+            AssetsBridge::force_unregister(Origin::root(), 1),
+            Error::<Test>::EscrowNotEmpty
+        );
+        crate::Escrows::<Test>::insert(1, 0);
+        assert_ok!(AssetsBridge::force_unregister(Origin::root(), 1));
+        assert_eq!(AssetsBridge::escrows(1), None);
+    })
+}
+
+#[test]
+fn deposit_native_should_not_exceed_escrow() {
+    use frame_support::traits::fungibles::Mutate;
+
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_SECRET_ADDR);
+        let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 0));
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(ALICE.into()),
+            address,
+            signature
+        ));
+
+        assert_ok!(AssetsBridge::register_native(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1),
+            1,
+            false
+        ));
+        assert_ok!(Assets::mint_into(1, &ALICE.into(), 100));
+
+        assert_noop!(
+            AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 100),
+            Error::<Test>::InsufficientEscrow
+        );
+    })
+}
+
+/// A minimal erc20 keeping the balance of each holder in the storage slot of its address,
+/// `transferFrom` doesn't check the allowance and an insufficient balance reverts.
+const ESCROW_ERC20: [u8; 109] = hex!["60003560e01c806370a08231146028578063a9059cbb14603557806323b872dd14604057600080fd5b6004355460005260206000f35b33600435602435604a565b6004356024356044355b82548181106068578190038355815401905550600160005260206000f35b600080fd"];
+
+fn erc20_balance(erc20: H160, who: H160) -> u64 {
+    pallet_evm::AccountStorages::<Test>::get(erc20, H256::from(who)).to_low_u64_be()
+}
+
+#[test]
+fn withdraw_and_deposit_native_should_escrow_erc20() {
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        let address = H160::from_slice(&EVM_SECRET_ADDR);
+        let escrow = EvmCaller::get();
+        pallet_evm::AccountCodes::<Test>::insert(erc20, ESCROW_ERC20.to_vec());
+        pallet_evm::AccountStorages::<Test>::insert(
+            erc20,
+            H256::from(address),
+            H256::from_low_u64_be(100),
+        );
+
+        let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 0));
+        assert_ok!(AssetsBridge::claim_account(
+            Origin::signed(ALICE.into()),
+            address,
+            signature
+        ));
+        assert_ok!(AssetsBridge::register_native(
+            Origin::signed(ALICE.into()),
+            1,
+            erc20,
+            1,
+            false
+        ));
+
+        // withdraw escrows the erc20 by `transferFrom` and mints the assets
+        assert_ok!(AssetsBridge::withdraw(Origin::signed(ALICE.into()), 1, 60));
+        assert_eq!(erc20_balance(erc20, address), 40);
+        assert_eq!(erc20_balance(erc20, escrow), 60);
+        assert_eq!(AssetsBridge::escrows(1), Some(60));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 60);
+
+        assert_noop!(
+            AssetsBridge::withdraw(Origin::signed(ALICE.into()), 1, 41),
+            Error::<Test>::ExecutedFailed
+        );
+
+        // deposit burns the assets and releases the escrowed erc20
+        assert_ok!(AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 25));
+        assert_eq!(erc20_balance(erc20, address), 65);
+        assert_eq!(erc20_balance(erc20, escrow), 35);
+        assert_eq!(AssetsBridge::escrows(1), Some(35));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 35);
+
+        assert_ok!(AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 35));
+        assert_eq!(erc20_balance(erc20, address), 100);
+        assert_eq!(erc20_balance(erc20, escrow), 0);
+        assert_eq!(AssetsBridge::escrows(1), Some(0));
+        assert_ok!(AssetsBridge::force_unregister(Origin::root(), 1));
+    })
+}
+
+#[test]
+fn pause_by_origin_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AssetsBridge::register(
+            Origin::root(),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+
+        assert_ok!(AssetsBridge::pause(Origin::root(), Some(1)));
+        expect_event(AssetsBridgeEvent::Paused(1));
+        assert_eq!(paused_assets(), vec![1]);
+
+        assert_ok!(AssetsBridge::unpause(Origin::root(), None));
+        expect_event(AssetsBridgeEvent::UnPausedAll);
+        assert!(paused_assets().is_empty());
+
+        assert_noop!(
+            AssetsBridge::force_unregister(Origin::signed(ALICE.into()), 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    })
+}
+
+#[test]
+fn pause_direction_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            AssetsBridge::pause_direction(
+                Origin::signed(ALICE.into()),
+                1,
+                crate::Direction::Deposit,
+                None
+            ),
+            Error::<Test>::AssetIdHasNotMapped
+        );
+
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+
+        assert_noop!(
+            AssetsBridge::pause_direction(
+                Origin::signed(BOB.into()),
+                1,
+                crate::Direction::Deposit,
+                None
+            ),
+            Error::<Test>::RequireAdmin
+        );
+
+        // pause deposit for 10 blocks
+        assert_ok!(AssetsBridge::pause_direction(
+            Origin::signed(ALICE.into()),
+            1,
+            crate::Direction::Deposit,
+            Some(10)
+        ));
+        expect_event(AssetsBridgeEvent::DirectionPaused(
+            1,
+            crate::Direction::Deposit,
+            Some(11),
+        ));
+
+        assert_noop!(
+            AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::InEmergency
+        );
+        assert_noop!(
+            AssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+
+        // expired
+        System::set_block_number(11);
+        assert_noop!(
+            AssetsBridge::deposit(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+        assert!(paused_assets().is_empty());
+
+        // the expired pause is removed
+        assert!(AssetsBridge::paused(1, crate::Direction::Deposit).is_some());
+        AssetsBridge::on_initialize(11);
+        assert_eq!(AssetsBridge::paused(1, crate::Direction::Deposit), None);
+        assert!(crate::PauseExpiries::<Test>::get(11).is_empty());
+
+        assert_noop!(
+            AssetsBridge::pause_direction(
+                Origin::signed(ALICE.into()),
+                1,
+                crate::Direction::Deposit,
+                Some(0)
+            ),
+            Error::<Test>::ZeroDuration
+        );
+
+        // pause withdraw until unpause
+        assert_ok!(AssetsBridge::pause_direction(
+            Origin::signed(ALICE.into()),
+            1,
+            crate::Direction::Withdraw,
+            None
+        ));
+        expect_event(AssetsBridgeEvent::DirectionPaused(
+            1,
+            crate::Direction::Withdraw,
+            None,
+        ));
+        System::set_block_number(u64::MAX - 1);
+        assert_noop!(
+            AssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::InEmergency
+        );
+
+        assert_ok!(AssetsBridge::unpause_direction(
+            Origin::signed(ALICE.into()),
+            1,
+            crate::Direction::Withdraw
+        ));
+        expect_event(AssetsBridgeEvent::DirectionUnPaused(
+            1,
+            crate::Direction::Withdraw,
+        ));
+        assert_noop!(
+            AssetsBridge::withdraw(Origin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+    })
+}
+
+#[test]
+fn pause_until_unpause_should_work() {
+    new_test_ext().execute_with(|| {
+        // the unmapped asset is skipped
+        AssetsBridge::pause_until_unpause(1);
+        assert!(paused_assets().is_empty());
+
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::pause_direction(
+            Origin::signed(ALICE.into()),
+            1,
+            crate::Direction::Deposit,
+            Some(10)
+        ));
+
+        AssetsBridge::pause_until_unpause(1);
+        expect_event(AssetsBridgeEvent::Paused(1));
+        assert_eq!(paused_assets(), vec![1]);
+        // the timed pause doesn't expire any more
+        AssetsBridge::on_initialize(11);
+        assert!(AssetsBridge::is_paused(1, crate::Direction::Deposit));
+    })
+}
+
+#[test]
+fn migrate_emergencies_should_work() {
+    new_test_ext().execute_with(|| {
+        frame_support::migration::put_storage_value(
+            b"AssetsBridge",
+            b"Emergencies",
+            b"",
+            vec![1u32, 2u32],
+        );
+        StorageVersion::new(0).put::<AssetsBridge>();
+
+        crate::migrations::apply::<Test>();
+
+        assert_eq!(paused_assets(), vec![1, 2]);
+        assert!(AssetsBridge::is_paused(2, crate::Direction::Teleport));
+        assert!(!frame_support::migration::have_storage_value(
+            b"AssetsBridge",
+            b"Emergencies",
+            b""
+        ));
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 2);
+
+        // run only once
+        crate::Paused::<Test>::remove_all(None);
+        crate::migrations::apply::<Test>();
+        assert!(paused_assets().is_empty());
+    })
+}
+
+#[test]
+fn registered_assets_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AssetsBridge::register(
+            Origin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::register_native(
+            Origin::signed(ALICE.into()),
+            2,
+            H160::from_slice(&ERC20_2),
+            1,
+            false
+        ));
+        assert_ok!(AssetsBridge::pause_direction(
+            Origin::signed(ALICE.into()),
+            2,
+            crate::Direction::Teleport,
+            Some(10)
+        ));
+        assert_ok!(AssetsBridge::pause(Origin::signed(ALICE.into()), Some(1)));
+
+        let mut assets = AssetsBridge::registered_assets();
+        assets.sort_by_key(|asset| asset.asset_id);
+        assert_eq!(
+            assets,
+            vec![
+                crate::RegisteredAsset {
+                    asset_id: 1,
+                    erc20: H160::from_slice(&ERC20_1),
+                    native: false,
+                    back_foreign: false,
+                    paused: vec![
+                        (crate::Direction::Deposit, None),
+                        (crate::Direction::Withdraw, None),
+                        (crate::Direction::Teleport, None),
+                    ],
+                },
+                crate::RegisteredAsset {
+                    asset_id: 2,
+                    erc20: H160::from_slice(&ERC20_2),
+                    native: true,
+                    back_foreign: false,
+                    paused: vec![(crate::Direction::Teleport, Some(11))],
+                },
+            ]
+        );
+
+        assert_eq!(
+            AssetsBridge::bridge_balance(2, &ALICE.into()),
+            crate::BridgeBalance {
+                evm_address: None,
+                substrate: 0,
+                erc20: 0,
+            }
+        );
+    })
+}
+
+fn evm_logs() -> Vec<pallet_evm::Log> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::Evm(pallet_evm::Event::Log(log)) => Some(log),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn transfer_log_encode() {
+    let from = H160::from_slice(&EVM_SECRET_ADDR);
+    let log = crate::abi::transfer_log(H160::from_slice(&ERC20_1), from, H160::zero(), 100);
+
+    assert_eq!(
+        log.topics[0].as_bytes(),
+        hex!["ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"]
+    );
+    assert_eq!(&log.topics[1][12..], &EVM_SECRET_ADDR[..]);
+    assert_eq!(log.topics[2], H256::zero());
+    assert_eq!(U256::from_big_endian(&log.data), U256::from(100));
+    assert!(crate::abi::is_transfer_log(
+        &log,
+        H160::from_slice(&ERC20_1)
+    ));
+    assert!(!crate::abi::is_transfer_log(
+        &log,
+        H160::from_slice(&ERC20_2)
+    ));
+}
+
+/// Register the asset `1` with a contract which returns `true` without any log,
+/// and claim ALICE with 100 of the asset.
+fn setup_stub_erc20() -> (H160, H160) {
+    use frame_support::traits::fungibles::Mutate;
+
+    let erc20 = H160::from_slice(&ERC20_1);
+    let address = H160::from_slice(&EVM_SECRET_ADDR);
+    pallet_evm::AccountCodes::<Test>::insert(erc20, hex!["600160005260206000f3"].to_vec());
+
+    assert_ok!(Assets::force_create(
+        Origin::root(),
+        1,
+        ALICE.into(),
+        true,
+        1
+    ));
+    assert_ok!(AssetsBridge::register(Origin::root(), 1, erc20));
+    let signature = sign_typed_data(AssetsBridge::claim_digest(&ALICE.into(), 0));
+    assert_ok!(AssetsBridge::claim_account(
+        Origin::signed(ALICE.into()),
+        address,
+        signature
+    ));
+    assert_ok!(Assets::mint_into(1, &ALICE.into(), 100));
+
+    (erc20, address)
+}
+
+#[test]
+fn deposit_and_withdraw_should_log_transfer() {
+    new_test_ext().execute_with(|| {
+        let (erc20, address) = setup_stub_erc20();
+
+        assert_ok!(AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 40));
+        assert_ok!(AssetsBridge::withdraw(Origin::signed(ALICE.into()), 1, 10));
+        assert_eq!(
+            evm_logs(),
+            vec![
+                crate::abi::transfer_log(erc20, H160::zero(), address, 40),
+                crate::abi::transfer_log(erc20, address, H160::zero(), 10),
+            ]
+        );
+        assert_eq!(
+            RecordedLogs::take(),
+            vec![
+                (
+                    EvmCaller::get(),
+                    erc20,
+                    vec![crate::abi::transfer_log(erc20, H160::zero(), address, 40)]
+                ),
+                (
+                    EvmCaller::get(),
+                    erc20,
+                    vec![crate::abi::transfer_log(erc20, address, H160::zero(), 10)]
+                ),
+            ]
+        );
+    })
+}
+
+#[test]
+fn deposit_should_pass_the_logs_of_erc20() {
+    new_test_ext().execute_with(|| {
+        let (erc20, _) = setup_stub_erc20();
+        // emit a `Transfer` log without data and return `true`
+        let mut code = vec![0x7f];
+        code.extend_from_slice(crate::abi::transfer_topic().as_bytes());
+        code.extend_from_slice(&hex!["60006000a1600160005260206000f3"]);
+        pallet_evm::AccountCodes::<Test>::insert(erc20, code);
+        RecordedLogs::take();
+
+        assert_ok!(AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 40));
+        let log = pallet_evm::Log {
+            address: erc20,
+            topics: vec![crate::abi::transfer_topic()],
+            data: vec![],
+        };
+        // the erc20 emitted a `Transfer` log, so the bridge doesn't deposit another one
+        assert_eq!(
+            RecordedLogs::take(),
+            vec![(EvmCaller::get(), erc20, vec![log])]
+        );
+    })
+}
+
+#[test]
+fn deposit_and_withdraw_should_weigh_used_gas() {
+    use crate::{WeightInfo, EVM_GAS_LIMIT, MAX_EVM_CALLS};
+    use frame_support::weights::Pays;
+
+    new_test_ext().execute_with(|| {
+        setup_stub_erc20();
+
+        let max_gas_weight = MAX_EVM_CALLS * EVM_GAS_LIMIT;
+        let post_info = AssetsBridge::deposit(Origin::signed(ALICE.into()), 1, 40).unwrap();
+        let base = <() as WeightInfo>::deposit();
+        let actual = post_info.actual_weight.unwrap();
+        assert!(actual > base && actual < base + max_gas_weight);
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+
+        let post_info = AssetsBridge::withdraw(Origin::signed(ALICE.into()), 1, 10).unwrap();
+        let base = <() as WeightInfo>::withdraw();
+        let actual = post_info.actual_weight.unwrap();
+        assert!(actual > base && actual < base + max_gas_weight);
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+    })
+}
+
+#[test]
+fn teleport_should_log_transfer() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_SECRET_ADDR);
+        let token = NativeTokenAddress::get().unwrap();
+
+        assert_ok!(AssetsBridge::teleport(
+            Origin::signed(BOB.into()),
+            100,
+            crate::ActionType::Direct(address)
+        ));
+        assert_eq!(
+            evm_logs(),
+            vec![crate::abi::transfer_log(token, H160::zero(), address, 100)]
+        );
+        assert_eq!(
+            RecordedLogs::take(),
+            vec![(
+                EvmCaller::get(),
+                token,
+                vec![crate::abi::transfer_log(token, H160::zero(), address, 100)]
+            )]
+        );
+        expect_event(AssetsBridgeEvent::Teleport(
+            BOB.into(),
+            100,
+            crate::ActionType::Direct(address),
+        ));
     })
 }
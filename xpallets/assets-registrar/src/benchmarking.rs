@@ -0,0 +1,101 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
+
+use xp_protocol::X_BTC;
+
+use super::*;
+
+fn b_asset_info<T: Config>() -> AssetInfo {
+    AssetInfo::new::<T>(
+        b"XBTC".to_vec(),
+        b"ChainX Bitcoin".to_vec(),
+        Chain::Bitcoin,
+        8,
+        b"ChainX's cross-chain Bitcoin".to_vec(),
+    )
+    .unwrap()
+}
+
+fn register_default<T: Config>() {
+    Pallet::<T>::register(
+        RawOrigin::Root.into(),
+        X_BTC,
+        b_asset_info::<T>(),
+        1u32.into(),
+    )
+    .unwrap();
+}
+
+benchmarks! {
+    register {
+        let asset_info = b_asset_info::<T>();
+    }: _(RawOrigin::Root, X_BTC, asset_info.clone(), 1u32.into())
+    verify {
+        assert_eq!(Pallet::<T>::asset_info_of(X_BTC), Some(asset_info));
+        assert!(Pallet::<T>::is_valid(&X_BTC));
+    }
+
+    adopt {
+        let asset_info = b_asset_info::<T>();
+        <pallet_assets::Pallet<T> as Create<T::AccountId>>::create(
+            X_BTC,
+            Pallet::<T>::account_id(),
+            true,
+            1u32.into(),
+        )?;
+    }: _(RawOrigin::Root, X_BTC, asset_info.clone())
+    verify {
+        assert_eq!(Pallet::<T>::asset_info_of(X_BTC), Some(asset_info));
+        assert!(Pallet::<T>::is_valid(&X_BTC));
+    }
+
+    deregister {
+        register_default::<T>();
+    }: _(RawOrigin::Root, X_BTC)
+    verify {
+        assert!(!Pallet::<T>::is_valid(&X_BTC));
+    }
+
+    recover {
+        register_default::<T>();
+        Pallet::<T>::deregister(RawOrigin::Root.into(), X_BTC).unwrap();
+    }: _(RawOrigin::Root, X_BTC)
+    verify {
+        assert!(Pallet::<T>::is_valid(&X_BTC));
+    }
+
+    update_asset_info {
+        register_default::<T>();
+    }: _(
+        RawOrigin::Root,
+        X_BTC,
+        Some(b"SBTC".to_vec()),
+        Some(b"SherpaX Bitcoin".to_vec()),
+        Some(b"SherpaX's cross-chain Bitcoin".to_vec())
+    )
+    verify {
+        let info = Pallet::<T>::asset_info_of(X_BTC).unwrap();
+        assert_eq!(info.token(), &b"SBTC".to_vec());
+        assert_eq!(info.desc(), &b"SherpaX's cross-chain Bitcoin".to_vec());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_register());
+            assert_ok!(Pallet::<Test>::test_benchmark_adopt());
+            assert_ok!(Pallet::<Test>::test_benchmark_deregister());
+            assert_ok!(Pallet::<Test>::test_benchmark_recover());
+            assert_ok!(Pallet::<Test>::test_benchmark_update_asset_info());
+        });
+    }
+}
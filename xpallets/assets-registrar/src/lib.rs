@@ -0,0 +1,372 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Asset Registrar Module
+//!
+//! This module is used to manage the cross-chain assets of the gateways,
+//! the balances of which are held by `pallet_assets`.
+//!
+//! - `register`: create the asset in `pallet_assets` with the validated asset info,
+//!   and record the chain of the asset in `xpallet_gateway_records`.
+//! - `adopt`: register an asset which already exists in `pallet_assets`, e.g. the
+//!   assets created in the genesis.
+//! - `deregister`: take the asset offline, the deposits and withdrawals of which
+//!   are no longer allowed, the balances are kept.
+//! - `recover`: take a deregistered asset back online.
+//!
+//! All the `RegistrarHandler` implementors are notified on each of the above.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub mod weights;
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    log::info,
+    traits::{tokens::fungibles::Create, Get},
+    transactional, PalletId,
+};
+use sp_runtime::traits::AccountIdConversion;
+use sp_std::prelude::*;
+
+use sherpax_primitives::{AssetId, Desc, Token};
+use xp_assets_registrar::{Chain, RegistrarHandler};
+
+pub use self::types::AssetInfo;
+pub use self::weights::WeightInfo;
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config
+        + pallet_assets::Config<AssetId = AssetId>
+        + xpallet_gateway_records::Config
+    {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The pallet id, the account of which owns the registered assets.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The origin which may register, deregister, recover and update the assets.
+        type RegistrarOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Handler for doing stuff after the asset is registered/deregistered.
+        type RegistrarHandler: RegistrarHandler;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// The storage version, `1` since the genesis assets are adopted.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register a new cross-chain asset.
+        ///
+        /// The asset is created in `pallet_assets` as a sufficient asset owned by
+        /// the pallet account.
+        ///
+        /// This is a RegistrarOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::register())]
+        #[transactional]
+        pub fn register(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            asset: AssetInfo,
+            #[pallet::compact] min_balance: T::Balance,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            asset.is_valid::<T>()?;
+            ensure!(!Self::exists(&asset_id), Error::<T>::AssetAlreadyExists);
+
+            info!(
+                target: "runtime::assets_registrar",
+                "[register_asset] id:{}, info:{:?}",
+                asset_id, asset
+            );
+
+            <pallet_assets::Pallet<T> as Create<T::AccountId>>::create(
+                asset_id,
+                Self::account_id(),
+                true,
+                min_balance,
+            )?;
+            Self::set_metadata(asset_id, &asset)?;
+            Self::apply_register(asset_id, asset)?;
+
+            Self::deposit_event(Event::Registered(asset_id));
+            T::RegistrarHandler::on_register(&asset_id)?;
+            Ok(())
+        }
+
+        /// Register a cross-chain asset which already exists in `pallet_assets`.
+        ///
+        /// The metadata of the asset is overwritten by the asset info.
+        ///
+        /// This is a RegistrarOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::adopt())]
+        #[transactional]
+        pub fn adopt(
+            origin: OriginFor<T>,
+            #[pallet::compact] asset_id: AssetId,
+            asset: AssetInfo,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            Self::do_adopt(asset_id, asset)
+        }
+
+        /// Deregister an asset with given `id`.
+        ///
+        /// The deposits and withdrawals of this asset are no longer allowed.
+        ///
+        /// This is a RegistrarOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::deregister())]
+        #[transactional]
+        pub fn deregister(origin: OriginFor<T>, #[pallet::compact] id: AssetId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            ensure!(Self::is_valid(&id), Error::<T>::AssetIsInvalid);
+
+            AssetOnline::<T>::remove(id);
+            xpallet_gateway_records::Pallet::<T>::remove_asset_chain(id);
+
+            Self::deposit_event(Event::Deregistered(id));
+            T::RegistrarHandler::on_deregister(&id)?;
+            Ok(())
+        }
+
+        /// Recover a deregister asset to the valid state.
+        ///
+        /// `RegistrarHandler::on_register()` will be triggered again during the recover process.
+        ///
+        /// This is a RegistrarOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::recover())]
+        #[transactional]
+        pub fn recover(origin: OriginFor<T>, #[pallet::compact] id: AssetId) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            let info = Self::asset_info_of(id).ok_or(Error::<T>::AssetDoesNotExist)?;
+            ensure!(!Self::is_valid(&id), Error::<T>::AssetAlreadyValid);
+
+            AssetOnline::<T>::insert(id, ());
+            xpallet_gateway_records::Pallet::<T>::set_asset_chain(id, info.chain());
+
+            Self::deposit_event(Event::Recovered(id));
+            T::RegistrarHandler::on_register(&id)?;
+            Ok(())
+        }
+
+        /// Update the asset info, all the new fields are optional.
+        ///
+        /// This is a RegistrarOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::update_asset_info())]
+        pub fn update_asset_info(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: AssetId,
+            token: Option<Token>,
+            token_name: Option<Token>,
+            desc: Option<Desc>,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            let mut info = Self::asset_info_of(&id).ok_or(Error::<T>::AssetDoesNotExist)?;
+            if let Some(t) = token {
+                info.set_token(t)
+            }
+            if let Some(name) = token_name {
+                info.set_token_name(name);
+            }
+            if let Some(desc) = desc {
+                info.set_desc(desc);
+            }
+            info.is_valid::<T>()?;
+            Self::set_metadata(id, &info)?;
+            AssetInfoOf::<T>::insert(id, info);
+
+            Self::deposit_event(Event::AssetInfoUpdated(id));
+            Ok(())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A new asset was registered. [asset_id]
+        Registered(AssetId),
+        /// A deregistered asset was recovered. [asset_id]
+        Recovered(AssetId),
+        /// An asset was deregistered. [asset_id]
+        Deregistered(AssetId),
+        /// The info of an asset was updated. [asset_id]
+        AssetInfoUpdated(AssetId),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Token symbol length is zero or too long
+        InvalidAssetTokenSymbolLength,
+        /// Token symbol char is invalid, only allow ASCII alphanumeric character or '-', '.', '|', '~'
+        InvalidAssetTokenSymbolChar,
+        /// Token name length is zero or too long
+        InvalidAssetTokenNameLength,
+        /// Desc length is too long
+        InvalidAssetDescLength,
+        /// Text is invalid ASCII, only allow ASCII visible character [0x20, 0x7E]
+        InvalidAscii,
+        /// The asset info is invalid
+        InvalidAssetInfo,
+        /// The asset already exists.
+        AssetAlreadyExists,
+        /// The asset is already valid (online), no need to recover.
+        AssetAlreadyValid,
+        /// The asset is invalid (not online).
+        AssetIsInvalid,
+        /// Fail to find the asset.
+        AssetDoesNotExist,
+        /// The asset to adopt does not exist in `pallet_assets`.
+        AssetNotCreated,
+    }
+
+    /// Asset id list for each Chain.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_ids_of)]
+    pub(crate) type AssetIdsOf<T: Config> =
+        StorageMap<_, Twox64Concat, Chain, Vec<AssetId>, ValueQuery>;
+
+    /// Asset info of each asset.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_info_of)]
+    pub(crate) type AssetInfoOf<T: Config> = StorageMap<_, Twox64Concat, AssetId, AssetInfo>;
+
+    /// The map of asset to the online state.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_online)]
+    pub(crate) type AssetOnline<T: Config> = StorageMap<_, Twox64Concat, AssetId, ()>;
+
+    /// The map of asset to the block number at which the asset was registered.
+    #[pallet::storage]
+    #[pallet::getter(fn registered_at)]
+    pub(crate) type RegisteredAt<T: Config> =
+        StorageMap<_, Twox64Concat, AssetId, T::BlockNumber, ValueQuery>;
+}
+
+impl<T: Config> Pallet<T> {
+    /// The account which owns the registered assets.
+    pub fn account_id() -> T::AccountId {
+        T::PalletId::get().into_account()
+    }
+
+    /// Returns an iterator of all the asset ids of all chains so far.
+    #[inline]
+    pub fn asset_ids() -> impl Iterator<Item = AssetId> {
+        Chain::iter().flat_map(Self::asset_ids_of)
+    }
+
+    /// Returns an iterator of all the valid asset ids of all chains so far.
+    #[inline]
+    pub fn valid_asset_ids() -> impl Iterator<Item = AssetId> {
+        Self::asset_ids().filter(Self::is_valid)
+    }
+
+    /// Returns an iterator of tuple (AssetId, AssetInfo) of all assets.
+    #[inline]
+    pub fn asset_infos() -> impl Iterator<Item = (AssetId, AssetInfo)> {
+        AssetInfoOf::<T>::iter()
+    }
+
+    /// Returns the chain of given asset `asset_id`.
+    pub fn chain_of(asset_id: &AssetId) -> Result<Chain, Error<T>> {
+        Self::asset_info_of(asset_id)
+            .map(|info| info.chain())
+            .ok_or(Error::<T>::AssetDoesNotExist)
+    }
+
+    /// Returns true if the given `asset_id` is an online asset.
+    pub fn is_online(asset_id: &AssetId) -> bool {
+        Self::asset_online(asset_id).is_some()
+    }
+
+    /// Returns true if the asset info record of given `asset_id` exists.
+    pub fn exists(asset_id: &AssetId) -> bool {
+        Self::asset_info_of(asset_id).is_some()
+    }
+
+    /// Returns true if the asset of given `asset_id` is valid (online).
+    pub fn is_valid(asset_id: &AssetId) -> bool {
+        Self::is_online(asset_id)
+    }
+
+    /// Register an asset which already exists in `pallet_assets`.
+    pub fn do_adopt(asset_id: AssetId, asset: AssetInfo) -> DispatchResult {
+        asset.is_valid::<T>()?;
+        ensure!(!Self::exists(&asset_id), Error::<T>::AssetAlreadyExists);
+        ensure!(
+            pallet_assets::Pallet::<T>::maybe_total_supply(asset_id).is_some(),
+            Error::<T>::AssetNotCreated
+        );
+
+        info!(
+            target: "runtime::assets_registrar",
+            "[adopt_asset] id:{}, info:{:?}",
+            asset_id, asset
+        );
+
+        Self::set_metadata(asset_id, &asset)?;
+        Self::apply_register(asset_id, asset)?;
+
+        Self::deposit_event(Event::Registered(asset_id));
+        T::RegistrarHandler::on_register(&asset_id)
+    }
+
+    /// Keep the metadata of the asset in `pallet_assets` in line with the asset info.
+    fn set_metadata(id: AssetId, asset: &AssetInfo) -> DispatchResult {
+        pallet_assets::Pallet::<T>::do_force_set_metadata(
+            id,
+            asset.token_name().to_vec(),
+            asset.token().to_vec(),
+            asset.decimals(),
+            false,
+        )
+    }
+
+    /// Actually register an asset.
+    fn apply_register(id: AssetId, asset: AssetInfo) -> DispatchResult {
+        let chain = asset.chain();
+        AssetIdsOf::<T>::mutate(chain, |ids| {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        });
+
+        AssetInfoOf::<T>::insert(&id, asset);
+        AssetOnline::<T>::insert(&id, ());
+
+        RegisteredAt::<T>::insert(&id, frame_system::Pallet::<T>::block_number());
+
+        xpallet_gateway_records::Pallet::<T>::set_asset_chain(id, chain);
+        Ok(())
+    }
+}
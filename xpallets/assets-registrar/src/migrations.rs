@@ -0,0 +1,63 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    log::{error, info},
+    traits::{tokens::fungibles::metadata::Inspect, Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use sp_std::prelude::*;
+
+use xp_assets_registrar::Chain;
+
+use crate::{AssetInfo, Config, Pallet};
+
+/// Adopt the given assets created in the genesis of `pallet_assets`, the asset info
+/// of which is taken from their metadata.
+pub fn adopt_genesis_assets<T: Config>(
+    assets: &[(<T as pallet_assets::Config>::AssetId, Chain)],
+) -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        info!(
+            target: "runtime::assets_registrar",
+            "✅ The genesis assets have been adopted, skip."
+        );
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+
+    info!(
+        target: "runtime::assets_registrar",
+        "✅ Running migration for adopting the genesis assets"
+    );
+    let mut adopted = 0u64;
+    for &(asset_id, chain) in assets {
+        if Pallet::<T>::exists(&asset_id) {
+            continue;
+        }
+        let result = AssetInfo::new::<T>(
+            pallet_assets::Pallet::<T>::symbol(asset_id),
+            pallet_assets::Pallet::<T>::name(asset_id),
+            chain,
+            pallet_assets::Pallet::<T>::decimals(asset_id),
+            Vec::new(),
+        )
+        .map_err(Into::into)
+        .and_then(|info| Pallet::<T>::do_adopt(asset_id, info));
+        match result {
+            Ok(()) => adopted += 1,
+            Err(err) => error!(
+                target: "runtime::assets_registrar",
+                "[migration] fail to adopt the asset, id:{}, err:{:?}",
+                asset_id, err
+            ),
+        }
+    }
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    info!(
+        target: "runtime::assets_registrar",
+        "✅ Migration for adopting {} genesis assets done",
+        adopted
+    );
+    let count = assets.len() as Weight;
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count * 3 + 1, adopted * 7 + 1)
+}
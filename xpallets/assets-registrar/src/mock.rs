@@ -0,0 +1,205 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::cell::RefCell;
+
+use frame_support::{parameter_types, sp_io, PalletId};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchResult,
+};
+
+use sherpax_primitives::AssetId;
+use xp_assets_registrar::RegistrarHandler;
+
+pub use xp_protocol::{X_BTC, X_DOGE};
+
+use crate::{self as xpallet_assets_registrar, *};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        XGatewayRecords: xpallet_gateway_records::{Pallet, Call, Storage, Event<T>},
+        XAssetsRegistrar: xpallet_assets_registrar::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+    pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type ReserveIdentifier = [u8; 8];
+    type MaxReserves = MaxReserves;
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 1;
+    pub const ApprovalDeposit: Balance = 1;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 1;
+    pub const MetadataDepositPerByte: Balance = 1;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = ();
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type Currency = Balances;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type FeeRateOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = ();
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Test>;
+}
+
+parameter_types! {
+    pub const BtcAssetId: AssetId = X_BTC;
+    pub const DogeAssetId: AssetId = X_DOGE;
+}
+
+impl xpallet_gateway_records::Config for Test {
+    type Event = ();
+    type BtcAssetId = BtcAssetId;
+    type DogeAssetId = DogeAssetId;
+    type Currency = Balances;
+    type WeightInfo = xpallet_gateway_records::weights::SubstrateWeight<Test>;
+}
+
+thread_local! {
+    pub static REGISTERED: RefCell<Vec<AssetId>> = RefCell::new(vec![]);
+    pub static DEREGISTERED: RefCell<Vec<AssetId>> = RefCell::new(vec![]);
+}
+
+/// Records the calls of `RegistrarHandler`.
+pub struct MockRegistrarHandler;
+impl RegistrarHandler for MockRegistrarHandler {
+    fn on_register(asset_id: &AssetId) -> DispatchResult {
+        REGISTERED.with(|r| r.borrow_mut().push(*asset_id));
+        Ok(())
+    }
+
+    fn on_deregister(asset_id: &AssetId) -> DispatchResult {
+        DEREGISTERED.with(|r| r.borrow_mut().push(*asset_id));
+        Ok(())
+    }
+}
+
+parameter_types! {
+    pub const AssetsRegistrarPalletId: PalletId = PalletId(*b"pcx/asrg");
+}
+
+impl Config for Test {
+    type Event = ();
+    type PalletId = AssetsRegistrarPalletId;
+    type RegistrarOrigin = EnsureRoot<AccountId>;
+    type RegistrarHandler = MockRegistrarHandler;
+    type WeightInfo = ();
+}
+
+pub type XAssetsRegistrarErr = Error<Test>;
+
+pub fn btc() -> AssetInfo {
+    AssetInfo::new::<Test>(
+        b"XBTC".to_vec(),
+        b"ChainX Bitcoin".to_vec(),
+        Chain::Bitcoin,
+        8,
+        b"ChainX's cross-chain Bitcoin".to_vec(),
+    )
+    .unwrap()
+}
+
+pub fn doge() -> AssetInfo {
+    AssetInfo::new::<Test>(
+        b"XDOGE".to_vec(),
+        b"ChainX Dogecoin".to_vec(),
+        Chain::Dogecoin,
+        8,
+        b"ChainX's cross-chain Dogecoin".to_vec(),
+    )
+    .unwrap()
+}
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+        sp_io::TestExternalities::new(storage)
+    }
+    pub fn build_and_execute(self, test: impl FnOnce()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
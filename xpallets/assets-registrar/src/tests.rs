@@ -0,0 +1,215 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        tokens::fungibles::metadata::Inspect as MetadataInspect, GetStorageVersion, StorageVersion,
+    },
+};
+use sp_runtime::traits::BadOrigin;
+
+use crate::mock::*;
+use crate::*;
+
+#[test]
+fn register_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::signed(1), X_BTC, btc(), 1),
+            BadOrigin
+        );
+        assert_ok!(XAssetsRegistrar::register(Origin::root(), X_BTC, btc(), 1));
+
+        assert_eq!(XAssetsRegistrar::asset_info_of(X_BTC), Some(btc()));
+        assert_eq!(XAssetsRegistrar::asset_ids_of(Chain::Bitcoin), vec![X_BTC]);
+        assert_eq!(XAssetsRegistrar::registered_at(X_BTC), 1);
+        assert!(XAssetsRegistrar::is_valid(&X_BTC));
+        assert_eq!(XGatewayRecords::chain_of(&X_BTC), Ok(Chain::Bitcoin));
+        assert!(Assets::maybe_total_supply(X_BTC).is_some());
+        assert_eq!(Assets::symbol(X_BTC), b"XBTC".to_vec());
+        assert_eq!(Assets::name(X_BTC), b"ChainX Bitcoin".to_vec());
+        assert_eq!(Assets::decimals(X_BTC), 8);
+        assert_eq!(REGISTERED.with(|r| r.borrow().clone()), vec![X_BTC]);
+
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, btc(), 1),
+            XAssetsRegistrarErr::AssetAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn register_should_validate_asset_info() {
+    ExtBuilder::default().build_and_execute(|| {
+        let mut asset = btc();
+        asset.set_token(b"".to_vec());
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, asset.clone(), 1),
+            XAssetsRegistrarErr::InvalidAssetTokenSymbolLength
+        );
+        asset.set_token(vec![b'X'; xp_protocol::ASSET_TOKEN_SYMBOL_MAX_LEN + 1]);
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, asset.clone(), 1),
+            XAssetsRegistrarErr::InvalidAssetTokenSymbolLength
+        );
+        asset.set_token(b"X BTC".to_vec());
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, asset.clone(), 1),
+            XAssetsRegistrarErr::InvalidAssetTokenSymbolChar
+        );
+
+        let mut asset = btc();
+        asset.set_token_name(vec![b'X'; xp_protocol::ASSET_TOKEN_NAME_MAX_LEN + 1]);
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, asset.clone(), 1),
+            XAssetsRegistrarErr::InvalidAssetTokenNameLength
+        );
+        asset.set_token_name(b"<script>".to_vec());
+        assert!(XAssetsRegistrar::register(Origin::root(), X_BTC, asset, 1).is_err());
+
+        let mut asset = btc();
+        asset.set_desc(vec![b'X'; xp_protocol::ASSET_DESC_MAX_LEN + 1]);
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), X_BTC, asset, 1),
+            XAssetsRegistrarErr::InvalidAssetDescLength
+        );
+    });
+}
+
+#[test]
+fn deregister_and_recover_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssetsRegistrar::register(Origin::root(), X_BTC, btc(), 1));
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            X_DOGE,
+            doge(),
+            1
+        ));
+        assert_eq!(
+            XAssetsRegistrar::valid_asset_ids().collect::<Vec<_>>(),
+            vec![X_BTC, X_DOGE]
+        );
+
+        assert_ok!(XAssetsRegistrar::deregister(Origin::root(), X_BTC));
+        assert!(!XAssetsRegistrar::is_valid(&X_BTC));
+        assert!(XGatewayRecords::chain_of(&X_BTC).is_err());
+        assert_eq!(
+            XAssetsRegistrar::valid_asset_ids().collect::<Vec<_>>(),
+            vec![X_DOGE]
+        );
+        assert_eq!(DEREGISTERED.with(|r| r.borrow().clone()), vec![X_BTC]);
+        // the balances are kept in pallet_assets
+        assert!(Assets::maybe_total_supply(X_BTC).is_some());
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::root(), X_BTC),
+            XAssetsRegistrarErr::AssetIsInvalid
+        );
+
+        assert_noop!(
+            XAssetsRegistrar::recover(Origin::root(), X_DOGE),
+            XAssetsRegistrarErr::AssetAlreadyValid
+        );
+        assert_ok!(XAssetsRegistrar::recover(Origin::root(), X_BTC));
+        assert!(XAssetsRegistrar::is_valid(&X_BTC));
+        assert_eq!(XGatewayRecords::chain_of(&X_BTC), Ok(Chain::Bitcoin));
+        assert_eq!(
+            REGISTERED.with(|r| r.borrow().clone()),
+            vec![X_BTC, X_DOGE, X_BTC]
+        );
+    });
+}
+
+#[test]
+fn update_asset_info_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(Origin::root(), X_BTC, None, None, None),
+            XAssetsRegistrarErr::AssetDoesNotExist
+        );
+        assert_ok!(XAssetsRegistrar::register(Origin::root(), X_BTC, btc(), 1));
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                Some(b"X BTC".to_vec()),
+                None,
+                None
+            ),
+            XAssetsRegistrarErr::InvalidAssetTokenSymbolChar
+        );
+        assert_ok!(XAssetsRegistrar::update_asset_info(
+            Origin::root(),
+            X_BTC,
+            Some(b"SBTC".to_vec()),
+            None,
+            None
+        ));
+        let info = XAssetsRegistrar::asset_info_of(X_BTC).unwrap();
+        assert_eq!(info.token(), &b"SBTC".to_vec());
+        assert_eq!(info.token_name(), btc().token_name());
+        assert_eq!(Assets::symbol(X_BTC), b"SBTC".to_vec());
+        assert_eq!(Assets::name(X_BTC), b"ChainX Bitcoin".to_vec());
+    });
+}
+
+#[test]
+fn adopt_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssetsRegistrar::adopt(Origin::root(), X_DOGE, doge()),
+            XAssetsRegistrarErr::AssetNotCreated
+        );
+        assert_ok!(Assets::force_create(Origin::root(), X_DOGE, 1, true, 1));
+        assert_noop!(
+            XAssetsRegistrar::adopt(Origin::signed(1), X_DOGE, doge()),
+            BadOrigin
+        );
+        assert_ok!(XAssetsRegistrar::adopt(Origin::root(), X_DOGE, doge()));
+
+        assert_eq!(XAssetsRegistrar::asset_info_of(X_DOGE), Some(doge()));
+        assert!(XAssetsRegistrar::is_valid(&X_DOGE));
+        assert_eq!(XGatewayRecords::chain_of(&X_DOGE), Ok(Chain::Dogecoin));
+        assert_eq!(Assets::symbol(X_DOGE), b"XDOGE".to_vec());
+        assert_eq!(REGISTERED.with(|r| r.borrow().clone()), vec![X_DOGE]);
+
+        assert_noop!(
+            XAssetsRegistrar::adopt(Origin::root(), X_DOGE, doge()),
+            XAssetsRegistrarErr::AssetAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn adopt_genesis_assets_migration_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(Assets::force_create(Origin::root(), X_BTC, 1, true, 1));
+        assert_ok!(Assets::force_set_metadata(
+            Origin::root(),
+            X_BTC,
+            b"SBTC".to_vec(),
+            b"SBTC".to_vec(),
+            8,
+            false
+        ));
+        let genesis_assets = [(X_BTC, Chain::Bitcoin), (X_DOGE, Chain::Dogecoin)];
+        StorageVersion::new(0).put::<XAssetsRegistrar>();
+
+        migrations::adopt_genesis_assets::<Test>(&genesis_assets);
+        let info = XAssetsRegistrar::asset_info_of(X_BTC).unwrap();
+        assert_eq!(info.token(), &b"SBTC".to_vec());
+        assert_eq!(info.chain(), Chain::Bitcoin);
+        assert_eq!(info.decimals(), 8);
+        assert!(XAssetsRegistrar::is_valid(&X_BTC));
+        // the asset which is not created is skipped
+        assert!(!XAssetsRegistrar::exists(&X_DOGE));
+
+        assert_eq!(XAssetsRegistrar::on_chain_storage_version(), 1);
+
+        // run again, the asset created later is not adopted
+        assert_ok!(Assets::force_create(Origin::root(), X_DOGE, 1, true, 1));
+        migrations::adopt_genesis_assets::<Test>(&genesis_assets);
+        assert!(!XAssetsRegistrar::exists(&X_DOGE));
+        assert_eq!(REGISTERED.with(|r| r.borrow().clone()), vec![X_BTC]);
+    });
+}
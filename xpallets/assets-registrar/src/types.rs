@@ -0,0 +1,125 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::{dispatch::DispatchResult, ensure, RuntimeDebug};
+
+use sherpax_primitives::{Decimals, Desc, Token};
+use xp_assets_registrar::Chain;
+use xp_protocol::{ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN, ASSET_TOKEN_SYMBOL_MAX_LEN};
+use xp_runtime::xss_check;
+
+use crate::{Config, Error};
+
+/// Token can only use ASCII alphanumeric character or "-.|~".
+pub fn is_valid_token<T: Config>(token: &[u8]) -> DispatchResult {
+    ensure!(
+        !token.is_empty() && token.len() <= ASSET_TOKEN_SYMBOL_MAX_LEN,
+        Error::<T>::InvalidAssetTokenSymbolLength
+    );
+    let is_valid = |c: &u8| -> bool { c.is_ascii_alphanumeric() || b"-.|~".contains(c) };
+    for c in token {
+        ensure!(is_valid(c), Error::<T>::InvalidAssetTokenSymbolChar);
+    }
+    Ok(())
+}
+
+/// A valid token name should have a legal length and be visible ASCII chars only.
+pub fn is_valid_token_name<T: Config>(name: &[u8]) -> DispatchResult {
+    ensure!(
+        !name.is_empty() && name.len() <= ASSET_TOKEN_NAME_MAX_LEN,
+        Error::<T>::InvalidAssetTokenNameLength
+    );
+    xss_check(name)?;
+    for c in name {
+        ensure!((0x20..=0x7E).contains(c), Error::<T>::InvalidAscii);
+    }
+    Ok(())
+}
+
+/// A valid desc should be visible ASCII chars only and not too long.
+pub fn is_valid_desc<T: Config>(desc: &[u8]) -> DispatchResult {
+    ensure!(
+        desc.len() <= ASSET_DESC_MAX_LEN,
+        Error::<T>::InvalidAssetDescLength
+    );
+    xss_check(desc)?;
+    for c in desc {
+        ensure!((0x20..=0x7E).contains(c), Error::<T>::InvalidAscii);
+    }
+    Ok(())
+}
+
+/// The info of a cross-chain asset.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetInfo {
+    token: Token,
+    token_name: Token,
+    chain: Chain,
+    decimals: Decimals,
+    desc: Desc,
+}
+
+impl AssetInfo {
+    pub fn new<T: Config>(
+        token: Token,
+        token_name: Token,
+        chain: Chain,
+        decimals: Decimals,
+        desc: Desc,
+    ) -> Result<Self, Error<T>> {
+        let a = AssetInfo {
+            token,
+            token_name,
+            chain,
+            decimals,
+            desc,
+        };
+        a.is_valid::<T>()
+            .map_err(|_| Error::<T>::InvalidAssetInfo)?;
+        Ok(a)
+    }
+
+    pub fn is_valid<T: Config>(&self) -> DispatchResult {
+        is_valid_token::<T>(&self.token)?;
+        is_valid_token_name::<T>(&self.token_name)?;
+        is_valid_desc::<T>(&self.desc)
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn token_name(&self) -> &Token {
+        &self.token_name
+    }
+
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    pub fn decimals(&self) -> Decimals {
+        self.decimals
+    }
+
+    pub fn desc(&self) -> &Desc {
+        &self.desc
+    }
+
+    pub fn set_token(&mut self, token: Token) {
+        self.token = token
+    }
+
+    pub fn set_token_name(&mut self, token_name: Token) {
+        self.token_name = token_name
+    }
+
+    pub fn set_desc(&mut self, desc: Desc) {
+        self.desc = desc
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::tokens::fungibles::Mutate;
+use frame_system::RawOrigin;
+
+use super::*;
+use crate::Pallet as XAssetsVesting;
+
+const SEED: u32 = 0;
+const LOCKED: u32 = 10_000;
+const PER_BLOCK: u32 = 10;
+
+fn create_asset<T: Config>() -> T::AssetId {
+    let id = T::AssetId::default();
+    let owner: T::AccountId = account("owner", 0, SEED);
+    let _ = pallet_assets::Pallet::<T>::force_create(
+        RawOrigin::Root.into(),
+        id,
+        T::Lookup::unlookup(owner),
+        true,
+        1u32.into(),
+    );
+    id
+}
+
+fn mint<T: Config>(id: T::AssetId, who: &T::AccountId, amount: T::Balance) {
+    <pallet_assets::Pallet<T> as Mutate<T::AccountId>>::mint_into(id, who, amount).unwrap();
+}
+
+fn schedule<T: Config>() -> VestingInfoOf<T> {
+    VestingInfo::new(LOCKED.into(), PER_BLOCK.into(), 1u32.into())
+}
+
+fn add_vesting_schedules<T: Config>(id: T::AssetId, who: &T::AccountId, s: u32) {
+    for _ in 0..s {
+        mint::<T>(id, who, LOCKED.into());
+        XAssetsVesting::<T>::add_vesting_schedule(id, who, schedule::<T>()).unwrap();
+    }
+}
+
+benchmarks! {
+    vest {
+        let s in 1 .. T::MAX_VESTING_SCHEDULES;
+
+        let id = create_asset::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        add_vesting_schedules::<T>(id, &caller, s);
+        frame_system::Pallet::<T>::set_block_number(11u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), id)
+    verify {
+        let unvested: T::Balance = ((LOCKED - PER_BLOCK * 10) * s).into();
+        assert_eq!(XAssetsVesting::<T>::locked(id, &caller), Some(unvested));
+    }
+
+    vest_other {
+        let s in 1 .. T::MAX_VESTING_SCHEDULES;
+
+        let id = create_asset::<T>();
+        let target: T::AccountId = account("target", 0, SEED);
+        add_vesting_schedules::<T>(id, &target, s);
+        frame_system::Pallet::<T>::set_block_number(11u32.into());
+
+        let caller: T::AccountId = whitelisted_caller();
+        let target_lookup = T::Lookup::unlookup(target.clone());
+    }: _(RawOrigin::Signed(caller), id, target_lookup)
+    verify {
+        let unvested: T::Balance = ((LOCKED - PER_BLOCK * 10) * s).into();
+        assert_eq!(XAssetsVesting::<T>::locked(id, &target), Some(unvested));
+    }
+
+    vested_transfer {
+        let s in 0 .. T::MAX_VESTING_SCHEDULES - 1;
+
+        let id = create_asset::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        mint::<T>(id, &caller, (LOCKED * 2).into());
+        let target: T::AccountId = account("target", 0, SEED);
+        add_vesting_schedules::<T>(id, &target, s);
+        let target_lookup = T::Lookup::unlookup(target.clone());
+    }: _(RawOrigin::Signed(caller), id, target_lookup, schedule::<T>())
+    verify {
+        assert_eq!(XAssetsVesting::<T>::vesting(id, &target).unwrap().len(), s as usize + 1);
+    }
+
+    force_vested_transfer {
+        let s in 0 .. T::MAX_VESTING_SCHEDULES - 1;
+
+        let id = create_asset::<T>();
+        let source: T::AccountId = account("source", 0, SEED);
+        mint::<T>(id, &source, (LOCKED * 2).into());
+        let source_lookup = T::Lookup::unlookup(source);
+        let target: T::AccountId = account("target", 0, SEED);
+        add_vesting_schedules::<T>(id, &target, s);
+        let target_lookup = T::Lookup::unlookup(target.clone());
+    }: _(RawOrigin::Root, id, source_lookup, target_lookup, schedule::<T>())
+    verify {
+        assert_eq!(XAssetsVesting::<T>::vesting(id, &target).unwrap().len(), s as usize + 1);
+    }
+
+    merge_schedules {
+        let s in 2 .. T::MAX_VESTING_SCHEDULES;
+
+        let id = create_asset::<T>();
+        let caller: T::AccountId = whitelisted_caller();
+        add_vesting_schedules::<T>(id, &caller, s);
+    }: _(RawOrigin::Signed(caller.clone()), id, 0, s - 1)
+    verify {
+        assert_eq!(XAssetsVesting::<T>::vesting(id, &caller).unwrap().len(), s as usize - 1);
+        assert_eq!(XAssetsVesting::<T>::locked(id, &caller), Some((LOCKED * s).into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(Pallet::<Test>::test_benchmark_vest());
+            assert_ok!(Pallet::<Test>::test_benchmark_vest_other());
+            assert_ok!(Pallet::<Test>::test_benchmark_vested_transfer());
+            assert_ok!(Pallet::<Test>::test_benchmark_force_vested_transfer());
+            assert_ok!(Pallet::<Test>::test_benchmark_merge_schedules());
+        });
+    }
+}
@@ -0,0 +1,469 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Assets Vesting Module
+//!
+//! A simple module providing the vesting schedules of the `pallet_assets` balances,
+//! e.g. the team and ecosystem allocations in X-BTC.
+//!
+//! The locked portion of the vested balance is frozen by a named lock of `pallet_assets`,
+//! so it can not be transferred or withdrawn.
+//!
+//! - `vest`: unlock any vested funds of the sender account.
+//! - `vest_other`: unlock any vested funds of a `target` account.
+//! - `vested_transfer`: create a vested transfer.
+//! - `force_vested_transfer`: force a vested transfer, ForceVestingOrigin-only.
+//! - `merge_schedules`: merge two vesting schedules together, creating a new vesting
+//!   schedule that unlocks over the highest possible start and end blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub mod weights;
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    log::info,
+    traits::{
+        tokens::fungibles::{Inspect, Transfer},
+        Get,
+    },
+    transactional, BoundedVec,
+};
+use sp_runtime::traits::{Convert, One, Saturating, StaticLookup, Zero};
+use sp_std::prelude::*;
+
+use pallet_assets::{LockIdentifier, LockReason, LockableAssets};
+
+pub use self::types::VestingInfo;
+pub use self::weights::WeightInfo;
+pub use pallet::*;
+
+/// The identifier of the `pallet_assets` lock which freezes the unvested balance.
+pub const VESTING_LOCK_ID: LockIdentifier = *b"xvesting";
+
+pub type VestingInfoOf<T> =
+    VestingInfo<<T as pallet_assets::Config>::Balance, <T as frame_system::Config>::BlockNumber>;
+
+/// Maximum number of vesting schedules an account may have for one asset.
+pub struct MaxVestingSchedulesGet<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Get<u32> for MaxVestingSchedulesGet<T> {
+    fn get() -> u32 {
+        T::MAX_VESTING_SCHEDULES
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_assets::Config {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Convert the block number into a balance.
+        type BlockNumberToBalance: Convert<Self::BlockNumber, Self::Balance>;
+
+        /// The origin which may force a vested transfer from any account.
+        type ForceVestingOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Maximum number of vesting schedules an account may have for one asset.
+        const MAX_VESTING_SCHEDULES: u32;
+    }
+
+    #[pallet::extra_constants]
+    impl<T: Config> Pallet<T> {
+        #[pallet::constant_name(MaxVestingSchedules)]
+        fn max_vesting_schedules() -> u32 {
+            T::MAX_VESTING_SCHEDULES
+        }
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(crate) trait Store)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn integrity_test() {
+            assert!(
+                T::MAX_VESTING_SCHEDULES > 0,
+                "`MaxVestingSchedules` must be greater than 0"
+            );
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Unlock any vested funds of the sender account.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and the sender must have funds
+        /// still locked under this pallet for the asset `id`.
+        #[pallet::weight(<T as Config>::WeightInfo::vest(T::MAX_VESTING_SCHEDULES))]
+        #[transactional]
+        pub fn vest(origin: OriginFor<T>, #[pallet::compact] id: T::AssetId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_vest(id, who)
+        }
+
+        /// Unlock any vested funds of a `target` account.
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        #[pallet::weight(<T as Config>::WeightInfo::vest_other(T::MAX_VESTING_SCHEDULES))]
+        #[transactional]
+        pub fn vest_other(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let who = T::Lookup::lookup(target)?;
+            Self::do_vest(id, who)
+        }
+
+        /// Create a vested transfer of the asset `id`.
+        ///
+        /// The locked amount of the `schedule` is transferred from the sender to `target`,
+        /// it must be at least the minimum balance of the asset.
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        #[pallet::weight(<T as Config>::WeightInfo::vested_transfer(T::MAX_VESTING_SCHEDULES))]
+        #[transactional]
+        pub fn vested_transfer(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            schedule: VestingInfoOf<T>,
+        ) -> DispatchResult {
+            let transactor = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+            Self::do_vested_transfer(id, transactor, target, schedule)
+        }
+
+        /// Force a vested transfer of the asset `id` from `source` to `target`.
+        ///
+        /// This is a ForceVestingOrigin-only operation.
+        #[pallet::weight(<T as Config>::WeightInfo::force_vested_transfer(T::MAX_VESTING_SCHEDULES))]
+        #[transactional]
+        pub fn force_vested_transfer(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            source: <T::Lookup as StaticLookup>::Source,
+            target: <T::Lookup as StaticLookup>::Source,
+            schedule: VestingInfoOf<T>,
+        ) -> DispatchResult {
+            T::ForceVestingOrigin::ensure_origin(origin)?;
+            let source = T::Lookup::lookup(source)?;
+            let target = T::Lookup::lookup(target)?;
+            Self::do_vested_transfer(id, source, target, schedule)
+        }
+
+        /// Merge two vesting schedules of the asset `id` together, creating a new vesting
+        /// schedule that unlocks over the highest possible start and end blocks. If both
+        /// schedules have already started the current block will be used as the schedule
+        /// start; with the caveat that if one schedule is finished by the current block,
+        /// the other will be treated as the new merged schedule, unmodified.
+        ///
+        /// NOTE: If `schedule1_index == schedule2_index` this is a no-op.
+        /// NOTE: This will unlock all schedules through the current block prior to merging.
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        #[pallet::weight(<T as Config>::WeightInfo::merge_schedules(T::MAX_VESTING_SCHEDULES))]
+        #[transactional]
+        pub fn merge_schedules(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            schedule1_index: u32,
+            schedule2_index: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            if schedule1_index == schedule2_index {
+                return Ok(());
+            }
+            let schedule1_index = schedule1_index as usize;
+            let schedule2_index = schedule2_index as usize;
+
+            let schedules = Self::vesting(id, &who).ok_or(Error::<T>::NotVesting)?;
+            ensure!(
+                schedule1_index < schedules.len() && schedule2_index < schedules.len(),
+                Error::<T>::ScheduleIndexOutOfBounds
+            );
+            let schedule1 = schedules[schedule1_index];
+            let schedule2 = schedules[schedule2_index];
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let (mut schedules, mut locked_now) =
+                Self::update_schedules(schedules.into_inner(), &[schedule1_index, schedule2_index]);
+            if let Some(new_schedule) = Self::merge_vesting_info(now, schedule1, schedule2) {
+                schedules.push(new_schedule);
+                locked_now = locked_now
+                    .saturating_add(new_schedule.locked_at::<T::BlockNumberToBalance>(now));
+            }
+
+            Self::write_vesting(id, &who, schedules)?;
+            Self::write_lock(id, &who, locked_now)
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The amount vested has been updated. This could indicate a change in funds available.
+        /// The balance given is the amount which is left unvested (and thus locked).
+        VestingUpdated {
+            asset_id: T::AssetId,
+            account: T::AccountId,
+            unvested: T::Balance,
+        },
+        /// An account has become fully vested.
+        VestingCompleted {
+            asset_id: T::AssetId,
+            account: T::AccountId,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The account given is not vesting.
+        NotVesting,
+        /// The account already has `MaxVestingSchedules` count of schedules and thus
+        /// cannot add another one. Consider merging existing schedules in order to add another.
+        AtMaxVestingSchedules,
+        /// Amount being transferred is too low to create a vesting schedule.
+        AmountLow,
+        /// An index was out of bounds of the vesting schedules.
+        ScheduleIndexOutOfBounds,
+        /// Failed to create a new schedule because some parameter was invalid.
+        InvalidScheduleParams,
+    }
+
+    /// Information regarding the vesting of a given asset of a given account.
+    #[pallet::storage]
+    #[pallet::getter(fn vesting)]
+    pub(crate) type Vesting<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<VestingInfoOf<T>, MaxVestingSchedulesGet<T>>,
+    >;
+}
+
+impl<T: Config> Pallet<T> {
+    fn do_vest(id: T::AssetId, who: T::AccountId) -> DispatchResult {
+        let schedules = Self::vesting(id, &who).ok_or(Error::<T>::NotVesting)?;
+        let (schedules, locked_now) = Self::update_schedules(schedules.into_inner(), &[]);
+
+        Self::write_vesting(id, &who, schedules)?;
+        Self::write_lock(id, &who, locked_now)
+    }
+
+    fn do_vested_transfer(
+        id: T::AssetId,
+        source: T::AccountId,
+        target: T::AccountId,
+        schedule: VestingInfoOf<T>,
+    ) -> DispatchResult {
+        ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+        ensure!(
+            schedule.locked() >= pallet_assets::Pallet::<T>::minimum_balance(id),
+            Error::<T>::AmountLow
+        );
+        Self::ensure_can_add_vesting_schedule(id, &target)?;
+
+        info!(
+            target: "runtime::assets_vesting",
+            "[vested_transfer] id:{:?}, source:{:?}, target:{:?}, schedule:{:?}",
+            id, source, target, schedule
+        );
+        <pallet_assets::Pallet<T> as Transfer<T::AccountId>>::transfer(
+            id,
+            &source,
+            &target,
+            schedule.locked(),
+            false,
+        )?;
+        Self::add_vesting_schedule(id, &target, schedule)
+    }
+
+    fn ensure_can_add_vesting_schedule(id: T::AssetId, who: &T::AccountId) -> DispatchResult {
+        let count = Self::vesting(id, who).map_or(0, |s| s.len());
+        ensure!(
+            count < T::MAX_VESTING_SCHEDULES as usize,
+            Error::<T>::AtMaxVestingSchedules
+        );
+        Ok(())
+    }
+
+    /// Add a vesting schedule to the account `who` of the asset `id`, the funds of
+    /// the schedule must have been deposited.
+    pub fn add_vesting_schedule(
+        id: T::AssetId,
+        who: &T::AccountId,
+        schedule: VestingInfoOf<T>,
+    ) -> DispatchResult {
+        ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+        let mut schedules = Self::vesting(id, who).unwrap_or_default();
+        schedules
+            .try_push(schedule)
+            .map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+
+        let (schedules, locked_now) = Self::update_schedules(schedules.into_inner(), &[]);
+        Self::write_vesting(id, who, schedules)?;
+        Self::write_lock(id, who, locked_now)
+    }
+
+    /// Remove the schedules at `skip` indices and the ones that have been fully vested,
+    /// returns the remaining schedules and the amount still locked by them.
+    fn update_schedules(
+        schedules: Vec<VestingInfoOf<T>>,
+        skip: &[usize],
+    ) -> (Vec<VestingInfoOf<T>>, T::Balance) {
+        let now = frame_system::Pallet::<T>::block_number();
+        let mut total_locked_now: T::Balance = Zero::zero();
+        let schedules = schedules
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, schedule)| {
+                if skip.contains(&index) {
+                    return None;
+                }
+                let locked_now = schedule.locked_at::<T::BlockNumberToBalance>(now);
+                if locked_now.is_zero() {
+                    None
+                } else {
+                    total_locked_now = total_locked_now.saturating_add(locked_now);
+                    Some(schedule)
+                }
+            })
+            .collect::<Vec<_>>();
+        (schedules, total_locked_now)
+    }
+
+    /// Merge two schedules into one, `None` if both of them have ended.
+    fn merge_vesting_info(
+        now: T::BlockNumber,
+        schedule1: VestingInfoOf<T>,
+        schedule2: VestingInfoOf<T>,
+    ) -> Option<VestingInfoOf<T>> {
+        let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
+        let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
+        let now_as_balance = T::BlockNumberToBalance::convert(now);
+
+        // Check if one or both schedules have ended.
+        match (
+            schedule1_ending_block <= now_as_balance,
+            schedule2_ending_block <= now_as_balance,
+        ) {
+            // If both schedules have ended, we don't merge and exit early.
+            (true, true) => return None,
+            // If one schedule has ended, we treat the one that has not ended as the new
+            // merged schedule.
+            (true, false) => return Some(schedule2),
+            (false, true) => return Some(schedule1),
+            // If neither schedule has ended don't exit early.
+            _ => {}
+        }
+
+        let locked = schedule1
+            .locked_at::<T::BlockNumberToBalance>(now)
+            .saturating_add(schedule2.locked_at::<T::BlockNumberToBalance>(now));
+        // This shouldn't happen because we know at least one ending block is greater than now,
+        // thus at least a schedule a some locked balance.
+        debug_assert!(
+            !locked.is_zero(),
+            "merge_vesting_info validation checks failed to catch a locked of 0"
+        );
+
+        let ending_block = schedule1_ending_block.max(schedule2_ending_block);
+        let starting_block = now
+            .max(schedule1.starting_block())
+            .max(schedule2.starting_block());
+
+        let per_block = {
+            let duration = ending_block
+                .saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+                .max(One::one());
+            (locked / duration).max(One::one())
+        };
+
+        let schedule = VestingInfo::new(locked, per_block, starting_block);
+        debug_assert!(
+            schedule.is_valid(),
+            "merge_vesting_info schedule validation check failed"
+        );
+
+        Some(schedule)
+    }
+
+    fn write_vesting(
+        id: T::AssetId,
+        who: &T::AccountId,
+        schedules: Vec<VestingInfoOf<T>>,
+    ) -> DispatchResult {
+        let schedules: BoundedVec<VestingInfoOf<T>, MaxVestingSchedulesGet<T>> = schedules
+            .try_into()
+            .map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+
+        if schedules.is_empty() {
+            Vesting::<T>::remove(id, who);
+        } else {
+            Vesting::<T>::insert(id, who, schedules)
+        }
+        Ok(())
+    }
+
+    /// The unvested balance of the asset `id` of `who`, which is frozen in `pallet_assets`.
+    pub fn locked(id: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+        let locked = <pallet_assets::Pallet<T> as LockableAssets<T::AccountId>>::lock_amount(
+            VESTING_LOCK_ID,
+            id,
+            who,
+        );
+        if locked.is_zero() {
+            None
+        } else {
+            Some(locked)
+        }
+    }
+
+    fn write_lock(
+        id: T::AssetId,
+        who: &T::AccountId,
+        total_locked_now: T::Balance,
+    ) -> DispatchResult {
+        <pallet_assets::Pallet<T> as LockableAssets<T::AccountId>>::set_lock(
+            VESTING_LOCK_ID,
+            id,
+            who,
+            total_locked_now,
+            LockReason::Freeze,
+        )?;
+        if total_locked_now.is_zero() {
+            Self::deposit_event(Event::<T>::VestingCompleted {
+                asset_id: id,
+                account: who.clone(),
+            });
+        } else {
+            Self::deposit_event(Event::<T>::VestingUpdated {
+                asset_id: id,
+                account: who.clone(),
+                unvested: total_locked_now,
+            });
+        }
+        Ok(())
+    }
+}
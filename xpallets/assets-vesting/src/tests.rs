@@ -0,0 +1,205 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
+
+use crate::mock::*;
+use crate::*;
+
+#[test]
+fn vested_transfer_should_lock_the_unvested_balance() {
+    ExtBuilder::default().build_and_execute(|| {
+        let schedule = VestingInfo::new(1000, 100, 10);
+        assert_ok!(XAssetsVesting::vested_transfer(
+            Origin::signed(ALICE),
+            X_BTC,
+            CHARLIE,
+            schedule
+        ));
+        assert_eq!(Assets::balance(X_BTC, CHARLIE), 1000);
+        assert_eq!(
+            XAssetsVesting::vesting(X_BTC, CHARLIE).unwrap().to_vec(),
+            vec![schedule]
+        );
+        assert_eq!(Assets::frozen_balance(X_BTC, &CHARLIE), Some(1000));
+        assert!(Assets::transfer(Origin::signed(CHARLIE), X_BTC, BOB, 1).is_err());
+
+        // nothing is released before the cliff
+        System::set_block_number(10);
+        assert_ok!(XAssetsVesting::vest(Origin::signed(CHARLIE), X_BTC));
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(1000));
+
+        System::set_block_number(15);
+        assert_ok!(XAssetsVesting::vest_other(
+            Origin::signed(BOB),
+            X_BTC,
+            CHARLIE
+        ));
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(500));
+        // keep the locked balance and the minimum balance
+        assert_ok!(Assets::transfer(Origin::signed(CHARLIE), X_BTC, BOB, 490));
+        assert!(Assets::transfer(Origin::signed(CHARLIE), X_BTC, BOB, 1).is_err());
+
+        System::set_block_number(20);
+        assert_ok!(XAssetsVesting::vest(Origin::signed(CHARLIE), X_BTC));
+        assert_eq!(XAssetsVesting::vesting(X_BTC, CHARLIE), None);
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), None);
+        assert_eq!(Assets::frozen_balance(X_BTC, &CHARLIE), None);
+        assert_ok!(Assets::transfer(Origin::signed(CHARLIE), X_BTC, BOB, 510));
+
+        assert_noop!(
+            XAssetsVesting::vest(Origin::signed(CHARLIE), X_BTC),
+            XAssetsVestingErr::NotVesting
+        );
+    });
+}
+
+#[test]
+fn vested_transfer_should_check_the_schedule() {
+    ExtBuilder::default().build_and_execute(|| {
+        // less than the minimum balance of the asset
+        assert_noop!(
+            XAssetsVesting::vested_transfer(
+                Origin::signed(ALICE),
+                X_BTC,
+                CHARLIE,
+                VestingInfo::new(5, 1, 10)
+            ),
+            XAssetsVestingErr::AmountLow
+        );
+        assert_noop!(
+            XAssetsVesting::vested_transfer(
+                Origin::signed(ALICE),
+                X_BTC,
+                CHARLIE,
+                VestingInfo::new(1000, 0, 10)
+            ),
+            XAssetsVestingErr::InvalidScheduleParams
+        );
+
+        for _ in 0..3 {
+            assert_ok!(XAssetsVesting::vested_transfer(
+                Origin::signed(ALICE),
+                X_BTC,
+                CHARLIE,
+                VestingInfo::new(100, 10, 10)
+            ));
+        }
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(300));
+        assert_noop!(
+            XAssetsVesting::vested_transfer(
+                Origin::signed(ALICE),
+                X_BTC,
+                CHARLIE,
+                VestingInfo::new(100, 10, 10)
+            ),
+            XAssetsVestingErr::AtMaxVestingSchedules
+        );
+
+        // the vested balance can not be transferred
+        assert!(XAssetsVesting::vested_transfer(
+            Origin::signed(CHARLIE),
+            X_BTC,
+            BOB,
+            VestingInfo::new(100, 10, 10)
+        )
+        .is_err());
+    });
+}
+
+#[test]
+fn force_vested_transfer_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let schedule = VestingInfo::new(1000, 100, 10);
+        assert_noop!(
+            XAssetsVesting::force_vested_transfer(
+                Origin::signed(BOB),
+                X_BTC,
+                BOB,
+                CHARLIE,
+                schedule
+            ),
+            BadOrigin
+        );
+        assert_ok!(XAssetsVesting::force_vested_transfer(
+            Origin::root(),
+            X_BTC,
+            BOB,
+            CHARLIE,
+            schedule
+        ));
+        assert_eq!(Assets::balance(X_BTC, BOB), 9000);
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(1000));
+    });
+}
+
+#[test]
+fn merge_schedules_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let schedule1 = VestingInfo::new(1000, 100, 10);
+        let schedule2 = VestingInfo::new(2000, 100, 20);
+        assert_ok!(XAssetsVesting::vested_transfer(
+            Origin::signed(ALICE),
+            X_BTC,
+            CHARLIE,
+            schedule1
+        ));
+        assert_ok!(XAssetsVesting::vested_transfer(
+            Origin::signed(ALICE),
+            X_BTC,
+            CHARLIE,
+            schedule2
+        ));
+
+        // no-op
+        assert_ok!(XAssetsVesting::merge_schedules(
+            Origin::signed(CHARLIE),
+            X_BTC,
+            0,
+            0
+        ));
+        assert_eq!(XAssetsVesting::vesting(X_BTC, CHARLIE).unwrap().len(), 2);
+        assert_noop!(
+            XAssetsVesting::merge_schedules(Origin::signed(CHARLIE), X_BTC, 0, 2),
+            XAssetsVestingErr::ScheduleIndexOutOfBounds
+        );
+        assert_noop!(
+            XAssetsVesting::merge_schedules(Origin::signed(BOB), X_BTC, 0, 1),
+            XAssetsVestingErr::NotVesting
+        );
+
+        // schedule1 ends at 20, schedule2 ends at 40.
+        assert_ok!(XAssetsVesting::merge_schedules(
+            Origin::signed(CHARLIE),
+            X_BTC,
+            0,
+            1
+        ));
+        assert_eq!(
+            XAssetsVesting::vesting(X_BTC, CHARLIE).unwrap().to_vec(),
+            vec![VestingInfo::new(3000, 150, 20)]
+        );
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(3000));
+
+        // a schedule that has ended is dropped, the other is kept unmodified.
+        let schedule3 = VestingInfo::new(100, 100, 30);
+        assert_ok!(XAssetsVesting::vested_transfer(
+            Origin::signed(ALICE),
+            X_BTC,
+            CHARLIE,
+            schedule3
+        ));
+        System::set_block_number(35);
+        assert_ok!(XAssetsVesting::merge_schedules(
+            Origin::signed(CHARLIE),
+            X_BTC,
+            0,
+            1
+        ));
+        assert_eq!(
+            XAssetsVesting::vesting(X_BTC, CHARLIE).unwrap().to_vec(),
+            vec![VestingInfo::new(3000, 150, 20)]
+        );
+        assert_eq!(XAssetsVesting::locked(X_BTC, &CHARLIE), Some(750));
+    });
+}
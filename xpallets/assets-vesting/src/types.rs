@@ -0,0 +1,96 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use frame_support::RuntimeDebug;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Convert, One, Saturating, Zero};
+
+/// Struct to encode the vesting schedule of an individual account.
+///
+/// Nothing is released before `starting_block`, which serves as the cliff,
+/// and then `per_block` is released linearly until `locked` is all released.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct VestingInfo<Balance, BlockNumber> {
+    /// Locked amount at genesis.
+    locked: Balance,
+    /// Amount that gets unlocked every block after `starting_block`.
+    per_block: Balance,
+    /// Starting block for unlocking(vesting).
+    starting_block: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingInfo<Balance, BlockNumber>
+where
+    Balance: AtLeast32BitUnsigned + Copy,
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Instantiate a new `VestingInfo`.
+    pub fn new(locked: Balance, per_block: Balance, starting_block: BlockNumber) -> Self {
+        Self {
+            locked,
+            per_block,
+            starting_block,
+        }
+    }
+
+    /// Validate parameters for `VestingInfo`. Note that this does not check
+    /// against the minimum balance of the asset.
+    pub fn is_valid(&self) -> bool {
+        !self.locked.is_zero() && !self.per_block.is_zero()
+    }
+
+    /// Locked amount at schedule creation.
+    pub fn locked(&self) -> Balance {
+        self.locked
+    }
+
+    /// Amount that gets unlocked every block after `starting_block`.
+    pub fn per_block(&self) -> Balance {
+        self.per_block
+    }
+
+    /// Starting block for unlocking(vesting).
+    pub fn starting_block(&self) -> BlockNumber {
+        self.starting_block
+    }
+
+    /// Amount locked at block `n`.
+    pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+        &self,
+        n: BlockNumber,
+    ) -> Balance {
+        // Number of blocks that count toward vesting;
+        // saturating to 0 when n < starting_block.
+        let vested_block_count = n.saturating_sub(self.starting_block);
+        let vested_block_count = BlockNumberToBalance::convert(vested_block_count);
+        // Return amount that is still locked in vesting.
+        vested_block_count
+            .checked_mul(&self.per_block)
+            .map(|balance| self.locked.saturating_sub(balance))
+            .unwrap_or_else(Zero::zero)
+    }
+
+    /// Block number at which the schedule ends (as type `Balance`).
+    pub fn ending_block_as_balance<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
+        &self,
+    ) -> Balance {
+        let starting_block = BlockNumberToBalance::convert(self.starting_block);
+        let duration = if self.per_block >= self.locked {
+            // If `per_block` is bigger than `locked`, the schedule will end
+            // the block after starting.
+            One::one()
+        } else {
+            self.locked / self.per_block
+                + if (self.locked % self.per_block).is_zero() {
+                    Zero::zero()
+                } else {
+                    // `per_block` does not perfectly divide `locked`, so we need an extra block to
+                    // unlock some amount less than `per_block`.
+                    One::one()
+                }
+        };
+
+        starting_block.saturating_add(duration)
+    }
+}
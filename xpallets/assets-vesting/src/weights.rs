@@ -0,0 +1,105 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_assets_vesting
+//! The weights are to be regenerated by running the benchmark CLI:
+
+// ./target/release/sherpax
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_assets_vesting
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/assets-vesting/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_assets_vesting.
+pub trait WeightInfo {
+    fn vest(s: u32) -> Weight;
+    fn vest_other(s: u32) -> Weight;
+    fn vested_transfer(s: u32) -> Weight;
+    fn force_vested_transfer(s: u32) -> Weight;
+    fn merge_schedules(s: u32) -> Weight;
+}
+
+/// Weights for xpallet_assets_vesting using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn vest(s: u32) -> Weight {
+        (34_512_000 as Weight)
+            .saturating_add((228_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn vest_other(s: u32) -> Weight {
+        (35_108_000 as Weight)
+            .saturating_add((231_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn vested_transfer(s: u32) -> Weight {
+        (58_730_000 as Weight)
+            .saturating_add((246_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn force_vested_transfer(s: u32) -> Weight {
+        (58_015_000 as Weight)
+            .saturating_add((249_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn merge_schedules(s: u32) -> Weight {
+        (37_904_000 as Weight)
+            .saturating_add((265_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn vest(s: u32) -> Weight {
+        (34_512_000 as Weight)
+            .saturating_add((228_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn vest_other(s: u32) -> Weight {
+        (35_108_000 as Weight)
+            .saturating_add((231_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn vested_transfer(s: u32) -> Weight {
+        (58_730_000 as Weight)
+            .saturating_add((246_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn force_vested_transfer(s: u32) -> Weight {
+        (58_015_000 as Weight)
+            .saturating_add((249_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn merge_schedules(s: u32) -> Weight {
+        (37_904_000 as Weight)
+            .saturating_add((265_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+}
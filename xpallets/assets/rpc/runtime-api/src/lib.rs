@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+use codec::Codec;
+
+pub use pallet_assets::{AssetLock, LockReason};
+pub use sherpax_primitives::AssetId;
+
+sp_api::decl_runtime_apis! {
+    pub trait AssetsApi<AccountId, Balance>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        fn locks(who: AccountId) -> BTreeMap<AssetId, Vec<AssetLock<Balance>>>;
+    }
+}
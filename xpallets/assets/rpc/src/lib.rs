@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::{runtime_error_into_rpc_err, Result};
+
+use pallet_assets_rpc_runtime_api::{
+    AssetId, AssetLock, AssetsApi as AssetsRuntimeApi, LockReason,
+};
+
+pub struct Assets<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Assets<C, B> {
+    /// Create new `Assets` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[rpc]
+pub trait AssetsApi<BlockHash, AccountId, Balance>
+where
+    Balance: Display + FromStr,
+{
+    /// Return all the named locks of the account, grouped by asset
+    #[rpc(name = "assets_locks")]
+    fn locks(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, Vec<RpcAssetLock<Balance>>>>;
+}
+
+impl<C, Block, AccountId, Balance> AssetsApi<<Block as BlockT>::Hash, AccountId, Balance>
+    for Assets<C, Block>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: AssetsRuntimeApi<Block, AccountId, Balance>,
+    Block: BlockT,
+    AccountId: Clone + Display + FromStr + Codec,
+    Balance: Clone + Display + FromStr + Codec,
+{
+    fn locks(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, Vec<RpcAssetLock<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.locks(&at, who)
+            .map(|locks| {
+                locks
+                    .into_iter()
+                    .map(|(id, locks)| (id, locks.into_iter().map(Into::into).collect()))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcLockReason {
+    /// Holds of the same account add up
+    Hold,
+    /// Freezes of the same account overlap
+    Freeze,
+}
+
+impl From<LockReason> for RpcLockReason {
+    fn from(reason: LockReason) -> Self {
+        match reason {
+            LockReason::Hold => Self::Hold,
+            LockReason::Freeze => Self::Freeze,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAssetLock<Balance: Display + FromStr> {
+    /// The lock identifier, as a utf8 string if possible
+    pub id: String,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub amount: Balance,
+    pub reason: RpcLockReason,
+}
+
+impl<Balance: Display + FromStr> From<AssetLock<Balance>> for RpcAssetLock<Balance> {
+    fn from(lock: AssetLock<Balance>) -> Self {
+        Self {
+            id: String::from_utf8_lossy(&lock.id).into_owned(),
+            amount: lock.amount,
+            reason: lock.reason.into(),
+        }
+    }
+}
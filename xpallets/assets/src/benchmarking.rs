@@ -438,5 +438,46 @@ benchmarks_instance_pallet! {
         assert_last_event::<T, I>(Event::ApprovalCancelled { asset_id: id, owner: caller, delegate }.into());
     }
 
+    transfer_with_memo {
+        let amount = T::Balance::from(100u32);
+        let (caller, caller_lookup) = create_default_minted_asset::<T, I>(true, amount);
+        let target: T::AccountId = account("target", 0, SEED);
+        let target_lookup = T::Lookup::unlookup(target.clone());
+        let memo: Memo = vec![b'm'; 128].into();
+    }: _(SystemOrigin::Signed(caller.clone()), Default::default(), target_lookup, amount, memo.clone())
+    verify {
+        assert_last_event::<T, I>(Event::TransferredWithMemo { asset_id: Default::default(), from: caller, to: target, amount, memo }.into());
+    }
+
+    transfer_keep_alive_with_memo {
+        let mint_amount = T::Balance::from(200u32);
+        let amount = T::Balance::from(100u32);
+        let (caller, caller_lookup) = create_default_minted_asset::<T, I>(true, mint_amount);
+        let target: T::AccountId = account("target", 0, SEED);
+        let target_lookup = T::Lookup::unlookup(target.clone());
+        let memo: Memo = vec![b'm'; 128].into();
+    }: _(SystemOrigin::Signed(caller.clone()), Default::default(), target_lookup, amount, memo.clone())
+    verify {
+        assert!(frame_system::Pallet::<T>::account_exists(&caller));
+        assert_last_event::<T, I>(Event::TransferredWithMemo { asset_id: Default::default(), from: caller, to: target, amount, memo }.into());
+    }
+
+    batch_transfer_with_memo {
+        let t in 1 .. T::MaxBatchTransfers::get();
+        let amount = T::Balance::from(100u32);
+        let (caller, caller_lookup) = create_default_minted_asset::<T, I>(true, T::Balance::from(100_000u32));
+        let memo: Memo = vec![b'm'; 128].into();
+        let transfers = (0..t)
+            .map(|i| {
+                let target: T::AccountId = account("target", i, SEED);
+                (T::Lookup::unlookup(target), amount, memo.clone())
+            })
+            .collect::<Vec<_>>();
+        let last: T::AccountId = account("target", t - 1, SEED);
+    }: _(SystemOrigin::Signed(caller.clone()), Default::default(), transfers)
+    verify {
+        assert_last_event::<T, I>(Event::TransferredWithMemo { asset_id: Default::default(), from: caller, to: last, amount, memo }.into());
+    }
+
     impl_benchmark_test_suite!(Assets, crate::mock::new_test_ext(), crate::mock::Test)
 }
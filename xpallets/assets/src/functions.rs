@@ -63,6 +63,28 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         Asset::<T, I>::get(id).map(|x| x.supply)
     }
 
+    /// Get the asset `id` balance of `who` which is frozen by the named locks and the
+    /// `T::Freezer`, or `None` if nothing is frozen.
+    pub fn frozen_balance(id: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+        let locks = Locks::<T, I>::get(who, id);
+        let locked = if locks.is_empty() {
+            None
+        } else {
+            Some(total_locked(&locks))
+        };
+        match (locked, T::Freezer::frozen_balance(id, who)) {
+            (Some(a), Some(b)) => Some(a.saturating_add(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Remove the named locks of the dead account `who` and execute the `FrozenBalance::died`
+    /// hook.
+    pub(super) fn died(id: T::AssetId, who: &T::AccountId) {
+        Locks::<T, I>::remove(who, id);
+        T::Freezer::died(id, who);
+    }
+
     pub(super) fn new_account(
         who: &T::AccountId,
         d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
@@ -163,7 +185,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             return Frozen;
         }
         if let Some(rest) = account.balance.checked_sub(&amount) {
-            if let Some(frozen) = T::Freezer::frozen_balance(id, who) {
+            if let Some(frozen) = Self::frozen_balance(id, who) {
                 match frozen.checked_add(&details.min_balance) {
                     Some(required) if rest < required => return Frozen,
                     None => return Overflow,
@@ -202,7 +224,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         let account = Account::<T, I>::get(id, who).ok_or(Error::<T, I>::NoAccount)?;
         ensure!(!account.is_frozen, Error::<T, I>::Frozen);
 
-        let amount = if let Some(frozen) = T::Freezer::frozen_balance(id, who) {
+        let amount = if let Some(frozen) = Self::frozen_balance(id, who) {
             // Frozen balance: account CANNOT be deleted
             let required = frozen
                 .checked_add(&details.min_balance)
@@ -337,7 +359,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         }
         Asset::<T, I>::insert(&id, details);
         // Executing a hook here is safe, since it is not in a `mutate`.
-        T::Freezer::died(id, &who);
+        Self::died(id, &who);
         Ok(())
     }
 
@@ -511,7 +533,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
         // Execute hook outside of `mutate`.
         if let Some(Remove) = target_died {
-            T::Freezer::died(id, target);
+            Self::died(id, target);
         }
         Ok(actual)
     }
@@ -535,11 +557,39 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         let (balance, died) =
             Self::transfer_and_die(id, source, dest, amount, maybe_need_admin, f)?;
         if let Some(Remove) = died {
-            T::Freezer::died(id, source);
+            Self::died(id, source);
         }
         Ok(balance)
     }
 
+    /// Same as `do_transfer` from a signed `source`, but checks the `memo` first and
+    /// emits the `TransferredWithMemo` event after the transfer.
+    pub(super) fn do_transfer_with_memo(
+        id: T::AssetId,
+        source: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Balance,
+        memo: Memo,
+        keep_alive: bool,
+    ) -> DispatchResult {
+        memo.check_validity()?;
+
+        let f = TransferFlags {
+            keep_alive,
+            best_effort: false,
+            burn_dust: false,
+        };
+        let amount = Self::do_transfer(id, source, dest, amount, None, f)?;
+        Self::deposit_event(Event::TransferredWithMemo {
+            asset_id: id,
+            from: source.clone(),
+            to: dest.clone(),
+            amount,
+            memo,
+        });
+        Ok(())
+    }
+
     /// Same as `do_transfer` but it does not execute the `FrozenBalance::died` hook and
     /// instead returns whether and how the `source` account died in this operation.
     fn transfer_and_die(
@@ -730,6 +780,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                 debug_assert_eq!(details.sufficients, 0);
 
                 let metadata = Metadata::<T, I>::take(&id);
+                FeeRates::<T, I>::remove(&id);
                 T::Currency::unreserve(
                     &details.owner,
                     details.deposit.saturating_add(metadata.deposit),
@@ -750,7 +801,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
         // Execute hooks outside of `mutate`.
         for who in dead_accounts {
-            T::Freezer::died(id, &who);
+            Self::died(id, &who);
         }
         Ok(result_witness)
     }
@@ -849,11 +900,51 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
         // Execute hook outside of `mutate`.
         if let Some(Remove) = owner_died {
-            T::Freezer::died(id, owner);
+            Self::died(id, owner);
         }
         Ok(())
     }
 
+    /// Set the metadata of an asset without taking any deposit, the existing deposit is kept.
+    pub fn do_force_set_metadata(
+        id: T::AssetId,
+        name: Vec<u8>,
+        symbol: Vec<u8>,
+        decimals: u8,
+        is_frozen: bool,
+    ) -> DispatchResult {
+        let bounded_name: BoundedVec<u8, T::StringLimit> = name
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T, I>::BadMetadata)?;
+
+        let bounded_symbol: BoundedVec<u8, T::StringLimit> = symbol
+            .clone()
+            .try_into()
+            .map_err(|_| Error::<T, I>::BadMetadata)?;
+
+        ensure!(Asset::<T, I>::contains_key(id), Error::<T, I>::Unknown);
+        Metadata::<T, I>::try_mutate_exists(id, |metadata| {
+            let deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+            *metadata = Some(AssetMetadata {
+                deposit,
+                name: bounded_name,
+                symbol: bounded_symbol,
+                decimals,
+                is_frozen,
+            });
+
+            Self::deposit_event(Event::MetadataSet {
+                asset_id: id,
+                name,
+                symbol,
+                decimals,
+                is_frozen,
+            });
+            Ok(())
+        })
+    }
+
     /// Do set metadata
     pub(super) fn do_set_metadata(
         id: T::AssetId,
@@ -0,0 +1,45 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Assets pallet's `LockableAssets` implementation.
+
+use super::*;
+
+impl<T: Config<I>, I: 'static> LockableAssets<T::AccountId> for Pallet<T, I> {
+    fn set_lock(
+        id: LockIdentifier,
+        asset: T::AssetId,
+        who: &T::AccountId,
+        amount: T::Balance,
+        reason: LockReason,
+    ) -> DispatchResult {
+        if amount.is_zero() {
+            Self::remove_lock(id, asset, who);
+            return Ok(());
+        }
+        Locks::<T, I>::try_mutate(who, asset, |locks| -> DispatchResult {
+            locks.retain(|lock| lock.id != id);
+            locks
+                .try_push(AssetLock { id, amount, reason })
+                .map_err(|_| Error::<T, I>::TooManyLocks)?;
+            Ok(())
+        })
+    }
+
+    fn remove_lock(id: LockIdentifier, asset: T::AssetId, who: &T::AccountId) {
+        Locks::<T, I>::mutate_exists(who, asset, |maybe_locks| {
+            if let Some(locks) = maybe_locks {
+                locks.retain(|lock| lock.id != id);
+                if locks.is_empty() {
+                    *maybe_locks = None;
+                }
+            }
+        });
+    }
+
+    fn lock_amount(id: LockIdentifier, asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+        Locks::<T, I>::get(who, asset)
+            .iter()
+            .find(|lock| lock.id == id)
+            .map_or_else(Zero::zero, |lock| lock.amount)
+    }
+}
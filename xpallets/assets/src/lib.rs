@@ -79,6 +79,10 @@
 //! * `create`: Creates a new asset class, taking the required deposit.
 //! * `transfer`: Transfer sender's assets to another account.
 //! * `transfer_keep_alive`: Transfer sender's assets to another account, keeping the sender alive.
+//! * `transfer_with_memo`: Transfer sender's assets to another account, leaving a memo.
+//! * `transfer_keep_alive_with_memo`: Transfer sender's assets to another account with a memo,
+//!   keeping the sender alive.
+//! * `batch_transfer_with_memo`: Pay out sender's assets to several accounts, each with a memo.
 //! * `set_metadata`: Set the metadata of an asset class.
 //! * `clear_metadata`: Remove the metadata of an asset class.
 //! * `approve_transfer`: Create or increase an delegated transfer.
@@ -92,6 +96,7 @@
 //! * `force_clear_metadata`: Remove the metadata of an asset class.
 //! * `force_asset_status`: Alter an asset class's attributes.
 //! * `force_cancel_approval`: Rescind a previous approval.
+//! * `force_set_fee_rate`: Whitelist an asset to pay the transaction fees with a conversion rate.
 //!
 //! ### Privileged Functions
 //! * `destroy`: Destroys an entire asset class; called by the asset class's Owner.
@@ -136,6 +141,7 @@ mod extra_mutator;
 pub use extra_mutator::*;
 mod functions;
 mod impl_fungibles;
+mod impl_locks;
 mod impl_stored_map;
 mod types;
 pub use types::*;
@@ -148,7 +154,7 @@ use sp_runtime::{
     },
     ArithmeticError, TokenError,
 };
-use sp_std::{borrow::Borrow, convert::TryInto, prelude::*};
+use sp_std::{borrow::Borrow, collections::btree_map::BTreeMap, convert::TryInto, prelude::*};
 
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
@@ -162,13 +168,19 @@ use frame_support::{
 };
 use frame_system::Config as SystemConfig;
 
+use xp_runtime::Memo;
+
+pub use frame_support::traits::LockIdentifier;
+
+/// The identifier of the named lock set by `force_set_lock`.
+pub const GOVERNANCE_LOCK_ID: LockIdentifier = *b"assetgov";
 pub use pallet::*;
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{pallet_prelude::*, transactional};
     use frame_system::pallet_prelude::*;
 
     #[pallet::pallet]
@@ -209,6 +221,10 @@ pub mod pallet {
         /// attributes.
         type ForceOrigin: EnsureOrigin<Self::Origin>;
 
+        /// The origin which may whitelist an asset to pay the transaction fees and maintain its
+        /// conversion rate.
+        type FeeRateOrigin: EnsureOrigin<Self::Origin>;
+
         /// The basic amount of funds that must be reserved for an asset.
         #[pallet::constant]
         type AssetDeposit: Get<DepositBalanceOf<Self, I>>;
@@ -242,6 +258,14 @@ pub mod pallet {
         /// Additional data to be stored with an account's asset balance.
         type Extra: Member + Parameter + Default + MaxEncodedLen;
 
+        /// The maximum number of named locks that should exist on an asset account.
+        #[pallet::constant]
+        type MaxLocks: Get<u32>;
+
+        /// The maximum number of payouts in a `batch_transfer_with_memo`.
+        #[pallet::constant]
+        type MaxBatchTransfers: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -295,6 +319,25 @@ pub mod pallet {
         GetDefault,
     >;
 
+    #[pallet::storage]
+    /// The assets whitelisted to pay the transaction fees, with the conversion rate.
+    pub(super) type FeeRates<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AssetId, FeeRate>;
+
+    #[pallet::storage]
+    /// The named locks of the asset balance of an account, see `LockableAssets`.
+    ///
+    /// Indexed by the account first, so that the locks of an account can be iterated.
+    pub(super) type Locks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AssetId,
+        BoundedVec<AssetLock<T::Balance>, T::MaxLocks>,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         /// Genesis assets: id, owner, is_sufficient, min_balance
@@ -400,6 +443,14 @@ pub mod pallet {
             to: T::AccountId,
             amount: T::Balance,
         },
+        /// Some assets were transferred with a memo, following the `Transferred` event.
+        TransferredWithMemo {
+            asset_id: T::AssetId,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: T::Balance,
+            memo: Memo,
+        },
         /// Some assets were destroyed.
         Burned {
             asset_id: T::AssetId,
@@ -473,6 +524,19 @@ pub mod pallet {
         },
         /// An asset has had its attributes changed by the `Force` origin.
         AssetStatusChanged { asset_id: T::AssetId },
+        /// The fee rate of an asset has been changed.
+        FeeRateChanged {
+            asset_id: T::AssetId,
+            rate: Option<FeeRate>,
+        },
+        /// The governance lock of an account has been set by the `Force` origin, a zero
+        /// `amount` means the lock is removed.
+        GovernanceLockSet {
+            asset_id: T::AssetId,
+            who: T::AccountId,
+            amount: T::Balance,
+            reason: LockReason,
+        },
     }
 
     #[pallet::error]
@@ -509,6 +573,12 @@ pub mod pallet {
         NoDeposit,
         /// The operation would result in funds being burned.
         WouldBurn,
+        /// Invalid fee rate given.
+        BadFeeRate,
+        /// The account already has `MaxLocks` named locks of the asset.
+        TooManyLocks,
+        /// There are more than `MaxBatchTransfers` payouts in a batch.
+        TooManyTransfers,
     }
 
     #[pallet::call]
@@ -776,6 +846,93 @@ pub mod pallet {
             Self::do_transfer(id, &source, &dest, amount, None, f).map(|_| ())
         }
 
+        /// Same as [`transfer`], but leaves a `memo` for the `target`, which is usually a
+        /// payment reference used to credit the deposit to a sub-account of the `target`.
+        ///
+        /// Origin must be Signed.
+        ///
+        /// - `memo`: At most 128 bytes, `<` and `>` are not allowed.
+        ///
+        /// Emits `Transferred` and then `TransferredWithMemo` with the actual amount
+        /// transferred.
+        ///
+        /// Weight: `O(1)`
+        ///
+        /// [`transfer`]: Call::transfer
+        #[pallet::weight(T::WeightInfo::transfer_with_memo())]
+        pub fn transfer_with_memo(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] amount: T::Balance,
+            memo: Memo,
+        ) -> DispatchResult {
+            let source = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(target)?;
+
+            Self::do_transfer_with_memo(id, &source, &dest, amount, memo, false)
+        }
+
+        /// Same as [`transfer_keep_alive`], but leaves a `memo` for the `target`.
+        ///
+        /// Origin must be Signed.
+        ///
+        /// - `memo`: At most 128 bytes, `<` and `>` are not allowed.
+        ///
+        /// Emits `Transferred` and then `TransferredWithMemo` with the actual amount
+        /// transferred.
+        ///
+        /// Weight: `O(1)`
+        ///
+        /// [`transfer_keep_alive`]: Call::transfer_keep_alive
+        #[pallet::weight(T::WeightInfo::transfer_keep_alive_with_memo())]
+        pub fn transfer_keep_alive_with_memo(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] amount: T::Balance,
+            memo: Memo,
+        ) -> DispatchResult {
+            let source = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(target)?;
+
+            Self::do_transfer_with_memo(id, &source, &dest, amount, memo, true)
+        }
+
+        /// Pay out the asset `id` to several accounts at once, each with its own memo,
+        /// keeping the sender account alive.
+        ///
+        /// Origin must be Signed.
+        ///
+        /// - `id`: The identifier of the asset to be paid out.
+        /// - `transfers`: The `(target, amount, memo)` of each payout, at most
+        /// `MaxBatchTransfers`.
+        ///
+        /// Either all the payouts succeed, or none of them is applied.
+        ///
+        /// Emits `Transferred` and then `TransferredWithMemo` for each payout.
+        ///
+        /// Weight: `O(T)` where `T` is the number of `transfers`.
+        #[pallet::weight(T::WeightInfo::batch_transfer_with_memo(transfers.len() as u32))]
+        #[transactional]
+        pub fn batch_transfer_with_memo(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            transfers: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance, Memo)>,
+        ) -> DispatchResult {
+            let source = ensure_signed(origin)?;
+            ensure!(
+                transfers.len() <= T::MaxBatchTransfers::get() as usize,
+                Error::<T, I>::TooManyTransfers
+            );
+
+            for (target, amount, memo) in transfers {
+                let dest = T::Lookup::lookup(target)?;
+                Self::do_transfer_with_memo(id, &source, &dest, amount, memo, true)?;
+            }
+            Ok(())
+        }
+
         /// Move some assets from one account to another.
         ///
         /// Origin must be Signed and the sender should be the Admin of the asset `id`.
@@ -1104,36 +1261,7 @@ pub mod pallet {
         ) -> DispatchResult {
             T::ForceOrigin::ensure_origin(origin)?;
 
-            let bounded_name: BoundedVec<u8, T::StringLimit> = name
-                .clone()
-                .try_into()
-                .map_err(|_| Error::<T, I>::BadMetadata)?;
-
-            let bounded_symbol: BoundedVec<u8, T::StringLimit> = symbol
-                .clone()
-                .try_into()
-                .map_err(|_| Error::<T, I>::BadMetadata)?;
-
-            ensure!(Asset::<T, I>::contains_key(id), Error::<T, I>::Unknown);
-            Metadata::<T, I>::try_mutate_exists(id, |metadata| {
-                let deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
-                *metadata = Some(AssetMetadata {
-                    deposit,
-                    name: bounded_name,
-                    symbol: bounded_symbol,
-                    decimals,
-                    is_frozen,
-                });
-
-                Self::deposit_event(Event::MetadataSet {
-                    asset_id: id,
-                    name,
-                    symbol,
-                    decimals,
-                    is_frozen,
-                });
-                Ok(())
-            })
+            Self::do_force_set_metadata(id, name, symbol, decimals, is_frozen)
         }
 
         /// Clear the metadata for an asset.
@@ -1392,5 +1520,97 @@ pub mod pallet {
         ) -> DispatchResult {
             Self::do_refund(id, ensure_signed(origin)?, allow_burn)
         }
+
+        /// Whitelist an asset to pay the transaction fees, or remove it from the whitelist.
+        ///
+        /// Origin must be FeeRateOrigin.
+        ///
+        /// - `id`: The identifier of the asset.
+        /// - `rate`: The conversion rate from the native balance into the asset balance, `None`
+        /// removes the asset from the whitelist.
+        ///
+        /// Emits `FeeRateChanged` with the identity of the asset.
+        ///
+        /// Weight: `O(1)`
+        #[pallet::weight(T::WeightInfo::force_asset_status())]
+        pub fn force_set_fee_rate(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            rate: Option<FeeRate>,
+        ) -> DispatchResult {
+            T::FeeRateOrigin::ensure_origin(origin)?;
+
+            if let Some(rate) = rate {
+                let asset = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
+                match rate {
+                    FeeRate::Fixed(rate) => ensure!(!rate.is_zero(), Error::<T, I>::BadFeeRate),
+                    // only sufficient assets have a min balance with reliable value
+                    FeeRate::MinBalance => {
+                        ensure!(asset.is_sufficient, Error::<T, I>::BadFeeRate)
+                    }
+                }
+                FeeRates::<T, I>::insert(id, rate);
+            } else {
+                FeeRates::<T, I>::remove(id);
+            }
+
+            Self::deposit_event(Event::FeeRateChanged { asset_id: id, rate });
+            Ok(())
+        }
+
+        /// Set the governance lock on the asset balance of an account, e.g. to freeze the
+        /// funds under investigation, without clobbering the locks of the other pallets.
+        ///
+        /// Origin must be ForceOrigin.
+        ///
+        /// - `id`: The identifier of the asset.
+        /// - `who`: The account to be locked.
+        /// - `amount`: The amount to lock, a zero amount removes the lock.
+        /// - `reason`: How the lock aggregates with the other locks of the account.
+        ///
+        /// Emits `GovernanceLockSet`.
+        ///
+        /// Weight: `O(1)`
+        #[pallet::weight(T::WeightInfo::force_asset_status())]
+        pub fn force_set_lock(
+            origin: OriginFor<T>,
+            #[pallet::compact] id: T::AssetId,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[pallet::compact] amount: T::Balance,
+            reason: LockReason,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            ensure!(Asset::<T, I>::contains_key(id), Error::<T, I>::Unknown);
+
+            Self::set_lock(GOVERNANCE_LOCK_ID, id, &who, amount, reason)?;
+
+            Self::deposit_event(Event::GovernanceLockSet {
+                asset_id: id,
+                who,
+                amount,
+                reason,
+            });
+            Ok(())
+        }
+    }
+
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// The fee rate of the asset, `None` if the asset can't pay the transaction fees.
+        pub fn fee_rate(id: T::AssetId) -> Option<FeeRate> {
+            FeeRates::<T, I>::get(id)
+        }
+
+        /// The named locks of the asset `id` on the account `who`.
+        pub fn locks(id: T::AssetId, who: &T::AccountId) -> Vec<AssetLock<T::Balance>> {
+            Locks::<T, I>::get(who, id).into_inner()
+        }
+
+        /// The named locks of all the assets on the account `who`.
+        pub fn locks_of(who: &T::AccountId) -> BTreeMap<T::AssetId, Vec<AssetLock<T::Balance>>> {
+            Locks::<T, I>::iter_prefix(who)
+                .map(|(id, locks)| (id, locks.into_inner()))
+                .collect()
+        }
     }
 }
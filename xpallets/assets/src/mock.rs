@@ -90,6 +90,7 @@ impl Config for Test {
     type AssetId = u32;
     type Currency = Balances;
     type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type FeeRateOrigin = frame_system::EnsureRoot<u64>;
     type AssetDeposit = ConstU64<1>;
     type AssetAccountDeposit = ConstU64<10>;
     type MetadataDepositBase = ConstU64<1>;
@@ -99,6 +100,8 @@ impl Config for Test {
     type Freezer = TestFreezer;
     type WeightInfo = ();
     type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
 }
 
 use std::{cell::RefCell, collections::HashMap};
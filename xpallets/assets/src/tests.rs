@@ -21,7 +21,10 @@ use super::*;
 use crate::{mock::*, Error};
 use frame_support::{assert_noop, assert_ok, traits::Currency};
 use pallet_balances::Error as BalancesError;
-use sp_runtime::{traits::ConvertInto, TokenError};
+use sp_runtime::{
+    traits::{BadOrigin, ConvertInto},
+    TokenError,
+};
 
 #[test]
 fn basic_minting_should_work() {
@@ -731,6 +734,143 @@ fn transferring_less_than_one_unit_is_fine() {
     });
 }
 
+#[test]
+fn transfer_with_memo_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+        let memo: Memo = b"sub-account 42".to_vec().into();
+        assert_ok!(Assets::transfer_with_memo(
+            Origin::signed(1),
+            0,
+            2,
+            50,
+            memo.clone()
+        ));
+        assert_eq!(Assets::balance(0, 1), 50);
+        assert_eq!(Assets::balance(0, 2), 50);
+        System::assert_has_event(mock::Event::Assets(crate::Event::Transferred {
+            asset_id: 0,
+            from: 1,
+            to: 2,
+            amount: 50,
+        }));
+        System::assert_last_event(mock::Event::Assets(crate::Event::TransferredWithMemo {
+            asset_id: 0,
+            from: 1,
+            to: 2,
+            amount: 50,
+            memo: memo.clone(),
+        }));
+
+        assert_noop!(
+            Assets::transfer_keep_alive_with_memo(Origin::signed(1), 0, 2, 50, memo.clone()),
+            Error::<Test>::BalanceLow
+        );
+        assert_ok!(Assets::transfer_keep_alive_with_memo(
+            Origin::signed(1),
+            0,
+            2,
+            49,
+            memo
+        ));
+        assert_eq!(Assets::balance(0, 1), 1);
+        assert_eq!(Assets::balance(0, 2), 99);
+    });
+}
+
+#[test]
+fn transfer_with_invalid_memo_should_not_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+        assert_noop!(
+            Assets::transfer_with_memo(Origin::signed(1), 0, 2, 50, vec![b'm'; 129].into()),
+            DispatchError::Other("transaction memo too long, valid byte length range: [0, 128]")
+        );
+        assert_noop!(
+            Assets::transfer_keep_alive_with_memo(
+                Origin::signed(1),
+                0,
+                2,
+                50,
+                b"<script>".to_vec().into()
+            ),
+            DispatchError::Other("'<' and '>' are not allowed, which could be abused off-chain.")
+        );
+        assert_ok!(Assets::transfer_with_memo(
+            Origin::signed(1),
+            0,
+            2,
+            50,
+            vec![b'm'; 128].into()
+        ));
+    });
+}
+
+#[test]
+fn batch_transfer_with_memo_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+        let memo = |raw: &[u8]| -> Memo { raw.into() };
+        assert_ok!(Assets::batch_transfer_with_memo(
+            Origin::signed(1),
+            0,
+            vec![(2, 20, memo(b"payout 1")), (3, 30, memo(b"payout 2"))]
+        ));
+        assert_eq!(Assets::balance(0, 1), 50);
+        assert_eq!(Assets::balance(0, 2), 20);
+        assert_eq!(Assets::balance(0, 3), 30);
+        System::assert_has_event(mock::Event::Assets(crate::Event::TransferredWithMemo {
+            asset_id: 0,
+            from: 1,
+            to: 2,
+            amount: 20,
+            memo: memo(b"payout 1"),
+        }));
+        System::assert_last_event(mock::Event::Assets(crate::Event::TransferredWithMemo {
+            asset_id: 0,
+            from: 1,
+            to: 3,
+            amount: 30,
+            memo: memo(b"payout 2"),
+        }));
+
+        // all the payouts are reverted if any of them fails
+        assert_noop!(
+            Assets::batch_transfer_with_memo(
+                Origin::signed(1),
+                0,
+                vec![(2, 20, memo(b"payout 3")), (3, 30, memo(b"payout 4"))]
+            ),
+            Error::<Test>::BalanceLow
+        );
+        assert_noop!(
+            Assets::batch_transfer_with_memo(
+                Origin::signed(1),
+                0,
+                vec![(2, 20, memo(b"payout 3")), (3, 20, memo(b"<payout 4>"))]
+            ),
+            DispatchError::Other("'<' and '>' are not allowed, which could be abused off-chain.")
+        );
+        assert_eq!(Assets::balance(0, 1), 50);
+
+        // at most `MaxBatchTransfers` payouts
+        assert_noop!(
+            Assets::batch_transfer_with_memo(
+                Origin::signed(1),
+                0,
+                vec![(2, 0, memo(b"payout")); 101]
+            ),
+            Error::<Test>::TooManyTransfers
+        );
+    });
+}
+
 #[test]
 fn transferring_more_units_than_total_supply_should_not_work() {
     new_test_ext().execute_with(|| {
@@ -1095,6 +1235,77 @@ fn balance_conversion_should_work() {
     });
 }
 
+#[test]
+fn fee_rate_conversion_should_work() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::tokens::BalanceConversion;
+        use sp_runtime::{FixedPointNumber, FixedU128};
+
+        type Converter = FeeRateToAssetBalance<Balances, Test, ConvertInto>;
+
+        let id = 42;
+        assert_ok!(Assets::force_create(Origin::root(), id, 1, true, 10));
+        let not_sufficient = 23;
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            not_sufficient,
+            1,
+            false,
+            10
+        ));
+
+        assert_eq!(
+            Converter::to_asset_balance(100, 1234),
+            Err(ConversionError::AssetMissing)
+        );
+        assert_eq!(
+            Converter::to_asset_balance(100, id),
+            Err(ConversionError::AssetNotWhitelisted)
+        );
+
+        let rate = FeeRate::Fixed(FixedU128::saturating_from_rational(1, 2));
+        assert_noop!(
+            Assets::force_set_fee_rate(Origin::signed(1), id, Some(rate)),
+            BadOrigin
+        );
+        assert_noop!(
+            Assets::force_set_fee_rate(Origin::root(), 1234, Some(rate)),
+            Error::<Test>::Unknown
+        );
+        assert_noop!(
+            Assets::force_set_fee_rate(Origin::root(), id, Some(FeeRate::Fixed(FixedU128::zero()))),
+            Error::<Test>::BadFeeRate
+        );
+        assert_noop!(
+            Assets::force_set_fee_rate(Origin::root(), not_sufficient, Some(FeeRate::MinBalance)),
+            Error::<Test>::BadFeeRate
+        );
+
+        assert_ok!(Assets::force_set_fee_rate(Origin::root(), id, Some(rate)));
+        assert_eq!(Assets::fee_rate(id), Some(rate));
+        assert_eq!(Converter::to_asset_balance(100, id), Ok(50));
+
+        assert_ok!(Assets::force_set_fee_rate(
+            Origin::root(),
+            id,
+            Some(FeeRate::MinBalance)
+        ));
+        assert_eq!(Converter::to_asset_balance(100, id), Ok(100 * 10));
+
+        assert_ok!(Assets::force_set_fee_rate(Origin::root(), id, None));
+        assert_eq!(
+            Converter::to_asset_balance(100, id),
+            Err(ConversionError::AssetNotWhitelisted)
+        );
+
+        // destroying the asset removes it from the whitelist
+        assert_ok!(Assets::force_set_fee_rate(Origin::root(), id, Some(rate)));
+        let w = Asset::<Test>::get(id).unwrap().destroy_witness();
+        assert_ok!(Assets::destroy(Origin::root(), id, w));
+        assert_eq!(Assets::fee_rate(id), None);
+    });
+}
+
 #[test]
 fn assets_from_genesis_should_exist() {
     new_test_ext().execute_with(|| {
@@ -1138,3 +1349,158 @@ fn querying_allowance_should_work() {
         assert_eq!(Assets::allowance(0, &1, &2), 0);
     });
 }
+
+#[test]
+fn named_locks_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+        assert_eq!(Assets::frozen_balance(0, &1), None);
+
+        // the freezes overlap
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"freeze_a",
+            0,
+            &1,
+            30,
+            LockReason::Freeze
+        ));
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"freeze_b",
+            0,
+            &1,
+            50,
+            LockReason::Freeze
+        ));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(50));
+        // the holds add up on top of the freezes
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"hold_a__",
+            0,
+            &1,
+            20,
+            LockReason::Hold
+        ));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(70));
+        assert_eq!(
+            <Assets as LockableAssets<u64>>::lock_amount(*b"hold_a__", 0, &1),
+            20
+        );
+        assert_eq!(Assets::locks(0, &1).len(), 3);
+        assert_eq!(Assets::locks_of(&1).len(), 1);
+        assert!(Assets::locks_of(&2).is_empty());
+
+        assert_noop!(
+            Assets::transfer(Origin::signed(1), 0, 2, 30),
+            Error::<Test>::BalanceLow
+        );
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 29));
+
+        // replace and remove the locks
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"freeze_b",
+            0,
+            &1,
+            10,
+            LockReason::Freeze
+        ));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(50));
+        <Assets as LockableAssets<u64>>::remove_lock(*b"hold_a__", 0, &1);
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"freeze_a",
+            0,
+            &1,
+            0,
+            LockReason::Freeze
+        ));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(10));
+
+        for i in 0..9u8 {
+            assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+                [i; 8],
+                0,
+                &1,
+                1,
+                LockReason::Hold
+            ));
+        }
+        assert_noop!(
+            <Assets as LockableAssets<u64>>::set_lock(*b"too_many", 0, &1, 1, LockReason::Hold),
+            Error::<Test>::TooManyLocks
+        );
+
+        // the locks are removed with the account
+        let w = Asset::<Test>::get(0).unwrap().destroy_witness();
+        assert_ok!(Assets::destroy(Origin::signed(1), 0, w));
+        assert_eq!(Assets::locks(0, &1), vec![]);
+    });
+}
+
+#[test]
+fn force_set_lock_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Assets::force_set_lock(Origin::root(), 0, 1, 10, LockReason::Freeze),
+            Error::<Test>::Unknown
+        );
+        assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+        assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+        assert_noop!(
+            Assets::force_set_lock(Origin::signed(1), 0, 1, 10, LockReason::Freeze),
+            BadOrigin
+        );
+
+        // the governance lock works along with the locks of the other pallets
+        assert_ok!(<Assets as LockableAssets<u64>>::set_lock(
+            *b"hold_a__",
+            0,
+            &1,
+            20,
+            LockReason::Hold
+        ));
+        assert_ok!(Assets::force_set_lock(
+            Origin::root(),
+            0,
+            1,
+            30,
+            LockReason::Freeze
+        ));
+        System::assert_last_event(mock::Event::Assets(crate::Event::GovernanceLockSet {
+            asset_id: 0,
+            who: 1,
+            amount: 30,
+            reason: LockReason::Freeze,
+        }));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(50));
+        assert_eq!(
+            <Assets as LockableAssets<u64>>::lock_amount(GOVERNANCE_LOCK_ID, 0, &1),
+            30
+        );
+        assert_noop!(
+            Assets::transfer(Origin::signed(1), 0, 2, 51),
+            Error::<Test>::BalanceLow
+        );
+
+        assert_ok!(Assets::force_set_lock(
+            Origin::root(),
+            1,
+            1,
+            5,
+            LockReason::Freeze
+        ));
+        let locks = Assets::locks_of(&1);
+        assert_eq!(locks.keys().copied().collect::<Vec<_>>(), vec![0, 1]);
+
+        // a zero amount removes the lock
+        assert_ok!(Assets::force_set_lock(
+            Origin::root(),
+            0,
+            1,
+            0,
+            LockReason::Freeze
+        ));
+        assert_eq!(Assets::frozen_balance(0, &1), Some(20));
+        assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+    });
+}
@@ -185,6 +185,60 @@ impl<AssetId, AccountId, Balance> FrozenBalance<AssetId, AccountId, Balance> for
     fn died(_: AssetId, _: &AccountId) {}
 }
 
+/// The kind of a named lock on the asset balance of an account.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum LockReason {
+    /// The balance is set aside for a particular purpose and is going to be moved out,
+    /// e.g. a pending withdrawal. The holds of an account add up.
+    Hold,
+    /// The balance is only prevented from being moved out, e.g. a vesting schedule. The freezes
+    /// of an account overlap, only the largest one counts.
+    Freeze,
+}
+
+/// A named lock on the asset balance of an account.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AssetLock<Balance> {
+    /// An identifier for this lock. Only one lock may be in existence for each identifier.
+    pub id: LockIdentifier,
+    /// The amount which the locked balance may not drop below when this lock is in effect.
+    pub amount: Balance,
+    /// How the lock aggregates with the other locks of the account.
+    pub reason: LockReason,
+}
+
+/// The balance locked by `locks`, the holds are added up on top of the largest freeze.
+pub fn total_locked<Balance: AtLeast32BitUnsigned + Copy>(locks: &[AssetLock<Balance>]) -> Balance {
+    let (held, frozen) = locks.iter().fold(
+        (Balance::zero(), Balance::zero()),
+        |(held, frozen), lock| match lock.reason {
+            LockReason::Hold => (held.saturating_add(lock.amount), frozen),
+            LockReason::Freeze => (held, frozen.max(lock.amount)),
+        },
+    );
+    held.saturating_add(frozen)
+}
+
+/// An asset which can place the named locks on the balances of the accounts, so that the
+/// pallets can lock the balances without clobbering each other.
+pub trait LockableAssets<AccountId>: fungibles::Inspect<AccountId> {
+    /// Create a new balance lock of the asset on account `who`, or replace the existing one
+    /// of the same `id`. A zero `amount` removes the lock.
+    fn set_lock(
+        id: LockIdentifier,
+        asset: Self::AssetId,
+        who: &AccountId,
+        amount: Self::Balance,
+        reason: LockReason,
+    ) -> DispatchResult;
+
+    /// Remove an existing lock of the asset on account `who`.
+    fn remove_lock(id: LockIdentifier, asset: Self::AssetId, who: &AccountId);
+
+    /// The amount of the lock of `id`, zero if there is no such lock.
+    fn lock_amount(id: LockIdentifier, asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub(super) struct TransferFlags {
     /// The debited account must stay alive at the end of the operation; an error is returned if
@@ -229,6 +283,18 @@ pub enum ConversionError {
     /// The asset is not sufficient and thus does not have a reliable `min_balance` so it cannot be
     /// converted.
     AssetNotSufficient,
+    /// The asset is not whitelisted to pay the transaction fees.
+    AssetNotWhitelisted,
+}
+
+/// The conversion rate from the native balance into an asset balance, used for
+/// paying the transaction fees in assets.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum FeeRate {
+    /// The asset balance of one native balance, maintained by the `Force` origin.
+    Fixed(FixedU128),
+    /// Derived from the ratio between the minimum balances, see [`BalanceToAssetBalance`].
+    MinBalance,
 }
 
 // Type alias for `frame_system`'s account id.
@@ -277,3 +343,39 @@ where
         )
     }
 }
+
+/// Converts a balance value into an asset balance based on the [`FeeRate`] of the asset.
+///
+/// Only the assets whitelisted by `force_set_fee_rate` can be converted.
+pub struct FeeRateToAssetBalance<F, T, CON, I = ()>(PhantomData<(F, T, CON, I)>);
+impl<F, T, CON, I> BalanceConversion<BalanceOf<F, T>, AssetIdOf<T, I>, AssetBalanceOf<T, I>>
+    for FeeRateToAssetBalance<F, T, CON, I>
+where
+    F: fungible::Inspect<AccountIdOf<T>>,
+    T: Config<I>,
+    I: 'static,
+    CON: Convert<BalanceOf<F, T>, AssetBalanceOf<T, I>>,
+    BalanceOf<F, T>: FixedPointOperand + Zero,
+    AssetBalanceOf<T, I>: FixedPointOperand + Zero,
+{
+    type Error = ConversionError;
+
+    /// Convert the given balance value into an asset balance based on the fee rate of the asset.
+    ///
+    /// Will return `Err` if the asset is not found or not whitelisted.
+    fn to_asset_balance(
+        balance: BalanceOf<F, T>,
+        asset_id: AssetIdOf<T, I>,
+    ) -> Result<AssetBalanceOf<T, I>, ConversionError> {
+        ensure!(
+            Asset::<T, I>::contains_key(asset_id),
+            ConversionError::AssetMissing
+        );
+        match FeeRates::<T, I>::get(asset_id).ok_or(ConversionError::AssetNotWhitelisted)? {
+            FeeRate::Fixed(rate) => Ok(rate.saturating_mul_int(CON::convert(balance))),
+            FeeRate::MinBalance => {
+                BalanceToAssetBalance::<F, T, CON, I>::to_asset_balance(balance, asset_id)
+            }
+        }
+    }
+}
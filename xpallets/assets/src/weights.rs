@@ -20,6 +20,10 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-01-30, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! The weights of the following calls are estimated by hand, to be regenerated by the executed
+//! command below: `transfer_with_memo`, `transfer_keep_alive_with_memo`,
+//! `batch_transfer_with_memo`.
 
 // Executed Command:
 // ./target/production/substrate
@@ -69,6 +73,9 @@ pub trait WeightInfo {
     fn transfer_approved() -> Weight;
     fn cancel_approval() -> Weight;
     fn force_cancel_approval() -> Weight;
+    fn transfer_with_memo() -> Weight;
+    fn transfer_keep_alive_with_memo() -> Weight;
+    fn batch_transfer_with_memo(t: u32, ) -> Weight;
 }
 
 /// Weights for pallet_assets using the Substrate node and recommended hardware.
@@ -257,6 +264,33 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn transfer_with_memo() -> Weight {
+        (46_215_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn transfer_keep_alive_with_memo() -> Weight {
+        (39_502_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:101 w:101)
+    // Storage: System Account (r:100 w:100)
+    fn batch_transfer_with_memo(t: u32, ) -> Weight {
+        (2_617_000 as Weight)
+            .saturating_add((38_940_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -444,4 +478,31 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn transfer_with_memo() -> Weight {
+        (46_215_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    // Storage: System Account (r:1 w:1)
+    fn transfer_keep_alive_with_memo() -> Weight {
+        (39_502_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Assets Asset (r:1 w:1)
+    // Storage: Assets Account (r:101 w:101)
+    // Storage: System Account (r:100 w:100)
+    fn batch_transfer_with_memo(t: u32, ) -> Weight {
+        (2_617_000 as Weight)
+            .saturating_add((38_940_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+    }
 }
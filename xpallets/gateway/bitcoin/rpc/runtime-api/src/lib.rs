@@ -6,9 +6,14 @@
 
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
-pub use xpallet_gateway_bitcoin::{types::BtcHeaderInfo, BtcHeader, BtcWithdrawalProposal, H256};
+pub use xpallet_gateway_bitcoin::{
+    types::{BtcHeaderIndex, BtcHeaderInfo, BtcTxState},
+    BtcAddress, BtcHeader, BtcWithdrawalProposal, H256,
+};
 
 sp_api::decl_runtime_apis! {
+    /// The version `2` adds the methods used by the built-in relayer and the gateway metrics.
+    #[api_version(2)]
     pub trait XGatewayBitcoinApi<AccountId>
         where AccountId: codec::Codec
     {
@@ -23,5 +28,13 @@ sp_api::decl_runtime_apis! {
         fn get_genesis_info() -> (BtcHeader, u32);
 
         fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo>;
+
+        fn get_header_indexes() -> (BtcHeaderIndex, Option<BtcHeaderIndex>);
+
+        fn get_pending_deposit_stats() -> (u32, u64);
+
+        fn get_tx_state(txid: H256) -> Option<BtcTxState>;
+
+        fn get_trustee_address_pairs() -> Vec<(BtcAddress, BtcAddress)>;
     }
 }
@@ -5,6 +5,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod header;
+pub mod migrations;
 pub mod trustee;
 mod tx;
 pub mod types;
@@ -25,18 +26,20 @@ pub use light_bitcoin::primitives::h256_rev;
 pub use light_bitcoin::{
     chain::{BlockHeader as BtcHeader, Transaction, TransactionOutputArray},
     keys::{Address, DisplayLayout, Network as BtcNetwork},
+    merkle::PartialMerkleTree,
     primitives::{hash_rev, Compact, H256, H264},
     serialization::{deserialize, Reader},
 };
 
 use sherpax_primitives::ReferralId;
 use xp_assets_registrar::Chain;
+use xp_gateway_bitcoin::AddressError;
 use xp_gateway_common::AccountExtractor;
 
 use xpallet_gateway_common::{
     traits::{
-        AddressBinding, ProposalProvider, ReferralBinding, TotalSupply, TrusteeInfoUpdate,
-        TrusteeSession,
+        AddressBinding, ProposalProvider, ReferralBinding, RelayerRegistry, TotalSupply,
+        TrusteeInfoUpdate, TrusteeSession,
     },
     trustees::bitcoin::BtcTrusteeAddrInfo,
 };
@@ -45,7 +48,7 @@ use xpallet_support::try_addr;
 
 use self::{
     trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
-    tx::remove_pending_deposit,
+    tx::{decrease_pending_deposit_total, remove_pending_deposit},
     types::{
         BtcDepositCache, BtcHeaderIndex, BtcRelayedTx, BtcRelayedTxInfo, BtcTxResult, BtcTxState,
     },
@@ -77,11 +80,23 @@ pub mod pallet {
     };
     use frame_system::pallet_prelude::*;
 
+    /// The storage version, `1` since the `PendingDepositTotal` is maintained.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::ensure_invariants()
+        }
+    }
+
     #[pallet::config]
     pub trait Config:
         frame_system::Config + pallet_assets::Config + xpallet_gateway_records::Config
@@ -114,6 +129,9 @@ pub mod pallet {
         /// Handle address binding about pending deposit.
         type AddressBinding: AddressBinding<Self::AccountId, BtcAddress>;
 
+        /// The bonded relayers who can push headers and transactions.
+        type RelayerRegistry: RelayerRegistry<Self::AccountId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -127,11 +145,18 @@ pub mod pallet {
             let header: BtcHeader =
                 deserialize(header.as_slice()).map_err(|_| Error::<T>::DeserializeErr)?;
             log!(debug, "[push_header] from:{:?}, header:{:?}", from, header);
+            T::RelayerRegistry::ensure_relayer(Chain::Bitcoin, &from)?;
 
+            let best_index = Self::best_index();
             Self::apply_push_header(header)?;
 
-            // Relayer does not pay a fee.
-            Ok(Pays::No.into())
+            // Relayer does not pay a fee only if the header advances the best index.
+            if Self::best_index() != best_index {
+                T::RelayerRegistry::note_submission(Chain::Bitcoin, &from);
+                Ok(Pays::No.into())
+            } else {
+                Ok(Pays::Yes.into())
+            }
         }
 
         /// if use `RelayTx` struct would export in metadata, cause complex in front-end
@@ -142,7 +167,8 @@ pub mod pallet {
             relayed_info: Vec<u8>,
             prev_tx: Option<Vec<u8>>,
         ) -> DispatchResultWithPostInfo {
-            let _from = ensure_signed(origin)?;
+            let from = ensure_signed(origin)?;
+            T::RelayerRegistry::ensure_relayer(Chain::Bitcoin, &from)?;
             let raw_tx = Self::deserialize_tx(raw_tx.as_slice())?;
             let relayed_info: BtcRelayedTxInfo =
                 Decode::decode(&mut &relayed_info[..]).map_err(|_| Error::<T>::DeserializeErr)?;
@@ -155,12 +181,14 @@ pub mod pallet {
             log!(
                 debug,
                 "[push_transaction] from:{:?}, relay_tx:{:?}, prev_tx:{:?}",
-                _from,
+                from,
                 relay_tx,
                 prev_tx
             );
 
+            // The tx is new and processed successfully, otherwise it's rejected.
             Self::apply_push_transaction(relay_tx, prev_tx)?;
+            T::RelayerRegistry::note_submission(Chain::Bitcoin, &from);
 
             Ok(Pays::No.into())
         }
@@ -212,7 +240,7 @@ pub mod pallet {
         }
 
         /// Dangerous! Be careful to set ConfirmedIndex
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_confirmed_number())]
         pub fn set_confirmed_number(origin: OriginFor<T>, number: u32) -> DispatchResult {
             ensure_root(origin)?;
             ConfirmationNumber::<T>::put(number);
@@ -236,7 +264,8 @@ pub mod pallet {
                 remove_pending_deposit::<T>(&addr, &w);
             } else {
                 log!(info, "[remove_pending] Release pending deposit directly, not deposit to someone, addr:{:?}", try_addr(&addr));
-                PendingDeposits::<T>::remove(&addr);
+                let records = PendingDeposits::<T>::take(&addr);
+                decrease_pending_deposit_total::<T>(&records);
             }
             Ok(())
         }
@@ -375,6 +404,10 @@ pub mod pallet {
         TxOutputNotColdAddr,
         /// The total amount of the trust must be transferred out in full
         TxNotFullAmount,
+        /// the address does not belong to the configured network
+        InvalidAddrNetwork,
+        /// the script type of the address is not supported
+        UnsupportedAddrType,
     }
 
     #[pallet::event]
@@ -441,6 +474,11 @@ pub mod pallet {
     pub(crate) type PendingDeposits<T: Config> =
         StorageMap<_, Blake2_128Concat, BtcAddress, Vec<BtcDepositCache>, ValueQuery>;
 
+    /// The total value of all the unclaimed deposits in `PendingDeposits`.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_deposit_total)]
+    pub(crate) type PendingDepositTotal<T: Config> = StorageValue<_, u64, ValueQuery>;
+
     /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_proposal)]
@@ -563,11 +601,11 @@ pub mod pallet {
         }
 
         fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
-            // this addr is base58 addr
-            let address = Self::verify_btc_address(addr).map_err(|err| {
+            // this addr is base58 or bech32/bech32m addr
+            let address = Self::verify_withdrawal_address(addr).map_err(|err| {
                 log!(
                     error,
-                    "[verify_btc_address] Verify failed, error:{:?}, source addr:{:?}",
+                    "[verify_withdrawal_address] Verify failed, error:{:?}, source addr:{:?}",
                     err,
                     xpallet_support::try_addr(addr)
                 );
@@ -577,7 +615,7 @@ pub mod pallet {
             match get_current_trustee_address_pair::<T>() {
                 Ok((hot_addr, cold_addr)) => {
                     // do not allow withdraw from trustee address
-                    if address == hot_addr || address == cold_addr {
+                    if xp_gateway_bitcoin::is_trustee_addr(address, (hot_addr, cold_addr)) {
                         return Err(Error::<T>::InvalidAddress.into());
                     }
                 }
@@ -606,15 +644,7 @@ pub mod pallet {
 
     impl<T: Config> TotalSupply<T::Balance> for Pallet<T> {
         fn total_supply() -> T::Balance {
-            let pending_deposits: T::Balance = PendingDeposits::<T>::iter_values()
-                .map(|deposits| {
-                    deposits
-                        .into_iter()
-                        .map(|deposit| deposit.balance)
-                        .sum::<u64>()
-                })
-                .sum::<u64>()
-                .saturated_into();
+            let pending_deposits: T::Balance = Self::pending_deposit_total().saturated_into();
 
             let asset_id = T::BtcAssetId::get();
             let asset_supply = pallet_assets::Pallet::<T>::total_supply(asset_id);
@@ -629,6 +659,143 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// Sum all the unclaimed deposits by iterating `PendingDeposits`,
+        /// returns the count of the addresses and the total value.
+        pub fn sum_pending_deposits() -> (u64, u64) {
+            PendingDeposits::<T>::iter_values().fold((0, 0), |(count, total), deposits| {
+                let value = deposits
+                    .into_iter()
+                    .map(|deposit| deposit.balance)
+                    .fold(0u64, |acc, balance| acc.saturating_add(balance));
+                (count + 1, total.saturating_add(value))
+            })
+        }
+
+        /// Ensure the maintained `PendingDepositTotal` matches the sum of `PendingDeposits`.
+        pub fn ensure_pending_deposit_total() -> Result<(), &'static str> {
+            let (_, total) = Self::sum_pending_deposits();
+            if total != Self::pending_deposit_total() {
+                log!(
+                    error,
+                    "[ensure_pending_deposit_total] PendingDepositTotal:{} != sum of PendingDeposits:{}",
+                    Self::pending_deposit_total(),
+                    total
+                );
+                return Err("PendingDepositTotal mismatches the sum of PendingDeposits");
+            }
+            Ok(())
+        }
+
+        /// Check the invariants of the header chain, returns all the violated ones, see
+        /// [`Self::check_invariants`].
+        pub fn invariant_violations() -> Vec<&'static str> {
+            Self::check_invariants(u32::MAX).unwrap_or_default()
+        }
+
+        /// Check the invariants of the header chain, returns all the violated ones:
+        ///
+        /// - `BestIndex` and `ConfirmedIndex` point to the headers of their heights.
+        /// - the confirmed header is in the main chain and not higher than the best one.
+        /// - every height of `BlockHashFor` has at most one main chain header, and all its
+        ///   headers are of that height.
+        /// - `PendingDepositTotal` matches the sum of `PendingDeposits`.
+        ///
+        /// Returns `None` without checking if `BlockHashFor` or `PendingDeposits` has more
+        /// than `max_items` entries.
+        pub fn check_invariants(max_items: u32) -> Option<Vec<&'static str>> {
+            let limit = (max_items as usize).saturating_add(1);
+            if BlockHashFor::<T>::iter_keys().take(limit).count() > max_items as usize
+                || PendingDeposits::<T>::iter_keys().take(limit).count() > max_items as usize
+            {
+                log!(
+                    warn,
+                    "[check_invariants] More than {} items to scan, skip",
+                    max_items
+                );
+                return None;
+            }
+
+            let mut violations = Vec::new();
+            let is_indexed = |index: &BtcHeaderIndex| {
+                Self::headers(&index.hash).map_or(false, |info| info.height == index.height)
+            };
+
+            let best = Self::best_index();
+            if !is_indexed(&best) {
+                log!(
+                    error,
+                    "[check_invariants] BestIndex:{:?} not in Headers",
+                    best
+                );
+                violations.push("BestIndex is not in Headers");
+            }
+            if let Some(confirmed) = Self::confirmed_index() {
+                if !is_indexed(&confirmed) {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} not in Headers",
+                        confirmed
+                    );
+                    violations.push("ConfirmedIndex is not in Headers");
+                }
+                if !Self::main_chain(&confirmed.hash) {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} not in MainChain",
+                        confirmed
+                    );
+                    violations.push("ConfirmedIndex is not in MainChain");
+                }
+                if confirmed.height > best.height {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} above BestIndex:{:?}",
+                        confirmed,
+                        best
+                    );
+                    violations.push("ConfirmedIndex is above BestIndex");
+                }
+            }
+
+            for (height, hashes) in BlockHashFor::<T>::iter() {
+                if hashes.iter().filter(|hash| Self::main_chain(hash)).count() > 1 {
+                    log!(
+                        error,
+                        "[check_invariants] height:{}, multiple MainChain headers",
+                        height
+                    );
+                    violations.push("Multiple MainChain headers at the same height");
+                }
+                let indexed = hashes
+                    .into_iter()
+                    .all(|hash| is_indexed(&BtcHeaderIndex { hash, height }));
+                if !indexed {
+                    log!(
+                        error,
+                        "[check_invariants] height:{}, BlockHashFor not in Headers",
+                        height
+                    );
+                    violations.push("BlockHashFor is not in Headers");
+                }
+            }
+
+            if let Err(violation) = Self::ensure_pending_deposit_total() {
+                violations.push(violation);
+            }
+
+            Some(violations)
+        }
+
+        /// Ensure the invariants of the header chain, see [`Self::invariant_violations`].
+        pub fn ensure_invariants() -> Result<(), &'static str> {
+            match Self::invariant_violations().into_iter().next() {
+                Some(violation) => Err(violation),
+                None => Ok(()),
+            }
+        }
+    }
+
     /// Storage Query RPCs
     impl<T: Config> Pallet<T> {
         /// Get withdrawal proposal
@@ -645,6 +812,33 @@ pub mod pallet {
         pub fn get_btc_block_header(txid: H256) -> Option<BtcHeaderInfo> {
             Self::headers(txid)
         }
+
+        /// Get the best index and the confirmed index of the header chain
+        pub fn get_header_indexes() -> (BtcHeaderIndex, Option<BtcHeaderIndex>) {
+            (Self::best_index(), Self::confirmed_index())
+        }
+
+        /// Get the number and the total value of the unclaimed deposits
+        pub fn get_pending_deposit_stats() -> (u32, u64) {
+            let count = PendingDeposits::<T>::iter_values()
+                .map(|deposits| deposits.len() as u32)
+                .sum();
+            (count, Self::pending_deposit_total())
+        }
+
+        /// Get the processing state of a relayed transaction
+        pub fn get_tx_state(txid: H256) -> Option<BtcTxState> {
+            Self::tx_state(txid)
+        }
+
+        /// Get the (hot, cold) addresses of the current and the last trustee sessions
+        pub fn get_trustee_address_pairs() -> Vec<(BtcAddress, BtcAddress)> {
+            let current = T::TrusteeSessionProvider::current_trustee_session()
+                .map(|info| (info.hot_address.addr, info.cold_address.addr));
+            let last = T::TrusteeSessionProvider::last_trustee_session()
+                .map(|info| (info.hot_address.addr, info.cold_address.addr));
+            current.into_iter().chain(last).collect()
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -669,6 +863,33 @@ pub mod pallet {
             Address::from_str(addr).map_err(|_| Error::<T>::InvalidAddr.into())
         }
 
+        /// Verify the withdrawal address belongs to the configured `NetworkId`.
+        ///
+        /// Only `p2pkh`, `p2sh` (base58), `p2wpkh`, `p2wsh` (bech32) and `p2tr` (bech32m)
+        /// destinations are supported.
+        pub fn verify_withdrawal_address(data: &[u8]) -> Result<Address, DispatchError> {
+            let network = Self::network_id();
+            // a bech32 string may happen to be a valid base58 string
+            let legacy = bs58::decode(data).into_vec().ok().and_then(|layout| {
+                Address::from_layout(&layout)
+                    .ok()
+                    .map(|address| (layout, address))
+            });
+            let result = match legacy {
+                Some((layout, address)) => {
+                    xp_gateway_bitcoin::check_legacy_address(&layout, &address, network)
+                        .map(|_| address)
+                }
+                None => xp_gateway_bitcoin::parse_segwit_address(data, network),
+            };
+            match result {
+                Ok(address) => Ok(Address { network, ..address }),
+                Err(AddressError::InvalidEncoding) => Err(Error::<T>::InvalidAddr.into()),
+                Err(AddressError::NetworkMismatch) => Err(Error::<T>::InvalidAddrNetwork.into()),
+                Err(AddressError::UnsupportedType) => Err(Error::<T>::UnsupportedAddrType.into()),
+            }
+        }
+
         pub fn verify_tx_valid(
             raw_tx: Vec<u8>,
             withdrawal_id_list: Vec<u32>,
@@ -727,6 +948,28 @@ pub mod pallet {
             }
         }
 
+        /// Verify that the transaction is packed in the confirmed main chain block `block_hash`
+        /// by the merkle proof. Returns the height of the block.
+        ///
+        /// Used by the bitcoin spv precompile, nothing would be written into storage.
+        pub fn verify_tx_inclusion(
+            tx: Transaction,
+            block_hash: H256,
+            merkle_proof: PartialMerkleTree,
+        ) -> Result<u32, DispatchError> {
+            let relayed_tx = BtcRelayedTx {
+                block_hash,
+                raw: tx,
+                merkle_proof,
+            };
+            Self::check_relayed_tx(&relayed_tx, None)
+        }
+
+        /// Get the height of confirmed header, `None` if confirmed header does not exist yet.
+        pub fn confirmed_height() -> Option<u32> {
+            Self::confirmed_index().map(|index| index.height)
+        }
+
         /// Helper function for deserializing the slice of raw tx.
         #[inline]
         pub(crate) fn deserialize_tx(input: &[u8]) -> Result<Transaction, Error<T>> {
@@ -826,23 +1069,25 @@ pub mod pallet {
             Ok(())
         }
 
-        pub(crate) fn apply_push_transaction(
-            tx: BtcRelayedTx,
-            prev_tx: Option<Transaction>,
-        ) -> DispatchResult {
+        /// Verify the merkle proof of the relayed tx, and ensure that the block which contains
+        /// the tx is in the main chain and has been confirmed. Returns the block height.
+        pub(crate) fn check_relayed_tx(
+            tx: &BtcRelayedTx,
+            prev_tx: Option<&Transaction>,
+        ) -> Result<u32, DispatchError> {
             let tx_hash = tx.raw.hash();
             let block_hash = tx.block_hash;
             let header_info = Pallet::<T>::headers(&tx.block_hash).ok_or_else(|| {
                 log!(
                     error,
-                    "[apply_push_transaction] Tx's block header ({:?}) must exist before",
+                    "[check_relayed_tx] Tx's block header ({:?}) must exist before",
                     block_hash
                 );
                 "Tx's block header must already exist"
             })?;
             let merkle_root = header_info.header.merkle_root_hash;
             // verify, check merkle proof
-            tx::validate_transaction::<T>(&tx, merkle_root, prev_tx.as_ref())?;
+            tx::validate_transaction::<T>(tx, merkle_root, prev_tx)?;
 
             // ensure the tx should belong to the main chain, means should submit main chain tx,
             // e.g. a tx may be packed in main chain block, and forked chain block, only submit main chain tx
@@ -853,11 +1098,22 @@ pub mod pallet {
             let height = header_info.height;
             if height > confirmed.height {
                 log!(error,
-                "[apply_push_transaction] Receive an unconfirmed tx (height:{}, hash:{:?}), confirmed index (height:{}, hash:{:?})",
+                "[check_relayed_tx] Receive an unconfirmed tx (height:{}, hash:{:?}), confirmed index (height:{}, hash:{:?})",
                 height, tx_hash, confirmed.height, confirmed.hash
             );
                 return Err(Error::<T>::UnconfirmedTx.into());
             }
+            Ok(height)
+        }
+
+        pub(crate) fn apply_push_transaction(
+            tx: BtcRelayedTx,
+            prev_tx: Option<Transaction>,
+        ) -> DispatchResult {
+            let tx_hash = tx.raw.hash();
+            let block_hash = tx.block_hash;
+            Self::check_relayed_tx(&tx, prev_tx.as_ref())?;
+
             // check whether replayed tx has been processed, just process failed and not processed tx;
             match Self::tx_state(&tx_hash) {
                 None => { /* do nothing */ }
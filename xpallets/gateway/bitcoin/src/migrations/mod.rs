@@ -0,0 +1,5 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! All migrations of this pallet.
+
+pub mod pending_total;
@@ -219,3 +219,52 @@ fn test_call() {
         assert_ok!(XGatewayBitcoin::push_header(origin, v));
     })
 }
+
+#[test]
+fn test_header_invariants() {
+    let (base_height, c1, forked) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((*c1.get(0).unwrap(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            assert_ok!(XGatewayBitcoin::ensure_invariants());
+            for header in c1[1..4].iter().chain(forked[2..5].iter()) {
+                assert_ok!(XGatewayBitcoin::apply_push_header(*header));
+            }
+            // switch back to the normal chain
+            assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(4).unwrap()));
+            assert_ok!(XGatewayBitcoin::apply_push_header(*c1.get(5).unwrap()));
+            assert_eq!(XGatewayBitcoin::invariant_violations(), Vec::<&str>::new());
+            // too many headers to scan
+            assert_eq!(XGatewayBitcoin::check_invariants(1), None);
+
+            let root = frame_system::RawOrigin::Root;
+            let best = XGatewayBitcoin::best_index();
+            assert_ok!(XGatewayBitcoin::set_confirmed_index(
+                root.clone().into(),
+                BtcHeaderIndex {
+                    hash: forked.get(3).unwrap().hash(),
+                    height: best.height + 1,
+                }
+            ));
+            assert_eq!(
+                XGatewayBitcoin::invariant_violations(),
+                vec![
+                    "ConfirmedIndex is not in Headers",
+                    "ConfirmedIndex is not in MainChain",
+                    "ConfirmedIndex is above BestIndex",
+                ]
+            );
+
+            assert_ok!(XGatewayBitcoin::set_best_index(
+                root.into(),
+                BtcHeaderIndex {
+                    hash: Default::default(),
+                    height: best.height,
+                }
+            ));
+            assert_eq!(
+                XGatewayBitcoin::ensure_invariants(),
+                Err("BestIndex is not in Headers")
+            );
+        })
+}
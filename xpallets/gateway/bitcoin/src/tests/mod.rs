@@ -10,8 +10,10 @@ use xp_gateway_common::AccountExtractor;
 
 use light_bitcoin::script::Script;
 
+use frame_support::assert_noop;
+
 use crate::{
-    mock::{Test, XGatewayBitcoin},
+    mock::{ExtBuilder, Test, XGatewayBitcoin, XGatewayBitcoinErr},
     Config,
 };
 
@@ -21,6 +23,54 @@ pub fn test_verify_btc_address() {
     assert!(XGatewayBitcoin::verify_btc_address(&address).is_ok());
 }
 
+#[test]
+pub fn test_verify_withdrawal_address() {
+    // the mock network is testnet
+    ExtBuilder::default().build_and_execute(|| {
+        for addr in [
+            // p2pkh
+            &b"mqVznxoxdeSNYgDCg6ZVE5pc6476BY6zHK"[..],
+            // p2sh
+            b"2N6mJFLkjN9muneSeHCsMCxWXVZ4ruLKfFo",
+            // p2wpkh
+            b"tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+            // p2wsh
+            b"tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            // p2tr
+            b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68",
+        ] {
+            assert!(XGatewayBitcoin::verify_withdrawal_address(addr).is_ok());
+        }
+
+        for addr in [
+            // mainnet p2pkh
+            &b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"[..],
+            // mainnet p2wpkh
+            b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            // mainnet p2tr
+            b"bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        ] {
+            assert_noop!(
+                XGatewayBitcoin::verify_withdrawal_address(addr),
+                XGatewayBitcoinErr::InvalidAddrNetwork
+            );
+        }
+
+        // witness version 16
+        assert_noop!(
+            XGatewayBitcoin::verify_withdrawal_address(b"tb1sw50qadvs0e"),
+            XGatewayBitcoinErr::UnsupportedAddrType
+        );
+        // p2tr encoded with bech32 instead of bech32m
+        assert_noop!(
+            XGatewayBitcoin::verify_withdrawal_address(
+                b"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"
+            ),
+            XGatewayBitcoinErr::InvalidAddr
+        );
+    });
+}
+
 #[test]
 fn test_account_ss58_version() {
     set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
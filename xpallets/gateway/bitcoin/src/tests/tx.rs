@@ -3,13 +3,17 @@
 #![allow(non_upper_case_globals)]
 
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, StorageVersion},
+};
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormatRegistry};
 
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, Network},
     merkle::PartialMerkleTree,
+    primitives::H256,
     serialization::{self, Reader},
 };
 
@@ -22,7 +26,7 @@ use crate::{
         BtcDepositCache, BtcRelayedTxInfo, BtcTxResult, BtcTxState, BtcWithdrawalProposal,
         VoteResult,
     },
-    Config, WithdrawalProposal,
+    Config, PendingDepositTotal, PendingDeposits, WithdrawalProposal,
 };
 
 // Tyoe is p2tr. Address farmat is Mainnet.:
@@ -188,6 +192,7 @@ fn test_process_tx() {
                 balance: 100000,
             }]
         );
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 100000);
 
         // withdraw
         WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
@@ -286,3 +291,88 @@ fn test_push_tx_call() {
         );
     });
 }
+
+#[test]
+fn test_verify_tx_inclusion() {
+    set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
+    let normal_deposit: Transaction = "02000000000101aeee49e0bbf7a36f78ea4321b5c8bae0b8c72bdf2c024d2484b137fa7d0f8e1f01000000000000000003a0860100000000002251209a9ea267884f5549c206b2aec2bd56d98730f90532ea7f7154d4d4f923b7e3bb0000000000000000326a3035516a706f3772516e7751657479736167477a6334526a376f737758534c6d4d7141754332416255364c464646476a38801a060000000000225120c9929543dfa1e0bb84891acd47bfa6546b05e26b7a04af8eb6765fcc969d565f01409e325889515ed47099fdd7098e6fafdc880b21456d3f368457de923f4229286e34cef68816348a0581ae5885ede248a35ac4b09da61a7b9b90f34c200872d2e300000000".parse().unwrap();
+    let headers = generate_blocks_63290_63310();
+    let block_hash = headers[&63299].hash();
+
+    let raw_proof = hex::decode("0a000000050a59b195a68a29037580798ca0414941eb46eaf7607db2d0da1ff89e9570ce455fea22ec1a3e3e7e1167fa220cc8376225f07bd20aa194e7f3c4ac68c7375d8e0a35e47541de7d0aa7312dabcf3bc9f06603e832427b8e4fe9a97a309f8cd7141687d11a3fd8f21e2105a52a3c36a17ea870e326ecddb23221d4cc0398b6c44bdcce3f191919a31f4cfaca5a786cc8315db76683ad6b8008f2ed9b348df76a0d022f00").unwrap();
+    let proof: PartialMerkleTree = serialization::deserialize(Reader::new(&raw_proof)).unwrap();
+
+    ExtBuilder::default().build_and_execute(|| {
+        let confirmed = XGatewayBitcoin::confirmation_number();
+        for i in 63291..63299 + confirmed - 1 {
+            assert_ok!(XGatewayBitcoin::apply_push_header(headers[&i]));
+        }
+        // the block is in the main chain, but not confirmed yet
+        assert_noop!(
+            XGatewayBitcoin::verify_tx_inclusion(normal_deposit.clone(), block_hash, proof.clone()),
+            XGatewayBitcoinErr::UnconfirmedTx,
+        );
+
+        assert_ok!(XGatewayBitcoin::apply_push_header(
+            headers[&(63299 + confirmed - 1)]
+        ));
+        assert_eq!(XGatewayBitcoin::confirmed_height(), Some(63299));
+        assert_eq!(
+            XGatewayBitcoin::verify_tx_inclusion(normal_deposit.clone(), block_hash, proof.clone()),
+            Ok(63299)
+        );
+
+        // the tx is not packed in this block
+        assert_noop!(
+            XGatewayBitcoin::verify_tx_inclusion(
+                normal_deposit.clone(),
+                headers[&63298].hash(),
+                proof.clone()
+            ),
+            XGatewayBitcoinErr::BadMerkleProof,
+        );
+        // the proof does not contain this tx
+        assert_noop!(
+            XGatewayBitcoin::verify_tx_inclusion(hot_to_cold.clone(), block_hash, proof),
+            XGatewayBitcoinErr::BadMerkleProof,
+        );
+    });
+}
+
+#[test]
+fn test_pending_deposit_total() {
+    ExtBuilder::default().build_and_execute(|| {
+        let addr1 = b"addr1".to_vec();
+        let addr2 = b"addr2".to_vec();
+        let cache = |n: u8, balance: u64| BtcDepositCache {
+            txid: H256::repeat_byte(n),
+            balance,
+        };
+        PendingDeposits::<Test>::insert(&addr1, vec![cache(1, 100), cache(2, 200)]);
+        PendingDeposits::<Test>::insert(&addr2, vec![cache(3, 300)]);
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 0);
+
+        // the migration computes the initial value
+        StorageVersion::new(0).put::<XGatewayBitcoin>();
+        crate::migrations::pending_total::apply::<Test>();
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 600);
+        assert_eq!(XGatewayBitcoin::on_chain_storage_version(), 1);
+
+        // run only once
+        PendingDepositTotal::<Test>::put(0);
+        crate::migrations::pending_total::apply::<Test>();
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 0);
+        PendingDepositTotal::<Test>::put(600);
+        assert_eq!(XGatewayBitcoin::sum_pending_deposits(), (2, 600));
+
+        assert_ok!(XGatewayBitcoin::remove_pending(Origin::root(), addr1, None));
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 300);
+        assert_ok!(XGatewayBitcoin::remove_pending(
+            Origin::root(),
+            addr2,
+            Some(alice())
+        ));
+        assert_eq!(XGatewayBitcoin::pending_deposit_total(), 0);
+        assert_eq!(XGatewayBitcoin::sum_pending_deposits(), (0, 0));
+    });
+}
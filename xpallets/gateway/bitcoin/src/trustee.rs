@@ -148,10 +148,7 @@ impl<T: Config> TrusteeForChain<T::AccountId, T::BlockNumber, BtcTrusteeType, Bt
     }
 
     fn generate_trustee_session_info(
-        props: Vec<(
-            T::AccountId,
-            TrusteeIntentionProps<T::AccountId, BtcTrusteeType>,
-        )>,
+        props: Vec<(T::AccountId, TrusteeIntentionProps<BtcTrusteeType>)>,
         config: TrusteeInfoConfig,
     ) -> Result<
         (
@@ -162,7 +159,7 @@ impl<T: Config> TrusteeForChain<T::AccountId, T::BlockNumber, BtcTrusteeType, Bt
     > {
         let (trustees, props_info): (
             Vec<T::AccountId>,
-            Vec<TrusteeIntentionProps<T::AccountId, BtcTrusteeType>>,
+            Vec<TrusteeIntentionProps<BtcTrusteeType>>,
         ) = props.into_iter().unzip();
 
         let (hot_keys, cold_keys): (Vec<Public>, Vec<Public>) = props_info
@@ -463,9 +460,11 @@ fn check_withdraw_tx_impl<T: Config>(
     for withdraw_index in withdrawal_id_list.iter() {
         let record = xpallet_gateway_records::Pallet::<T>::pending_withdrawals(withdraw_index)
             .ok_or(Error::<T>::NoWithdrawalRecord)?;
-        // record.addr() is base58
-        // verify btc address would conveRelayedTx a base58 addr to Address
-        let addr: Address = Pallet::<T>::verify_btc_address(record.addr())?;
+        // record.addr() is base58 or bech32/bech32m
+        // the records applied before the strict check keep the lenient parse, so that
+        // they don't fail the withdrawal of the whole batch
+        let addr: Address = Pallet::<T>::verify_withdrawal_address(record.addr())
+            .or_else(|_| Pallet::<T>::verify_btc_address(record.addr()))?;
 
         appl_withdrawal_list.push((addr, record.balance().saturated_into::<u64>()));
     }
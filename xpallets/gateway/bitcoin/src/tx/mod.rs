@@ -24,7 +24,7 @@ use xp_assets_registrar::Chain;
 pub use self::validator::validate_transaction;
 use crate::{
     types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState},
-    Config, Event, Pallet, PendingDeposits, WithdrawalProposal,
+    Config, Event, Pallet, PendingDepositTotal, PendingDeposits, WithdrawalProposal,
 };
 use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
 use xp_gateway_common::AccountExtractor;
@@ -161,6 +161,7 @@ fn deposit_token<T: Config>(txid: H256, who: &T::AccountId, balance: T::Balance)
 pub fn remove_pending_deposit<T: Config>(input_address: &BtcAddress, who: &T::AccountId) {
     // notice this would delete this cache
     let records = PendingDeposits::<T>::take(input_address);
+    decrease_pending_deposit_total::<T>(&records);
     for record in records {
         // ignore error
         let _ = deposit_token::<T>(record.txid, who, record.balance.saturated_into());
@@ -179,6 +180,14 @@ pub fn remove_pending_deposit<T: Config>(input_address: &BtcAddress, who: &T::Ac
     }
 }
 
+/// Subtract the removed unclaimed deposits from `PendingDepositTotal`.
+pub fn decrease_pending_deposit_total<T: Config>(records: &[BtcDepositCache]) {
+    let removed = records
+        .iter()
+        .fold(0u64, |acc, record| acc.saturating_add(record.balance));
+    PendingDepositTotal::<T>::mutate(|total| *total = total.saturating_sub(removed));
+}
+
 fn insert_pending_deposit<T: Config>(input_addr: &Address, txid: H256, balance: u64) {
     let addr_bytes = input_addr.to_string().into_bytes();
 
@@ -194,6 +203,7 @@ fn insert_pending_deposit<T: Config>(input_addr: &Address, txid: H256, balance:
                 balance
             );
             list.push(cache);
+            PendingDepositTotal::<T>::mutate(|total| *total = total.saturating_add(balance));
 
             Pallet::<T>::deposit_event(Event::<T>::UnclaimedDeposit(txid, addr_bytes.clone()));
         }
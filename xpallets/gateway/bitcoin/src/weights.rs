@@ -4,6 +4,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-04-28, STEPS: 50, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 1024
+//!
+//! The weights of the following calls are estimated by hand, to be regenerated by the executed
+//! command below: `set_confirmed_number`.
 
 // Executed Command:
 // ./target/release/sherpax
@@ -41,6 +44,7 @@ pub trait WeightInfo {
     fn set_btc_withdrawal_fee() -> Weight;
     fn set_btc_deposit_limit() -> Weight;
     fn set_coming_bot() -> Weight;
+    fn set_confirmed_number() -> Weight;
 }
 
 /// Weights for xpallet_gateway_bitcoin using the Substrate node and recommended hardware.
@@ -86,6 +90,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn set_coming_bot() -> Weight {
         (1_979_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_confirmed_number() -> Weight {
+        (4_851_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -130,4 +137,7 @@ impl WeightInfo for () {
     fn set_coming_bot() -> Weight {
         (1_979_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_confirmed_number() -> Weight {
+        (4_851_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
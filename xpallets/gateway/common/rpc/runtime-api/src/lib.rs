@@ -15,7 +15,7 @@ pub use xp_runtime::Memo;
 
 pub use xpallet_gateway_common::{
     trustees,
-    types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, ScriptInfo},
+    types::{GatewayHealth, GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, ScriptInfo},
 };
 pub use xpallet_gateway_records::{
     Withdrawal, WithdrawalLimit, WithdrawalRecordId, WithdrawalState,
@@ -23,6 +23,10 @@ pub use xpallet_gateway_records::{
 
 sp_api::decl_runtime_apis! {
     /// The API to query account nonce (aka transaction index).
+    ///
+    /// The version `2` adds `trustee_transition_status` used by the gateway metrics, and drops
+    /// the proxy account of the trustee properties.
+    #[api_version(2)]
     pub trait XGatewayCommonApi<AccountId, Balance, BlockNumber>
     where
         AccountId: codec::Codec,
@@ -50,10 +54,19 @@ sp_api::decl_runtime_apis! {
         /// Get all trustee multisig.
         fn trustee_multisigs() -> BTreeMap<Chain, AccountId>;
 
-        fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps<AccountId>>;
+        fn trustee_properties(chain: Chain, who: AccountId) -> Option<GenericTrusteeIntentionProps>;
 
         fn trustee_session_info(chain: Chain, session_number: i32) -> Option<GenericTrusteeSessionInfo<AccountId, BlockNumber>>;
 
         fn generate_trustee_session_info(chain: Chain, Vec<AccountId>) -> Result<(GenericTrusteeSessionInfo<AccountId, BlockNumber>, ScriptInfo<AccountId>), DispatchError>;
+
+        fn trustee_transition_status(chain: Chain) -> bool;
+    }
+
+    /// The API to check the invariants of the gateway pallets, for monitoring.
+    ///
+    /// The pallets with more than `max_items` storage items to scan are left unchecked.
+    pub trait XGatewayHealthApi {
+        fn health_report(max_items: u32) -> Vec<GatewayHealth>;
     }
 }
@@ -89,7 +89,7 @@ where
         chain: Chain,
         who: AccountId,
         at: Option<BlockHash>,
-    ) -> Result<BtcTrusteeIntentionProps<AccountId>>;
+    ) -> Result<BtcTrusteeIntentionProps>;
 
     /// Return bitcoin trustee for current session(e.g. trustee hot/cold address and else)
     #[rpc(name = "xgatewaycommon_trusteeSessionInfo")]
@@ -141,7 +141,7 @@ where
         chain: Chain,
         who: AccountId,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<GenericTrusteeIntentionProps<AccountId>> {
+    ) -> Result<GenericTrusteeIntentionProps> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
@@ -340,9 +340,9 @@ where
         chain: Chain,
         who: AccountId,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<BtcTrusteeIntentionProps<AccountId>> {
+    ) -> Result<BtcTrusteeIntentionProps> {
         let props = self.generic_trustee_properties(chain, who, at)?;
-        BtcTrusteeIntentionProps::<_>::try_from(props).map_err(trustee_decode_error_into_rpc_err)
+        BtcTrusteeIntentionProps::try_from(props).map_err(trustee_decode_error_into_rpc_err)
     }
 
     fn trustee_session_info(
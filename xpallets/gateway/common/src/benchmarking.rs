@@ -7,7 +7,7 @@ use frame_system::RawOrigin;
 use sp_core::crypto::AccountId32;
 #[cfg(feature = "runtime-benchmarks")]
 use sp_runtime::traits::CheckedDiv;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{StaticLookup, Zero};
 use sp_std::prelude::*;
 
 use xp_assets_registrar::Chain;
@@ -16,6 +16,7 @@ use xpallet_gateway_records::{Pallet as XGatewayRecords, WithdrawalRecordId, Wit
 use crate::{
     traits::TrusteeSession, types::*, Balanceof, Call, Config, LittleBlackHouse, Pallet,
     TrusteeIntentionPropertiesOf, TrusteeMultiSigAddr, TrusteeSessionInfoLen, TrusteeSessionInfoOf,
+    TrusteeTransitionStatus,
 };
 
 fn create_default_asset<T: Config>(who: T::AccountId) {
@@ -102,6 +103,93 @@ fn new_trustees<T: Config>() -> Vec<(T::AccountId, Vec<u8>, Vec<u8>, Vec<u8>)> {
     ]
 }
 
+/// Uncompressed hot and cold pubkeys of the election candidates.
+const CANDIDATE_KEYS: [(&str, &str); 12] = [
+    (
+        "0414fde2d9c6b4241ec0c6832f51406d6468ec6a9f8010f58f510288faf64637d01ef57648ae0b9d79a779bbdbf0f8091a86d1d1d1a2044eecd88f2ee3b664c354",
+        "043da7244f8145a717ca1ec746a204cb9f96ec360cdf4910c3ab078fbbf9882a6a26dc19b32526405ddd17ed130ca2d5328719011705639f411a702738a9ca5936",
+    ),
+    (
+        "04b4ce57fb31d10c399a8d17c61e69b5d07fc9b7a16e9546d09b293b94cdeaeaf68fa6d9213dfc271a5aa73fc4c342fdc0f9ba67524b32041ce69b3191906fee9a",
+        "0463c900da38d62779c8ed6c57c505daa7fa61ff7a3ca35cea589f53ca2d6e8f91978595b819e1cd90135d394e2f2b0c78d1946ad44da5838ec52b2ea4c6091a7b",
+    ),
+    (
+        "0405c9bd7f2ff137c262d3b00b0fc593902aa3387c56df9ab1ea5195a07000f01ea592c69af646594292b80571e07aaed98e5fcc44beb631d9553bed47aaee2aa1",
+        "04d7582ad30efb2ff8fa088dc6e4e7b6076f45267f35b48668b816157a42391c764b3ea1379016993cc02ce68a96c0ab6431f03fa7fe193220e0f285fc23ede586",
+    ),
+    (
+        "040157189e1fa12ce326c09bad73cdfd157ddd33310989a7173216bcc950a60fc9ee6716315631ad2efd1a3d989b2198925a47075a9942956ac4fd46dc8de42180",
+        "046356cb77de4cd1aa2f4f29b9ceda482c85a747d72bc4271a58373606a12c4414747a4e1f1e27cceb4c58d3fbb17abf8425e8ef7894c53b8a1ce0bb626157bc58",
+    ),
+    (
+        "04e1740cfb1bafbe9d99e233b92f3054cbfcd31f736a6f1d3d0caccfd630b83c7581db8b228379a0e15f0f0ac1dc4f09ad80598b007eaf0f37793878c00c2ea347",
+        "0402f0c2774fea92d6e485a0e9892027c7385e56af373b5656a4e472dcab074fd3241457cc58acfc9870eaab09321b7136f993e31c2ff25c13336a1f67799a7ee4",
+    ),
+    (
+        "04613e725e04e43d45174b554062a0c2ff702d42756b6682b31246d9d9760c02c4ca531bc1e585e88b789b6a328bd5b7cbfd3f6cdeef449b88304a1f5346d36dde",
+        "0443d264a9e151d0a28e4227f099302e8776a76af482f0c2aff4f90b377fa28f4962c119028a4350831732eb070513f586d3fee088e80692ff21e3f4f3430bca31",
+    ),
+    (
+        "04e6cdc8cba36ef02da0c7b8fcdb48eb76c68deb0d061885df9d52b43fa0151073215717630c0c40aa29aa36209e4bd233ce22f978f20c7f6f250fdf2eba96cd94",
+        "047ba364e98386d420176b28e9bbb9cbd7e322f5e40a61c14a8f6b09189d988126b1b5a84f1e17545eae36492baea0aa85754a6b4f2d6addf79cbb54917198c54b",
+    ),
+    (
+        "0404113308302f539759469f2bb6fbfe33cd858959236e36984c57d5b4b7f19b3cf0ccbe13a8e1d43576a539601990076a11f1d6ff3c2358554e85033a45b4c7c8",
+        "04f6edee14a5c64dc8179e60e90590c5c03e81453c200775eb8564a6e6dce1e2f3064c320946310ed265d82090388291eb5fff02d0dc3a67daf8a15bbfac6c39d3",
+    ),
+    (
+        "0402e4aab32a4835339b9241d81b6afbe10c79fdbff47cfa2312c78ae642e2e8a80e913614083c3a4352a9e43371fc10890bfd314a82c98ace9b577ab25306cad2",
+        "04e2dabbf2a2aca46b7ab1ec671e6682b0b05b0e2c809c9f7f555c7a339aff1f490cdb3ebe9a6120b40ba1993e24bd6cf433a9ee02e55939c47356fd45603d7248",
+    ),
+    (
+        "0448cf86a82191a145ca17c02e36eb35809bf02be809233dae565301e2ab689dfc5d0b379ac5e844f9aa46dd49607d6cc365fe42d875e958f0135b65678118326c",
+        "04270e4a03150701a69f68bbe4740f4b0eab1b25005fddec57d2a680402e6e247edae2fd9e7153f75a9a10941d82a55ae7aeed4e4b1f7d37cf8b7cf73d10b9583a",
+    ),
+    (
+        "04d53836abe67aa9333115734d4ffa9ce3d54023543d786b5f902481228404e944bdb084a0951a49eeacfdecc9482d7fd5def6e1d68c19ff6632ed53138a034900",
+        "04f624037d97a2bf025dc508b0a5e4b63dbcb4150e338beed00d13bfcb43780373fbfdba49e35fc481575687a44a539b9de6c27f16ff07b1beb2fdb604563fae9b",
+    ),
+    (
+        "043f693342040910ead9145b3de934ec54ec5273775672b2fbdcc8be1bfb804c55a75806bcf5a6e088768048aa333e977e7b0286b9c172e613734d6f6b1c4243dd",
+        "048233463bfd07a9b9fb79a93bb1a774abe33e8027db35e6d1129e38a27ab0aa33ee9ab3be0d59f20931481e4365933ee6646b211c4c2ffe2aa9f3f45f45c34261",
+    ),
+];
+
+/// Make `count` candidates the elected members and register them as bitcoin trustees.
+fn setup_candidates<T: Config>(count: u32) -> Vec<T::AccountId> {
+    let candidates = (0..count)
+        .map(|i| frame_benchmarking::account::<T::AccountId>("candidate", i, 0))
+        .collect::<Vec<_>>();
+    let members = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, who)| pallet_elections_phragmen::SeatHolder {
+            who: who.clone(),
+            stake: (count - i as u32).into(),
+            deposit: Zero::zero(),
+        })
+        .collect::<Vec<_>>();
+    pallet_elections_phragmen::Members::<T>::put(members);
+
+    for (i, who) in candidates.iter().enumerate() {
+        let (hot, cold) = CANDIDATE_KEYS[i];
+        Pallet::<T>::setup_trustee_impl(
+            who.clone(),
+            Chain::Bitcoin,
+            b"".to_vec(),
+            hex::decode(hot).unwrap(),
+            hex::decode(cold).unwrap(),
+        )
+        .unwrap();
+    }
+    TrusteeTransitionStatus::<T>::insert(Chain::Bitcoin, false);
+    candidates
+}
+
+fn desired_trustees<T: Config>() -> u32 {
+    <T as pallet_elections_phragmen::Config>::DesiredMembers::get() - 1
+}
+
 /// removes all the storage items to reverse any genesis state.
 fn clean<T: Config>() {
     <LittleBlackHouse<T>>::remove_all(None);
@@ -166,22 +254,11 @@ benchmarks! {
                 .unwrap();
 
         assert!(Pallet::<T>::trustee_intention_props_of(caller.clone(), Chain::Bitcoin).is_none());
-    }: _(RawOrigin::Signed(caller.clone()), None, Chain::Bitcoin, b"about".to_vec(), hot, cold)
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin, b"about".to_vec(), hot, cold)
     verify {
         assert!(Pallet::<T>::trustee_intention_props_of(caller, Chain::Bitcoin).is_some());
     }
 
-    set_trustee_proxy {
-        let caller: T::AccountId = alice::<T>();
-        assert!(Pallet::<T>::trustee_intention_props_of(caller.clone(), Chain::Bitcoin).is_some());
-    }: _(RawOrigin::Signed(caller.clone()), bob::<T>(), Chain::Bitcoin)
-    verify {
-        assert_eq!(
-            Pallet::<T>::trustee_intention_props_of(caller, Chain::Bitcoin).unwrap().0.proxy_account,
-            Some(bob::<T>())
-        );
-    }
-
     set_trustee_info_config {
         let config = TrusteeInfoConfig {
             min_trustee_count: 5,
@@ -195,7 +272,7 @@ benchmarks! {
     set_trustee_admin {
         let who: T::AccountId = alice::<T>();
         for (account, about, hot, cold) in new_trustees::<T>() {
-            Pallet::<T>::setup_trustee_impl(account.clone(), None, Chain::Bitcoin, about, hot, cold).unwrap();
+            Pallet::<T>::setup_trustee_impl(account.clone(), Chain::Bitcoin, about, hot, cold).unwrap();
         }
     }: _(RawOrigin::Root, who.clone())
     verify {
@@ -219,7 +296,7 @@ benchmarks! {
         let trustee_info = new_trustees::<T>();
         let trustee_len = trustee_info.len();
         for (account, about, hot, cold) in (&trustee_info[0..trustee_len-1]).to_vec() {
-            Pallet::<T>::setup_trustee_impl(account.clone(), None, Chain::Bitcoin, about, hot, cold).unwrap();
+            Pallet::<T>::setup_trustee_impl(account.clone(), Chain::Bitcoin, about, hot, cold).unwrap();
             candidators.push(account);
         }
         assert_eq!(Pallet::<T>::transition_trustee_session_impl(Chain::Bitcoin, candidators), Ok(()));
@@ -228,7 +305,7 @@ benchmarks! {
         let trustee_info = new_trustees::<T>();
         let trustee_len = trustee_info.len();
         for (account, about, hot, cold) in (&trustee_info[1..trustee_len]).to_vec() {
-            Pallet::<T>::setup_trustee_impl(account.clone(), None, Chain::Bitcoin, about, hot, cold).unwrap();
+            Pallet::<T>::setup_trustee_impl(account.clone(), Chain::Bitcoin, about, hot, cold).unwrap();
             candidators.push(account);
         }
         assert_eq!(Pallet::<T>::transition_trustee_session_impl(Chain::Bitcoin, candidators), Ok(()));
@@ -247,6 +324,99 @@ benchmarks! {
         #[cfg(not(feature = "runtime-benchmarks"))]
         assert_eq!(<T as xpallet_gateway_records::Config>::Currency::free_balance(&trustee_info[0].0), 33333333u32.into());
     }
+
+    register_relayer {
+        let caller: T::AccountId = alice::<T>();
+        let bond = T::RelayerBond::get();
+        <T as xpallet_gateway_records::Config>::Currency::make_free_balance_be(&caller, bond + bond);
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin)
+    verify {
+        assert_eq!(Pallet::<T>::relayer_bond_of(Chain::Bitcoin, caller), Some(bond));
+    }
+
+    unregister_relayer {
+        let caller: T::AccountId = alice::<T>();
+        let bond = T::RelayerBond::get();
+        <T as xpallet_gateway_records::Config>::Currency::make_free_balance_be(&caller, bond + bond);
+        Pallet::<T>::register_relayer(RawOrigin::Signed(caller.clone()).into(), Chain::Bitcoin)?;
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin)
+    verify {
+        assert!(Pallet::<T>::relayer_bond_of(Chain::Bitcoin, caller).is_none());
+    }
+
+    slash_relayer {
+        let caller: T::AccountId = alice::<T>();
+        let bond = T::RelayerBond::get();
+        <T as xpallet_gateway_records::Config>::Currency::make_free_balance_be(&caller, bond + bond);
+        Pallet::<T>::register_relayer(RawOrigin::Signed(caller.clone()).into(), Chain::Bitcoin)?;
+    }: _(RawOrigin::Root, Chain::Bitcoin, caller.clone())
+    verify {
+        assert!(Pallet::<T>::relayer_bond_of(Chain::Bitcoin, caller).is_none());
+    }
+
+    withdraw_relayer_bond {
+        let caller: T::AccountId = alice::<T>();
+        let bond = T::RelayerBond::get();
+        <T as xpallet_gateway_records::Config>::Currency::make_free_balance_be(&caller, bond + bond);
+        Pallet::<T>::register_relayer(RawOrigin::Signed(caller.clone()).into(), Chain::Bitcoin)?;
+        Pallet::<T>::unregister_relayer(RawOrigin::Signed(caller.clone()).into(), Chain::Bitcoin)?;
+        let unlock_at = frame_system::Pallet::<T>::block_number() + T::RelayerUnbondingPeriod::get();
+        frame_system::Pallet::<T>::set_block_number(unlock_at);
+    }: _(RawOrigin::Signed(caller.clone()), Chain::Bitcoin)
+    verify {
+        assert!(Pallet::<T>::relayer_unbonding(Chain::Bitcoin, caller).is_none());
+    }
+    execute_trustee_election {
+        setup_candidates::<T>(desired_trustees::<T>());
+        let session_len = Pallet::<T>::trustee_session_info_len(Chain::Bitcoin);
+    }: _(RawOrigin::Root, Chain::Bitcoin)
+    verify {
+        assert_eq!(Pallet::<T>::trustee_session_info_len(Chain::Bitcoin), session_len + 1);
+    }
+
+    cancel_trustee_election {
+        let candidates = setup_candidates::<T>(desired_trustees::<T>());
+        Pallet::<T>::transition_trustee_session_impl(Chain::Bitcoin, candidates)?;
+        TrusteeTransitionStatus::<T>::insert(Chain::Bitcoin, true);
+    }: _(RawOrigin::Root, Chain::Bitcoin)
+    verify {
+        assert!(!Pallet::<T>::trustee_transition_status(Chain::Bitcoin));
+    }
+
+    move_trust_into_black_room {
+        let candidates = setup_candidates::<T>(desired_trustees::<T>() + 1);
+        let session_len = Pallet::<T>::trustee_session_info_len(Chain::Bitcoin);
+        let trustee = candidates[0].clone();
+    }: _(RawOrigin::Root, Chain::Bitcoin, Some(vec![trustee.clone()]))
+    verify {
+        assert!(Pallet::<T>::little_black_house(Chain::Bitcoin).contains(&trustee));
+        assert_eq!(Pallet::<T>::trustee_session_info_len(Chain::Bitcoin), session_len + 1);
+    }
+
+    move_trust_out_black_room {
+        let member: T::AccountId = alice::<T>();
+        LittleBlackHouse::<T>::append(Chain::Bitcoin, member.clone());
+    }: _(RawOrigin::Root, Chain::Bitcoin, vec![member.clone()])
+    verify {
+        assert!(!Pallet::<T>::little_black_house(Chain::Bitcoin).contains(&member));
+    }
+
+    force_trustee_election {
+        TrusteeTransitionStatus::<T>::insert(Chain::Bitcoin, true);
+    }: _(RawOrigin::Root, Chain::Bitcoin)
+    verify {
+        assert!(!Pallet::<T>::trustee_transition_status(Chain::Bitcoin));
+    }
+
+    force_update_trustee {
+        let who = frame_benchmarking::account::<T::AccountId>("candidate", 0, 0);
+        let (hot, cold) = CANDIDATE_KEYS[0];
+        let hot = hex::decode(hot).unwrap();
+        let cold = hex::decode(cold).unwrap();
+    }: _(RawOrigin::Root, who.clone(), Chain::Bitcoin, b"about".to_vec(), hot, cold)
+    verify {
+        assert!(Pallet::<T>::trustee_intention_props_of(who, Chain::Bitcoin).is_some());
+    }
 }
 
 #[cfg(test)]
@@ -261,11 +431,20 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_withdraw());
             assert_ok!(Pallet::<Test>::test_benchmark_cancel_withdrawal());
             assert_ok!(Pallet::<Test>::test_benchmark_setup_trustee());
-            assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_proxy());
             assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_info_config());
             assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_admin());
             assert_ok!(Pallet::<Test>::test_benchmark_set_trustee_admin_multiply());
             assert_ok!(Pallet::<Test>::test_benchmark_claim_trustee_reward());
+            assert_ok!(Pallet::<Test>::test_benchmark_register_relayer());
+            assert_ok!(Pallet::<Test>::test_benchmark_unregister_relayer());
+            assert_ok!(Pallet::<Test>::test_benchmark_slash_relayer());
+            assert_ok!(Pallet::<Test>::test_benchmark_withdraw_relayer_bond());
+            assert_ok!(Pallet::<Test>::test_benchmark_execute_trustee_election());
+            assert_ok!(Pallet::<Test>::test_benchmark_cancel_trustee_election());
+            assert_ok!(Pallet::<Test>::test_benchmark_move_trust_into_black_room());
+            assert_ok!(Pallet::<Test>::test_benchmark_move_trust_out_black_room());
+            assert_ok!(Pallet::<Test>::test_benchmark_force_trustee_election());
+            assert_ok!(Pallet::<Test>::test_benchmark_force_update_trustee());
         });
     }
 }
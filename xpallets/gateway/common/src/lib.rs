@@ -16,6 +16,7 @@ mod tests;
 
 mod binding;
 pub mod migrations;
+mod relayer;
 pub mod traits;
 pub mod trustees;
 pub mod types;
@@ -25,8 +26,9 @@ pub mod weights;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    log::{error, info},
-    traits::{fungibles, ChangeMembers, Currency, ExistenceRequirement, Get},
+    log::{error, info, warn},
+    traits::{fungibles, ChangeMembers, Currency, ExistenceRequirement, Get, ReservableCurrency},
+    PalletId,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::OriginFor};
 
@@ -45,7 +47,10 @@ use xpallet_gateway_records::{ChainT, Withdrawal, WithdrawalLimit, WithdrawalRec
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
 use self::{
-    traits::{ProposalProvider, TotalSupply, TrusteeForChain, TrusteeInfoUpdate, TrusteeSession},
+    traits::{
+        ProposalProvider, TotalSupply, TrusteeForChain, TrusteeInfoUpdate, TrusteeProxy,
+        TrusteeSession,
+    },
     types::{
         GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, RewardInfo, ScriptInfo,
         TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo,
@@ -64,6 +69,7 @@ type Balanceof<T> = <<T as xpallet_gateway_records::Config>::Currency as Currenc
 pub mod pallet {
     use super::*;
     use frame_support::{pallet_prelude::*, traits::fungibles::Inspect, transactional};
+    use frame_system::pallet_prelude::BlockNumberFor;
 
     #[pallet::config]
     pub trait Config:
@@ -80,6 +86,8 @@ pub mod pallet {
         type Validator: Validator<Self::AccountId>;
         /// A majority of the council can excute some transactions.
         type CouncilOrigin: EnsureOrigin<Self::Origin>;
+        /// The `GatewayTrustee` proxies acting for the trustees.
+        type TrusteeProxy: TrusteeProxy<Self::AccountId>;
 
         /// Bitcoin
         /// Get btc chain info.
@@ -125,14 +133,59 @@ pub mod pallet {
         /// Get btc withdrawal proposal.
         type DogecoinWithdrawalProposal: ProposalProvider;
 
+        /// Relayer
+        /// The bond reserved by a relayer of a chain.
+        #[pallet::constant]
+        type RelayerBond: Get<Balanceof<Self>>;
+        /// The number of blocks of a relayer reward era.
+        #[pallet::constant]
+        type RelayerEraLength: Get<Self::BlockNumber>;
+        /// The id of the relayer reward pool account, which is funded by the slashed bonds
+        /// and the transfers to it, e.g. the treasury spends.
+        #[pallet::constant]
+        type RelayerPalletId: Get<PalletId>;
+        /// The number of blocks before the bond of an unregistered relayer is withdrawable,
+        /// during which the relayer can still be slashed.
+        #[pallet::constant]
+        type RelayerUnbondingPeriod: Get<Self::BlockNumber>;
+        /// The number of blocks the unregistered relayers can still relay after the upgrade
+        /// introducing the relayer registry.
+        #[pallet::constant]
+        type RelayerTransitionPeriod: Get<Self::BlockNumber>;
+        /// The maximum number of relayers rewarded in a block.
+        #[pallet::constant]
+        type MaxRelayerRewardsPerBlock: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
+    /// The storage version, `1` since the trustee proxies are `GatewayTrustee` proxies,
+    /// `2` since the relayers are registered.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let era_length = T::RelayerEraLength::get();
+            let mut weight = 0;
+            if !era_length.is_zero() && (n % era_length).is_zero() {
+                weight += Self::end_relayer_era();
+            }
+            weight.saturating_add(Self::reward_relayers(T::MaxRelayerRewardsPerBlock::get()))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::ensure_invariants()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a withdrawal.
@@ -176,6 +229,9 @@ pub mod pallet {
 
         /// Setup the trustee info.
         ///
+        /// A trustee acting by another account adds it as a `GatewayTrustee` proxy of the chain,
+        /// which is also a member of the trustee multisig of the chain.
+        ///
         /// The hot and cold public keys of the current trustee cannot be replaced at will. If they
         /// are randomly replaced, the hot and cold public keys of the current trustee before the
         /// replacement will be lost, resulting in the inability to reconstruct the `Mast` tree and
@@ -200,7 +256,6 @@ pub mod pallet {
         #[pallet::weight(< T as Config >::WeightInfo::setup_trustee())]
         pub fn setup_trustee(
             origin: OriginFor<T>,
-            proxy_account: Option<T::AccountId>,
             chain: Chain,
             about: Text,
             hot_entity: Vec<u8>,
@@ -221,11 +276,11 @@ pub mod pallet {
                 Error::<T>::ExistCurrentTrustee
             );
 
-            Self::setup_trustee_impl(who, proxy_account, chain, about, hot_entity, cold_entity)
+            Self::setup_trustee_impl(who, chain, about, hot_entity, cold_entity)
         }
 
         /// Manual execution of the election by admin.
-        #[pallet::weight(0u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::execute_trustee_election())]
         pub fn execute_trustee_election(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
             T::CouncilOrigin::try_origin(origin)
                 .map(|_| ())
@@ -239,7 +294,7 @@ pub mod pallet {
         /// Force cancel trustee transition
         ///
         /// This is called by the root or council.
-        #[pallet::weight(0u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::cancel_trustee_election())]
         pub fn cancel_trustee_election(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
             T::CouncilOrigin::try_origin(origin)
                 .map(|_| ())
@@ -256,10 +311,7 @@ pub mod pallet {
         /// The trustee will be moved into the small black room.
         ///
         /// This is called by the trustee admin and root.
-        /// # <weight>
-        /// Since this is a root call and will go into trustee election, we assume full block for now.
-        /// # </weight>
-        #[pallet::weight(0u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::move_trust_into_black_room())]
         #[transactional]
         pub fn move_trust_into_black_room(
             origin: OriginFor<T>,
@@ -300,10 +352,7 @@ pub mod pallet {
         /// Move member out small black room.
         ///
         /// This is called by the trustee admin and root.
-        /// # <weight>
-        /// Since this is a root call and will go into trustee election, we assume full block for now.
-        /// # </weight>
-        #[pallet::weight(0u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::move_trust_out_black_room())]
         pub fn move_trust_out_black_room(
             origin: OriginFor<T>,
             chain: Chain,
@@ -334,7 +383,7 @@ pub mod pallet {
         /// Mandatory trustee renewal if the current trustee is not doing anything
         ///
         /// This is called by the root.
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::force_trustee_election())]
         pub fn force_trustee_election(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
             ensure_root(origin)?;
             Self::update_transition_status(chain, false, None);
@@ -345,11 +394,10 @@ pub mod pallet {
         /// Force update trustee info
         ///
         /// This is called by the root.
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(< T as Config >::WeightInfo::force_update_trustee())]
         pub fn force_update_trustee(
             origin: OriginFor<T>,
             who: T::AccountId,
-            proxy_account: Option<T::AccountId>,
             chain: Chain,
             about: Text,
             hot_entity: Vec<u8>,
@@ -357,26 +405,22 @@ pub mod pallet {
         ) -> DispatchResult {
             ensure_root(origin)?;
 
-            Self::setup_trustee_impl(who, proxy_account, chain, about, hot_entity, cold_entity)?;
+            Self::setup_trustee_impl(who, chain, about, hot_entity, cold_entity)?;
             Ok(())
         }
 
-        /// Set trustee's proxy account
-        #[pallet::weight(< T as Config >::WeightInfo::set_trustee_proxy())]
+        /// Deprecated, the proxy of a trustee is a `GatewayTrustee` proxy of `pallet_proxy`.
+        ///
+        /// Kept to keep the indices of the following calls, it still costs the weight of a
+        /// read so that it can not be spammed for free.
+        #[pallet::weight(T::DbWeight::get().reads(1))]
         pub fn set_trustee_proxy(
             origin: OriginFor<T>,
-            proxy_account: T::AccountId,
-            chain: Chain,
+            _proxy_account: T::AccountId,
+            _chain: Chain,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
-            ensure!(
-                TrusteeIntentionPropertiesOf::<T>::contains_key(&who, chain),
-                Error::<T>::NotRegistered
-            );
-
-            Self::set_trustee_proxy_impl(&who, proxy_account, chain);
-            Ok(())
+            ensure_signed(origin)?;
+            Err(Error::<T>::DeprecatedCall.into())
         }
 
         /// Set the config of trustee information.
@@ -484,18 +528,110 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Register as a relayer of the chain by reserving `RelayerBond`.
+        ///
+        /// Only the registered relayers can push the headers and transactions of the chain.
+        #[pallet::weight(< T as Config >::WeightInfo::register_relayer())]
+        pub fn register_relayer(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                matches!(chain, Chain::Bitcoin | Chain::Dogecoin),
+                Error::<T>::NotSupportedChain
+            );
+            ensure!(
+                !RelayerBondOf::<T>::contains_key(chain, &who),
+                Error::<T>::RelayerExisted
+            );
+            ensure!(
+                !RelayerUnbonding::<T>::contains_key(chain, &who),
+                Error::<T>::RelayerUnbonding
+            );
+
+            let bond = T::RelayerBond::get();
+            <T as xpallet_gateway_records::Config>::Currency::reserve(&who, bond)?;
+            RelayerBondOf::<T>::insert(chain, &who, bond);
+
+            Self::deposit_event(Event::<T>::RelayerRegistered(who, chain, bond));
+            Ok(())
+        }
+
+        /// Unregister the relayer of the chain, the bond is withdrawable after
+        /// `RelayerUnbondingPeriod`.
+        #[pallet::weight(< T as Config >::WeightInfo::unregister_relayer())]
+        pub fn unregister_relayer(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bond = RelayerBondOf::<T>::take(chain, &who).ok_or(Error::<T>::NotRelayer)?;
+
+            let unlock_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::RelayerUnbondingPeriod::get());
+            RelayerUnbonding::<T>::insert(chain, &who, (bond, unlock_at));
+
+            Self::deposit_event(Event::<T>::RelayerUnregistered(who, chain));
+            Ok(())
+        }
+
+        /// Remove the misbehaving relayer of the chain, the bond is slashed into the relayer
+        /// reward pool.
+        ///
+        /// This is called by the council or root.
+        #[pallet::weight(< T as Config >::WeightInfo::slash_relayer())]
+        pub fn slash_relayer(
+            origin: OriginFor<T>,
+            chain: Chain,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            T::CouncilOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+
+            // the unbonding relayer is slashable as well
+            let bond = RelayerBondOf::<T>::take(chain, &who)
+                .or_else(|| RelayerUnbonding::<T>::take(chain, &who).map(|(bond, _)| bond))
+                .ok_or(Error::<T>::NotRelayer)?;
+            let (imbalance, _) =
+                <T as xpallet_gateway_records::Config>::Currency::slash_reserved(&who, bond);
+            <T as xpallet_gateway_records::Config>::Currency::resolve_creating(
+                &Self::relayer_pool_account(),
+                imbalance,
+            );
+            // the submissions of the current and the unpaid eras are not rewarded
+            let era = Self::relayer_era();
+            let count = RelayerSubmissions::<T>::take(era, &who);
+            RelayerEraSubmissions::<T>::mutate(era, |total| *total = total.saturating_sub(count));
+            if let Some(payout) = Self::relayer_payout() {
+                RelayerSubmissions::<T>::remove(payout.era, &who);
+            }
+
+            Self::deposit_event(Event::<T>::RelayerSlashed(who, chain, bond));
+            Ok(())
+        }
+
+        /// Withdraw the bond of the unregistered relayer after `RelayerUnbondingPeriod`.
+        #[pallet::weight(< T as Config >::WeightInfo::withdraw_relayer_bond())]
+        pub fn withdraw_relayer_bond(origin: OriginFor<T>, chain: Chain) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (bond, unlock_at) =
+                Self::relayer_unbonding(chain, &who).ok_or(Error::<T>::NotUnbonding)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= unlock_at,
+                Error::<T>::RelayerBondLocked
+            );
+
+            RelayerUnbonding::<T>::remove(chain, &who);
+            <T as xpallet_gateway_records::Config>::Currency::unreserve(&who, bond);
+
+            Self::deposit_event(Event::<T>::RelayerBondWithdrawn(who, chain, bond));
+            Ok(())
+        }
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// A (potential) trustee set the required properties. [who, chain, trustee_props]
-        SetTrusteeProps(
-            T::AccountId,
-            Chain,
-            GenericTrusteeIntentionProps<T::AccountId>,
-        ),
-        /// A trustee set his proxy account. [who, chain, proxy_account]
+        SetTrusteeProps(T::AccountId, Chain, GenericTrusteeIntentionProps),
+        /// Deprecated, kept to keep the indices of the following events. [who, chain, proxy_account]
         SetTrusteeProxy(T::AccountId, Chain, T::AccountId),
         /// An account set its referral_account of some chain. [who, chain, referral_account]
         ReferralBinded(T::AccountId, Chain, T::AccountId),
@@ -514,6 +650,16 @@ pub mod pallet {
         AllocNativeReward(T::AccountId, u32, Balanceof<T>),
         /// The not native asset of trustee multi_account is assigned. [who, multi_account, session_number, asset_id, total_reward]
         AllocNotNativeReward(T::AccountId, u32, T::AssetId, T::Balance),
+        /// An account registered as a relayer of the chain. [who, chain, bond]
+        RelayerRegistered(T::AccountId, Chain, Balanceof<T>),
+        /// A relayer of the chain was unregistered, the bond is unbonding. [who, chain]
+        RelayerUnregistered(T::AccountId, Chain),
+        /// The bond of an unregistered relayer was withdrawn. [who, chain, bond]
+        RelayerBondWithdrawn(T::AccountId, Chain, Balanceof<T>),
+        /// A relayer of the chain was slashed and removed. [who, chain, bond]
+        RelayerSlashed(T::AccountId, Chain, Balanceof<T>),
+        /// The relayer was rewarded for the useful submissions of the era. [who, submissions, reward]
+        RelayerRewarded(T::AccountId, u32, Balanceof<T>),
     }
 
     #[pallet::error]
@@ -570,6 +716,18 @@ pub mod pallet {
         TrusteeMembersNotEnough,
         /// Exist in current trustee
         ExistCurrentTrustee,
+        /// The account has registered as a relayer of the chain
+        RelayerExisted,
+        /// The account is not a relayer of the chain
+        NotRelayer,
+        /// The bond of the relayer of the chain is unbonding
+        RelayerUnbonding,
+        /// The account has no unbonding bond of the chain
+        NotUnbonding,
+        /// The unbonding period of the relayer bond has not passed
+        RelayerBondLocked,
+        /// The call is deprecated
+        DeprecatedCall,
     }
 
     /// The trustee multi substrate account.
@@ -655,7 +813,7 @@ pub mod pallet {
         T::AccountId,
         Twox64Concat,
         Chain,
-        GenericTrusteeIntentionProps<T::AccountId>,
+        GenericTrusteeIntentionProps,
     >;
 
     /// The account of the corresponding chain and chain address.
@@ -702,6 +860,64 @@ pub mod pallet {
     pub(crate) type PreTotalSupply<T: Config> =
         StorageMap<_, Twox64Concat, T::AssetId, T::Balance, ValueQuery>;
 
+    /// The bond of the relayer of the corresponding chain and account.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_bond_of)]
+    pub(crate) type RelayerBondOf<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Chain,
+        Blake2_128Concat,
+        T::AccountId,
+        Balanceof<T>,
+        OptionQuery,
+    >;
+
+    /// The bonds of the unregistered relayers and the blocks they are withdrawable at.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_unbonding)]
+    pub(crate) type RelayerUnbonding<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        Chain,
+        Blake2_128Concat,
+        T::AccountId,
+        (Balanceof<T>, T::BlockNumber),
+        OptionQuery,
+    >;
+
+    /// The current relayer reward era.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_era)]
+    pub(crate) type RelayerEra<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The useful submissions of the relayers by the era.
+    ///
+    /// A submission is useful if it advances the best header or processes a new transaction.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_submissions)]
+    pub(crate) type RelayerSubmissions<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// The total useful submissions of the era.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_era_submissions)]
+    pub(crate) type RelayerEraSubmissions<T: Config> =
+        StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+    /// The rewards of the ended era being paid, at most `MaxRelayerRewardsPerBlock` relayers
+    /// per block.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_payout)]
+    pub(crate) type RelayerPayout<T: Config> =
+        StorageValue<_, types::RelayerPayoutInfo<Balanceof<T>>, OptionQuery>;
+
+    /// The block until which the unregistered relayers can still relay, set by the upgrade
+    /// introducing the relayer registry.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_transition_end)]
+    pub(crate) type RelayerTransitionEnd<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub trustees: Vec<(
@@ -731,7 +947,6 @@ pub mod pallet {
                     for (who, about, hot, cold) in trustee_infos.iter() {
                         Pallet::<T>::setup_trustee_impl(
                             who.clone(),
-                            None,
                             *chain,
                             about.clone(),
                             hot.clone(),
@@ -793,7 +1008,6 @@ impl<T: Config> Pallet<T> {
 impl<T: Config> Pallet<T> {
     pub fn setup_trustee_impl(
         who: T::AccountId,
-        proxy_account: Option<T::AccountId>,
         chain: Chain,
         about: Text,
         hot_entity: Vec<u8>,
@@ -815,17 +1029,7 @@ impl<T: Config> Pallet<T> {
             _ => return Err(Error::<T>::NotSupportedChain.into()),
         };
 
-        let proxy_account = if let Some(addr) = proxy_account {
-            Some(addr)
-        } else {
-            Some(who.clone())
-        };
-
-        let props = GenericTrusteeIntentionProps::<T::AccountId>(TrusteeIntentionProps::<
-            T::AccountId,
-            Vec<u8>,
-        > {
-            proxy_account,
+        let props = GenericTrusteeIntentionProps(TrusteeIntentionProps::<Vec<u8>> {
             about,
             hot_entity: hot,
             cold_entity: cold,
@@ -840,19 +1044,6 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
-    pub fn set_trustee_proxy_impl(who: &T::AccountId, proxy_account: T::AccountId, chain: Chain) {
-        TrusteeIntentionPropertiesOf::<T>::mutate(who, chain, |t| {
-            if let Some(props) = t {
-                props.0.proxy_account = Some(proxy_account.clone());
-            }
-        });
-        Self::deposit_event(Event::<T>::SetTrusteeProxy(
-            who.clone(),
-            chain,
-            proxy_account,
-        ));
-    }
-
     pub fn ensure_not_current_trustee(chain: Chain, who: &T::AccountId) -> bool {
         match chain {
             Chain::Bitcoin => {
@@ -1045,7 +1236,7 @@ impl<T: Config> Pallet<T> {
                     .map(|(id, prop)| {
                         (
                             id,
-                            TrusteeIntentionProps::<T::AccountId, _>::try_from(prop)
+                            TrusteeIntentionProps::try_from(prop)
                                 .expect("must decode succss from storage data"),
                         )
                     })
@@ -1060,7 +1251,7 @@ impl<T: Config> Pallet<T> {
                     .map(|(id, prop)| {
                         (
                             id,
-                            TrusteeIntentionProps::<T::AccountId, _>::try_from(prop)
+                            TrusteeIntentionProps::try_from(prop)
                                 .expect("must decode succss from storage data"),
                         )
                     })
@@ -1158,21 +1349,19 @@ impl<T: Config> Pallet<T> {
         chain: Chain,
         session_info: &GenericTrusteeSessionInfo<T::AccountId, T::BlockNumber>,
     ) -> Result<T::AccountId, DispatchError> {
-        // If there is a proxy account, choose a proxy account
+        // If there is a `GatewayTrustee` proxy, choose the proxy account
         let mut acc_list: Vec<T::AccountId> = vec![];
         for acc in session_info.0.trustee_list.iter() {
-            let acc = Self::trustee_intention_props_of(&acc.0, chain)
-                .ok_or_else::<DispatchError, _>(|| {
-                    error!(
-                        target: "runtime::gateway::common",
-                        "[generate_multisig_addr] acc {:?} has not in TrusteeIntentionPropertiesOf",
-                        acc.0
-                    );
-                    Error::<T>::NotRegistered.into()
-                })?
-                .0
-                .proxy_account
-                .unwrap_or_else(|| acc.0.clone());
+            if !TrusteeIntentionPropertiesOf::<T>::contains_key(&acc.0, chain) {
+                error!(
+                    target: "runtime::gateway::common",
+                    "[generate_multisig_addr] acc {:?} has not in TrusteeIntentionPropertiesOf",
+                    acc.0
+                );
+                return Err(Error::<T>::NotRegistered.into());
+            }
+            let acc =
+                T::TrusteeProxy::trustee_proxy(&acc.0, chain).unwrap_or_else(|| acc.0.clone());
             acc_list.push(acc);
         }
 
@@ -1348,6 +1537,86 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+/// Invariants
+impl<T: Config> Pallet<T> {
+    /// Check the invariants of the trustee sessions, returns all the violated ones, see
+    /// [`Self::check_invariants`].
+    pub fn invariant_violations() -> Vec<&'static str> {
+        Self::check_invariants(u32::MAX).unwrap_or_default()
+    }
+
+    /// Check the invariants of the trustee sessions, returns all the violated ones:
+    ///
+    /// - the sessions of a chain are contiguous from 1 to `TrusteeSessionInfoLen`, the one
+    ///   after may only be left by a cancelled transition.
+    /// - the multisig account of a chain is the one of its current session.
+    ///
+    /// Returns `None` without checking if there are more than `max_items` trustee sessions.
+    pub fn check_invariants(max_items: u32) -> Option<Vec<&'static str>> {
+        let limit = (max_items as usize).saturating_add(1);
+        let sessions = TrusteeSessionInfoLen::<T>::iter_values()
+            .fold(0u32, |sessions, len| sessions.saturating_add(len));
+        if sessions > max_items
+            || TrusteeSessionInfoOf::<T>::iter_keys().take(limit).count() > max_items as usize
+        {
+            warn!(
+                target: "runtime::gateway::common",
+                "[check_invariants] More than {} trustee sessions to scan, skip",
+                max_items
+            );
+            return None;
+        }
+
+        let mut violations = Vec::new();
+
+        for (chain, len) in TrusteeSessionInfoLen::<T>::iter() {
+            if (1..=len).any(|number| Self::trustee_session_info_of(chain, number).is_none()) {
+                error!(
+                    target: "runtime::gateway::common",
+                    "[check_invariants] chain:{:?}, sessions up to {} are not contiguous",
+                    chain, len
+                );
+                violations.push("Trustee sessions are not contiguous");
+            }
+            let beyond = TrusteeSessionInfoOf::<T>::iter_key_prefix(chain)
+                .any(|number| number == 0 || number > len.saturating_add(1));
+            if beyond {
+                error!(
+                    target: "runtime::gateway::common",
+                    "[check_invariants] chain:{:?}, sessions out of 1..={}",
+                    chain,
+                    len.saturating_add(1)
+                );
+                violations.push("Trustee sessions out of TrusteeSessionInfoLen");
+            }
+
+            let multi_account =
+                Self::trustee_session_info_of(chain, len).and_then(|info| info.0.multi_account);
+            if multi_account != Self::trustee_multisig_addr(chain) {
+                error!(
+                    target: "runtime::gateway::common",
+                    "[check_invariants] chain:{:?}, session {} multisig:{:?} != {:?}",
+                    chain,
+                    len,
+                    multi_account,
+                    Self::trustee_multisig_addr(chain)
+                );
+                violations.push("TrusteeMultiSigAddr mismatches the current trustee session");
+            }
+        }
+
+        Some(violations)
+    }
+
+    /// Ensure the invariants of the trustee sessions, see [`Self::invariant_violations`].
+    pub fn ensure_invariants() -> Result<(), &'static str> {
+        match Self::invariant_violations().into_iter().next() {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Rpc Calls
 impl<T: Config> Pallet<T> {
     pub fn withdrawal_limit(
@@ -1,13 +1,14 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use super::trustee_proxy::LegacyTrusteeIntentionProps;
 use crate::{
-    Config, GenericTrusteeIntentionProps, LittleBlackHouse, TrusteeInfoConfig, TrusteeInfoConfigOf,
-    TrusteeIntentionPropertiesOf, TrusteeIntentionProps, TrusteeSigRecord, TrusteeTransitionStatus,
+    Config, LittleBlackHouse, Pallet, TrusteeInfoConfig, TrusteeInfoConfigOf, TrusteeSigRecord,
+    TrusteeTransitionStatus,
 };
 use frame_support::{
     log::info,
-    migration::{storage_key_iter, take_storage_value},
-    traits::Get,
+    migration::{put_storage_value, storage_iter, storage_key_iter, take_storage_value},
+    traits::{Get, GetStorageVersion},
     weights::Weight,
     Twox64Concat,
 };
@@ -80,25 +81,36 @@ pub fn migrate_little_black_house<T: Config>() -> Weight {
 }
 
 /// Migrate from the old trustee intention properties.
+///
+/// The properties are still in the [`LegacyTrusteeIntentionProps`] before the trustee proxies
+/// are migrated by [`trustee_proxy`](super::trustee_proxy).
 pub fn migrate_trustee_intention_properties<T: Config>() -> Weight {
-    TrusteeIntentionPropertiesOf::<T>::translate::<GenericTrusteeIntentionProps<T::AccountId>, _>(
-        |_, _, props| {
-            let hot_key = &props.0.hot_entity;
-            let cold_key = &props.0.cold_entity;
-            let hot_pubkey =
-                PublicKey::parse_slice(hot_key).expect("must be success, or panic; qed");
-            let cold_pubkey =
-                PublicKey::parse_slice(cold_key).expect("must be success, or panic; qed");
-            // Unified use of the full public key
-            Some(GenericTrusteeIntentionProps(TrusteeIntentionProps {
-                proxy_account: props.0.proxy_account,
-                about: props.0.about,
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+    let mut count = 0;
+    for (key, props) in storage_iter::<LegacyTrusteeIntentionProps<T::AccountId>>(
+        b"XGatewayCommon",
+        b"TrusteeIntentionPropertiesOf",
+    ) {
+        let hot_pubkey =
+            PublicKey::parse_slice(&props.hot_entity).expect("must be success, or panic; qed");
+        let cold_pubkey =
+            PublicKey::parse_slice(&props.cold_entity).expect("must be success, or panic; qed");
+        // Unified use of the full public key
+        put_storage_value(
+            b"XGatewayCommon",
+            b"TrusteeIntentionPropertiesOf",
+            &key,
+            LegacyTrusteeIntentionProps {
+                proxy_account: props.proxy_account,
+                about: props.about,
                 hot_entity: hot_pubkey.serialize().to_vec(),
                 cold_entity: cold_pubkey.serialize().to_vec(),
-            }))
-        },
-    );
-    let count = TrusteeIntentionPropertiesOf::<T>::iter_values().count();
+            },
+        );
+        count += 1;
+    }
     info!(
         target: "runtime::gateway::common",
         "✅ Migration for trustee_intention_properties done. Migrated count -> {}.",
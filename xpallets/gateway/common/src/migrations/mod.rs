@@ -3,3 +3,5 @@
 //! All migrations of this pallet.
 
 pub mod chains;
+pub mod relayer_registry;
+pub mod trustee_proxy;
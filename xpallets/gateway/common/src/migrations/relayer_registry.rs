@@ -0,0 +1,36 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    log::info,
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use sp_runtime::traits::Saturating;
+
+use crate::{Config, Pallet, RelayerTransitionEnd};
+
+/// Start the transition period of the relayer registry, during which the relayers before the
+/// registry can still relay without the bond.
+///
+/// This runs after the trustee proxy migration, which puts the storage version `1`.
+pub fn apply<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 2 {
+        info!(
+            target: "runtime::gateway::common",
+            "✅ The relayer registry has been migrated, skip."
+        );
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+
+    let end =
+        frame_system::Pallet::<T>::block_number().saturating_add(T::RelayerTransitionPeriod::get());
+    RelayerTransitionEnd::<T>::put(end);
+    StorageVersion::new(2).put::<Pallet<T>>();
+
+    info!(
+        target: "runtime::gateway::common",
+        "✅ Migration for the relayer registry done, transition until block {:?}.",
+        end
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(2, 2)
+}
@@ -0,0 +1,117 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    log::{error, info},
+    storage,
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use sp_std::prelude::*;
+
+use sherpax_primitives::Text;
+use xp_assets_registrar::Chain;
+
+use crate::{
+    traits::TrusteeProxy, Config, GenericTrusteeIntentionProps, Pallet,
+    TrusteeIntentionPropertiesOf, TrusteeIntentionProps,
+};
+
+/// The trustee intention properties with the proxy account, i.e. the storage version `0`.
+#[derive(Encode, Decode)]
+pub struct LegacyTrusteeIntentionProps<AccountId> {
+    pub proxy_account: Option<AccountId>,
+    pub about: Text,
+    pub hot_entity: Vec<u8>,
+    pub cold_entity: Vec<u8>,
+}
+
+/// Return the proxy accounts of the trustees to be migrated, i.e. `[(who, chain, proxy)]`.
+///
+/// A trustee being its own proxy has no proxy.
+pub fn legacy_proxies<T: Config>() -> Vec<(T::AccountId, Chain, T::AccountId)> {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return Vec::new();
+    }
+    TrusteeIntentionPropertiesOf::<T>::iter_keys()
+        .filter_map(|(who, chain)| {
+            let key = TrusteeIntentionPropertiesOf::<T>::hashed_key_for(&who, chain);
+            let props = storage::unhashed::get::<LegacyTrusteeIntentionProps<T::AccountId>>(&key)?;
+            match props.proxy_account {
+                Some(proxy) if proxy != who => Some((who, chain, proxy)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Ensure the proxy accounts returned by [`legacy_proxies`] before the migration are the
+/// `GatewayTrustee` proxies.
+pub fn ensure_proxies<T: Config>(
+    proxies: &[(T::AccountId, Chain, T::AccountId)],
+) -> Result<(), &'static str> {
+    for (who, chain, proxy) in proxies {
+        if T::TrusteeProxy::trustee_proxy(who, *chain).as_ref() != Some(proxy) {
+            error!(
+                target: "runtime::gateway::common",
+                "[migration] the trustee proxy is lost, who:{:?}, chain:{:?}, proxy:{:?}",
+                who, chain, proxy
+            );
+            return Err("The trustee proxy is lost");
+        }
+    }
+    Ok(())
+}
+
+/// Move the proxy accounts of the trustee intention properties into the `GatewayTrustee`
+/// proxies of the chains without the deposits, so that the trustee multisig addresses are kept.
+pub fn apply<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        info!(
+            target: "runtime::gateway::common",
+            "✅ The trustee proxies have been migrated, skip."
+        );
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+
+    info!(
+        target: "runtime::gateway::common",
+        "✅ Running migration for the trustee proxies of gateway common..."
+    );
+    let mut count = 0u64;
+    let mut proxies = Vec::new();
+    TrusteeIntentionPropertiesOf::<T>::translate::<LegacyTrusteeIntentionProps<T::AccountId>, _>(
+        |who, chain, props| {
+            count += 1;
+            match props.proxy_account {
+                Some(proxy) if proxy != who => proxies.push((who, chain, proxy)),
+                _ => {}
+            }
+            Some(GenericTrusteeIntentionProps(TrusteeIntentionProps {
+                about: props.about,
+                hot_entity: props.hot_entity,
+                cold_entity: props.cold_entity,
+            }))
+        },
+    );
+
+    let proxy_count = proxies.len() as u64;
+    for (who, chain, proxy) in proxies {
+        if let Err(err) = T::TrusteeProxy::insert_trustee_proxy(&who, chain, &proxy) {
+            error!(
+                target: "runtime::gateway::common",
+                "[migration] fail to insert the trustee proxy, who:{:?}, chain:{:?}, proxy:{:?}, err:{:?}",
+                who, chain, proxy, err
+            );
+        }
+    }
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    info!(
+        target: "runtime::gateway::common",
+        "✅ Migration for {} trustee properties done, {} proxies inserted.",
+        count, proxy_count
+    );
+    <T as frame_system::Config>::DbWeight::get()
+        .reads_writes(count + proxy_count, count + proxy_count + 1)
+}
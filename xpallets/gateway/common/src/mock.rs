@@ -2,12 +2,13 @@
 
 use std::cmp::max;
 use std::convert::TryInto;
-use std::{cell::RefCell, convert::TryFrom, time::Duration};
+use std::{cell::RefCell, collections::BTreeMap, convert::TryFrom, time::Duration};
 
 use codec::{Decode, Encode};
 use frame_support::{
     parameter_types, sp_io,
     traits::{ChangeMembers, GenesisBuild, LockIdentifier, UnixTime},
+    PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned};
 use light_bitcoin::keys::{Address, Public};
@@ -33,7 +34,7 @@ use crate::traits::TotalSupply;
 use crate::utils::{two_thirds_unsafe, MAX_TAPROOT_NODES};
 use crate::{
     self as xpallet_gateway_common,
-    traits::TrusteeForChain,
+    traits::{TrusteeForChain, TrusteeProxy},
     trustees::{
         self,
         bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
@@ -206,14 +207,17 @@ impl pallet_assets::Config for Test {
     type AssetId = AssetId;
     type Currency = Balances;
     type ForceOrigin = EnsureRoot<AccountId>;
+    type FeeRateOrigin = EnsureRoot<AccountId>;
     type AssetDeposit = AssetDeposit;
     type AssetAccountDeposit = ();
     type MetadataDepositBase = MetadataDepositBase;
     type MetadataDepositPerByte = MetadataDepositPerByte;
     type ApprovalDeposit = ApprovalDeposit;
     type StringLimit = StringLimit;
-    type Freezer = XGatewayRecords;
+    type Freezer = ();
     type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
     type WeightInfo = pallet_assets::weights::SubstrateWeight<Test>;
 }
 
@@ -258,9 +262,29 @@ impl xpallet_gateway_bitcoin::Config for Test {
     type TrusteeInfoUpdate = ();
     type ReferralBinding = ();
     type AddressBinding = ();
+    type RelayerRegistry = ();
     type WeightInfo = ();
 }
 
+thread_local! {
+    pub static TRUSTEE_PROXIES: RefCell<BTreeMap<(AccountId, Chain), AccountId>> = RefCell::new(BTreeMap::new());
+}
+pub struct MockTrusteeProxy;
+impl TrusteeProxy<AccountId> for MockTrusteeProxy {
+    fn trustee_proxy(who: &AccountId, chain: Chain) -> Option<AccountId> {
+        TRUSTEE_PROXIES.with(|proxies| proxies.borrow().get(&(who.clone(), chain)).cloned())
+    }
+
+    fn insert_trustee_proxy(who: &AccountId, chain: Chain, proxy: &AccountId) -> DispatchResult {
+        TRUSTEE_PROXIES.with(|proxies| {
+            proxies
+                .borrow_mut()
+                .insert((who.clone(), chain), proxy.clone())
+        });
+        Ok(())
+    }
+}
+
 pub struct MultisigAddr;
 impl MultisigAddressFor<AccountId> for MultisigAddr {
     fn calc_multisig(who: &[AccountId], threshold: u16) -> AccountId {
@@ -337,10 +361,7 @@ impl<T: xpallet_gateway_bitcoin::Config>
     }
 
     fn generate_trustee_session_info(
-        props: Vec<(
-            T::AccountId,
-            TrusteeIntentionProps<T::AccountId, BtcTrusteeType>,
-        )>,
+        props: Vec<(T::AccountId, TrusteeIntentionProps<BtcTrusteeType>)>,
         config: TrusteeInfoConfig,
     ) -> Result<
         (
@@ -351,7 +372,7 @@ impl<T: xpallet_gateway_bitcoin::Config>
     > {
         let (trustees, props_info): (
             Vec<T::AccountId>,
-            Vec<TrusteeIntentionProps<T::AccountId, BtcTrusteeType>>,
+            Vec<TrusteeIntentionProps<BtcTrusteeType>>,
         ) = props.into_iter().unzip();
 
         let (hot_keys, cold_keys): (Vec<Public>, Vec<Public>) = props_info
@@ -461,11 +482,21 @@ impl<T: xpallet_gateway_bitcoin::Config>
     }
 }
 
+parameter_types! {
+    pub const RelayerBond: Balance = 10;
+    pub const RelayerEraLength: BlockNumber = 10;
+    pub const RelayerPalletId: PalletId = PalletId(*b"pcx/rlyr");
+    pub const RelayerUnbondingPeriod: BlockNumber = 10;
+    pub const RelayerTransitionPeriod: BlockNumber = 10;
+    pub const MaxRelayerRewardsPerBlock: u32 = 1;
+}
+
 impl crate::Config for Test {
     type Event = ();
     type Validator = AlwaysValidator;
     type DetermineMultisigAddress = MultisigAddr;
     type CouncilOrigin = EnsureSigned<AccountId>;
+    type TrusteeProxy = MockTrusteeProxy;
     type Bitcoin = MockBitcoin<Test>;
     type BitcoinTrustee = MockBitcoin<Test>;
     type BitcoinTrusteeSessionProvider = trustees::bitcoin::BtcTrusteeSessionManager<Test>;
@@ -476,6 +507,12 @@ impl crate::Config for Test {
     type DogecoinTrusteeSessionProvider = ();
     type DogecoinTotalSupply = ();
     type DogecoinWithdrawalProposal = ();
+    type RelayerBond = RelayerBond;
+    type RelayerEraLength = RelayerEraLength;
+    type RelayerPalletId = RelayerPalletId;
+    type RelayerUnbondingPeriod = RelayerUnbondingPeriod;
+    type RelayerTransitionPeriod = RelayerTransitionPeriod;
+    type MaxRelayerRewardsPerBlock = MaxRelayerRewardsPerBlock;
     type WeightInfo = ();
 }
 
@@ -0,0 +1,127 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    log::info,
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    Perbill,
+};
+
+use crate::traits::RelayerRegistry;
+use crate::types::RelayerPayoutInfo;
+use crate::{
+    Config, Error, Event, Pallet, RelayerBondOf, RelayerEra, RelayerEraSubmissions, RelayerPayout,
+    RelayerSubmissions,
+};
+use xp_assets_registrar::Chain;
+
+type CurrencyOf<T> = <T as xpallet_gateway_records::Config>::Currency;
+
+impl<T: Config> RelayerRegistry<T::AccountId> for Pallet<T> {
+    fn ensure_relayer(chain: Chain, who: &T::AccountId) -> DispatchResult {
+        if RelayerBondOf::<T>::contains_key(chain, who) {
+            return Ok(());
+        }
+        // the relayers before the registry keep relaying until the end of the transition
+        let in_transition = Self::relayer_transition_end()
+            .map_or(false, |end| frame_system::Pallet::<T>::block_number() < end);
+        ensure!(in_transition, Error::<T>::NotRelayer);
+        Ok(())
+    }
+
+    fn note_submission(chain: Chain, who: &T::AccountId) {
+        // only the bonded relayers share the rewards
+        if !RelayerBondOf::<T>::contains_key(chain, who) {
+            return;
+        }
+        let era = Self::relayer_era();
+        RelayerSubmissions::<T>::mutate(era, who, |count| *count = count.saturating_add(1));
+        RelayerEraSubmissions::<T>::mutate(era, |total| *total = total.saturating_add(1));
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_register(chain: Chain, who: &T::AccountId) {
+        RelayerBondOf::<T>::insert(chain, who, crate::Balanceof::<T>::zero());
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The account of the relayer reward pool.
+    pub fn relayer_pool_account() -> T::AccountId {
+        T::RelayerPalletId::get().into_account()
+    }
+
+    /// End the current era, the free balance of the reward pool is paid to the relayers in
+    /// proportion to their useful submissions of the era.
+    ///
+    /// The era is extended if the rewards of the last era are still being paid.
+    pub(crate) fn end_relayer_era() -> Weight {
+        let db_weight = T::DbWeight::get();
+        if Self::relayer_payout().is_some() {
+            return db_weight.reads(1);
+        }
+
+        let era = Self::relayer_era();
+        RelayerEra::<T>::put(era.saturating_add(1));
+        let submissions = RelayerEraSubmissions::<T>::take(era);
+        if submissions.is_zero() {
+            return db_weight.reads_writes(3, 2);
+        }
+
+        let pool_account = Self::relayer_pool_account();
+        // keep the pool account alive
+        let reward = CurrencyOf::<T>::free_balance(&pool_account)
+            .saturating_sub(CurrencyOf::<T>::minimum_balance());
+        RelayerPayout::<T>::put(RelayerPayoutInfo {
+            era,
+            submissions,
+            reward,
+        });
+        db_weight.reads_writes(4, 3)
+    }
+
+    /// Pay the rewards of the ended era to at most `max` relayers.
+    pub(crate) fn reward_relayers(max: u32) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let payout = match Self::relayer_payout() {
+            Some(payout) => payout,
+            None => return db_weight.reads(1),
+        };
+
+        let pool_account = Self::relayer_pool_account();
+        let mut paid = 0u32;
+        let mut weight = db_weight.reads(1);
+        for (relayer, count) in RelayerSubmissions::<T>::drain_prefix(payout.era).take(max as usize)
+        {
+            paid += 1;
+            weight = weight.saturating_add(db_weight.reads_writes(1, 1));
+            let reward = Perbill::from_rational(count, payout.submissions).mul_floor(payout.reward);
+            if reward.is_zero() {
+                continue;
+            }
+            weight = weight.saturating_add(db_weight.reads_writes(2, 2));
+            match CurrencyOf::<T>::transfer(
+                &pool_account,
+                &relayer,
+                reward,
+                ExistenceRequirement::KeepAlive,
+            ) {
+                Ok(()) => Self::deposit_event(Event::<T>::RelayerRewarded(relayer, count, reward)),
+                Err(err) => info!(
+                    target: "runtime::gateway::common",
+                    "[reward_relayers] Reward {:?} failed, error:{:?}", relayer, err
+                ),
+            }
+        }
+        if paid < max {
+            RelayerPayout::<T>::kill();
+            weight = weight.saturating_add(db_weight.writes(1));
+        }
+        weight
+    }
+}
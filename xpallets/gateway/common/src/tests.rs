@@ -1,12 +1,27 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use crate::{
-    mock::{bob, charlie, dave, Assets, ExtBuilder, Test, XGatewayCommon, XGatewayRecords},
-    Pallet, TrusteeSessionInfoLen, TrusteeSessionInfoOf, TrusteeSigRecord,
+    migrations::{
+        relayer_registry,
+        trustee_proxy::{self, LegacyTrusteeIntentionProps},
+    },
+    mock::{
+        alice, bob, charlie, dave, Assets, Balances, ExtBuilder, MockTrusteeProxy, MultisigAddr,
+        Origin, Test, XGatewayCommon, XGatewayRecords,
+    },
+    traits::{RelayerRegistry, TrusteeProxy, TrusteeSession},
+    trustees::bitcoin::BtcTrusteeSessionManager,
+    Error, Pallet, TrusteeIntentionPropertiesOf, TrusteeMultiSigAddr, TrusteeSessionInfoLen,
+    TrusteeSessionInfoOf, TrusteeSigRecord,
 };
-use frame_support::assert_ok;
+use frame_support::{
+    assert_noop, assert_ok, storage,
+    traits::{Currency, GetStorageVersion, Hooks, ReservableCurrency, StorageVersion},
+};
+use sp_runtime::AccountId32;
 use xp_assets_registrar::Chain;
 use xp_protocol::X_BTC;
+use xpallet_support::traits::MultisigAddressFor;
 
 #[test]
 fn test_do_trustee_election() {
@@ -62,3 +77,360 @@ fn test_claim_not_native_asset_reward() {
         assert_eq!(Assets::balance(X_BTC, &charlie()), 1);
     });
 }
+
+#[test]
+fn test_trustee_session_invariants() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(XGatewayCommon::ensure_invariants());
+        assert_ok!(XGatewayCommon::do_trustee_election(Chain::Bitcoin));
+        assert_eq!(XGatewayCommon::invariant_violations(), Vec::<&str>::new());
+        // too many sessions to scan
+        assert_eq!(XGatewayCommon::check_invariants(0), None);
+        assert_eq!(XGatewayCommon::check_invariants(10), Some(vec![]));
+
+        TrusteeMultiSigAddr::<Test>::insert(Chain::Bitcoin, bob());
+        assert_eq!(
+            XGatewayCommon::ensure_invariants(),
+            Err("TrusteeMultiSigAddr mismatches the current trustee session")
+        );
+
+        let session = TrusteeSessionInfoOf::<Test>::take(Chain::Bitcoin, 1).unwrap();
+        TrusteeSessionInfoOf::<Test>::insert(Chain::Bitcoin, 3, session);
+        assert_eq!(
+            XGatewayCommon::invariant_violations(),
+            vec![
+                "Trustee sessions are not contiguous",
+                "Trustee sessions out of TrusteeSessionInfoLen",
+                "TrusteeMultiSigAddr mismatches the current trustee session",
+            ]
+        );
+    })
+}
+
+#[test]
+fn test_relayer_registry() {
+    ExtBuilder::default().build().execute_with(|| {
+        Balances::make_free_balance_be(&bob(), 100);
+
+        assert_noop!(
+            XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()),
+            Error::<Test>::NotRelayer
+        );
+        assert_noop!(
+            XGatewayCommon::register_relayer(Origin::signed(bob()), Chain::ChainX),
+            Error::<Test>::NotSupportedChain
+        );
+        assert_ok!(XGatewayCommon::register_relayer(
+            Origin::signed(bob()),
+            Chain::Bitcoin
+        ));
+        assert_noop!(
+            XGatewayCommon::register_relayer(Origin::signed(bob()), Chain::Bitcoin),
+            Error::<Test>::RelayerExisted
+        );
+        assert_eq!(Balances::reserved_balance(&bob()), 10);
+        assert_ok!(XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()));
+        assert_noop!(
+            XGatewayCommon::ensure_relayer(Chain::Dogecoin, &bob()),
+            Error::<Test>::NotRelayer
+        );
+
+        // the bond is unbonding after the unregistration
+        frame_system::Pallet::<Test>::set_block_number(5);
+        assert_ok!(XGatewayCommon::unregister_relayer(
+            Origin::signed(bob()),
+            Chain::Bitcoin
+        ));
+        assert_eq!(
+            XGatewayCommon::relayer_unbonding(Chain::Bitcoin, bob()),
+            Some((10, 15))
+        );
+        assert_eq!(Balances::reserved_balance(&bob()), 10);
+        assert_noop!(
+            XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()),
+            Error::<Test>::NotRelayer
+        );
+        assert_noop!(
+            XGatewayCommon::unregister_relayer(Origin::signed(bob()), Chain::Bitcoin),
+            Error::<Test>::NotRelayer
+        );
+        assert_noop!(
+            XGatewayCommon::register_relayer(Origin::signed(bob()), Chain::Bitcoin),
+            Error::<Test>::RelayerUnbonding
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(14);
+        assert_noop!(
+            XGatewayCommon::withdraw_relayer_bond(Origin::signed(bob()), Chain::Bitcoin),
+            Error::<Test>::RelayerBondLocked
+        );
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(XGatewayCommon::withdraw_relayer_bond(
+            Origin::signed(bob()),
+            Chain::Bitcoin
+        ));
+        assert_eq!(Balances::reserved_balance(&bob()), 0);
+        assert_noop!(
+            XGatewayCommon::withdraw_relayer_bond(Origin::signed(bob()), Chain::Bitcoin),
+            Error::<Test>::NotUnbonding
+        );
+    })
+}
+
+#[test]
+fn test_slash_unbonding_relayer() {
+    ExtBuilder::default().build().execute_with(|| {
+        let pool = XGatewayCommon::relayer_pool_account();
+        Balances::make_free_balance_be(&bob(), 100);
+        assert_ok!(XGatewayCommon::register_relayer(
+            Origin::signed(bob()),
+            Chain::Bitcoin
+        ));
+        assert_ok!(XGatewayCommon::unregister_relayer(
+            Origin::signed(bob()),
+            Chain::Bitcoin
+        ));
+
+        assert_ok!(XGatewayCommon::slash_relayer(
+            Origin::root(),
+            Chain::Bitcoin,
+            bob()
+        ));
+        assert_eq!(Balances::total_balance(&bob()), 90);
+        assert_eq!(Balances::free_balance(&pool), 10);
+        assert!(XGatewayCommon::relayer_unbonding(Chain::Bitcoin, bob()).is_none());
+    })
+}
+
+#[test]
+fn test_relayer_transition() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()),
+            Error::<Test>::NotRelayer
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(5);
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        relayer_registry::apply::<Test>();
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 2);
+        assert_eq!(XGatewayCommon::relayer_transition_end(), Some(15));
+
+        // the relayers before the registry relay without rewards during the transition
+        assert_ok!(XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()));
+        XGatewayCommon::note_submission(Chain::Bitcoin, &bob());
+        assert_eq!(XGatewayCommon::relayer_submissions(0, bob()), 0);
+
+        // the migration is skipped once applied
+        frame_system::Pallet::<Test>::set_block_number(10);
+        relayer_registry::apply::<Test>();
+        assert_eq!(XGatewayCommon::relayer_transition_end(), Some(15));
+
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_noop!(
+            XGatewayCommon::ensure_relayer(Chain::Bitcoin, &bob()),
+            Error::<Test>::NotRelayer
+        );
+    })
+}
+
+#[test]
+fn test_relayer_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        let pool = XGatewayCommon::relayer_pool_account();
+        Balances::make_free_balance_be(&pool, 90);
+        for relayer in [bob(), charlie(), dave()] {
+            Balances::make_free_balance_be(&relayer, 100);
+            assert_ok!(XGatewayCommon::register_relayer(
+                Origin::signed(relayer),
+                Chain::Bitcoin
+            ));
+        }
+
+        // the bond of the slashed relayer goes to the reward pool
+        XGatewayCommon::note_submission(Chain::Bitcoin, &dave());
+        assert_ok!(XGatewayCommon::slash_relayer(
+            Origin::root(),
+            Chain::Bitcoin,
+            dave()
+        ));
+        assert_eq!(Balances::total_balance(&dave()), 90);
+        assert_eq!(Balances::free_balance(&pool), 100);
+
+        XGatewayCommon::note_submission(Chain::Bitcoin, &bob());
+        XGatewayCommon::note_submission(Chain::Bitcoin, &bob());
+        XGatewayCommon::note_submission(Chain::Bitcoin, &bob());
+        XGatewayCommon::note_submission(Chain::Bitcoin, &charlie());
+        assert_eq!(XGatewayCommon::relayer_submissions(0, bob()), 3);
+        assert_eq!(XGatewayCommon::relayer_era_submissions(0), 4);
+
+        // not the end of the era
+        XGatewayCommon::on_initialize(9);
+        assert_eq!(Balances::free_balance(&bob()), 90);
+
+        // at most one relayer is rewarded per block in the mock
+        XGatewayCommon::on_initialize(10);
+        assert_eq!(XGatewayCommon::relayer_era(), 1);
+        assert_ne!(
+            Balances::free_balance(&bob()) == 90,
+            Balances::free_balance(&charlie()) == 90
+        );
+        // the submissions of the new era are rewarded in the next payout
+        XGatewayCommon::note_submission(Chain::Bitcoin, &charlie());
+        XGatewayCommon::on_initialize(11);
+        assert_eq!(Balances::free_balance(&bob()), 165);
+        assert_eq!(Balances::free_balance(&charlie()), 115);
+        assert_eq!(Balances::free_balance(&pool), 0);
+        assert!(XGatewayCommon::relayer_payout().is_some());
+        XGatewayCommon::on_initialize(12);
+        assert!(XGatewayCommon::relayer_payout().is_none());
+        assert_eq!(XGatewayCommon::relayer_submissions(0, bob()), 0);
+        assert_eq!(XGatewayCommon::relayer_submissions(1, charlie()), 1);
+
+        // the era is extended while the payout is pending
+        Balances::make_free_balance_be(&pool, 100);
+        XGatewayCommon::note_submission(Chain::Bitcoin, &bob());
+        XGatewayCommon::on_initialize(20);
+        assert_eq!(XGatewayCommon::relayer_era(), 2);
+        XGatewayCommon::on_initialize(30);
+        assert_eq!(XGatewayCommon::relayer_era(), 2);
+        XGatewayCommon::on_initialize(31);
+        XGatewayCommon::on_initialize(32);
+        assert_eq!(XGatewayCommon::relayer_era(), 2);
+        assert_eq!(Balances::free_balance(&pool), 0);
+        assert_eq!(Balances::free_balance(&bob()), 215);
+        assert_eq!(Balances::free_balance(&charlie()), 165);
+
+        // no submissions in the new era
+        Balances::make_free_balance_be(&pool, 100);
+        XGatewayCommon::on_initialize(40);
+        assert_eq!(XGatewayCommon::relayer_era(), 3);
+        assert_eq!(Balances::free_balance(&pool), 100);
+        assert!(XGatewayCommon::relayer_payout().is_none());
+    })
+}
+
+#[test]
+fn test_trustee_proxy_in_multisig() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(XGatewayCommon::do_trustee_election(Chain::Bitcoin));
+        let session = XGatewayCommon::trustee_session_info_of(Chain::Bitcoin, 1).unwrap();
+        assert_noop!(
+            XGatewayCommon::generate_multisig_addr(Chain::Bitcoin, &session),
+            Error::<Test>::DuplicatedMultiAddress
+        );
+        assert_eq!(
+            BtcTrusteeSessionManager::<Test>::current_proxy_account(),
+            Ok(vec![])
+        );
+
+        let proxy = AccountId32::new([9; 32]);
+        // the proxy of another chain is not a member
+        assert_ok!(MockTrusteeProxy::insert_trustee_proxy(
+            &alice(),
+            Chain::Dogecoin,
+            &proxy
+        ));
+        assert_noop!(
+            XGatewayCommon::generate_multisig_addr(Chain::Bitcoin, &session),
+            Error::<Test>::DuplicatedMultiAddress
+        );
+        assert_ok!(MockTrusteeProxy::insert_trustee_proxy(
+            &alice(),
+            Chain::Bitcoin,
+            &proxy
+        ));
+        let members = session
+            .0
+            .trustee_list
+            .iter()
+            .map(|(who, _)| {
+                if who == &alice() {
+                    proxy.clone()
+                } else {
+                    who.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            XGatewayCommon::generate_multisig_addr(Chain::Bitcoin, &session),
+            Ok(MultisigAddr::calc_multisig(&members, session.0.threshold))
+        );
+        assert_eq!(
+            BtcTrusteeSessionManager::<Test>::current_proxy_account(),
+            Ok(vec![proxy])
+        );
+    })
+}
+
+#[test]
+fn test_deprecated_set_trustee_proxy() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            XGatewayCommon::set_trustee_proxy(Origin::signed(alice()), bob(), Chain::Bitcoin),
+            Error::<Test>::DeprecatedCall
+        );
+    })
+}
+
+#[test]
+fn test_migrate_trustee_proxy() {
+    ExtBuilder::default().build().execute_with(|| {
+        StorageVersion::new(0).put::<Pallet<Test>>();
+        let proxy = AccountId32::new([9; 32]);
+        let doge_proxy = AccountId32::new([10; 32]);
+        for (who, chain, proxy_account) in [
+            (alice(), Chain::Bitcoin, Some(proxy.clone())),
+            (alice(), Chain::Dogecoin, Some(doge_proxy.clone())),
+            (bob(), Chain::Bitcoin, Some(bob())),
+        ] {
+            let props = XGatewayCommon::trustee_intention_props_of(&who, Chain::Bitcoin).unwrap();
+            storage::unhashed::put(
+                &TrusteeIntentionPropertiesOf::<Test>::hashed_key_for(&who, chain),
+                &LegacyTrusteeIntentionProps {
+                    proxy_account,
+                    about: b"about".to_vec(),
+                    hot_entity: props.0.hot_entity,
+                    cold_entity: props.0.cold_entity,
+                },
+            );
+        }
+
+        let mut proxies = trustee_proxy::legacy_proxies::<Test>();
+        proxies.sort();
+        let mut expected = vec![
+            (alice(), Chain::Bitcoin, proxy.clone()),
+            (alice(), Chain::Dogecoin, doge_proxy.clone()),
+        ];
+        expected.sort();
+        assert_eq!(proxies, expected);
+        assert!(trustee_proxy::ensure_proxies::<Test>(&proxies).is_err());
+
+        trustee_proxy::apply::<Test>();
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 1);
+        assert_ok!(trustee_proxy::ensure_proxies::<Test>(&proxies));
+        // one proxy per chain
+        assert_eq!(
+            MockTrusteeProxy::trustee_proxy(&alice(), Chain::Bitcoin),
+            Some(proxy)
+        );
+        assert_eq!(
+            MockTrusteeProxy::trustee_proxy(&alice(), Chain::Dogecoin),
+            Some(doge_proxy)
+        );
+        assert_eq!(
+            MockTrusteeProxy::trustee_proxy(&bob(), Chain::Bitcoin),
+            None
+        );
+        let props = XGatewayCommon::trustee_intention_props_of(&alice(), Chain::Bitcoin).unwrap();
+        assert_eq!(props.0.about, b"about".to_vec());
+
+        // the migrated properties are not migrated again.
+        assert!(trustee_proxy::legacy_proxies::<Test>().is_empty());
+        trustee_proxy::apply::<Test>();
+        assert_eq!(
+            XGatewayCommon::trustee_intention_props_of(&alice(), Chain::Bitcoin),
+            Some(props)
+        );
+    })
+}
@@ -49,7 +49,7 @@ pub trait TrusteeForChain<
     fn check_trustee_entity(raw_addr: &[u8]) -> Result<TrusteeEntity, DispatchError>;
 
     fn generate_trustee_session_info(
-        props: Vec<(AccountId, TrusteeIntentionProps<AccountId, TrusteeEntity>)>,
+        props: Vec<(AccountId, TrusteeIntentionProps<TrusteeEntity>)>,
         config: TrusteeInfoConfig,
     ) -> Result<
         (
@@ -68,7 +68,7 @@ impl<AccountId, BlockNumber, TrusteeEntity: BytesLike, TrusteeAddress: BytesLike
     }
 
     fn generate_trustee_session_info(
-        _: Vec<(AccountId, TrusteeIntentionProps<AccountId, TrusteeEntity>)>,
+        _: Vec<(AccountId, TrusteeIntentionProps<TrusteeEntity>)>,
         _: TrusteeInfoConfig,
     ) -> Result<
         (
@@ -173,3 +173,42 @@ impl<AccountId, Address: Into<Vec<u8>>> AddressBinding<AccountId, Address> for (
         None
     }
 }
+
+pub trait RelayerRegistry<AccountId> {
+    /// Ensure the account has bonded as a relayer of the chain.
+    fn ensure_relayer(chain: Chain, who: &AccountId) -> DispatchResult;
+    /// Record a useful submission of the relayer, which is rewarded at the end of the era.
+    fn note_submission(chain: Chain, who: &AccountId);
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_register(chain: Chain, who: &AccountId);
+}
+
+impl<AccountId> RelayerRegistry<AccountId> for () {
+    fn ensure_relayer(_: Chain, _: &AccountId) -> DispatchResult {
+        Ok(())
+    }
+
+    fn note_submission(_: Chain, _: &AccountId) {}
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_register(_: Chain, _: &AccountId) {}
+}
+
+pub trait TrusteeProxy<AccountId> {
+    /// Return the `GatewayTrustee` proxy acting for the trustee of the chain, if any.
+    fn trustee_proxy(who: &AccountId, chain: Chain) -> Option<AccountId>;
+    /// Insert a `GatewayTrustee` proxy of the chain for the trustee without reserving the
+    /// deposit, used by the migration of the proxy accounts.
+    fn insert_trustee_proxy(who: &AccountId, chain: Chain, proxy: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> TrusteeProxy<AccountId> for () {
+    fn trustee_proxy(_: &AccountId, _: Chain) -> Option<AccountId> {
+        None
+    }
+
+    fn insert_trustee_proxy(_: &AccountId, _: Chain, _: &AccountId) -> DispatchResult {
+        Err("NoProxy".into())
+    }
+}
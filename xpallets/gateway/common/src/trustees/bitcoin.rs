@@ -17,7 +17,7 @@ use xp_assets_registrar::Chain;
 pub type BtcAddress = Vec<u8>;
 pub type BtcTrusteeSessionInfo<AccountId, BlockNumber> =
     TrusteeSessionInfo<AccountId, BlockNumber, BtcTrusteeAddrInfo>;
-pub type BtcTrusteeIntentionProps<AccountId> = TrusteeIntentionProps<AccountId, BtcTrusteeType>;
+pub type BtcTrusteeIntentionProps = TrusteeIntentionProps<BtcTrusteeType>;
 pub type BtcTrusteeSessionManager<T> = TrusteeSessionManager<T, BtcTrusteeAddrInfo>;
 pub type BtcTrusteeMultisig<T> = TrusteeMultisigProvider<T, BtcTrusteeType>;
 
@@ -17,7 +17,7 @@ use xp_assets_registrar::Chain;
 pub type DogeAddress = Vec<u8>;
 pub type DogeTrusteeSessionInfo<AccountId, BlockNumber> =
     TrusteeSessionInfo<AccountId, BlockNumber, DogeTrusteeAddrInfo>;
-pub type DogeTrusteeIntentionProps<AccountId> = TrusteeIntentionProps<AccountId, DogeTrusteeType>;
+pub type DogeTrusteeIntentionProps = TrusteeIntentionProps<DogeTrusteeType>;
 pub type DogeTrusteeSessionManager<T> = TrusteeSessionManager<T, DogeTrusteeAddrInfo>;
 pub type DogeTrusteeMultisig<T> = TrusteeMultisigProvider<T, DogeTrusteeType>;
 
@@ -21,7 +21,7 @@ use xp_assets_registrar::Chain;
 use xpallet_support::traits::MultiSig;
 
 use crate::{
-    traits::{BytesLike, ChainProvider, TrusteeInfoUpdate, TrusteeSession},
+    traits::{BytesLike, ChainProvider, TrusteeInfoUpdate, TrusteeProxy, TrusteeSession},
     types::TrusteeSessionInfo,
     CheckedDiv, Config, Error, Event, Pallet, PreTotalSupply, SaturatedConversion, Saturating,
     TrusteeSessionInfoOf, TrusteeSigRecord, TrusteeTransitionStatus,
@@ -70,12 +70,7 @@ impl<T: Config, TrusteeAddress: BytesLike + ChainProvider>
         Ok(Self::current_trustee_session()?
             .trustee_list
             .iter()
-            .filter_map(|info| {
-                match Pallet::<T>::trustee_intention_props_of(&info.0, TrusteeAddress::chain()) {
-                    None => None,
-                    Some(n) => n.0.proxy_account,
-                }
-            })
+            .filter_map(|info| T::TrusteeProxy::trustee_proxy(&info.0, TrusteeAddress::chain()))
             .collect::<Vec<T::AccountId>>())
     }
 
@@ -53,6 +53,36 @@ pub struct ScriptInfo<AccountId> {
     pub personal_accounts: Vec<Vec<AccountId>>,
 }
 
+/// The violated invariants of a gateway pallet, the pallet is healthy if it's checked
+/// and there is none.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct GatewayHealth {
+    /// The name of the pallet
+    pub pallet: Text,
+    /// Whether the pallet is checked, false if there are too many items to scan
+    pub checked: bool,
+    /// The violated invariants
+    pub violations: Vec<Text>,
+}
+
+impl GatewayHealth {
+    pub fn new(pallet: &str, violations: Option<Vec<&'static str>>) -> Self {
+        Self {
+            pallet: pallet.as_bytes().to_vec(),
+            checked: violations.is_some(),
+            violations: violations
+                .unwrap_or_default()
+                .into_iter()
+                .map(|violation| violation.as_bytes().to_vec())
+                .collect(),
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.checked && self.violations.is_empty()
+    }
+}
+
 /// Used to record the rewards distributed by the trustee.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -111,11 +141,13 @@ impl<AccountId, BlockNumber, TrusteeAddress: BytesLike>
 }
 
 /// The trustee intention properties.
+///
+/// The proxy of a trustee is no longer a property, it is a `GatewayTrustee` proxy provided by
+/// [`TrusteeProxy`](crate::traits::TrusteeProxy).
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct TrusteeIntentionProps<AccountId, TrusteeEntity: BytesLike> {
-    pub proxy_account: Option<AccountId>,
+pub struct TrusteeIntentionProps<TrusteeEntity: BytesLike> {
     #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
     pub about: Text,
     pub hot_entity: TrusteeEntity,
@@ -126,14 +158,24 @@ pub struct TrusteeIntentionProps<AccountId, TrusteeEntity: BytesLike> {
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct GenericTrusteeIntentionProps<AccountId>(pub TrusteeIntentionProps<AccountId, Vec<u8>>);
+pub struct GenericTrusteeIntentionProps(pub TrusteeIntentionProps<Vec<u8>>);
+
+/// The relayer rewards of an ended era, paid in the following blocks.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct RelayerPayoutInfo<Balance> {
+    /// The ended era.
+    pub era: u32,
+    /// The useful submissions of the era.
+    pub submissions: u32,
+    /// The reward shared by the submissions.
+    pub reward: Balance,
+}
 
-impl<AccountId, TrusteeEntity: BytesLike> From<TrusteeIntentionProps<AccountId, TrusteeEntity>>
-    for GenericTrusteeIntentionProps<AccountId>
+impl<TrusteeEntity: BytesLike> From<TrusteeIntentionProps<TrusteeEntity>>
+    for GenericTrusteeIntentionProps
 {
-    fn from(props: TrusteeIntentionProps<AccountId, TrusteeEntity>) -> Self {
+    fn from(props: TrusteeIntentionProps<TrusteeEntity>) -> Self {
         GenericTrusteeIntentionProps(TrusteeIntentionProps {
-            proxy_account: props.proxy_account,
             about: props.about,
             hot_entity: props.hot_entity.into(),
             cold_entity: props.cold_entity.into(),
@@ -141,15 +183,14 @@ impl<AccountId, TrusteeEntity: BytesLike> From<TrusteeIntentionProps<AccountId,
     }
 }
 
-impl<AccountId, TrusteeEntity: BytesLike> TryFrom<GenericTrusteeIntentionProps<AccountId>>
-    for TrusteeIntentionProps<AccountId, TrusteeEntity>
+impl<TrusteeEntity: BytesLike> TryFrom<GenericTrusteeIntentionProps>
+    for TrusteeIntentionProps<TrusteeEntity>
 {
     // TODO, may use a better error
     type Error = ();
 
-    fn try_from(value: GenericTrusteeIntentionProps<AccountId>) -> Result<Self, Self::Error> {
-        Ok(TrusteeIntentionProps::<AccountId, TrusteeEntity> {
-            proxy_account: value.0.proxy_account,
+    fn try_from(value: GenericTrusteeIntentionProps) -> Result<Self, Self::Error> {
+        Ok(TrusteeIntentionProps::<TrusteeEntity> {
             about: value.0.about,
             hot_entity: TrusteeEntity::try_from(value.0.hot_entity).map_err(|_| ())?,
             cold_entity: TrusteeEntity::try_from(value.0.cold_entity).map_err(|_| ())?,
@@ -4,6 +4,12 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-04-28, STEPS: 50, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 1024
+//!
+//! The weights of the following calls are estimated by hand, to be regenerated by the executed
+//! command below: `register_relayer`, `unregister_relayer`, `slash_relayer`,
+//! `withdraw_relayer_bond`, `execute_trustee_election`, `cancel_trustee_election`,
+//! `move_trust_into_black_room`, `move_trust_out_black_room`, `force_trustee_election`,
+//! `force_update_trustee`.
 
 // Executed Command:
 // ./target/release/sherpax
@@ -34,11 +40,20 @@ pub trait WeightInfo {
     fn withdraw() -> Weight;
     fn cancel_withdrawal() -> Weight;
     fn setup_trustee() -> Weight;
-    fn set_trustee_proxy() -> Weight;
     fn set_trustee_info_config() -> Weight;
     fn set_trustee_admin() -> Weight;
     fn set_trustee_admin_multiply() -> Weight;
     fn claim_trustee_reward() -> Weight;
+    fn register_relayer() -> Weight;
+    fn unregister_relayer() -> Weight;
+    fn slash_relayer() -> Weight;
+    fn withdraw_relayer_bond() -> Weight;
+    fn execute_trustee_election() -> Weight;
+    fn cancel_trustee_election() -> Weight;
+    fn move_trust_into_black_room() -> Weight;
+    fn move_trust_out_black_room() -> Weight;
+    fn force_trustee_election() -> Weight;
+    fn force_update_trustee() -> Weight;
 }
 
 /// Weights for xpallet_gateway_common using the Substrate node and recommended hardware.
@@ -59,11 +74,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(7 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
-    fn set_trustee_proxy() -> Weight {
-        (26_447_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(1 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
-    }
     fn set_trustee_info_config() -> Weight {
         (3_407_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
@@ -78,6 +88,56 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(7 as Weight))
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
+    fn register_relayer() -> Weight {
+        (35_912_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn unregister_relayer() -> Weight {
+        (33_287_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn slash_relayer() -> Weight {
+        (58_913_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn withdraw_relayer_bond() -> Weight {
+        (33_287_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn execute_trustee_election() -> Weight {
+        (1_146_530_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(32 as Weight))
+            .saturating_add(T::DbWeight::get().writes(141 as Weight))
+    }
+    fn cancel_trustee_election() -> Weight {
+        (1_021_782_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(24 as Weight))
+            .saturating_add(T::DbWeight::get().writes(139 as Weight))
+    }
+    fn move_trust_into_black_room() -> Weight {
+        (1_163_294_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(33 as Weight))
+            .saturating_add(T::DbWeight::get().writes(143 as Weight))
+    }
+    fn move_trust_out_black_room() -> Weight {
+        (11_620_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn force_trustee_election() -> Weight {
+        (43_417_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn force_update_trustee() -> Weight {
+        (32_045_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -97,11 +157,6 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(7 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
-    fn set_trustee_proxy() -> Weight {
-        (26_447_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
-    }
     fn set_trustee_info_config() -> Weight {
         (3_407_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
@@ -116,4 +171,54 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(7 as Weight))
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
+    fn register_relayer() -> Weight {
+        (35_912_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn unregister_relayer() -> Weight {
+        (33_287_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn slash_relayer() -> Weight {
+        (58_913_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn withdraw_relayer_bond() -> Weight {
+        (33_287_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn execute_trustee_election() -> Weight {
+        (1_146_530_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(32 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(141 as Weight))
+    }
+    fn cancel_trustee_election() -> Weight {
+        (1_021_782_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(24 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(139 as Weight))
+    }
+    fn move_trust_into_black_room() -> Weight {
+        (1_163_294_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(33 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(143 as Weight))
+    }
+    fn move_trust_out_black_room() -> Weight {
+        (11_620_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn force_trustee_election() -> Weight {
+        (43_417_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn force_update_trustee() -> Weight {
+        (32_045_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
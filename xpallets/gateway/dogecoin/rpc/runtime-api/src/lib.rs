@@ -7,10 +7,13 @@
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
 pub use xpallet_gateway_dogecoin::{
-    types::DogeHeaderInfo, BlockHeader, DogeWithdrawalProposal, H256,
+    types::{DogeHeaderIndex, DogeHeaderInfo},
+    BlockHeader, DogeWithdrawalProposal, H256,
 };
 
 sp_api::decl_runtime_apis! {
+    /// The version `2` adds the methods used by the gateway metrics.
+    #[api_version(2)]
     pub trait XGatewayDogecoinApi<AccountId>
         where AccountId: codec::Codec
     {
@@ -25,5 +28,9 @@ sp_api::decl_runtime_apis! {
         fn get_genesis_info() -> (BlockHeader, u32);
 
         fn get_doge_block_header(txid: H256) -> Option<DogeHeaderInfo>;
+
+        fn get_header_indexes() -> (DogeHeaderIndex, Option<DogeHeaderIndex>);
+
+        fn get_pending_deposit_stats() -> (u32, u64);
     }
 }
@@ -7,7 +7,11 @@ use frame_system::RawOrigin;
 use sp_runtime::{traits::StaticLookup, AccountId32};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
+#[cfg(feature = "runtime-benchmarks")]
+use xp_assets_registrar::Chain;
 use xp_gateway_dogecoin::DogeTxType;
+#[cfg(feature = "runtime-benchmarks")]
+use xpallet_gateway_common::traits::RelayerRegistry;
 use xpallet_gateway_records::{Pallet as XGatewayRecords, WithdrawalState};
 
 use light_bitcoin::{
@@ -17,7 +21,10 @@ use light_bitcoin::{
     serialization::{self, Reader, SERIALIZE_TRANSACTION_WITNESS},
 };
 
-use crate::{types::*, Call, Config, Pallet, PendingDeposits, TxState, WithdrawalProposal};
+use crate::{
+    types::*, Call, Config, Pallet, PendingDepositTotal, PendingDeposits, TxState,
+    WithdrawalProposal,
+};
 
 fn create_default_asset<T: Config>(who: T::AccountId) {
     let miner = T::Lookup::unlookup(who);
@@ -75,6 +82,8 @@ fn withdraw_tx() -> (Transaction, Vec<u8>, Transaction) {
 
 // push header 3836100 - 3782230
 fn prepare_headers<T: Config>(caller: &T::AccountId) {
+    #[cfg(feature = "runtime-benchmarks")]
+    T::RelayerRegistry::benchmark_register(Chain::Dogecoin, caller);
     for (height, header) in generate_blocks_3836100_3836160() {
         if height == 3836100 {
             continue;
@@ -91,6 +100,8 @@ benchmarks! {
         let header = generate_blocks_3836100_3836160()[&insert_height];
         let hash = header.hash();
         let header_raw = serialization::serialize(&header).into();
+        #[cfg(feature = "runtime-benchmarks")]
+        T::RelayerRegistry::benchmark_register(Chain::Dogecoin, &receiver);
     }: _(RawOrigin::Signed(receiver), header_raw)
     verify {
         assert!(Pallet::<T>::headers(&hash).is_some());
@@ -185,6 +196,13 @@ benchmarks! {
         assert_eq!(Pallet::<T>::confirmed_index(), Some(confirmed));
     }
 
+    set_confirmed_number {
+        let number = 6;
+    }: _(RawOrigin::Root, number)
+    verify {
+        assert_eq!(Pallet::<T>::confirmation_number(), number);
+    }
+
     remove_pending {
         let addr = b"3AWmpzJ1kSF1cktFTDEb3qmLcdN8YydxA7".to_vec();
         let v = vec![
@@ -201,11 +219,13 @@ benchmarks! {
                 balance: 300000000,
             },
         ];
+        PendingDepositTotal::<T>::put(v.iter().map(|deposit| deposit.balance).sum::<u64>());
         PendingDeposits::<T>::insert(&addr, v);
         let receiver: T::AccountId = whitelisted_caller();
     }: _(RawOrigin::Root, addr.clone(), Some(receiver))
     verify {
         assert!(Pallet::<T>::pending_deposits(&addr).is_empty());
+        assert_eq!(Pallet::<T>::pending_deposit_total(), 0);
         // assert_eq!(XAssets::<T>::usable_balance(&receiver, &T::DogeAssetId::get()), (100000000u32 + 200000000u32 + 300000000u32).into());
     }
 
@@ -268,6 +288,7 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_create_dogecoin_withdraw_tx());
             assert_ok!(Pallet::<Test>::test_benchmark_set_best_index());
             assert_ok!(Pallet::<Test>::test_benchmark_set_confirmed_index());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_confirmed_number());
             assert_ok!(Pallet::<Test>::test_benchmark_remove_pending());
             assert_ok!(Pallet::<Test>::test_benchmark_set_doge_withdrawal_fee());
             assert_ok!(Pallet::<Test>::test_benchmark_set_doge_deposit_limit());
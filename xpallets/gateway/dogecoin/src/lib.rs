@@ -33,11 +33,12 @@ pub use light_bitcoin::{
 use sherpax_primitives::ReferralId;
 use xp_assets_registrar::Chain;
 use xp_gateway_common::AccountExtractor;
+use xp_gateway_dogecoin::AddressError;
 
 use xpallet_gateway_common::{
     traits::{
-        AddressBinding, ProposalProvider, ReferralBinding, TotalSupply, TrusteeInfoUpdate,
-        TrusteeSession,
+        AddressBinding, ProposalProvider, ReferralBinding, RelayerRegistry, TotalSupply,
+        TrusteeInfoUpdate, TrusteeSession,
     },
     trustees::dogecoin::DogeTrusteeAddrInfo,
 };
@@ -46,7 +47,7 @@ use xpallet_support::try_addr;
 
 use self::{
     trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
-    tx::remove_pending_deposit,
+    tx::{decrease_pending_deposit_total, remove_pending_deposit},
     types::{
         DogeDepositCache, DogeHeaderIndex, DogeRelayedTx, DogeRelayedTxInfo, DogeTxResult,
         DogeTxState,
@@ -79,11 +80,23 @@ pub mod pallet {
     };
     use frame_system::pallet_prelude::*;
 
+    /// The storage version, `1` since the `PendingDepositTotal` is maintained.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::ensure_invariants()
+        }
+    }
+
     #[pallet::config]
     pub trait Config:
         frame_system::Config + pallet_assets::Config + xpallet_gateway_records::Config
@@ -116,6 +129,9 @@ pub mod pallet {
         /// Handle address binding about pending deposit.
         type AddressBinding: AddressBinding<Self::AccountId, DogeAddress>;
 
+        /// The bonded relayers who can push headers and transactions.
+        type RelayerRegistry: RelayerRegistry<Self::AccountId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -129,11 +145,18 @@ pub mod pallet {
             let header: BlockHeader =
                 deserialize(header.as_slice()).map_err(|_| Error::<T>::DeserializeErr)?;
             log!(debug, "[push_header] from:{:?}, header:{:?}", from, header);
+            T::RelayerRegistry::ensure_relayer(Chain::Dogecoin, &from)?;
 
+            let best_index = Self::best_index();
             Self::apply_push_header(header)?;
 
-            // Relayer does not pay a fee.
-            Ok(Pays::No.into())
+            // Relayer does not pay a fee only if the header advances the best index.
+            if Self::best_index() != best_index {
+                T::RelayerRegistry::note_submission(Chain::Dogecoin, &from);
+                Ok(Pays::No.into())
+            } else {
+                Ok(Pays::Yes.into())
+            }
         }
 
         /// if use `RelayTx` struct would export in metadata, cause complex in front-end
@@ -144,7 +167,8 @@ pub mod pallet {
             relayed_info: Vec<u8>,
             prev_tx: Option<Vec<u8>>,
         ) -> DispatchResultWithPostInfo {
-            let _from = ensure_signed(origin)?;
+            let from = ensure_signed(origin)?;
+            T::RelayerRegistry::ensure_relayer(Chain::Dogecoin, &from)?;
             let raw_tx = Self::deserialize_tx(raw_tx.as_slice())?;
             let relayed_info: DogeRelayedTxInfo =
                 Decode::decode(&mut &relayed_info[..]).map_err(|_| Error::<T>::DeserializeErr)?;
@@ -157,12 +181,14 @@ pub mod pallet {
             log!(
                 debug,
                 "[push_transaction] from:{:?}, relay_tx:{:?}, prev_tx:{:?}",
-                _from,
+                from,
                 relay_tx,
                 prev_tx
             );
 
+            // The tx is new and processed successfully, otherwise it's rejected.
             Self::apply_push_transaction(relay_tx, prev_tx)?;
+            T::RelayerRegistry::note_submission(Chain::Dogecoin, &from);
 
             Ok(Pays::No.into())
         }
@@ -214,7 +240,7 @@ pub mod pallet {
         }
 
         /// Dangerous! Be careful to set ConfirmedIndex
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_confirmed_number())]
         pub fn set_confirmed_number(origin: OriginFor<T>, number: u32) -> DispatchResult {
             ensure_root(origin)?;
             ConfirmationNumber::<T>::put(number);
@@ -238,7 +264,8 @@ pub mod pallet {
                 remove_pending_deposit::<T>(&addr, &w);
             } else {
                 log!(info, "[remove_pending] Release pending deposit directly, not deposit to someone, addr:{:?}", try_addr(&addr));
-                PendingDeposits::<T>::remove(&addr);
+                let records = PendingDeposits::<T>::take(&addr);
+                decrease_pending_deposit_total::<T>(&records);
             }
             Ok(())
         }
@@ -377,6 +404,10 @@ pub mod pallet {
         TxOutputNotColdAddr,
         /// The total amount of the trust must be transferred out in full
         TxNotFullAmount,
+        /// the address does not belong to the configured network
+        InvalidAddrNetwork,
+        /// the script type of the address is not supported
+        UnsupportedAddrType,
     }
 
     #[pallet::event]
@@ -443,6 +474,11 @@ pub mod pallet {
     pub(crate) type PendingDeposits<T: Config> =
         StorageMap<_, Blake2_128Concat, DogeAddress, Vec<DogeDepositCache>, ValueQuery>;
 
+    /// The total value of all the unclaimed deposits in `PendingDeposits`.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_deposit_total)]
+    pub(crate) type PendingDepositTotal<T: Config> = StorageValue<_, u64, ValueQuery>;
+
     /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_proposal)]
@@ -566,10 +602,10 @@ pub mod pallet {
 
         fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
             // this addr is base58 addr
-            let address = Self::verify_doge_address(addr).map_err(|err| {
+            let address = Self::verify_withdrawal_address(addr).map_err(|err| {
                 log!(
                     error,
-                    "[verify_doge_address] Verify failed, error:{:?}, source addr:{:?}",
+                    "[verify_withdrawal_address] Verify failed, error:{:?}, source addr:{:?}",
                     err,
                     xpallet_support::try_addr(addr)
                 );
@@ -579,7 +615,7 @@ pub mod pallet {
             match get_current_trustee_address_pair::<T>() {
                 Ok((hot_addr, cold_addr)) => {
                     // do not allow withdraw from trustee address
-                    if address == hot_addr || address == cold_addr {
+                    if xp_gateway_dogecoin::is_trustee_addr(address, (hot_addr, cold_addr)) {
                         return Err(Error::<T>::InvalidAddress.into());
                     }
                 }
@@ -608,15 +644,7 @@ pub mod pallet {
 
     impl<T: Config> TotalSupply<T::Balance> for Pallet<T> {
         fn total_supply() -> T::Balance {
-            let pending_deposits: T::Balance = PendingDeposits::<T>::iter_values()
-                .map(|deposits| {
-                    deposits
-                        .into_iter()
-                        .map(|deposit| deposit.balance)
-                        .sum::<u64>()
-                })
-                .sum::<u64>()
-                .saturated_into();
+            let pending_deposits: T::Balance = Self::pending_deposit_total().saturated_into();
 
             let asset_id = T::DogeAssetId::get();
             let asset_supply = pallet_assets::Pallet::<T>::total_supply(asset_id);
@@ -631,6 +659,143 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// Sum all the unclaimed deposits by iterating `PendingDeposits`,
+        /// returns the count of the addresses and the total value.
+        pub fn sum_pending_deposits() -> (u64, u64) {
+            PendingDeposits::<T>::iter_values().fold((0, 0), |(count, total), deposits| {
+                let value = deposits
+                    .into_iter()
+                    .map(|deposit| deposit.balance)
+                    .fold(0u64, |acc, balance| acc.saturating_add(balance));
+                (count + 1, total.saturating_add(value))
+            })
+        }
+
+        /// Ensure the maintained `PendingDepositTotal` matches the sum of `PendingDeposits`.
+        pub fn ensure_pending_deposit_total() -> Result<(), &'static str> {
+            let (_, total) = Self::sum_pending_deposits();
+            if total != Self::pending_deposit_total() {
+                log!(
+                    error,
+                    "[ensure_pending_deposit_total] PendingDepositTotal:{} != sum of PendingDeposits:{}",
+                    Self::pending_deposit_total(),
+                    total
+                );
+                return Err("PendingDepositTotal mismatches the sum of PendingDeposits");
+            }
+            Ok(())
+        }
+
+        /// Check the invariants of the header chain, returns all the violated ones, see
+        /// [`Self::check_invariants`].
+        pub fn invariant_violations() -> Vec<&'static str> {
+            Self::check_invariants(u32::MAX).unwrap_or_default()
+        }
+
+        /// Check the invariants of the header chain, returns all the violated ones:
+        ///
+        /// - `BestIndex` and `ConfirmedIndex` point to the headers of their heights.
+        /// - the confirmed header is in the main chain and not higher than the best one.
+        /// - every height of `BlockHashFor` has at most one main chain header, and all its
+        ///   headers are of that height.
+        /// - `PendingDepositTotal` matches the sum of `PendingDeposits`.
+        ///
+        /// Returns `None` without checking if `BlockHashFor` or `PendingDeposits` has more
+        /// than `max_items` entries.
+        pub fn check_invariants(max_items: u32) -> Option<Vec<&'static str>> {
+            let limit = (max_items as usize).saturating_add(1);
+            if BlockHashFor::<T>::iter_keys().take(limit).count() > max_items as usize
+                || PendingDeposits::<T>::iter_keys().take(limit).count() > max_items as usize
+            {
+                log!(
+                    warn,
+                    "[check_invariants] More than {} items to scan, skip",
+                    max_items
+                );
+                return None;
+            }
+
+            let mut violations = Vec::new();
+            let is_indexed = |index: &DogeHeaderIndex| {
+                Self::headers(&index.hash).map_or(false, |info| info.height == index.height)
+            };
+
+            let best = Self::best_index();
+            if !is_indexed(&best) {
+                log!(
+                    error,
+                    "[check_invariants] BestIndex:{:?} not in Headers",
+                    best
+                );
+                violations.push("BestIndex is not in Headers");
+            }
+            if let Some(confirmed) = Self::confirmed_index() {
+                if !is_indexed(&confirmed) {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} not in Headers",
+                        confirmed
+                    );
+                    violations.push("ConfirmedIndex is not in Headers");
+                }
+                if !Self::main_chain(&confirmed.hash) {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} not in MainChain",
+                        confirmed
+                    );
+                    violations.push("ConfirmedIndex is not in MainChain");
+                }
+                if confirmed.height > best.height {
+                    log!(
+                        error,
+                        "[check_invariants] ConfirmedIndex:{:?} above BestIndex:{:?}",
+                        confirmed,
+                        best
+                    );
+                    violations.push("ConfirmedIndex is above BestIndex");
+                }
+            }
+
+            for (height, hashes) in BlockHashFor::<T>::iter() {
+                if hashes.iter().filter(|hash| Self::main_chain(hash)).count() > 1 {
+                    log!(
+                        error,
+                        "[check_invariants] height:{}, multiple MainChain headers",
+                        height
+                    );
+                    violations.push("Multiple MainChain headers at the same height");
+                }
+                let indexed = hashes
+                    .into_iter()
+                    .all(|hash| is_indexed(&DogeHeaderIndex { hash, height }));
+                if !indexed {
+                    log!(
+                        error,
+                        "[check_invariants] height:{}, BlockHashFor not in Headers",
+                        height
+                    );
+                    violations.push("BlockHashFor is not in Headers");
+                }
+            }
+
+            if let Err(violation) = Self::ensure_pending_deposit_total() {
+                violations.push(violation);
+            }
+
+            Some(violations)
+        }
+
+        /// Ensure the invariants of the header chain, see [`Self::invariant_violations`].
+        pub fn ensure_invariants() -> Result<(), &'static str> {
+            match Self::invariant_violations().into_iter().next() {
+                Some(violation) => Err(violation),
+                None => Ok(()),
+            }
+        }
+    }
+
     /// Storage Query RPCs
     impl<T: Config> Pallet<T> {
         /// Get withdrawal proposal
@@ -647,6 +812,19 @@ pub mod pallet {
         pub fn get_doge_block_header(txid: H256) -> Option<DogeHeaderInfo> {
             Self::headers(txid)
         }
+
+        /// Get the best index and the confirmed index of the header chain
+        pub fn get_header_indexes() -> (DogeHeaderIndex, Option<DogeHeaderIndex>) {
+            (Self::best_index(), Self::confirmed_index())
+        }
+
+        /// Get the number and the total value of the unclaimed deposits
+        pub fn get_pending_deposit_stats() -> (u32, u64) {
+            let count = PendingDeposits::<T>::iter_values()
+                .map(|deposits| deposits.len() as u32)
+                .sum();
+            (count, Self::pending_deposit_total())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -658,6 +836,23 @@ pub mod pallet {
             Ok(addr)
         }
 
+        /// Verify the withdrawal address belongs to the configured `NetworkId`.
+        ///
+        /// Only `p2pkh` and `p2sh` destinations are supported.
+        pub fn verify_withdrawal_address(data: &[u8]) -> Result<Address, DispatchError> {
+            let network = Self::network_id();
+            let layout = bs58::decode(data)
+                .into_vec()
+                .map_err(|_| Error::<T>::InvalidBase58)?;
+            let address = Address::from_layout(&layout).map_err(|_| Error::<T>::InvalidAddr)?;
+            match xp_gateway_dogecoin::check_legacy_address(&layout, &address, network) {
+                Ok(()) => Ok(Address { network, ..address }),
+                Err(AddressError::InvalidEncoding) => Err(Error::<T>::InvalidAddr.into()),
+                Err(AddressError::NetworkMismatch) => Err(Error::<T>::InvalidAddrNetwork.into()),
+                Err(AddressError::UnsupportedType) => Err(Error::<T>::UnsupportedAddrType.into()),
+            }
+        }
+
         pub fn verify_tx_valid(
             raw_tx: Vec<u8>,
             withdrawal_id_list: Vec<u32>,
@@ -3,3 +3,4 @@
 //! All migrations of this pallet.
 
 pub mod genesis;
+pub mod pending_total;
@@ -0,0 +1,34 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::{Config, Pallet, PendingDepositTotal};
+use frame_support::{
+    log::info,
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+/// Initialize the `PendingDepositTotal` with the sum of all the `PendingDeposits`.
+pub fn apply<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        info!(
+            target: "runtime::gateway::dogecoin",
+            "✅ The pending deposit total has been initialized, skip."
+        );
+        return T::DbWeight::get().reads(1);
+    }
+
+    info!(
+        target: "runtime::gateway::dogecoin",
+        "✅ Running migration for the pending deposit total of gateway dogecoin pallet..."
+    );
+    let (count, total) = Pallet::<T>::sum_pending_deposits();
+    PendingDepositTotal::<T>::put(total);
+    StorageVersion::new(1).put::<Pallet<T>>();
+    info!(
+        target: "runtime::gateway::dogecoin",
+        "✅ Migration for the pending deposit total done, {} addresses, total: {}",
+        count,
+        total,
+    );
+    T::DbWeight::get().reads_writes(count + 2, 2)
+}
@@ -12,6 +12,7 @@ use frame_support::{
     parameter_types, sp_io,
     traits::{LockIdentifier, UnixTime},
     weights::Weight,
+    PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::{blake2_256, H256};
@@ -158,13 +159,16 @@ impl pallet_assets::Config for Test {
     type AssetId = AssetId;
     type Currency = Balances;
     type ForceOrigin = EnsureRoot<AccountId>;
+    type FeeRateOrigin = EnsureRoot<AccountId>;
     type AssetDeposit = AssetDeposit;
     type MetadataDepositBase = MetadataDepositBase;
     type MetadataDepositPerByte = MetadataDepositPerByte;
     type ApprovalDeposit = ApprovalDeposit;
     type StringLimit = StringLimit;
-    type Freezer = XGatewayRecords;
+    type Freezer = ();
     type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
     type AssetAccountDeposit = ();
     type WeightInfo = pallet_assets::weights::SubstrateWeight<Test>;
 }
@@ -190,11 +194,21 @@ impl MultisigAddressFor<AccountId> for MultisigAddr {
     }
 }
 
+parameter_types! {
+    pub const RelayerBond: Balance = 10;
+    pub const RelayerEraLength: BlockNumber = 10;
+    pub const RelayerPalletId: PalletId = PalletId(*b"pcx/rlyr");
+    pub const RelayerUnbondingPeriod: BlockNumber = 10;
+    pub const RelayerTransitionPeriod: BlockNumber = 10;
+    pub const MaxRelayerRewardsPerBlock: u32 = 100;
+}
+
 impl xpallet_gateway_common::Config for Test {
     type Event = ();
     type Validator = ();
     type DetermineMultisigAddress = MultisigAddr;
     type CouncilOrigin = EnsureSigned<AccountId>;
+    type TrusteeProxy = ();
     type Bitcoin = ();
     type BitcoinTrustee = ();
     type BitcoinTrusteeSessionProvider = ();
@@ -205,6 +219,12 @@ impl xpallet_gateway_common::Config for Test {
     type DogecoinTrusteeSessionProvider = trustees::dogecoin::DogeTrusteeSessionManager<Test>;
     type DogecoinTotalSupply = XGatewayDogecoin;
     type DogecoinWithdrawalProposal = XGatewayDogecoin;
+    type RelayerBond = RelayerBond;
+    type RelayerEraLength = RelayerEraLength;
+    type RelayerPalletId = RelayerPalletId;
+    type RelayerUnbondingPeriod = RelayerUnbondingPeriod;
+    type RelayerTransitionPeriod = RelayerTransitionPeriod;
+    type MaxRelayerRewardsPerBlock = MaxRelayerRewardsPerBlock;
     type WeightInfo = ();
 }
 thread_local! {
@@ -236,6 +256,7 @@ impl Config for Test {
     type TrusteeInfoUpdate = XGatewayCommon;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type RelayerRegistry = ();
     type WeightInfo = ();
 }
 
@@ -218,3 +218,52 @@ fn test_call() {
         assert_ok!(XGatewayDogecoin::push_header(origin, v));
     })
 }
+
+#[test]
+fn test_header_invariants() {
+    let (base_height, c1, forked) = generate_blocks_478557_478563();
+    ExtBuilder::default()
+        .build_mock((*c1.get(0).unwrap(), base_height), Network::Mainnet)
+        .execute_with(|| {
+            assert_ok!(XGatewayDogecoin::ensure_invariants());
+            for header in c1[1..4].iter().chain(forked[2..5].iter()) {
+                assert_ok!(XGatewayDogecoin::apply_push_header(*header));
+            }
+            // switch back to the normal chain
+            assert_ok!(XGatewayDogecoin::apply_push_header(*c1.get(4).unwrap()));
+            assert_ok!(XGatewayDogecoin::apply_push_header(*c1.get(5).unwrap()));
+            assert_eq!(XGatewayDogecoin::invariant_violations(), Vec::<&str>::new());
+            // too many headers to scan
+            assert_eq!(XGatewayDogecoin::check_invariants(1), None);
+
+            let root = frame_system::RawOrigin::Root;
+            let best = XGatewayDogecoin::best_index();
+            assert_ok!(XGatewayDogecoin::set_confirmed_index(
+                root.clone().into(),
+                DogeHeaderIndex {
+                    hash: forked.get(3).unwrap().hash(),
+                    height: best.height + 1,
+                }
+            ));
+            assert_eq!(
+                XGatewayDogecoin::invariant_violations(),
+                vec![
+                    "ConfirmedIndex is not in Headers",
+                    "ConfirmedIndex is not in MainChain",
+                    "ConfirmedIndex is above BestIndex",
+                ]
+            );
+
+            assert_ok!(XGatewayDogecoin::set_best_index(
+                root.into(),
+                DogeHeaderIndex {
+                    hash: Default::default(),
+                    height: best.height,
+                }
+            ));
+            assert_eq!(
+                XGatewayDogecoin::ensure_invariants(),
+                Err("BestIndex is not in Headers")
+            );
+        })
+}
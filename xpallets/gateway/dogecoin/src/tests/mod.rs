@@ -10,8 +10,10 @@ use xp_gateway_common::AccountExtractor;
 
 use light_bitcoin::script::Script;
 
+use frame_support::assert_noop;
+
 use crate::{
-    mock::{Test, XGatewayDogecoin},
+    mock::{ExtBuilder, Test, XGatewayDogecoin, XGatewayDogecoinErr},
     Config,
 };
 
@@ -21,6 +23,45 @@ pub fn test_verify_doge_address() {
     assert!(XGatewayDogecoin::verify_doge_address(&address).is_ok());
 }
 
+#[test]
+pub fn test_verify_withdrawal_address() {
+    // the mock network is dogecoin testnet
+    ExtBuilder::default().build_and_execute(|| {
+        // p2pkh
+        assert!(
+            XGatewayDogecoin::verify_withdrawal_address(b"nbGodDo7pezD2LcKN8AFMc9nMPvT1YhXcc")
+                .is_ok()
+        );
+        // p2sh
+        assert!(XGatewayDogecoin::verify_withdrawal_address(
+            b"2N6mJFLkjN9muneSeHCsMCxWXVZ4ruLKfFo"
+        )
+        .is_ok());
+
+        for addr in [
+            // dogecoin mainnet p2pkh
+            &b"DFpN6QqFfUm3gKNaxN6tNcab1FArL9cZLE"[..],
+            // dogecoin mainnet p2sh
+            b"A37YDYSwz3438rFtm1SLVcQHyD7JeueC9H",
+            // bitcoin testnet p2pkh
+            b"mqVznxoxdeSNYgDCg6ZVE5pc6476BY6zHK",
+        ] {
+            assert_noop!(
+                XGatewayDogecoin::verify_withdrawal_address(addr),
+                XGatewayDogecoinErr::InvalidAddrNetwork
+            );
+        }
+
+        // dogecoin has no segwit
+        assert_noop!(
+            XGatewayDogecoin::verify_withdrawal_address(
+                b"tb1pexff2s7l58sthpyfrtx500ax234stcnt0gz2lr4kwe0ue95a2e0srxsc68"
+            ),
+            XGatewayDogecoinErr::InvalidBase58
+        );
+    });
+}
+
 #[test]
 fn test_account_ss58_version() {
     set_default_ss58_version(Ss58AddressFormatRegistry::ChainxAccount.into());
@@ -3,13 +3,17 @@
 #![allow(non_upper_case_globals)]
 
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, StorageVersion},
+};
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormatRegistry};
 
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, Network},
     merkle::PartialMerkleTree,
+    primitives::H256,
     serialization::{self, Reader},
 };
 
@@ -18,8 +22,11 @@ use xp_gateway_dogecoin::{AccountExtractor, DogeTxMetaType, DogeTxTypeDetector};
 use crate::{
     mock::*,
     tx::process_tx,
-    types::{DogeRelayedTxInfo, DogeTxResult, DogeTxState, DogeWithdrawalProposal, VoteResult},
-    Config, WithdrawalProposal,
+    types::{
+        DogeDepositCache, DogeRelayedTxInfo, DogeTxResult, DogeTxState, DogeWithdrawalProposal,
+        VoteResult,
+    },
+    Config, PendingDepositTotal, PendingDeposits, WithdrawalProposal,
 };
 
 // Tyoe is p2tr. Address farmat is Mainnet.:
@@ -271,3 +278,45 @@ fn test_push_tx_call() {
         );
     });
 }
+
+#[test]
+fn test_pending_deposit_total() {
+    ExtBuilder::default().build_and_execute(|| {
+        let addr1 = b"addr1".to_vec();
+        let addr2 = b"addr2".to_vec();
+        let cache = |n: u8, balance: u64| DogeDepositCache {
+            txid: H256::repeat_byte(n),
+            balance,
+        };
+        PendingDeposits::<Test>::insert(&addr1, vec![cache(1, 100), cache(2, 200)]);
+        PendingDeposits::<Test>::insert(&addr2, vec![cache(3, 300)]);
+        assert_eq!(XGatewayDogecoin::pending_deposit_total(), 0);
+
+        // the migration computes the initial value
+        StorageVersion::new(0).put::<XGatewayDogecoin>();
+        crate::migrations::pending_total::apply::<Test>();
+        assert_eq!(XGatewayDogecoin::pending_deposit_total(), 600);
+        assert_eq!(XGatewayDogecoin::on_chain_storage_version(), 1);
+
+        // run only once
+        PendingDepositTotal::<Test>::put(0);
+        crate::migrations::pending_total::apply::<Test>();
+        assert_eq!(XGatewayDogecoin::pending_deposit_total(), 0);
+        PendingDepositTotal::<Test>::put(600);
+        assert_eq!(XGatewayDogecoin::sum_pending_deposits(), (2, 600));
+
+        assert_ok!(XGatewayDogecoin::remove_pending(
+            Origin::root(),
+            addr1,
+            None
+        ));
+        assert_eq!(XGatewayDogecoin::pending_deposit_total(), 300);
+        assert_ok!(XGatewayDogecoin::remove_pending(
+            Origin::root(),
+            addr2,
+            Some(alice())
+        ));
+        assert_eq!(XGatewayDogecoin::pending_deposit_total(), 0);
+        assert_eq!(XGatewayDogecoin::sum_pending_deposits(), (0, 0));
+    });
+}
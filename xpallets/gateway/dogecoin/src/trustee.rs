@@ -143,10 +143,7 @@ impl<T: Config> TrusteeForChain<T::AccountId, T::BlockNumber, DogeTrusteeType, D
 
     // generate dogecoin multi-sig address
     fn generate_trustee_session_info(
-        props: Vec<(
-            T::AccountId,
-            TrusteeIntentionProps<T::AccountId, DogeTrusteeType>,
-        )>,
+        props: Vec<(T::AccountId, TrusteeIntentionProps<DogeTrusteeType>)>,
         config: TrusteeInfoConfig,
     ) -> Result<
         (
@@ -157,7 +154,7 @@ impl<T: Config> TrusteeForChain<T::AccountId, T::BlockNumber, DogeTrusteeType, D
     > {
         let (trustees, props_info): (
             Vec<T::AccountId>,
-            Vec<TrusteeIntentionProps<T::AccountId, DogeTrusteeType>>,
+            Vec<TrusteeIntentionProps<DogeTrusteeType>>,
         ) = props.into_iter().unzip();
 
         let (hot_keys, cold_keys): (Vec<Public>, Vec<Public>) = props_info
@@ -427,8 +424,10 @@ fn check_withdraw_tx_impl<T: Config>(
         let record = xpallet_gateway_records::Pallet::<T>::pending_withdrawals(withdraw_index)
             .ok_or(Error::<T>::NoWithdrawalRecord)?;
         // record.addr() is base58
-        // verify Doge address would conveRelayedTx a base58 addr to Address
-        let addr: Address = Pallet::<T>::verify_doge_address(record.addr())?;
+        // the records applied before the strict check keep the lenient parse, so that
+        // they don't fail the withdrawal of the whole batch
+        let addr: Address = Pallet::<T>::verify_withdrawal_address(record.addr())
+            .or_else(|_| Pallet::<T>::verify_doge_address(record.addr()))?;
 
         appl_withdrawal_list.push((addr, record.balance().saturated_into::<u64>()));
     }
@@ -3,15 +3,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
 
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, prelude::Vec};
 
 use codec::Codec;
 
 pub use sherpax_primitives::{AssetId, Decimals};
 pub use xp_assets_registrar::Chain;
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
+pub use xpallet_gateway_records::{GatewayEvent, Withdrawal, WithdrawalRecordId, WithdrawalState};
 
 sp_api::decl_runtime_apis! {
+    /// The version `2` adds the methods used by the subscriptions of the gateway events.
+    #[api_version(2)]
     pub trait XGatewayRecordsApi<AccountId, Balance, BlockNumber>
     where
         AccountId: Codec,
@@ -21,5 +23,11 @@ sp_api::decl_runtime_apis! {
         fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, AssetId, Balance, BlockNumber>>;
 
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, AssetId, Balance, BlockNumber>>;
+
+        /// The pending withdrawals of the ids.
+        fn withdrawals_of(ids: Vec<WithdrawalRecordId>) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, AssetId, Balance, BlockNumber>>;
+
+        /// The gateway deposit and withdrawal events of the block.
+        fn gateway_events() -> Vec<GatewayEvent<AccountId, AssetId, Balance>>;
     }
 }
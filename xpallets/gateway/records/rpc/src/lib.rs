@@ -1,5 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+mod pubsub;
+
 use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -20,6 +22,11 @@ use xpallet_gateway_records_rpc_runtime_api::{
     XGatewayRecordsApi as GatewayRecordsRuntimeApi,
 };
 
+pub use self::pubsub::{
+    BlockStatus, GatewayEventFilter, GatewayEventsNotification, RpcGatewayEvent,
+    XGatewayRecordsPubSub, XGatewayRecordsPubSubApi,
+};
+
 pub struct XGatewayRecords<C, B> {
     client: Arc<C>,
     _marker: std::marker::PhantomData<B>,
@@ -0,0 +1,951 @@
+// Copyright 2019-2022 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! The subscriptions of the gateway deposit and withdrawal events.
+//!
+//! A notification is pushed for each block with the matched events, with the status:
+//!
+//! - `best`: the block becomes a part of the best chain;
+//! - `retracted`: the block is retracted from the best chain by a reorg, whose events should be
+//!   reverted by the subscriber;
+//! - `finalized`: the block is finalized.
+//!
+//! The events of a block are read once and shared by all the subscriptions.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Display,
+    marker::PhantomData,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use codec::Codec;
+use futures::{future, stream, FutureExt, SinkExt, Stream, StreamExt};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use serde::{Deserialize, Serialize};
+
+use sc_client_api::{BlockImportNotification, BlockchainEvents, FinalityNotification};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::{HeaderBackend, TreeRoute};
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, Header as HeaderT, NumberFor},
+};
+
+use xp_rpc::Result;
+
+use xpallet_gateway_records_rpc_runtime_api::{
+    AssetId, Chain, GatewayEvent, WithdrawalRecordId, WithdrawalState,
+    XGatewayRecordsApi as GatewayRecordsRuntimeApi,
+};
+
+const LOG_TARGET: &str = "xgateway-rpc";
+
+/// The version of `XGatewayRecordsApi` providing the methods used by the subscriptions.
+const MIN_RECORDS_API_VERSION: u32 = 2;
+
+/// The number of the recent blocks whose events are cached.
+const EVENTS_CACHE_SIZE: usize = 64;
+
+pub struct XGatewayRecordsPubSub<C, B: BlockT, AccountId, Balance> {
+    client: Arc<C>,
+    subscriptions: SubscriptionManager,
+    cache: Arc<Mutex<EventsCache<B::Hash, AccountId, Balance>>>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B: BlockT, AccountId, Balance> XGatewayRecordsPubSub<C, B, AccountId, Balance> {
+    /// Create new `XGatewayRecordsPubSub` with the given reference to the client.
+    pub fn new(client: Arc<C>, subscriptions: SubscriptionManager) -> Self {
+        Self {
+            client,
+            subscriptions,
+            cache: Arc::new(Mutex::new(EventsCache::default())),
+            _marker: Default::default(),
+        }
+    }
+}
+
+#[rpc(server)]
+pub trait XGatewayRecordsPubSubApi<BlockHash, BlockNumber, AccountId, Balance>
+where
+    Balance: Display + FromStr,
+{
+    type Metadata;
+
+    /// Subscribe the `Deposited` and `UnclaimedDeposit` events, optionally filtered by the
+    /// account or the asset.
+    #[pubsub(
+        subscription = "xgateway_deposits",
+        subscribe,
+        name = "xgateway_subscribeDeposits"
+    )]
+    fn subscribe_deposits(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<
+            GatewayEventsNotification<BlockHash, BlockNumber, AccountId, Balance>,
+        >,
+        filter: Option<GatewayEventFilter<AccountId>>,
+    );
+
+    /// Unsubscribe the deposit events.
+    #[pubsub(
+        subscription = "xgateway_deposits",
+        unsubscribe,
+        name = "xgateway_unsubscribeDeposits"
+    )]
+    fn unsubscribe_deposits(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool>;
+
+    /// Subscribe the `WithdrawalProposalCreated`, `Withdrawn` and the withdrawal record events,
+    /// optionally filtered by the applicant or the asset of the withdrawals.
+    #[pubsub(
+        subscription = "xgateway_withdrawals",
+        subscribe,
+        name = "xgateway_subscribeWithdrawals"
+    )]
+    fn subscribe_withdrawals(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<
+            GatewayEventsNotification<BlockHash, BlockNumber, AccountId, Balance>,
+        >,
+        filter: Option<GatewayEventFilter<AccountId>>,
+    );
+
+    /// Unsubscribe the withdrawal events.
+    #[pubsub(
+        subscription = "xgateway_withdrawals",
+        unsubscribe,
+        name = "xgateway_unsubscribeWithdrawals"
+    )]
+    fn unsubscribe_withdrawals(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool>;
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    XGatewayRecordsPubSubApi<<Block as BlockT>::Hash, BlockNumber, AccountId, Balance>
+    for XGatewayRecordsPubSub<C, Block, AccountId, Balance>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C: BlockchainEvents<Block>,
+    C::Api: GatewayRecordsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    Block: BlockT,
+    AccountId: Clone + PartialEq + Display + FromStr + Codec + Send + Sync + 'static,
+    Balance: Clone + Display + FromStr + Codec + Send + Sync + 'static,
+    BlockNumber: Clone + Display + Codec + Send + Sync + 'static,
+    NumberFor<Block>: Into<BlockNumber>,
+{
+    type Metadata = sc_rpc_api::Metadata;
+
+    fn subscribe_deposits(
+        &self,
+        _metadata: Self::Metadata,
+        subscriber: Subscriber<
+            GatewayEventsNotification<<Block as BlockT>::Hash, BlockNumber, AccountId, Balance>,
+        >,
+        filter: Option<GatewayEventFilter<AccountId>>,
+    ) {
+        self.subscribe(subscriber, EventKind::Deposit, filter.unwrap_or_default());
+    }
+
+    fn unsubscribe_deposits(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        Ok(self.subscriptions.cancel(id))
+    }
+
+    fn subscribe_withdrawals(
+        &self,
+        _metadata: Self::Metadata,
+        subscriber: Subscriber<
+            GatewayEventsNotification<<Block as BlockT>::Hash, BlockNumber, AccountId, Balance>,
+        >,
+        filter: Option<GatewayEventFilter<AccountId>>,
+    ) {
+        self.subscribe(
+            subscriber,
+            EventKind::Withdrawal,
+            filter.unwrap_or_default(),
+        );
+    }
+
+    fn unsubscribe_withdrawals(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        Ok(self.subscriptions.cancel(id))
+    }
+}
+
+impl<C, Block, AccountId, Balance> XGatewayRecordsPubSub<C, Block, AccountId, Balance>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C: BlockchainEvents<Block>,
+    Block: BlockT,
+{
+    fn subscribe<BlockNumber>(
+        &self,
+        subscriber: Subscriber<
+            GatewayEventsNotification<<Block as BlockT>::Hash, BlockNumber, AccountId, Balance>,
+        >,
+        kind: EventKind,
+        filter: GatewayEventFilter<AccountId>,
+    ) where
+        C::Api: GatewayRecordsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+        AccountId: Clone + PartialEq + Codec + Send + Sync + 'static,
+        Balance: Clone + Display + FromStr + Codec + Send + Sync + 'static,
+        BlockNumber: Codec + Send + Sync + 'static,
+        NumberFor<Block>: Into<BlockNumber>,
+    {
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        self.subscriptions.add(subscriber, move |sink| {
+            block_stream(client.clone())
+                .filter_map(move |(hash, number, status)| {
+                    let events = cache
+                        .lock()
+                        .expect("cache lock poisoned")
+                        .get_or_try_insert(hash, || {
+                            block_events::<_, _, _, _, BlockNumber>(&*client, hash)
+                        })
+                        .map(|block| block.select(kind, &filter));
+                    let notification = match events {
+                        Ok(events) if events.is_empty() => None,
+                        Ok(events) => Some(GatewayEventsNotification {
+                            block_hash: hash,
+                            block_number: number.into(),
+                            status,
+                            events: events.into_iter().map(Into::into).collect(),
+                        }),
+                        Err(err) => {
+                            log::warn!(
+                                target: LOG_TARGET,
+                                "Failed to read gateway events of {:?}: {}",
+                                hash,
+                                err
+                            );
+                            None
+                        }
+                    };
+                    future::ready(notification)
+                })
+                .map(|notification| Ok::<_, ()>(Ok(notification)))
+                .forward(sink.sink_map_err(|e| {
+                    log::warn!(target: LOG_TARGET, "Error sending notifications: {:?}", e)
+                }))
+                .map(|_| ())
+        });
+    }
+}
+
+/// The blocks whose gateway events are notified, in the order of the notifications.
+fn block_stream<C, Block>(
+    client: Arc<C>,
+) -> impl Stream<Item = (Block::Hash, NumberFor<Block>, BlockStatus)>
+where
+    C: BlockchainEvents<Block> + HeaderBackend<Block>,
+    Block: BlockT,
+{
+    enum ChainEvent<Block: BlockT> {
+        Imported(BlockImportNotification<Block>),
+        Finalized(FinalityNotification<Block>),
+    }
+
+    let imports = client
+        .import_notification_stream()
+        .map(ChainEvent::Imported);
+    let finality = client
+        .finality_notification_stream()
+        .map(ChainEvent::Finalized);
+    let mut last_finalized = client.info().finalized_number;
+    stream::select(imports, finality).flat_map(move |event| {
+        let blocks = match event {
+            ChainEvent::Imported(notification) => imported_blocks::<Block>(
+                notification.hash,
+                *notification.header.number(),
+                notification.is_new_best,
+                notification.tree_route.as_deref(),
+            ),
+            ChainEvent::Finalized(notification) => {
+                let blocks =
+                    finalized_blocks::<Block>(&notification.header, last_finalized, |hash| {
+                        client.header(BlockId::hash(*hash)).ok().flatten()
+                    });
+                last_finalized = last_finalized.max(*notification.header.number());
+                blocks
+            }
+        };
+        stream::iter(blocks)
+    })
+}
+
+/// The blocks retracted and enacted by the new best block, and the new best block itself.
+fn imported_blocks<Block: BlockT>(
+    hash: Block::Hash,
+    number: NumberFor<Block>,
+    is_new_best: bool,
+    tree_route: Option<&TreeRoute<Block>>,
+) -> Vec<(Block::Hash, NumberFor<Block>, BlockStatus)> {
+    if !is_new_best {
+        // The block is notified once it is enacted by a new best block.
+        return Vec::new();
+    }
+    let mut blocks = Vec::new();
+    // The tree route is from the old best block to the parent of the new best block.
+    if let Some(route) = tree_route {
+        blocks.extend(
+            route
+                .retracted()
+                .iter()
+                .map(|block| (block.hash, block.number, BlockStatus::Retracted)),
+        );
+        blocks.extend(
+            route
+                .enacted()
+                .iter()
+                .map(|block| (block.hash, block.number, BlockStatus::Best)),
+        );
+    }
+    blocks.push((hash, number, BlockStatus::Best));
+    blocks
+}
+
+/// The blocks finalized by the finalized header since the last finalized block, the parent
+/// headers are read by `header_of`.
+fn finalized_blocks<Block: BlockT>(
+    header: &Block::Header,
+    last_finalized: NumberFor<Block>,
+    header_of: impl Fn(&Block::Hash) -> Option<Block::Header>,
+) -> Vec<(Block::Hash, NumberFor<Block>, BlockStatus)> {
+    let mut blocks = Vec::new();
+    let mut header = Some(header.clone());
+    while let Some(current) = header {
+        if *current.number() <= last_finalized {
+            break;
+        }
+        blocks.push((current.hash(), *current.number(), BlockStatus::Finalized));
+        header = header_of(current.parent_hash());
+    }
+    blocks.reverse();
+    blocks
+}
+
+/// The gateway events of a block, with the applicants and the assets of the withdrawals which
+/// are referred to by the events.
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct BlockEvents<AccountId, Balance> {
+    events: Vec<GatewayEvent<AccountId, AssetId, Balance>>,
+    withdrawals: BTreeMap<WithdrawalRecordId, (AccountId, AssetId)>,
+}
+
+impl<AccountId: Clone + PartialEq, Balance: Clone> BlockEvents<AccountId, Balance> {
+    /// Select the events of the subscription.
+    fn select(
+        &self,
+        kind: EventKind,
+        filter: &GatewayEventFilter<AccountId>,
+    ) -> Vec<GatewayEvent<AccountId, AssetId, Balance>> {
+        self.events
+            .iter()
+            .filter(|event| kind.contains(event) && filter.matches(event, &self.withdrawals))
+            .cloned()
+            .collect()
+    }
+}
+
+/// The events of the recent blocks, shared by all the subscriptions.
+struct EventsCache<Hash, AccountId, Balance> {
+    blocks: VecDeque<(Hash, Arc<BlockEvents<AccountId, Balance>>)>,
+}
+
+impl<Hash, AccountId, Balance> Default for EventsCache<Hash, AccountId, Balance> {
+    fn default() -> Self {
+        Self {
+            blocks: VecDeque::new(),
+        }
+    }
+}
+
+impl<Hash: PartialEq, AccountId, Balance> EventsCache<Hash, AccountId, Balance> {
+    /// Return the cached events of the block, or read and cache them.
+    fn get_or_try_insert(
+        &mut self,
+        hash: Hash,
+        read: impl FnOnce() -> std::result::Result<BlockEvents<AccountId, Balance>, String>,
+    ) -> std::result::Result<Arc<BlockEvents<AccountId, Balance>>, String> {
+        if let Some((_, block)) = self.blocks.iter().find(|(cached, _)| *cached == hash) {
+            return Ok(block.clone());
+        }
+        let block = Arc::new(read()?);
+        if self.blocks.len() == EVENTS_CACHE_SIZE {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((hash, block.clone()));
+        Ok(block)
+    }
+}
+
+/// Read the gateway events of the block.
+fn block_events<C, Block, AccountId, Balance, BlockNumber>(
+    client: &C,
+    hash: Block::Hash,
+) -> std::result::Result<BlockEvents<AccountId, Balance>, String>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: GatewayRecordsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    Block: BlockT,
+    AccountId: Clone + PartialEq + Codec,
+    Balance: Clone + Codec,
+    BlockNumber: Codec,
+{
+    let err = |e: sp_api::ApiError| format!("Runtime api error: {:?}", e);
+    let api = client.runtime_api();
+    let at = BlockId::hash(hash);
+    let version = api
+        .api_version::<dyn GatewayRecordsRuntimeApi<Block, AccountId, Balance, BlockNumber>>(&at)
+        .map_err(err)?;
+    match version {
+        Some(version) if version >= MIN_RECORDS_API_VERSION => {}
+        _ => {
+            return Err(format!(
+                "XGatewayRecordsApi version {:?} is not supported, expect {}",
+                version, MIN_RECORDS_API_VERSION
+            ))
+        }
+    }
+    let events = api.gateway_events(&at).map_err(err)?;
+
+    // The withdrawal events except `WithdrawalCreated` are matched by the applicant and the
+    // asset of the withdrawal records, which are removed once the withdrawals are finished.
+    let mut withdrawals = BTreeMap::new();
+    let mut referred = BTreeSet::new();
+    for event in &events {
+        match event {
+            GatewayEvent::WithdrawalCreated {
+                withdrawal_id,
+                applicant,
+                asset_id,
+                ..
+            } => {
+                withdrawals.insert(*withdrawal_id, (applicant.clone(), *asset_id));
+            }
+            GatewayEvent::WithdrawalStateChanged { withdrawal_id, .. } => {
+                referred.insert(*withdrawal_id);
+            }
+            GatewayEvent::WithdrawalProposalCreated { withdrawal_ids, .. }
+            | GatewayEvent::Withdrawn { withdrawal_ids, .. } => {
+                referred.extend(withdrawal_ids.iter().copied());
+            }
+            GatewayEvent::Deposited { .. } | GatewayEvent::UnclaimedDeposit { .. } => {}
+        }
+    }
+    let referred = referred
+        .into_iter()
+        .filter(|id| !withdrawals.contains_key(id))
+        .collect::<Vec<_>>();
+    if !referred.is_empty() {
+        let parent_hash = client
+            .header(at)
+            .map_err(|e| format!("{:?}", e))?
+            .map(|header| *header.parent_hash())
+            .ok_or_else(|| format!("Unknown block {:?}", hash))?;
+        withdrawals.extend(
+            api.withdrawals_of(&BlockId::hash(parent_hash), referred)
+                .map_err(err)?
+                .into_iter()
+                .map(|(id, withdrawal)| (id, (withdrawal.applicant, withdrawal.asset_id))),
+        );
+    }
+    Ok(BlockEvents {
+        events,
+        withdrawals,
+    })
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum EventKind {
+    Deposit,
+    Withdrawal,
+}
+
+impl EventKind {
+    fn contains<AccountId, Balance>(
+        self,
+        event: &GatewayEvent<AccountId, AssetId, Balance>,
+    ) -> bool {
+        match event {
+            GatewayEvent::Deposited { .. } | GatewayEvent::UnclaimedDeposit { .. } => {
+                self == EventKind::Deposit
+            }
+            GatewayEvent::WithdrawalProposalCreated { .. }
+            | GatewayEvent::Withdrawn { .. }
+            | GatewayEvent::WithdrawalCreated { .. }
+            | GatewayEvent::WithdrawalStateChanged { .. } => self == EventKind::Withdrawal,
+        }
+    }
+}
+
+/// The filter of the subscribed events, all the events are matched if it's empty.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GatewayEventFilter<AccountId> {
+    /// The depositor or the applicant of the withdrawals.
+    #[serde(default)]
+    pub account: Option<AccountId>,
+    #[serde(default)]
+    pub asset_id: Option<AssetId>,
+}
+
+impl<AccountId> Default for GatewayEventFilter<AccountId> {
+    fn default() -> Self {
+        Self {
+            account: None,
+            asset_id: None,
+        }
+    }
+}
+
+impl<AccountId: PartialEq> GatewayEventFilter<AccountId> {
+    fn is_empty(&self) -> bool {
+        self.account.is_none() && self.asset_id.is_none()
+    }
+
+    fn matches_owner(&self, who: Option<&AccountId>, asset_id: AssetId) -> bool {
+        self.account
+            .as_ref()
+            .map_or(true, |account| Some(account) == who)
+            && self.asset_id.map_or(true, |id| id == asset_id)
+    }
+
+    fn matches<Balance>(
+        &self,
+        event: &GatewayEvent<AccountId, AssetId, Balance>,
+        withdrawals: &BTreeMap<WithdrawalRecordId, (AccountId, AssetId)>,
+    ) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let matches_withdrawal = |id: &WithdrawalRecordId| {
+            withdrawals.get(id).map_or(false, |(applicant, asset_id)| {
+                self.matches_owner(Some(applicant), *asset_id)
+            })
+        };
+        match event {
+            GatewayEvent::Deposited { who, asset_id, .. } => {
+                self.matches_owner(Some(who), *asset_id)
+            }
+            // The unclaimed deposit is not bound to any account.
+            GatewayEvent::UnclaimedDeposit { asset_id, .. } => self.matches_owner(None, *asset_id),
+            GatewayEvent::WithdrawalCreated {
+                applicant,
+                asset_id,
+                ..
+            } => self.matches_owner(Some(applicant), *asset_id),
+            GatewayEvent::WithdrawalStateChanged { withdrawal_id, .. } => {
+                matches_withdrawal(withdrawal_id)
+            }
+            GatewayEvent::WithdrawalProposalCreated { withdrawal_ids, .. }
+            | GatewayEvent::Withdrawn { withdrawal_ids, .. } => {
+                withdrawal_ids.iter().any(matches_withdrawal)
+            }
+        }
+    }
+}
+
+/// The status of the notified block.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockStatus {
+    Best,
+    Retracted,
+    Finalized,
+}
+
+/// The matched gateway events of a block.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayEventsNotification<BlockHash, BlockNumber, AccountId, Balance>
+where
+    Balance: Display + FromStr,
+{
+    pub block_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub status: BlockStatus,
+    pub events: Vec<RpcGatewayEvent<AccountId, Balance>>,
+}
+
+/// The gateway event, whose transaction hash is the txid shown by the block explorers.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub enum RpcGatewayEvent<AccountId, Balance: Display + FromStr> {
+    #[serde(rename_all = "camelCase")]
+    Deposited {
+        chain: Chain,
+        txid: String,
+        who: AccountId,
+        asset_id: AssetId,
+        #[serde(with = "xp_rpc::serde_num_str")]
+        amount: Balance,
+    },
+    #[serde(rename_all = "camelCase")]
+    UnclaimedDeposit {
+        chain: Chain,
+        txid: String,
+        asset_id: AssetId,
+        address: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    WithdrawalProposalCreated {
+        chain: Chain,
+        proposer: AccountId,
+        withdrawal_ids: Vec<WithdrawalRecordId>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Withdrawn {
+        chain: Chain,
+        txid: String,
+        withdrawal_ids: Vec<WithdrawalRecordId>,
+        #[serde(with = "xp_rpc::serde_num_str")]
+        total: Balance,
+    },
+    #[serde(rename_all = "camelCase")]
+    WithdrawalCreated {
+        withdrawal_id: WithdrawalRecordId,
+        applicant: AccountId,
+        asset_id: AssetId,
+        #[serde(with = "xp_rpc::serde_num_str")]
+        balance: Balance,
+    },
+    #[serde(rename_all = "camelCase")]
+    WithdrawalStateChanged {
+        withdrawal_id: WithdrawalRecordId,
+        state: WithdrawalState,
+    },
+}
+
+/// The txid in the reversed byte order of the transaction hash.
+fn txid(hash: impl AsRef<[u8]>) -> String {
+    hash.as_ref()
+        .iter()
+        .rev()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+impl<AccountId, Balance: Display + FromStr> From<GatewayEvent<AccountId, AssetId, Balance>>
+    for RpcGatewayEvent<AccountId, Balance>
+{
+    fn from(event: GatewayEvent<AccountId, AssetId, Balance>) -> Self {
+        match event {
+            GatewayEvent::Deposited {
+                chain,
+                tx_hash,
+                who,
+                asset_id,
+                amount,
+            } => Self::Deposited {
+                chain,
+                txid: txid(tx_hash),
+                who,
+                asset_id,
+                amount,
+            },
+            GatewayEvent::UnclaimedDeposit {
+                chain,
+                tx_hash,
+                asset_id,
+                address,
+            } => Self::UnclaimedDeposit {
+                chain,
+                txid: txid(tx_hash),
+                asset_id,
+                address: String::from_utf8_lossy(&address).into_owned(),
+            },
+            GatewayEvent::WithdrawalProposalCreated {
+                chain,
+                proposer,
+                withdrawal_ids,
+            } => Self::WithdrawalProposalCreated {
+                chain,
+                proposer,
+                withdrawal_ids,
+            },
+            GatewayEvent::Withdrawn {
+                chain,
+                tx_hash,
+                withdrawal_ids,
+                total,
+            } => Self::Withdrawn {
+                chain,
+                txid: txid(tx_hash),
+                withdrawal_ids,
+                total,
+            },
+            GatewayEvent::WithdrawalCreated {
+                withdrawal_id,
+                applicant,
+                asset_id,
+                balance,
+            } => Self::WithdrawalCreated {
+                withdrawal_id,
+                applicant,
+                asset_id,
+                balance,
+            },
+            GatewayEvent::WithdrawalStateChanged {
+                withdrawal_id,
+                state,
+            } => Self::WithdrawalStateChanged {
+                withdrawal_id,
+                state,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sp_blockchain::HashAndNumber;
+    use sp_runtime::{
+        generic::{Digest, Header},
+        testing::H256,
+        traits::BlakeTwo256,
+        OpaqueExtrinsic,
+    };
+
+    type Block = sp_runtime::generic::Block<Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+    type Event = GatewayEvent<u64, AssetId, u128>;
+
+    const ALICE: u64 = 1;
+    const BOB: u64 = 2;
+    const BTC: AssetId = 1;
+    const DOGE: AssetId = 9;
+
+    fn filter(account: Option<u64>, asset_id: Option<AssetId>) -> GatewayEventFilter<u64> {
+        GatewayEventFilter { account, asset_id }
+    }
+
+    fn header(number: u32, parent_hash: H256) -> Header<u32, BlakeTwo256> {
+        Header::new(
+            number,
+            Default::default(),
+            Default::default(),
+            parent_hash,
+            Digest::default(),
+        )
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let withdrawals = vec![(0, (ALICE, BTC)), (1, (BOB, DOGE))]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        let deposited = Event::Deposited {
+            chain: Chain::Bitcoin,
+            tx_hash: H256::zero(),
+            who: ALICE,
+            asset_id: BTC,
+            amount: 100,
+        };
+        let unclaimed = Event::UnclaimedDeposit {
+            chain: Chain::Bitcoin,
+            tx_hash: H256::zero(),
+            asset_id: BTC,
+            address: b"addr".to_vec(),
+        };
+        let state_changed = |withdrawal_id| Event::WithdrawalStateChanged {
+            withdrawal_id,
+            state: WithdrawalState::Processing,
+        };
+        let withdrawn = Event::Withdrawn {
+            chain: Chain::Dogecoin,
+            tx_hash: H256::zero(),
+            withdrawal_ids: vec![1, 2],
+            total: 100,
+        };
+
+        // all the events are matched by the empty filter, even the unknown withdrawals
+        for event in [&deposited, &unclaimed, &state_changed(2), &withdrawn] {
+            assert!(filter(None, None).matches(event, &withdrawals));
+        }
+
+        assert!(filter(Some(ALICE), None).matches(&deposited, &withdrawals));
+        assert!(!filter(Some(BOB), None).matches(&deposited, &withdrawals));
+        assert!(!filter(Some(ALICE), Some(DOGE)).matches(&deposited, &withdrawals));
+        // the unclaimed deposit is only matched by the asset
+        assert!(filter(None, Some(BTC)).matches(&unclaimed, &withdrawals));
+        assert!(!filter(Some(ALICE), Some(BTC)).matches(&unclaimed, &withdrawals));
+
+        // the withdrawals are matched by the records
+        assert!(filter(Some(ALICE), Some(BTC)).matches(&state_changed(0), &withdrawals));
+        assert!(!filter(Some(BOB), None).matches(&state_changed(0), &withdrawals));
+        assert!(!filter(Some(ALICE), None).matches(&state_changed(2), &withdrawals));
+        assert!(filter(Some(BOB), None).matches(&withdrawn, &withdrawals));
+        assert!(!filter(Some(ALICE), None).matches(&withdrawn, &withdrawals));
+    }
+
+    #[test]
+    fn test_select_block_events() {
+        let block = BlockEvents {
+            events: vec![
+                Event::Deposited {
+                    chain: Chain::Bitcoin,
+                    tx_hash: H256::zero(),
+                    who: ALICE,
+                    asset_id: BTC,
+                    amount: 100,
+                },
+                Event::WithdrawalCreated {
+                    withdrawal_id: 0,
+                    applicant: BOB,
+                    asset_id: BTC,
+                    balance: 50,
+                },
+            ],
+            withdrawals: vec![(0, (BOB, BTC))].into_iter().collect(),
+        };
+        let all = filter(None, None);
+        assert_eq!(block.select(EventKind::Deposit, &all), block.events[..1]);
+        assert_eq!(block.select(EventKind::Withdrawal, &all), block.events[1..]);
+        assert!(block
+            .select(EventKind::Withdrawal, &filter(Some(ALICE), None))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_events_cache() {
+        let block = |amount| BlockEvents::<u64, u128> {
+            events: vec![Event::Withdrawn {
+                chain: Chain::Bitcoin,
+                tx_hash: H256::zero(),
+                withdrawal_ids: vec![],
+                total: amount,
+            }],
+            withdrawals: BTreeMap::new(),
+        };
+        let mut cache = EventsCache::default();
+        assert_eq!(
+            *cache.get_or_try_insert(0, || Ok(block(1))).unwrap(),
+            block(1)
+        );
+        // the events of a block are read once
+        assert_eq!(
+            *cache.get_or_try_insert(0, || unreachable!()).unwrap(),
+            block(1)
+        );
+        assert!(cache.get_or_try_insert(1, || Err("error".into())).is_err());
+
+        for hash in 1..=EVENTS_CACHE_SIZE {
+            assert!(cache.get_or_try_insert(hash, || Ok(block(2))).is_ok());
+        }
+        assert_eq!(cache.blocks.len(), EVENTS_CACHE_SIZE);
+        // the oldest block is evicted
+        assert_eq!(
+            *cache.get_or_try_insert(0, || Ok(block(3))).unwrap(),
+            block(3)
+        );
+    }
+
+    #[test]
+    fn test_txid() {
+        let hash = H256::from_slice(&(0..32).collect::<Vec<u8>>());
+        assert_eq!(
+            txid(hash),
+            "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100"
+        );
+
+        let event = RpcGatewayEvent::<u64, u128>::from(Event::Withdrawn {
+            chain: Chain::Bitcoin,
+            tx_hash: hash,
+            withdrawal_ids: vec![0],
+            total: 100,
+        });
+        match event {
+            RpcGatewayEvent::Withdrawn { txid: id, .. } => assert_eq!(id, txid(hash)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_imported_blocks_with_reorg() {
+        // 1 <- 2a <- 3a (the old best)
+        //   <- 2b <- 3b <- 4b (the new best)
+        let block = |number, fork: u8| HashAndNumber::<Block> {
+            number,
+            hash: H256::repeat_byte(number as u8 * 16 + fork),
+        };
+        let route = TreeRoute::new(
+            vec![
+                block(3, 0xa),
+                block(2, 0xa),
+                block(1, 0),
+                block(2, 0xb),
+                block(3, 0xb),
+            ],
+            2,
+        );
+        let new_best = block(4, 0xb);
+        assert_eq!(
+            imported_blocks::<Block>(new_best.hash, new_best.number, true, Some(&route)),
+            vec![
+                (block(3, 0xa).hash, 3, BlockStatus::Retracted),
+                (block(2, 0xa).hash, 2, BlockStatus::Retracted),
+                (block(2, 0xb).hash, 2, BlockStatus::Best),
+                (block(3, 0xb).hash, 3, BlockStatus::Best),
+                (new_best.hash, 4, BlockStatus::Best),
+            ]
+        );
+        // the block is notified once it is enacted by a new best block
+        assert!(
+            imported_blocks::<Block>(new_best.hash, new_best.number, false, Some(&route))
+                .is_empty()
+        );
+        assert_eq!(
+            imported_blocks::<Block>(new_best.hash, new_best.number, true, None),
+            vec![(new_best.hash, 4, BlockStatus::Best)]
+        );
+    }
+
+    #[test]
+    fn test_finalized_blocks() {
+        let mut headers = BTreeMap::new();
+        let mut parent_hash = H256::zero();
+        for number in 0..=4 {
+            let header = header(number, parent_hash);
+            parent_hash = header.hash();
+            headers.insert(parent_hash, header);
+        }
+        let header_of = |hash: &H256| headers.get(hash).cloned();
+        let best = headers.get(&parent_hash).unwrap();
+        let hash_of = |number| {
+            headers
+                .values()
+                .find(|header| header.number == number)
+                .unwrap()
+                .hash()
+        };
+
+        // the blocks since the last finalized block, in the ascending order
+        assert_eq!(
+            finalized_blocks::<Block>(best, 1, header_of),
+            (2..=4)
+                .map(|number| (hash_of(number), number, BlockStatus::Finalized))
+                .collect::<Vec<_>>()
+        );
+        assert!(finalized_blocks::<Block>(best, 4, header_of).is_empty());
+    }
+}
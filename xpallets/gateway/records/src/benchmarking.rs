@@ -109,6 +109,13 @@ benchmarks! {
     verify {
         assert_eq!(XGatewayRecords::<T>::locks(receiver, T::BtcAssetId::get()), Some(amount));
     }
+
+    set_chain_asset_id {
+        let asset_id = T::BtcAssetId::get();
+    }: _(RawOrigin::Root, Chain::Dogecoin, asset_id)
+    verify {
+        assert_eq!(XGatewayRecords::<T>::chain_of(&asset_id), Ok(Chain::Dogecoin));
+    }
 }
 
 #[cfg(test)]
@@ -125,6 +132,7 @@ mod tests {
             assert_ok!(Pallet::<Test>::test_benchmark_set_withdrawal_state());
             assert_ok!(Pallet::<Test>::test_benchmark_set_withdrawal_state_list());
             assert_ok!(Pallet::<Test>::test_benchmark_set_locked_assets());
+            assert_ok!(Pallet::<Test>::test_benchmark_set_chain_asset_id());
         });
     }
 }
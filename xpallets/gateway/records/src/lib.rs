@@ -17,7 +17,7 @@ pub mod weights;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    log::{error, info},
+    log::{error, info, warn},
     traits::fungibles::{Inspect, Mutate},
     transactional,
 };
@@ -30,17 +30,23 @@ use sherpax_primitives::AddrStr;
 use xp_assets_registrar::Chain;
 use xp_runtime::Memo;
 
-use pallet_assets::FrozenBalance;
+use pallet_assets::{LockIdentifier, LockReason, LockableAssets};
 use xpallet_support::try_addr;
 
 pub use self::{
     traits::{ChainT, OnAssetChanged},
-    types::{Withdrawal, WithdrawalLimit, WithdrawalRecord, WithdrawalRecordId, WithdrawalState},
+    types::{
+        GatewayEvent, Withdrawal, WithdrawalLimit, WithdrawalRecord, WithdrawalRecordId,
+        WithdrawalState,
+    },
     weights::WeightInfo,
 };
 
 pub use pallet::*;
 
+/// The identifier of the `pallet_assets` lock which holds the assets of the pending withdrawals.
+pub const WITHDRAWAL_LOCK_ID: LockIdentifier = *b"withdraw";
+
 pub type WithdrawalRecordOf<T> = WithdrawalRecord<
     <T as frame_system::Config>::AccountId,
     <T as pallet_assets::Config>::AssetId,
@@ -78,11 +84,24 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
+    /// The storage version, `1` since the assets of the pending withdrawals are held by the
+    /// named locks of `pallet_assets`.
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(crate) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::ensure_invariants()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Deposit asset token.
@@ -151,6 +170,7 @@ pub mod pallet {
         ///
         /// This is a root-only operation.
         #[pallet::weight(<T as Config>::WeightInfo::set_locked_assets())]
+        #[transactional]
         pub fn set_locked_assets(
             origin: OriginFor<T>,
             who: T::AccountId,
@@ -158,29 +178,29 @@ pub mod pallet {
             locked_balance: T::Balance,
         ) -> DispatchResult {
             ensure_root(origin)?;
-            if Locks::<T>::contains_key(who.clone(), asset_id) {
-                Locks::<T>::mutate(who, asset_id, |balance| match balance {
+            if Locks::<T>::contains_key(&who, asset_id) {
+                Locks::<T>::mutate(&who, asset_id, |balance| match balance {
                     Some(amount) => *amount = locked_balance,
                     None => *balance = Some(locked_balance),
                 });
             } else {
-                Locks::<T>::insert(who, asset_id, locked_balance);
+                Locks::<T>::insert(&who, asset_id, locked_balance);
             }
 
-            Ok(())
+            Self::sync_asset_lock(&who, asset_id)
         }
 
         /// Set asset id of Chain
         ///
         /// This is a root-only operation.
-        #[pallet::weight(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_chain_asset_id())]
         pub fn set_chain_asset_id(
             origin: OriginFor<T>,
             chain: Chain,
             asset_id: T::AssetId,
         ) -> DispatchResult {
             ensure_root(origin)?;
-            AssetChainOf::<T>::insert(asset_id, chain);
+            Self::set_asset_chain(asset_id, chain);
             Ok(())
         }
     }
@@ -339,6 +359,7 @@ impl<T: Config> Pallet<T> {
     /// WithdrawalRecord State: `Applying`
     ///
     /// NOTE: this function has included withdrawal_init and withdrawal_locking.
+    #[transactional]
     pub fn withdraw(
         who: &T::AccountId,
         asset_id: T::AssetId,
@@ -627,7 +648,7 @@ impl<T: Config> Pallet<T> {
         } else {
             Locks::<T>::insert(who, asset_id, value);
         }
-        Ok(())
+        Self::sync_asset_lock(who, asset_id)
     }
 
     fn unlock(who: &T::AccountId, asset_id: T::AssetId, value: T::Balance) -> DispatchResult {
@@ -645,7 +666,7 @@ impl<T: Config> Pallet<T> {
                 }
             },
         })?;
-        Ok(())
+        Self::sync_asset_lock(who, asset_id)
     }
 
     fn destroy(who: &T::AccountId, asset_id: T::AssetId, value: T::Balance) -> DispatchResult {
@@ -676,6 +697,24 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 
+    /// Return the pending withdrawals of the ids, the finished ones are skipped.
+    pub fn withdrawals_of(
+        ids: Vec<WithdrawalRecordId>,
+    ) -> BTreeMap<
+        WithdrawalRecordId,
+        Withdrawal<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+    > {
+        ids.into_iter()
+            .filter_map(|id| {
+                let record = Self::pending_withdrawals(id)?;
+                Some((
+                    id,
+                    Withdrawal::new(record, Self::state_of(id).unwrap_or_default()),
+                ))
+            })
+            .collect()
+    }
+
     pub fn withdrawals_list_by_chain(
         chain: Chain,
     ) -> BTreeMap<
@@ -698,12 +737,142 @@ impl<T: Config> Pallet<T> {
     pub fn chain_of(asset_id: &T::AssetId) -> Result<Chain, DispatchError> {
         Self::asset_chain_of(asset_id).ok_or_else(|| Error::<T>::InvalidAssetId.into())
     }
-}
 
-impl<T: Config> FrozenBalance<T::AssetId, T::AccountId, T::Balance> for Pallet<T> {
-    fn frozen_balance(asset: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
-        Self::locks(who, asset)
+    /// Record the chain of given asset `asset_id`.
+    pub fn set_asset_chain(asset_id: T::AssetId, chain: Chain) {
+        AssetChainOf::<T>::insert(asset_id, chain);
+    }
+
+    /// Remove the chain of given asset `asset_id`, the asset can no longer be
+    /// deposited or withdrawn.
+    pub fn remove_asset_chain(asset_id: T::AssetId) {
+        AssetChainOf::<T>::remove(asset_id);
     }
 
-    fn died(_asset: T::AssetId, _who: &T::AccountId) {}
+    /// Check the invariants of the withdrawal records, returns all the violated ones, see
+    /// [`Self::check_invariants`].
+    pub fn invariant_violations() -> Vec<&'static str> {
+        Self::check_invariants(u32::MAX).unwrap_or_default()
+    }
+
+    /// Check the invariants of the withdrawal records, returns all the violated ones:
+    ///
+    /// - every pending withdrawal is `Applying` or `Processing` and its asset has a chain.
+    /// - every `Locks` entry equals the sum of the account's pending withdrawals of the asset,
+    ///   and is held by the `WITHDRAWAL_LOCK_ID` lock of `pallet_assets`.
+    /// - every asset of `AssetChainOf` exists in `pallet_assets`.
+    ///
+    /// Returns `None` without checking if `PendingWithdrawals`, `Locks` or `AssetChainOf`
+    /// has more than `max_items` entries.
+    pub fn check_invariants(max_items: u32) -> Option<Vec<&'static str>> {
+        let limit = (max_items as usize).saturating_add(1);
+        if PendingWithdrawals::<T>::iter_keys().take(limit).count() > max_items as usize
+            || Locks::<T>::iter_keys().take(limit).count() > max_items as usize
+            || AssetChainOf::<T>::iter_keys().take(limit).count() > max_items as usize
+        {
+            warn!(
+                target: "runtime::gateway::records",
+                "[check_invariants] More than {} items to scan, skip",
+                max_items
+            );
+            return None;
+        }
+
+        let mut violations = Vec::new();
+
+        let mut pending = BTreeMap::<(T::AccountId, T::AssetId), T::Balance>::new();
+        for (id, record) in PendingWithdrawals::<T>::iter() {
+            match Self::state_of(id) {
+                Some(WithdrawalState::Applying) | Some(WithdrawalState::Processing) => {
+                    let total = pending
+                        .entry((record.applicant().clone(), record.asset_id()))
+                        .or_default();
+                    *total = total.saturating_add(record.balance());
+                }
+                state => {
+                    error!(
+                        target: "runtime::gateway::records",
+                        "[check_invariants] id:{:?}, pending withdrawal in state {:?}",
+                        id, state
+                    );
+                    violations.push("Pending withdrawal is neither Applying nor Processing");
+                }
+            }
+            if Self::asset_chain_of(record.asset_id()).is_none() {
+                error!(
+                    target: "runtime::gateway::records",
+                    "[check_invariants] id:{:?}, asset {:?} has no chain",
+                    id,
+                    record.asset_id()
+                );
+                violations.push("Pending withdrawal of an asset without chain");
+            }
+        }
+
+        for (who, asset_id, locked) in Locks::<T>::iter() {
+            let withdrawing = pending.remove(&(who.clone(), asset_id)).unwrap_or_default();
+            if locked != withdrawing {
+                error!(
+                    target: "runtime::gateway::records",
+                    "[check_invariants] who:{:?}, asset_id:{:?}, locked:{:?} != withdrawing:{:?}",
+                    who, asset_id, locked, withdrawing
+                );
+                violations.push("Locks mismatches the sum of pending withdrawals");
+            }
+            let held = <pallet_assets::Pallet<T> as LockableAssets<T::AccountId>>::lock_amount(
+                WITHDRAWAL_LOCK_ID,
+                asset_id,
+                &who,
+            );
+            if locked != held {
+                error!(
+                    target: "runtime::gateway::records",
+                    "[check_invariants] who:{:?}, asset_id:{:?}, locked:{:?} != held:{:?}",
+                    who, asset_id, locked, held
+                );
+                violations.push("Locks mismatches the withdrawal lock of Assets");
+            }
+        }
+        if !pending.is_empty() {
+            error!(
+                target: "runtime::gateway::records",
+                "[check_invariants] pending withdrawals without Locks:{:?}",
+                pending
+            );
+            violations.push("Pending withdrawals without Locks");
+        }
+
+        for (asset_id, chain) in AssetChainOf::<T>::iter() {
+            if pallet_assets::Pallet::<T>::maybe_total_supply(asset_id).is_none() {
+                error!(
+                    target: "runtime::gateway::records",
+                    "[check_invariants] asset_id:{:?} of chain {:?} does not exist",
+                    asset_id, chain
+                );
+                violations.push("AssetChainOf contains an inexistent asset");
+            }
+        }
+
+        Some(violations)
+    }
+
+    /// Ensure the invariants of the withdrawal records, see [`Self::invariant_violations`].
+    pub fn ensure_invariants() -> Result<(), &'static str> {
+        match Self::invariant_violations().into_iter().next() {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Hold the locked assets of `who` in `pallet_assets` under `WITHDRAWAL_LOCK_ID`.
+    pub(crate) fn sync_asset_lock(who: &T::AccountId, asset_id: T::AssetId) -> DispatchResult {
+        let locked = Self::locks(who, asset_id).unwrap_or_default();
+        <pallet_assets::Pallet<T> as LockableAssets<T::AccountId>>::set_lock(
+            WITHDRAWAL_LOCK_ID,
+            asset_id,
+            who,
+            locked,
+            LockReason::Hold,
+        )
+    }
 }
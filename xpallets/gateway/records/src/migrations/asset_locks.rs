@@ -0,0 +1,43 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    log::{error, info},
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+use crate::{Config, Locks, Pallet};
+
+/// Hold the locked assets of the existing pending withdrawals in `pallet_assets`.
+pub fn apply<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        info!(
+            target: "runtime::gateway::records",
+            "✅ The asset locks of gateway records have been held, skip."
+        );
+        return <T as frame_system::Config>::DbWeight::get().reads(1);
+    }
+
+    info!(
+        target: "runtime::gateway::records",
+        "✅ Running migration for the asset locks of gateway records"
+    );
+    let mut count = 0u64;
+    for (who, asset_id, _) in Locks::<T>::iter() {
+        if let Err(err) = Pallet::<T>::sync_asset_lock(&who, asset_id) {
+            error!(
+                target: "runtime::gateway::records",
+                "[migration] fail to hold the locked assets, who:{:?}, asset id:{:?}, err:{:?}",
+                who, asset_id, err
+            );
+        }
+        count += 1;
+    }
+    StorageVersion::new(1).put::<Pallet<T>>();
+    info!(
+        target: "runtime::gateway::records",
+        "✅ Migration for the asset locks of {} accounts done",
+        count
+    );
+    <T as frame_system::Config>::DbWeight::get().reads_writes(count * 2 + 1, count + 1)
+}
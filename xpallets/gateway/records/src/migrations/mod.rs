@@ -2,4 +2,5 @@
 
 //! All migrations of this pallet.
 
+pub mod asset_locks;
 pub mod genesis;
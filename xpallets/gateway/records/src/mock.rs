@@ -97,14 +97,17 @@ impl pallet_assets::Config for Test {
     type AssetId = AssetId;
     type Currency = Balances;
     type ForceOrigin = EnsureRoot<AccountId>;
+    type FeeRateOrigin = EnsureRoot<AccountId>;
     type AssetDeposit = AssetDeposit;
     type AssetAccountDeposit = ();
     type MetadataDepositBase = MetadataDepositBase;
     type MetadataDepositPerByte = MetadataDepositPerByte;
     type ApprovalDeposit = ApprovalDeposit;
     type StringLimit = StringLimit;
-    type Freezer = XGatewayRecords;
+    type Freezer = ();
     type Extra = ();
+    type MaxLocks = frame_support::traits::ConstU32<10>;
+    type MaxBatchTransfers = frame_support::traits::ConstU32<100>;
     type WeightInfo = pallet_assets::weights::SubstrateWeight<Test>;
 }
 
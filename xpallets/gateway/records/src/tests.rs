@@ -3,7 +3,10 @@
 pub use super::mock::*;
 use super::*;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, StorageVersion},
+};
 use frame_system::RawOrigin;
 
 #[test]
@@ -29,15 +32,19 @@ fn test_normal() {
             .map(|(i, _)| i)
             .collect::<Vec<_>>();
         assert_eq!(numbers.len(), 1);
+        // the unknown ids are skipped
+        let withdrawals = XGatewayRecords::withdrawals_of(vec![numbers[0], numbers[0] + 1]);
+        assert_eq!(withdrawals.keys().copied().collect::<Vec<_>>(), numbers);
 
         assert_ok!(XGatewayRecords::process_withdrawals(
             &numbers,
             Chain::Bitcoin
         ));
 
-        for i in numbers {
+        for i in numbers.clone() {
             assert_ok!(XGatewayRecords::finish_withdrawal(i, None));
         }
+        assert!(XGatewayRecords::withdrawals_of(numbers).is_empty());
 
         assert_eq!(Assets::balance(X_BTC, ALICE), 500 - 50);
     })
@@ -197,3 +204,106 @@ fn test_lock_unlock() {
         assert_eq!(Locks::<Test>::get(ALICE, X_BTC), Some(5));
     })
 }
+
+#[test]
+fn test_lock_holds_assets() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayRecords::deposit(&ALICE, X_BTC, 100));
+
+        assert_ok!(Pallet::<Test>::lock(&ALICE, X_BTC, 10));
+        assert_eq!(
+            Assets::locks(X_BTC, &ALICE),
+            vec![pallet_assets::AssetLock {
+                id: WITHDRAWAL_LOCK_ID,
+                amount: 10,
+                reason: LockReason::Hold,
+            }]
+        );
+        assert_eq!(Assets::frozen_balance(X_BTC, &ALICE), Some(10));
+        // the locked assets can't be transferred
+        assert!(Assets::transfer(Origin::signed(ALICE), X_BTC, 2, 91).is_err());
+
+        assert_ok!(XGatewayRecords::set_locked_assets(
+            RawOrigin::Root.into(),
+            ALICE,
+            X_BTC,
+            20
+        ));
+        assert_eq!(Assets::frozen_balance(X_BTC, &ALICE), Some(20));
+
+        assert_ok!(Pallet::<Test>::unlock(&ALICE, X_BTC, 20));
+        assert_eq!(Assets::locks(X_BTC, &ALICE), vec![]);
+        assert_eq!(Assets::frozen_balance(X_BTC, &ALICE), None);
+    })
+}
+
+#[test]
+fn test_asset_locks_migration() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayRecords::deposit(&ALICE, X_BTC, 100));
+        Locks::<Test>::insert(ALICE, X_BTC, 10);
+        StorageVersion::new(0).put::<XGatewayRecords>();
+
+        crate::migrations::asset_locks::apply::<Test>();
+        assert_eq!(Assets::frozen_balance(X_BTC, &ALICE), Some(10));
+        assert_eq!(XGatewayRecords::on_chain_storage_version(), 1);
+
+        // only run once
+        Locks::<Test>::insert(ALICE, X_BTC, 20);
+        crate::migrations::asset_locks::apply::<Test>();
+        assert_eq!(Assets::frozen_balance(X_BTC, &ALICE), Some(10));
+    })
+}
+
+#[test]
+fn test_invariants() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayRecords::deposit(&ALICE, X_BTC, 500));
+        assert_ok!(XGatewayRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_ok!(XGatewayRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            30,
+            b"addr".to_vec(),
+            b"ext".to_vec().into()
+        ));
+        assert_ok!(XGatewayRecords::process_withdrawals(&[0], Chain::Bitcoin));
+        assert_eq!(XGatewayRecords::invariant_violations(), Vec::<&str>::new());
+        assert_ok!(XGatewayRecords::ensure_invariants());
+        // too many items to scan
+        assert_eq!(XGatewayRecords::check_invariants(0), None);
+        assert_eq!(XGatewayRecords::check_invariants(10), Some(vec![]));
+
+        // the locked assets are no longer the sum of the pending withdrawals
+        assert_ok!(XGatewayRecords::set_locked_assets(
+            RawOrigin::Root.into(),
+            ALICE,
+            X_BTC,
+            70
+        ));
+        assert_eq!(
+            XGatewayRecords::invariant_violations(),
+            vec!["Locks mismatches the sum of pending withdrawals"]
+        );
+        assert_ok!(XGatewayRecords::set_locked_assets(
+            RawOrigin::Root.into(),
+            ALICE,
+            X_BTC,
+            80
+        ));
+        assert_ok!(XGatewayRecords::ensure_invariants());
+
+        // the asset of the pending withdrawals has no chain
+        XGatewayRecords::remove_asset_chain(X_BTC);
+        assert_eq!(
+            XGatewayRecords::ensure_invariants(),
+            Err("Pending withdrawal of an asset without chain")
+        );
+    })
+}
@@ -5,9 +5,12 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use sp_core::H256;
 use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
 
 use sherpax_primitives::AddrStr;
+use xp_assets_registrar::Chain;
 use xp_runtime::Memo;
 
 /// The id of withdrawal record (u32 is enough).
@@ -133,3 +136,49 @@ pub struct WithdrawalLimit<Balance> {
     pub minimal_withdrawal: Balance,
     pub fee: Balance,
 }
+
+/// The deposit and withdrawal events of the gateway pallets in a block, which are decoded from
+/// the events of `XGatewayBitcoin`, `XGatewayDogecoin` and `XGatewayRecords`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum GatewayEvent<AccountId, AssetId, Balance> {
+    /// A deposit transaction is processed and the asset is issued to the account.
+    Deposited {
+        chain: Chain,
+        tx_hash: H256,
+        who: AccountId,
+        asset_id: AssetId,
+        amount: Balance,
+    },
+    /// A deposit transaction is processed without a bound account.
+    UnclaimedDeposit {
+        chain: Chain,
+        tx_hash: H256,
+        asset_id: AssetId,
+        address: Vec<u8>,
+    },
+    /// A withdrawal proposal is created by the trustee.
+    WithdrawalProposalCreated {
+        chain: Chain,
+        proposer: AccountId,
+        withdrawal_ids: Vec<WithdrawalRecordId>,
+    },
+    /// A withdrawal transaction is processed.
+    Withdrawn {
+        chain: Chain,
+        tx_hash: H256,
+        withdrawal_ids: Vec<WithdrawalRecordId>,
+        total: Balance,
+    },
+    /// A withdrawal record is created.
+    WithdrawalCreated {
+        withdrawal_id: WithdrawalRecordId,
+        applicant: AccountId,
+        asset_id: AssetId,
+        balance: Balance,
+    },
+    /// The state of a withdrawal record is changed.
+    WithdrawalStateChanged {
+        withdrawal_id: WithdrawalRecordId,
+        state: WithdrawalState,
+    },
+}
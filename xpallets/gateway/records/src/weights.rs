@@ -4,6 +4,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-04-28, STEPS: 50, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 1024
+//!
+//! The weights of the following calls are estimated by hand, to be regenerated by the executed
+//! command below: `set_chain_asset_id`.
 
 // Executed Command:
 // ./target/release/sherpax
@@ -36,6 +39,7 @@ pub trait WeightInfo {
     fn set_withdrawal_state() -> Weight;
     fn set_withdrawal_state_list(u: u32) -> Weight;
     fn set_locked_assets() -> Weight;
+    fn set_chain_asset_id() -> Weight;
 }
 
 /// Weights for xpallet_gateway_records using the Substrate node and recommended hardware.
@@ -66,6 +70,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_chain_asset_id() -> Weight {
+        (5_218_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -95,4 +102,7 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_chain_asset_id() -> Weight {
+        (5_218_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }